@@ -0,0 +1,395 @@
+/*!
+## Configuration space access
+
+Decoders in this crate work on byte slices. [ConfigAccess] abstracts the source of those
+bytes, so the [Header](crate::Header), [Capabilities](crate::Capabilities) and
+[ExtendedCapabilities](crate::ExtendedCapabilities) could be read directly from hardware (sysfs
+file, ECAM window, etc.) or from a mock. Only registers reachable through capabilities lists
+are read.
+
+## Example
+```rust
+# use pcics::{
+#     access::ConfigAccess, Capabilities, ExtendedCapabilities, Header, DDR_LENGTH, ECS_LENGTH,
+# };
+# use core::cell::Cell;
+/// Configuration space mock counting DWORD reads
+struct Mock<'a> {
+    data: &'a [u8],
+    reads: Cell<usize>,
+}
+impl<'a> ConfigAccess for Mock<'a> {
+    type Error = ();
+    fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+        self.reads.set(self.reads.get() + 1);
+        let offset = offset as usize;
+        let bytes = self.data.get(offset..offset + 4).ok_or(())?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+let data = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/device/8086_2030/config"
+));
+let access = Mock { data, reads: Cell::new(0) };
+
+let header = Header::read_from(&access).unwrap();
+assert_eq!((0x8086, 0x2030), (header.vendor_id, header.device_id));
+
+let mut ddr = [0; DDR_LENGTH];
+let caps = Capabilities::read_from(&access, &header, &mut ddr).unwrap();
+assert_eq!(4, caps.count());
+
+let mut ecs = [0; ECS_LENGTH];
+let ecaps = ExtendedCapabilities::read_from(&access, &mut ecs).unwrap();
+assert_eq!(8, ecaps.count());
+
+assert!(access.reads.get() < data.len() / 4);
+```
+*/
+
+use snafu::prelude::*;
+
+/// Configuration space register reader
+///
+/// The only required method is [read_u32](ConfigAccess::read_u32). Byte and word reads are
+/// extracted from the DWORD containing them by default.
+pub trait ConfigAccess {
+    type Error;
+
+    /// Read DWORD at configuration space `offset`
+    ///
+    /// Decoders call this method with DWORD aligned offsets only.
+    fn read_u32(&self, offset: u16) -> Result<u32, Self::Error>;
+
+    /// Read WORD at configuration space `offset`
+    ///
+    /// `offset` should be WORD aligned, so the WORD never crosses a DWORD boundary.
+    fn read_u16(&self, offset: u16) -> Result<u16, Self::Error> {
+        debug_assert_eq!(0, offset & 1, "WORD read at unaligned offset {offset:#x}");
+        let dword = self.read_u32(offset & !0b11)?;
+        Ok((dword >> ((offset & 0b10) * 8)) as u16)
+    }
+
+    /// Read byte at configuration space `offset`
+    fn read_u8(&self, offset: u16) -> Result<u8, Self::Error> {
+        let dword = self.read_u32(offset & !0b11)?;
+        Ok((dword >> ((offset & 0b11) * 8)) as u8)
+    }
+}
+
+impl<A: ConfigAccess + ?Sized> ConfigAccess for &A {
+    type Error = A::Error;
+
+    fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+        (**self).read_u32(offset)
+    }
+    fn read_u16(&self, offset: u16) -> Result<u16, Self::Error> {
+        (**self).read_u16(offset)
+    }
+    fn read_u8(&self, offset: u16) -> Result<u8, Self::Error> {
+        (**self).read_u8(offset)
+    }
+}
+
+/// Configuration space register writer
+///
+/// Needed only by mechanisms that drive a device, e.g. the
+/// [DOE mailbox](crate::extended_capabilities::data_object_exchange::DoeMailbox). Decoders never
+/// write configuration space.
+pub trait ConfigWrite: ConfigAccess {
+    /// Write DWORD at configuration space `offset`
+    ///
+    /// Callers pass DWORD aligned offsets only.
+    fn write_u32(&self, offset: u16, value: u32) -> Result<(), Self::Error>;
+}
+
+impl<A: ConfigWrite + ?Sized> ConfigWrite for &A {
+    fn write_u32(&self, offset: u16, value: u32) -> Result<(), Self::Error> {
+        (**self).write_u32(offset, value)
+    }
+}
+
+/// Pre-captured configuration space, starting at offset 0
+impl ConfigAccess for [u8] {
+    type Error = OutOfBoundsError;
+
+    fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+        self.get(offset as usize..offset as usize + 4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .context(OutOfBoundsSnafu { offset })
+    }
+    fn read_u16(&self, offset: u16) -> Result<u16, Self::Error> {
+        self.get(offset as usize..offset as usize + 2)
+            .map(|bytes| u16::from_le_bytes(bytes.try_into().unwrap()))
+            .context(OutOfBoundsSnafu { offset })
+    }
+    fn read_u8(&self, offset: u16) -> Result<u8, Self::Error> {
+        self.get(offset as usize)
+            .copied()
+            .context(OutOfBoundsSnafu { offset })
+    }
+}
+
+/// Register lies outside of pre-captured configuration space data
+#[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+#[snafu(display("[{offset:03x}] register is out of configuration space data"))]
+pub struct OutOfBoundsError {
+    pub offset: u16,
+}
+
+/// PCI function address in *segment:bus:device.function* notation
+///
+/// ```
+/// # use pcics::access::Address;
+/// let address: Address = "0000:3a:00.1".parse().unwrap();
+/// assert_eq!(Address { segment: 0, bus: 0x3a, device: 0, function: 1 }, address);
+/// assert_eq!("0000:3a:00.1", address.to_string());
+/// // Segment could be omitted
+/// assert_eq!(Ok(address), "3a:00.1".parse());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address {
+    /// PCI Segment Group (also known as domain)
+    pub segment: u16,
+    pub bus: u8,
+    /// Device number (0..32)
+    pub device: u8,
+    /// Function number (0..8)
+    pub function: u8,
+}
+
+impl core::fmt::Display for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.segment, self.bus, self.device, self.function
+        )
+    }
+}
+
+impl core::str::FromStr for Address {
+    type Err = AddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (segment, rest) = match s.matches(':').count() {
+            1 => (None, s),
+            2 => s
+                .split_once(':')
+                .map(|(l, r)| (Some(l), r))
+                .ok_or(AddressParseError)?,
+            _ => return Err(AddressParseError),
+        };
+        let (bus, rest) = rest.split_once(':').ok_or(AddressParseError)?;
+        let (device, function) = rest.split_once('.').ok_or(AddressParseError)?;
+        let hex = |s: &str, len: usize| {
+            if s.len() == len && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+                u16::from_str_radix(s, 16).map_err(|_| AddressParseError)
+            } else {
+                Err(AddressParseError)
+            }
+        };
+        let segment = segment.map_or(Ok(0), |s| hex(s, 4))?;
+        let bus = hex(bus, 2)? as u8;
+        let device = hex(device, 2)? as u8;
+        let function = hex(function, 1)? as u8;
+        if device < 32 && function < 8 {
+            Ok(Self {
+                segment,
+                bus,
+                device,
+                function,
+            })
+        } else {
+            Err(AddressParseError)
+        }
+    }
+}
+
+/// String is not a valid *segment:bus:device.function* address
+#[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+#[snafu(display("expected [ssss:]bb:dd.f PCI function address"))]
+pub struct AddressParseError;
+
+/// Read all DWORDs covering configuration space `range` into `buf`, where `buf` holds
+/// configuration space data starting at `base` offset
+pub(crate) fn read_range<A: ConfigAccess + ?Sized>(
+    access: &A,
+    buf: &mut [u8],
+    base: usize,
+    range: core::ops::Range<usize>,
+) -> Result<(), A::Error> {
+    let start = range.start & !0b11;
+    let end = (range.end + 0b11) & !0b11;
+    for offset in (start..end.min(base + buf.len())).step_by(4) {
+        let dword = access.read_u32(offset as u16)?;
+        buf[offset - base..offset - base + 4].copy_from_slice(&dword.to_le_bytes());
+    }
+    Ok(())
+}
+
+/// Read every structure in `offsets` up to the beginning of the next one (or the end of `buf`)
+///
+/// Capability structures could not overlap, so this is the maximum data any decoder could
+/// require. If the structure size is known in advance, `size_hint` shortens the read. DWORDs
+/// covering the first `walked` bytes of the structure were already read while walking the list
+/// and are skipped.
+pub(crate) fn read_structures<A, F, W>(
+    access: &A,
+    buf: &mut [u8],
+    base: usize,
+    offsets: &mut [u16],
+    size_hint: F,
+    walked: W,
+) -> Result<(), A::Error>
+where
+    A: ConfigAccess + ?Sized,
+    F: Fn(&[u8], usize) -> Option<usize>,
+    W: Fn(&[u8], usize) -> usize,
+{
+    offsets.sort_unstable();
+    let end = base + buf.len();
+    for (i, &offset) in offsets.iter().enumerate() {
+        let offset = offset as usize;
+        let next = offsets.get(i + 1).map_or(end, |&next| next as usize);
+        let next = size_hint(buf, offset).map_or(next, |size| next.min(offset + size));
+        let start = (offset + walked(buf, offset) + 0b11) & !0b11;
+        read_range(access, buf, base, start..next)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        capabilities::pci_express::LinkWidth,
+        extended_capabilities::ExtendedCapabilityKind as Kind, Capabilities, ExtendedCapabilities,
+        Header, DDR_LENGTH, DDR_OFFSET, ECS_LENGTH, ECS_OFFSET,
+    };
+    use core::cell::RefCell;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    const DATA: &[u8; 4096] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/device/8086_2030/config"
+    ));
+
+    struct Recorder<'a> {
+        data: &'a [u8],
+        offsets: RefCell<Vec<u16>>,
+    }
+    impl<'a> ConfigAccess for Recorder<'a> {
+        type Error = OutOfBoundsError;
+        fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+            self.offsets.borrow_mut().push(offset);
+            self.data.read_u32(offset)
+        }
+    }
+
+    #[test]
+    fn slice_access() {
+        let data = [0x11, 0x22, 0x33, 0x44, 0x55];
+        assert_eq!(Ok(0x44332211), data[..].read_u32(0));
+        assert_eq!(Ok(0x4433), data[..].read_u16(2));
+        assert_eq!(Ok(0x55), data[..].read_u8(4));
+        assert_eq!(Err(OutOfBoundsError { offset: 2 }), data[..].read_u32(2));
+    }
+
+    #[test]
+    fn default_narrow_reads() {
+        let access = Recorder {
+            data: &[0x11, 0x22, 0x33, 0x44],
+            offsets: Default::default(),
+        };
+        assert_eq!(Ok(0x4433), access.read_u16(2));
+        assert_eq!(Ok(0x22), access.read_u8(1));
+        assert_eq!(vec![0, 0], *access.offsets.borrow());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "WORD read at unaligned offset 0x3")]
+    fn default_unaligned_word_read() {
+        let access = Recorder {
+            data: &[0x11, 0x22, 0x33, 0x44],
+            offsets: Default::default(),
+        };
+        let _ = access.read_u16(3);
+    }
+
+    #[test]
+    fn same_as_slice_decoding() {
+        let access = Recorder {
+            data: DATA,
+            offsets: Default::default(),
+        };
+
+        let header = Header::read_from(&access).unwrap();
+        assert_eq!(Header::try_from(&DATA[..DDR_OFFSET]).unwrap(), header);
+
+        let mut ddr = [0; DDR_LENGTH];
+        let result = Capabilities::read_from(&access, &header, &mut ddr)
+            .unwrap()
+            .collect::<Vec<_>>();
+        let sample = Capabilities::new(&DATA[DDR_OFFSET..ECS_OFFSET], &header).collect::<Vec<_>>();
+        assert_eq!(sample, result);
+
+        let mut ecs = [0; ECS_LENGTH];
+        let result = ExtendedCapabilities::read_from(&access, &mut ecs)
+            .unwrap()
+            .collect::<Vec<_>>();
+        let sample = ExtendedCapabilities::new(&DATA[ECS_OFFSET..]).collect::<Vec<_>>();
+        assert_eq!(sample.len(), result.len());
+        for (sample, result) in sample.into_iter().zip(result) {
+            let (sample, result) = (sample.unwrap(), result.unwrap());
+            assert_eq!((sample.offset, sample.id()), (result.offset, result.id()));
+            // ACS and Secondary PCI Express keep the rest of extended configuration space
+            match (sample.kind, result.kind) {
+                (Kind::AccessControlServices(sample), Kind::AccessControlServices(result)) => {
+                    assert_eq!(sample.acs_capability, result.acs_capability);
+                    assert_eq!(sample.acs_control, result.acs_control);
+                    assert_eq!(
+                        sample.egress_control_vectors().collect::<Vec<_>>(),
+                        result.egress_control_vectors().collect::<Vec<_>>(),
+                    );
+                }
+                (Kind::SecondaryPciExpress(sample), Kind::SecondaryPciExpress(result)) => {
+                    assert_eq!(sample.link_control_3, result.link_control_3);
+                    assert_eq!(sample.lane_error_status, result.lane_error_status);
+                    assert_eq!(
+                        sample
+                            .equalization_control_lanes(LinkWidth::X16)
+                            .collect::<Vec<_>>(),
+                        result
+                            .equalization_control_lanes(LinkWidth::X16)
+                            .collect::<Vec<_>>(),
+                    );
+                }
+                (sample, result) => assert_eq!(sample, result),
+            }
+        }
+
+        // Every DWORD read only once and not all configuration space was read
+        let mut offsets = access.offsets.borrow().clone();
+        let reads = offsets.len();
+        offsets.sort_unstable();
+        offsets.dedup();
+        assert_eq!(reads, offsets.len());
+        assert!(reads < DATA.len() / 4);
+    }
+
+    #[test]
+    fn access_error() {
+        let access = &DATA[..0x48];
+        let header = Header::read_from(access).unwrap();
+        let mut ddr = [0; DDR_LENGTH];
+        assert_eq!(
+            Err(OutOfBoundsError { offset: 0x60 }),
+            Capabilities::read_from(access, &header, &mut ddr).map(|_| ())
+        );
+    }
+}
@@ -10,12 +10,12 @@ Capabilities list
 - [x] [VPD](vital_product_data) (03h)
 - [x] [Slot Identification](slot_identification) (04h)
 - [x] [Message Signaled Interrupts](message_signaled_interrups) (05h)
-- [ ] [CompactPCI Hot Swap](compact_pci_hot_swap) (06h)
+- [x] [CompactPCI Hot Swap](compact_pci_hot_swap) (06h)
 - [x] [PCI-X](pci_x) (07h)
 - [x] [HyperTransport](hypertransport) (08h)
 - [x] [Vendor Specific](vendor_specific) (09h)
 - [x] [Debug port](debug_port) (0Ah)
-- [ ] [CompactPCI central resource control](compact_pci_resource_control) (0Bh)
+- [x] [CompactPCI central resource control](compact_pci_resource_control) (0Bh)
 - [x] [PCI Hot-Plug](pci_hot_plug) (0Ch)
 - [x] [PCI Bridge Subsystem Vendor ID](bridge_subsystem_vendor_id) (0Dh)
 - [x] [AGP 8x](agp_8x) (0Eh)
@@ -150,8 +150,10 @@ assert_eq!(sample, result);
 
 use snafu::prelude::*;
 
-use super::DDR_OFFSET;
+use super::{DDR_LENGTH, DDR_OFFSET, ECS_OFFSET};
+use crate::access::{self, ConfigAccess};
 use crate::header::{Header, HeaderType};
+use crate::register::RegisterMap;
 
 // 01h PCI Power Management Interface
 pub mod power_management_interface;
@@ -174,17 +176,7 @@ pub mod message_signaled_interrups;
 pub use message_signaled_interrups::MessageSignaledInterrups;
 
 // 06h CompactPCI Hot Swap
-pub mod compact_pci_hot_swap {
-    /*!
-    # CompactPCI Hot Swap
-
-    This Capability structure provides a standard interface to control and sense
-    status within a device that supports Hot Swap insertion and extraction in a
-    CompactPCI system.
-    */
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct CompactPciHotSwap;
-}
+pub mod compact_pci_hot_swap;
 pub use compact_pci_hot_swap::CompactPciHotSwap;
 
 // 07h PCI-X
@@ -197,6 +189,8 @@ pub use hypertransport::Hypertransport;
 
 // 09h Vendor Specific
 pub mod vendor_specific;
+#[cfg(feature = "alloc")]
+pub use vendor_specific::OwnedVendorSpecific;
 pub use vendor_specific::VendorSpecific;
 
 // 0Ah Debug port
@@ -204,26 +198,11 @@ pub mod debug_port;
 pub use debug_port::DebugPort;
 
 // 0Bh CompactPCI central resource control
-pub mod compact_pci_resource_control {
-    /*!
-    # CompactPCI central resource control
-    */
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct CompactPciResourceControl;
-}
+pub mod compact_pci_resource_control;
 pub use compact_pci_resource_control::CompactPciResourceControl;
 
 // 0Ch PCI Hot-Plug
-pub mod pci_hot_plug {
-    /*!
-    # PCI Hot-Plug
-
-    Indicates that the associated device conforms to the Standard Hot-Plug
-    Controller model
-    */
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct PciHotPlug;
-}
+pub mod pci_hot_plug;
 pub use pci_hot_plug::PciHotPlug;
 
 // 0Dh PCI Bridge Subsystem Vendor ID
@@ -231,23 +210,11 @@ pub mod bridge_subsystem_vendor_id;
 pub use bridge_subsystem_vendor_id::BridgeSubsystemVendorId;
 
 // 0Eh AGP 8x
-pub mod agp_8x {
-    /*!
-    # AGP 8x
-    */
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct Agp8x;
-}
+pub mod agp_8x;
 pub use agp_8x::Agp8x;
 
 // 0Fh Secure Device
-pub mod secure_device {
-    /*!
-    # Secure Device
-    */
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct SecureDevice;
-}
+pub mod secure_device;
 pub use secure_device::SecureDevice;
 
 // 10h PCI Express
@@ -269,6 +236,8 @@ pub use advanced_features::AdvancedFeatures;
 // 14h Enhanced Allocation
 pub mod enhanced_allocation;
 pub use enhanced_allocation::EnhancedAllocation;
+#[cfg(feature = "alloc")]
+pub use enhanced_allocation::OwnedEnhancedAllocation;
 
 // 15h Flattening Portal Bridge
 pub mod flattening_portal_bridge;
@@ -276,11 +245,23 @@ pub use flattening_portal_bridge::FlatteningPortalBridge;
 
 /// Capability parsing error
 #[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CapabilityError {
     #[snafu(display("capabilities pointer should be greater than 0x40"))]
     Pointer,
     #[snafu(display("[{ptr:02x}] capability header is not available"))]
     Header { ptr: u8 },
+    #[snafu(display("[{ptr:02x}] capability pointer is not DWORD aligned"))]
+    Misaligned { ptr: u8 },
+    #[snafu(display("[{ptr:02x}] capability was already visited, list is looped"))]
+    Loop { ptr: u8 },
+    #[snafu(display(
+        "capabilities list is longer than {} entries",
+        Capabilities::MAX_ENTRIES
+    ))]
+    Limit,
+    #[snafu(display("[{ptr:02x}] capability overlaps previous capabilities"))]
+    Overlap { ptr: u8 },
     #[snafu(display("[{ptr:02x}] {source} data read error"))]
     Data {
         ptr: u8,
@@ -326,6 +307,7 @@ pub enum CapabilityError {
 
 /// Common error for reading capability data
 #[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[snafu(display("{name} ({size} bytes)"))]
 pub struct CapabilityDataError {
     name: &'static str,
@@ -337,15 +319,76 @@ pub struct CapabilityDataError {
 /// Used to point to a linked list of new capabilities implemented by this device. This
 /// register is only valid if the “Capabilities List” bit in the [crate::header::Status] Register is set. If
 /// implemented, the bottom two bits are reserved and should be set to 00b.
+///
+/// Iteration stops after a misaligned or already visited pointer or after
+/// [Capabilities::MAX_ENTRIES] capabilities. Capabilities overlapping previous ones are reported
+/// as [CapabilityError::Overlap].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Capabilities<'a> {
     data: &'a [u8],
     header: &'a Header,
     pointer: u8,
+    /// Bit per DWORD of device dependent region with already visited capability pointers
+    visited: u64,
+    /// Bit per DWORD of device dependent region occupied by already parsed capabilities
+    occupied: u64,
+    /// Number of already visited capabilities
+    count: usize,
 }
 impl<'a> Capabilities<'a> {
+    /// Maximum number of capabilities that fit into device dependent region
+    pub const MAX_NUMBER: usize = DDR_LENGTH / Capability::HEADER_SIZE;
+    /// Maximum number of capabilities followed before the list is reported as too long
+    pub const MAX_ENTRIES: usize = 32;
+
     pub fn new(data: &'a [u8], header: &'a Header) -> Self {
-        Self { data, header, pointer: header.capabilities_pointer }
+        Self {
+            data,
+            header,
+            pointer: header.capabilities_pointer,
+            visited: 0,
+            occupied: 0,
+            count: 0,
+        }
+    }
+
+    /// Pointer to the capability which will be parsed next, 0 at the end of the list
+    pub fn pointer(&self) -> u8 {
+        self.pointer
+    }
+
+    /// Walk the capabilities list through [ConfigAccess] and read the capability structures
+    /// into device dependent region buffer `ddr`
+    ///
+    /// Registers not belonging to any capability are left untouched.
+    pub fn read_from<A: ConfigAccess + ?Sized>(
+        access: &A,
+        header: &'a Header,
+        ddr: &'a mut [u8; DDR_LENGTH],
+    ) -> Result<Self, A::Error> {
+        let mut offsets = [0u16; Self::MAX_NUMBER];
+        let mut count = 0;
+        let mut pointer = header.capabilities_pointer as usize;
+        while pointer >= DDR_OFFSET && count < offsets.len() {
+            if offsets[..count].contains(&(pointer as u16)) {
+                break;
+            }
+            offsets[count] = pointer as u16;
+            count += 1;
+            // Capability header is not DWORD aligned necessarily
+            let end = (pointer + Capability::HEADER_SIZE).min(ECS_OFFSET);
+            access::read_range(access, ddr, DDR_OFFSET, pointer..end)?;
+            pointer = ddr.get(pointer + 1 - DDR_OFFSET).map_or(0, |&next| next as usize);
+        }
+        access::read_structures(
+            access,
+            ddr,
+            DDR_OFFSET,
+            &mut offsets[..count],
+            |_, _| None,
+            |_, _| Capability::HEADER_SIZE,
+        )?;
+        Ok(Self::new(ddr, header))
     }
 }
 impl<'a> Iterator for Capabilities<'a> {
@@ -353,7 +396,68 @@ impl<'a> Iterator for Capabilities<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         // Stop iterating if next pointer is null
-        (self.pointer != 0).then(|| parse_cap(self.data, &mut self.pointer, self.header))
+        if self.pointer == 0 {
+            return None;
+        }
+        let ptr = self.pointer;
+        if let Err(err) = self.visit(ptr) {
+            // There is no sense to follow broken list
+            self.pointer = 0;
+            return Some(Err(err));
+        }
+        let result = parse_cap(self.data, &mut self.pointer, self.header);
+        let size = match &result {
+            Ok(cap) => cap.kind.registers().map_or(0, |map| map.size() as usize),
+            Err(_) => 0,
+        };
+        let overlap = self.occupy(ptr, size.max(Capability::HEADER_SIZE));
+        Some(result.and_then(|cap| overlap.map(|_| cap)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for Capabilities<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(*self)
+    }
+}
+
+impl<'a> Capabilities<'a> {
+    /// Check capability pointer and mark it as visited
+    fn visit(&mut self, ptr: u8) -> Result<(), CapabilityError> {
+        if ptr & 0b11 != 0 {
+            return Err(CapabilityError::Misaligned { ptr });
+        }
+        if self.count >= Self::MAX_ENTRIES {
+            return Err(CapabilityError::Limit);
+        }
+        self.count += 1;
+        // Pointers outside of device dependent region are reported by parse_cap
+        if (ptr as usize) < DDR_OFFSET {
+            return Ok(());
+        }
+        let bit = 1 << ((ptr as usize - DDR_OFFSET) / 4);
+        if self.visited & bit != 0 {
+            return Err(CapabilityError::Loop { ptr });
+        }
+        self.visited |= bit;
+        Ok(())
+    }
+    /// Mark DWORDs of `size` bytes capability structure as occupied
+    fn occupy(&mut self, ptr: u8, size: usize) -> Result<(), CapabilityError> {
+        if (ptr as usize) < DDR_OFFSET {
+            return Ok(());
+        }
+        let start = ptr as usize - DDR_OFFSET;
+        let end = (start + size).min(DDR_LENGTH);
+        let dwords = (start / 4..end.div_ceil(4)).fold(0u64, |acc, n| acc | 1 << n);
+        let overlapped = self.occupied & dwords != 0;
+        self.occupied |= dwords;
+        if overlapped {
+            Err(CapabilityError::Overlap { ptr })
+        } else {
+            Ok(())
+        }
     }
 }
 
@@ -369,6 +473,7 @@ fn parse_cap<'a>(bytes: &'a [u8], pointer: &mut u8, header: &'a Header) -> Capab
         *pointer = *next;
         (*id, rest)
     } else {
+        *pointer = 0;
         return Err(CapabilityError::Header { ptr });
     };
     use CapabilityKind as Kind;
@@ -394,7 +499,10 @@ fn parse_cap<'a>(bytes: &'a [u8], pointer: &mut u8, header: &'a Header) -> Capab
             .try_into()
             .map(Kind::MessageSignaledInterrups)
             .context(MessageSignaledInterrupsSnafu { ptr })?,
-        0x06 => Kind::CompactPciHotSwap(CompactPciHotSwap),
+        0x06 => cap_data
+            .try_into()
+            .map(Kind::CompactPciHotSwap)
+            .context(DataSnafu { ptr })?,
         0x07 => {
             if matches!(header.header_type, HeaderType::Bridge(_)) {
                 cap_data
@@ -419,14 +527,26 @@ fn parse_cap<'a>(bytes: &'a [u8], pointer: &mut u8, header: &'a Header) -> Capab
             .try_into()
             .map(Kind::DebugPort)
             .context(DataSnafu { ptr })?,
-        0x0b => Kind::CompactPciResourceControl(CompactPciResourceControl),
-        0x0c => Kind::PciHotPlug(PciHotPlug),
+        0x0b => cap_data
+            .try_into()
+            .map(Kind::CompactPciResourceControl)
+            .context(DataSnafu { ptr })?,
+        0x0c => cap_data
+            .try_into()
+            .map(Kind::PciHotPlug)
+            .context(DataSnafu { ptr })?,
         0x0d => cap_data
             .try_into()
             .map(Kind::BridgeSubsystemVendorId)
             .context(DataSnafu { ptr })?,
-        0x0e => Kind::Agp8x(Agp8x),
-        0x0f => Kind::SecureDevice(SecureDevice),
+        0x0e => cap_data
+            .try_into()
+            .map(Kind::Agp8x)
+            .context(DataSnafu { ptr })?,
+        0x0f => cap_data
+            .try_into()
+            .map(Kind::SecureDevice)
+            .context(DataSnafu { ptr })?,
         0x10 => cap_data
             .try_into()
             .map(Kind::PciExpress)
@@ -461,6 +581,7 @@ fn parse_cap<'a>(bytes: &'a [u8], pointer: &mut u8, header: &'a Header) -> Capab
 
 /// Capability structure
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Capability<'a> {
     pub pointer: u8,
     pub kind: CapabilityKind<'a>,
@@ -473,6 +594,7 @@ impl<'a> Capability<'a> {
 
 /// Capability ID assigned by the PCI-SIG
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CapabilityKind<'a> {
     /// Null Capability (00h)
     ///
@@ -504,6 +626,127 @@ pub enum CapabilityKind<'a> {
     Reserved(u8),
 }
 
+/// Owned [CapabilityKind]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedCapabilityKind {
+    /// Null Capability (00h)
+    ///
+    /// This capability contains no registers. It may be present in any Function. Functions may
+    /// contain multiple instances of this capability.
+    NullCapability,
+    PowerManagementInterface(PowerManagementInterface),
+    AcceleratedGraphicsPort(AcceleratedGraphicsPort),
+    VitalProductData(VitalProductData),
+    SlotIdentification(SlotIdentification),
+    MessageSignaledInterrups(MessageSignaledInterrups),
+    CompactPciHotSwap(CompactPciHotSwap),
+    PciX(PciX),
+    PciXBridge(PciXBridge),
+    Hypertransport(Hypertransport),
+    VendorSpecific(OwnedVendorSpecific),
+    DebugPort(DebugPort),
+    CompactPciResourceControl(CompactPciResourceControl),
+    PciHotPlug(PciHotPlug),
+    BridgeSubsystemVendorId(BridgeSubsystemVendorId),
+    Agp8x(Agp8x),
+    SecureDevice(SecureDevice),
+    PciExpress(PciExpress),
+    MsiX(MsiX),
+    Sata(Sata),
+    AdvancedFeatures(AdvancedFeatures),
+    EnhancedAllocation(OwnedEnhancedAllocation),
+    FlatteningPortalBridge(FlatteningPortalBridge),
+    Reserved(u8),
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&CapabilityKind<'a>> for OwnedCapabilityKind {
+    fn from(kind: &CapabilityKind<'a>) -> Self {
+        use CapabilityKind as Kind;
+        match kind {
+            Kind::NullCapability => Self::NullCapability,
+            Kind::PowerManagementInterface(data) => Self::PowerManagementInterface(data.clone()),
+            Kind::AcceleratedGraphicsPort(data) => Self::AcceleratedGraphicsPort(data.clone()),
+            Kind::VitalProductData(data) => Self::VitalProductData(data.clone()),
+            Kind::SlotIdentification(data) => Self::SlotIdentification(data.clone()),
+            Kind::MessageSignaledInterrups(data) => Self::MessageSignaledInterrups(data.clone()),
+            Kind::CompactPciHotSwap(data) => Self::CompactPciHotSwap(data.clone()),
+            Kind::PciX(data) => Self::PciX(data.clone()),
+            Kind::PciXBridge(data) => Self::PciXBridge(data.clone()),
+            Kind::Hypertransport(data) => Self::Hypertransport(data.clone()),
+            Kind::VendorSpecific(data) => Self::VendorSpecific(data.into()),
+            Kind::DebugPort(data) => Self::DebugPort(data.clone()),
+            Kind::CompactPciResourceControl(data) => Self::CompactPciResourceControl(data.clone()),
+            Kind::PciHotPlug(data) => Self::PciHotPlug(data.clone()),
+            Kind::BridgeSubsystemVendorId(data) => Self::BridgeSubsystemVendorId(data.clone()),
+            Kind::Agp8x(data) => Self::Agp8x(data.clone()),
+            Kind::SecureDevice(data) => Self::SecureDevice(data.clone()),
+            Kind::PciExpress(data) => Self::PciExpress(data.clone()),
+            Kind::MsiX(data) => Self::MsiX(data.clone()),
+            Kind::Sata(data) => Self::Sata(data.clone()),
+            Kind::AdvancedFeatures(data) => Self::AdvancedFeatures(data.clone()),
+            Kind::EnhancedAllocation(data) => Self::EnhancedAllocation(data.into()),
+            Kind::FlatteningPortalBridge(data) => Self::FlatteningPortalBridge(data.clone()),
+            Kind::Reserved(v) => Self::Reserved(*v),
+        }
+    }
+}
+
+/// Owned [Capability]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedCapability {
+    pub pointer: u8,
+    pub kind: OwnedCapabilityKind,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&Capability<'a>> for OwnedCapability {
+    fn from(cap: &Capability<'a>) -> Self {
+        Self {
+            pointer: cap.pointer,
+            kind: (&cap.kind).into(),
+        }
+    }
+}
+
+impl<'a> CapabilityKind<'a> {
+    /// Access attributes of capability registers with offsets relative to the capability pointer
+    ///
+    /// Returns `None` for capabilities which registers are not decoded.
+    pub fn registers(&self) -> Option<RegisterMap> {
+        let map = match self {
+            Self::PowerManagementInterface(_) => power_management_interface::REGISTERS.into(),
+            Self::AcceleratedGraphicsPort(_) => accelerated_graphics_port::REGISTERS.into(),
+            Self::VitalProductData(_) => vital_product_data::REGISTERS.into(),
+            Self::SlotIdentification(_) => slot_identification::REGISTERS.into(),
+            Self::MessageSignaledInterrups(msi) => msi.registers(),
+            Self::CompactPciHotSwap(_) => compact_pci_hot_swap::REGISTERS.into(),
+            Self::PciX(pci_x) => pci_x.registers(),
+            Self::PciXBridge(pci_x_bridge) => pci_x_bridge.registers(),
+            Self::Hypertransport(ht) => ht.registers(),
+            Self::VendorSpecific(_) => vendor_specific::REGISTERS.into(),
+            Self::DebugPort(_) => debug_port::REGISTERS.into(),
+            Self::CompactPciResourceControl(_) => compact_pci_resource_control::REGISTERS.into(),
+            Self::PciHotPlug(_) => pci_hot_plug::REGISTERS.into(),
+            Self::BridgeSubsystemVendorId(_) => bridge_subsystem_vendor_id::REGISTERS.into(),
+            Self::Agp8x(_) => agp_8x::REGISTERS.into(),
+            Self::SecureDevice(secure_device) => secure_device.registers(),
+            Self::PciExpress(pcie) => pcie.registers(),
+            Self::MsiX(_) => msi_x::REGISTERS.into(),
+            Self::Sata(_) => sata::REGISTERS.into(),
+            Self::AdvancedFeatures(_) => advanced_features::REGISTERS.into(),
+            Self::EnhancedAllocation(ea) => ea.registers(),
+            Self::FlatteningPortalBridge(_) => flattening_portal_bridge::REGISTERS.into(),
+            Self::NullCapability | Self::Reserved(_) => return None,
+        };
+        Some(map)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -548,4 +791,73 @@ mod tests {
         ];
         assert_eq!(sample, result);
     }
+
+    #[test]
+    fn malformed_lists() {
+        let data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_9dc8/config"
+        ));
+        let mut header: Header = data.as_slice().try_into().unwrap();
+        header.capabilities_pointer = 0x40;
+        let pointers = |ddr: &[u8]| {
+            Capabilities::new(ddr, &header)
+                .map(|cap| cap.map(|cap| cap.pointer))
+                .collect::<Vec<_>>()
+        };
+
+        // [40] Null -> [48] Null -> [40]
+        let mut ddr = [0u8; DDR_LENGTH];
+        ddr[0x01] = 0x48;
+        ddr[0x09] = 0x40;
+        let sample = vec![Ok(0x40), Ok(0x48), Err(CapabilityError::Loop { ptr: 0x40 })];
+        assert_eq!(sample, pointers(&ddr));
+
+        // [40] Null -> [4a]
+        let mut ddr = [0u8; DDR_LENGTH];
+        ddr[0x01] = 0x4a;
+        let sample = vec![Ok(0x40), Err(CapabilityError::Misaligned { ptr: 0x4a })];
+        assert_eq!(sample, pointers(&ddr));
+
+        // [40] Power Management (8 bytes) -> [44] Null
+        let mut ddr = [0u8; DDR_LENGTH];
+        ddr[0x00] = 0x01;
+        ddr[0x01] = 0x44;
+        let sample = vec![Ok(0x40), Err(CapabilityError::Overlap { ptr: 0x44 })];
+        assert_eq!(sample, pointers(&ddr));
+
+        // [40] Null -> [44] Null -> ... -> [fc] Null, longer than MAX_ENTRIES
+        let mut ddr = [0u8; DDR_LENGTH];
+        for offset in (0..DDR_LENGTH - 4).step_by(4) {
+            ddr[offset + 1] = (DDR_OFFSET + offset + 4) as u8;
+        }
+        let result = pointers(&ddr);
+        assert_eq!(Capabilities::MAX_ENTRIES + 1, result.len());
+        assert!(result[..Capabilities::MAX_ENTRIES]
+            .iter()
+            .all(Result::is_ok));
+        assert_eq!(Some(&Err(CapabilityError::Limit)), result.last());
+    }
+
+    #[test]
+    fn short_data() {
+        let data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_9dc8/config"
+        ));
+        let mut header: Header = data.as_slice().try_into().unwrap();
+        header.capabilities_pointer = 0xfc;
+        // CompactPCI central resource control registers are cut by the end of DDR
+        let mut ddr = [0u8; DDR_LENGTH];
+        ddr[DDR_LENGTH - 4] = 0x0b;
+        let result = Capabilities::new(&ddr, &header).collect::<Vec<_>>();
+        let sample = vec![Err(CapabilityError::Data {
+            ptr: 0xfc,
+            source: CapabilityDataError {
+                name: "CompactPCI central resource control",
+                size: 4,
+            },
+        })];
+        assert_eq!(sample, result);
+    }
 }
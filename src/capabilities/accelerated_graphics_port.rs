@@ -106,8 +106,10 @@ use heterob::{
 };
 
 use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AcceleratedGraphicsPort {
     pub identifier: Identifier,
     pub status: Status,
@@ -190,8 +192,26 @@ impl TryFrom<&[u8]> for AcceleratedGraphicsPort {
     }
 }
 
+/// AGP Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1), // Capability ID
+    Attr::new(0x01, 1), // Next Capability Pointer
+    Attr::new(0x02, 1), // Revision
+    Attr::new(0x03, 1).rsvdp(0xff),
+    Attr::new(0x04, 4),                                    // Status
+    Attr::new(0x08, 4).rw(0xff00_ffb7).rsvdp(0x00ff_0048), // Command
+    Attr::new(0x0c, 4),                                    // Isochronous Status
+    Attr::new(0x10, 4).rw(0x0000_0380).rsvdp(0xffff_fc7f), // Control
+    Attr::new(0x14, 2).rw(0x0fff).rsvdp(0xf000),           // Aperture Size
+    Attr::new(0x16, 2).rw(0x0fff).rsvdp(0xf000),           // Enabled Aperture Page Size
+    Attr::new(0x18, 4).rw(0xffff_f000).rsvdp(0x0fff),      // GART Pointer Lower
+    Attr::new(0x1c, 4).rw(0xffff_ffff),                    // GART Pointer Upper
+    Attr::new(0x20, 2).rw(0xffc0).rsvdp(0x003f),           // Isochronous Command
+];
+
 /// The Major and Minor Revision IDs
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identifier {
     pub minor: u8,
     pub major: u8,
@@ -206,6 +226,7 @@ impl From<u16> for Identifier {
 
 /// AGP Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Status {
     /// The maximum number of AGP3.0 command requests (both
     /// asynchronous and isochronous) that can be enqueued to the target
@@ -271,9 +292,34 @@ impl From<u32> for Status {
     }
 }
 
+impl From<Status> for u32 {
+    fn from(data: Status) -> Self {
+        (u8::from(data.rate) as u32 & 0b111)
+            | (data.agp_3_0_mode as u32) << 3
+            | (data.fw as u32) << 4
+            | (data.over4g as u32) << 5
+            | (data.htrans as u32) << 6
+            | (data.gart64b as u32) << 7
+            | (data.ita_coh as u32) << 8
+            | (data.sba as u32) << 9
+            | (u8::from(data.cal_cycle) as u32 & 0b111) << 10
+            | (data.arqsz as u32 & 0b111) << 13
+            | (data.reserved as u32) << 16
+            | (data.isoch_support as u32) << 17
+            | (data.rq as u32) << 24
+    }
+}
+
+impl Register for Status {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0;
+}
+
 /// Specifies required period for core-logic initiated bus cycle for
 /// calibrating I/O buffers
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CalCycle {
     /// 4 ms
     Period4ms,
@@ -317,6 +363,7 @@ impl From<CalCycle> for u8 {
 
 /// Data Rate Support
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataRateSupport {
     /// 4x
     Speed4x,
@@ -352,6 +399,7 @@ impl From<DataRateSupport> for u8 {
 
 /// AGP Command
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Command {
     /// Maximum number of requests
     pub prq: u8,
@@ -407,8 +455,29 @@ impl From<u32> for Command {
     }
 }
 
+impl From<Command> for u32 {
+    fn from(data: Command) -> Self {
+        (u8::from(data.drate) as u32 & 0b111)
+            | (data.fw_enable as u32) << 4
+            | (data.over4g as u32) << 5
+            | (data.gart64b as u32) << 7
+            | (data.agp_enable as u32) << 8
+            | (data.sba_enable as u32) << 9
+            | (u8::from(data.pcal_cycle) as u32 & 0b111) << 10
+            | (data.parqsz as u32 & 0b111) << 13
+            | (data.prq as u32) << 24
+    }
+}
+
+impl Register for Command {
+    type Value = u32;
+    const RSVDP: u32 = 0x00ff_0048;
+    const RW1C: u32 = 0;
+}
+
 /// Data Rate Enabled
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataRateEnabled {
     /// 4x
     Speed4x,
@@ -440,6 +509,7 @@ impl From<DataRateEnabled> for u8 {
 
 /// AGP Isochronous Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IsochronousStatus {
     /// Maximum Bandwidth (both asynchronous and isochronous) of the device per
     /// us in units of 32 bytes
@@ -465,9 +535,25 @@ impl From<u32> for IsochronousStatus {
         }
     }
 }
+impl From<IsochronousStatus> for u32 {
+    fn from(data: IsochronousStatus) -> Self {
+        (data.isoch_error_code.0 as u32 & 0b11)
+            | (data.isoch_l as u32 & 0b111) << 3
+            | (data.isoch_y.0 as u32 & 0b11) << 6
+            | (data.isoch_n as u32) << 8
+            | (data.maxbw as u32) << 16
+    }
+}
+
+impl Register for IsochronousStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0;
+}
 
 /// Isochronous payload sizes supported
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IsochY(pub u8);
 
 /// Isochronous Error Codes
@@ -484,10 +570,12 @@ pub struct IsochY(pub u8);
 /// - 10 = Write Buffer Overflow
 /// - 11 = Reserved
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IsochErrorCode(pub u8);
 
 /// AGP Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Control {
     /// Calibration cycle operation is disabled by the core-logic
     pub cal_cycle_dis: bool,
@@ -507,17 +595,31 @@ impl From<u32> for Control {
         }
     }
 }
+impl From<Control> for u32 {
+    fn from(data: Control) -> Self {
+        (data.gtlben as u32) << 7 | (data.aperenb as u32) << 8 | (data.cal_cycle_dis as u32) << 9
+    }
+}
+
+impl Register for Control {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_fc7f;
+    const RW1C: u32 = 0;
+}
 
 /// AGP Aperture size
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ApertureSize(pub u16);
 
 /// AGP Enabled Aperture Page Size
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnabledAperturePageSize(pub u16);
 
 /// Graphics AGP aperture Remapping Table (GART) Pointer
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GartPointer {
     /// Bits\[31:12\] of the start of the GART
     pub gartlo: u32,
@@ -527,6 +629,7 @@ pub struct GartPointer {
 
 /// AGP Isochronous Command (NICMD)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IsochronousCommand {
     /// Software programs this field with the maximum number of isochronous
     /// transactions that the master is allowed to request from the target in a single
@@ -542,6 +645,17 @@ impl From<u16> for IsochronousCommand {
         Self { pisoch_n, pisoch_y }
     }
 }
+impl From<IsochronousCommand> for u16 {
+    fn from(data: IsochronousCommand) -> Self {
+        (data.pisoch_y as u16 & 0b11) << 6 | (data.pisoch_n as u16) << 8
+    }
+}
+
+impl Register for IsochronousCommand {
+    type Value = u16;
+    const RSVDP: u16 = 0x003f;
+    const RW1C: u16 = 0;
+}
 
 #[cfg(test)]
 mod tests {
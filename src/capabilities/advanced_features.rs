@@ -40,9 +40,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3, P4};
 
 use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// Advanced Features
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdvancedFeatures {
     /// AF Structure Length (Bytes). Shall return a value of 06h.
     pub length: u8,
@@ -62,24 +64,31 @@ impl TryFrom<&[u8]> for AdvancedFeatures {
             size: 4,
         })?;
         let Lsb((cap_tp, function_level_reset, ())) = P3::<u8, 1, 1, 6>(capabilities).into();
-        let Lsb((initiate_flr, ())) = P2::<u8, 1, 7>(control).into();
-        let Lsb((st_tp, ())) = P2::<u8, 1, 7>(status).into();
         Ok(Self {
             length,
             capabilities: Capabilities {
                 transactions_pending: cap_tp,
                 function_level_reset,
             },
-            control: Control { initiate_flr },
-            status: Status {
-                transactions_pending: st_tp,
-            },
+            control: From::<u8>::from(control),
+            status: From::<u8>::from(status),
         })
     }
 }
 
+/// Advanced Features Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                      // Capability ID
+    Attr::new(0x01, 1),                      // Next Capability Pointer
+    Attr::new(0x02, 1),                      // Length
+    Attr::new(0x03, 1),                      // AF Capabilities
+    Attr::new(0x04, 1).rw(0x01).rsvdp(0xfe), // AF Control
+    Attr::new(0x05, 1).rsvdz(0xfe),          // AF Status
+];
+
 /// AF Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Capabilities {
     /// Indicate support for the Transactions Pending (TP) bit. TP must be supported if FLR is
     /// supported.
@@ -90,16 +99,52 @@ pub struct Capabilities {
 
 /// AF Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Control {
     /// A write of 1b initiates Function Level Reset (FLR). The value read by software from this
     /// bit shall always be 0b.
     pub initiate_flr: bool,
 }
+impl From<u8> for Control {
+    fn from(byte: u8) -> Self {
+        let Lsb((initiate_flr, ())) = P2::<u8, 1, 7>(byte).into();
+        Self { initiate_flr }
+    }
+}
+impl From<Control> for u8 {
+    fn from(data: Control) -> Self {
+        data.initiate_flr as u8
+    }
+}
+impl Register for Control {
+    type Value = u8;
+    const RSVDP: u8 = 0xfe;
+    const RW1C: u8 = 0;
+}
 
 /// AF Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Status {
     /// Indicates that the Function has issued one or more non-posted transactions which have not
     /// been completed, including non-posted transactions that a target has terminated with Retry
     pub transactions_pending: bool,
 }
+impl From<u8> for Status {
+    fn from(byte: u8) -> Self {
+        let Lsb((transactions_pending, ())) = P2::<u8, 1, 7>(byte).into();
+        Self {
+            transactions_pending,
+        }
+    }
+}
+impl From<Status> for u8 {
+    fn from(data: Status) -> Self {
+        data.transactions_pending as u8
+    }
+}
+impl Register for Status {
+    type Value = u8;
+    const RSVDP: u8 = 0;
+    const RW1C: u8 = 0;
+}
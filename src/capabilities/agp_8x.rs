@@ -0,0 +1,49 @@
+/*!
+# AGP 8x
+
+AGP 3.0 core logic may report its AGP registers with AGP 8x capability ID. Register layout is
+the same as in [Accelerated Graphics Port](super::accelerated_graphics_port) capability.
+
+## Struct diagram
+[Agp8x]
+- [AcceleratedGraphicsPort]
+
+## Examples
+
+```rust
+# use pcics::capabilities::accelerated_graphics_port::*;
+# use pcics::capabilities::agp_8x::*;
+let data = [
+    0x0e, 0x00, // Header
+    0x30, 0x00, // Identifier
+    0x0a, 0x02, 0x00, 0x1f, // Status
+    0x00, 0x00, 0x00, 0x00, // Command
+];
+let Agp8x(agp) = data[2..].try_into().unwrap();
+assert_eq!(Identifier { minor: 0, major: 3 }, agp.identifier);
+assert_eq!(DataRateSupport::Speed8x, agp.status.rate);
+assert!(agp.status.agp_3_0_mode);
+```
+*/
+
+use super::{accelerated_graphics_port, AcceleratedGraphicsPort, CapabilityDataError};
+use crate::register::RegisterAttributes as Attr;
+
+/// AGP 8x
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Agp8x(pub AcceleratedGraphicsPort);
+
+impl TryFrom<&[u8]> for Agp8x {
+    type Error = CapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        slice.try_into().map(Self).map_err(|_| CapabilityDataError {
+            name: "AGP 8x",
+            size: 10,
+        })
+    }
+}
+
+/// AGP 8x Capability registers
+pub const REGISTERS: &[Attr] = accelerated_graphics_port::REGISTERS;
@@ -3,6 +3,7 @@
 use heterob::{endianness::Le, P3};
 
 use super::CapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 /// PCI Bridge Subsystem Vendor ID
 /// ```
@@ -20,6 +21,7 @@ use super::CapabilityDataError;
 /// ```
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BridgeSubsystemVendorId {
     pub reserved: u16,
     /// PCI Bridge Subsystem Vendor ID
@@ -54,3 +56,12 @@ impl<'a> TryFrom<&'a [u8]> for BridgeSubsystemVendorId {
             .map(Self::from)
     }
 }
+
+/// Bridge Subsystem Vendor ID Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1), // Capability ID
+    Attr::new(0x01, 1), // Next Capability Pointer
+    Attr::new(0x02, 2).rsvdp(0xffff),
+    Attr::new(0x04, 2), // Subsystem Vendor ID
+    Attr::new(0x06, 2), // Subsystem ID
+];
@@ -0,0 +1,156 @@
+/*!
+# CompactPCI Hot Swap
+
+This Capability structure provides a standard interface to control and sense status within a
+device that supports Hot Swap insertion and extraction in a CompactPCI system.
+
+## Struct diagram
+[CompactPciHotSwap]
+- [HotSwapControlStatus]
+
+## Examples
+
+Board was inserted, ENUM# is asserted and blue LED is on
+
+```rust
+# use pcics::capabilities::compact_pci_hot_swap::*;
+# use pcics::Register;
+let data = [0x06, 0x00, 0x88, 0x00];
+let result = data[2..].try_into().unwrap();
+let sample = CompactPciHotSwap {
+    control_status: HotSwapControlStatus {
+        device_hiding_arm: false,
+        enum_signal_mask: false,
+        pending_insertion_extraction: false,
+        led_on: true,
+        programming_interface: 0,
+        extraction: false,
+        insertion: true,
+    },
+};
+assert_eq!(sample, result);
+// Writing 1 to INS bit clears it, LED stays on
+assert_eq!(0x88, sample.control_status.clone().clear_value(0x88));
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P7};
+
+use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
+
+/// CompactPCI Hot Swap
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompactPciHotSwap {
+    pub control_status: HotSwapControlStatus,
+}
+impl TryFrom<&[u8]> for CompactPciHotSwap {
+    type Error = CapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((control_status, _reserved)),
+            ..
+        }: Seq<Le<(u8, u8)>, _> = P2(slice).try_into().map_err(|_| CapabilityDataError {
+            name: "CompactPCI Hot Swap",
+            size: 2,
+        })?;
+        Ok(Self {
+            control_status: From::<u8>::from(control_status),
+        })
+    }
+}
+
+/// CompactPCI Hot Swap Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                     // Capability ID
+    Attr::new(0x01, 1),                     // Next Capability Pointer
+    Attr::new(0x02, 1).rw(0x0b).rw1c(0xc0), // Hot Swap Control/Status
+    Attr::new(0x03, 1).rsvdp(0xff),         // Reserved
+];
+
+/// Hot Swap Control/Status Register (HS_CSR)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotSwapControlStatus {
+    /// Device Hiding Arm (DHA). Arms the hardware that hides the device from software while the
+    /// ejector handle is open.
+    pub device_hiding_arm: bool,
+    /// ENUM# Signal Mask (EIM). Prevents the board from asserting ENUM# signal.
+    pub enum_signal_mask: bool,
+    /// Pending Insertion/Extraction (PIE). Insertion or extraction process is in progress.
+    pub pending_insertion_extraction: bool,
+    /// LED On/Off (LOO). Blue LED is on, it is safe to extract the board.
+    pub led_on: bool,
+    /// Programming Interface (PI). Hot Swap software interface level implemented by the board.
+    pub programming_interface: u8,
+    /// ENUM# Status - Extraction (EXT). Board is about to be extracted, ENUM# is asserted.
+    pub extraction: bool,
+    /// ENUM# Status - Insertion (INS). Board was inserted, ENUM# is asserted.
+    pub insertion: bool,
+}
+impl From<u8> for HotSwapControlStatus {
+    fn from(byte: u8) -> Self {
+        let Lsb((
+            device_hiding_arm,
+            enum_signal_mask,
+            pending_insertion_extraction,
+            led_on,
+            programming_interface,
+            extraction,
+            insertion,
+        )) = P7::<u8, 1, 1, 1, 1, 2, 1, 1>(byte).into();
+        Self {
+            device_hiding_arm,
+            enum_signal_mask,
+            pending_insertion_extraction,
+            led_on,
+            programming_interface,
+            extraction,
+            insertion,
+        }
+    }
+}
+impl From<HotSwapControlStatus> for u8 {
+    fn from(data: HotSwapControlStatus) -> Self {
+        (data.device_hiding_arm as u8)
+            | (data.enum_signal_mask as u8) << 1
+            | (data.pending_insertion_extraction as u8) << 2
+            | (data.led_on as u8) << 3
+            | (data.programming_interface & 0b11) << 4
+            | (data.extraction as u8) << 6
+            | (data.insertion as u8) << 7
+    }
+}
+impl Register for HotSwapControlStatus {
+    type Value = u8;
+    const RSVDP: u8 = 0;
+    const RW1C: u8 = 0xc0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hot_swap_control_status() {
+        // INS+ EXT- PI=1 LOO- PIE+ EIM+ DHA-
+        let byte = 0b1001_0110;
+        let sample = HotSwapControlStatus {
+            device_hiding_arm: false,
+            enum_signal_mask: true,
+            pending_insertion_extraction: true,
+            led_on: false,
+            programming_interface: 1,
+            extraction: false,
+            insertion: true,
+        };
+        let result = HotSwapControlStatus::from(byte);
+        assert_eq!(sample, result);
+        assert_eq!(byte, u8::from(result.clone()));
+        // PIE is read-only, INS is not cleared
+        assert_eq!(0b0001_0110, result.write_value(byte));
+    }
+}
@@ -0,0 +1,251 @@
+/*!
+# CompactPCI central resource control
+
+Capability of a board which could act as the system slot controller in a CompactPCI system
+with redundant system slots (PICMG 2.13). System slot board provides central resources of the
+backplane: PCI clocks, bus arbitration, PCI reset and interrupt and ENUM# servicing. Control
+register assigns these resources, Status register reports the slot state.
+
+For details, refer to the PICMG 2.13 CompactPCI Redundant System Slot Specification. It defines
+two registers following the capability header:
+- 02h [Central Resource Control](CentralResourceControl), enable bits 4:0 and Takeover bit 15 are
+  RW
+- 04h [Central Resource Status](CentralResourceStatus), Partner Fault bit 3 is RW1C
+
+## Struct diagram
+[CompactPciResourceControl]
+- [CentralResourceControl]
+- [CentralResourceStatus]
+
+## Examples
+
+Active system slot board driving all central resources
+
+```rust
+# use pcics::capabilities::compact_pci_resource_control::*;
+let data = [0x0b, 0x00, 0x1f, 0x00, 0x07, 0x00];
+let result = data[2..].try_into().unwrap();
+let sample = CompactPciResourceControl {
+    control: CentralResourceControl {
+        clock_enable: true,
+        arbiter_enable: true,
+        reset_enable: true,
+        interrupt_enable: true,
+        enum_enable: true,
+        takeover: false,
+    },
+    status: CentralResourceStatus {
+        system_slot: true,
+        active: true,
+        partner_present: true,
+        partner_fault: false,
+    },
+};
+assert_eq!(sample, result);
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P5, P7};
+
+use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
+
+/// CompactPCI central resource control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompactPciResourceControl {
+    pub control: CentralResourceControl,
+    pub status: CentralResourceStatus,
+}
+impl TryFrom<&[u8]> for CompactPciResourceControl {
+    type Error = CapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((control, status)),
+            ..
+        } = P2(slice).try_into().map_err(|_| CapabilityDataError {
+            name: "CompactPCI central resource control",
+            size: 4,
+        })?;
+        Ok(Self {
+            control: From::<u16>::from(control),
+            status: From::<u16>::from(status),
+        })
+    }
+}
+
+/// CompactPCI central resource control Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                            // Capability ID
+    Attr::new(0x01, 1),                            // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x801f).rsvdp(0x7fe0),   // Central Resource Control
+    Attr::new(0x04, 2).rw1c(0x0008).rsvdz(0xfff0), // Central Resource Status
+];
+
+/// Central Resource Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CentralResourceControl {
+    /// Board drives PCI clocks of the backplane
+    pub clock_enable: bool,
+    /// Board arbitrates PCI bus requests
+    pub arbiter_enable: bool,
+    /// Board drives PCI RST#
+    pub reset_enable: bool,
+    /// Board services INTA#..INTD# interrupts
+    pub interrupt_enable: bool,
+    /// Board services ENUM# signal of hot swap boards
+    pub enum_enable: bool,
+    /// Request to take over central resources from the partner system slot board
+    pub takeover: bool,
+}
+impl From<u16> for CentralResourceControl {
+    fn from(word: u16) -> Self {
+        let Lsb((
+            clock_enable,
+            arbiter_enable,
+            reset_enable,
+            interrupt_enable,
+            enum_enable,
+            (),
+            takeover,
+        )) = P7::<u16, 1, 1, 1, 1, 1, 10, 1>(word).into();
+        Self {
+            clock_enable,
+            arbiter_enable,
+            reset_enable,
+            interrupt_enable,
+            enum_enable,
+            takeover,
+        }
+    }
+}
+impl From<CentralResourceControl> for u16 {
+    fn from(data: CentralResourceControl) -> Self {
+        (data.clock_enable as u16)
+            | (data.arbiter_enable as u16) << 1
+            | (data.reset_enable as u16) << 2
+            | (data.interrupt_enable as u16) << 3
+            | (data.enum_enable as u16) << 4
+            | (data.takeover as u16) << 15
+    }
+}
+impl Register for CentralResourceControl {
+    type Value = u16;
+    const RSVDP: u16 = 0x7fe0;
+    const RW1C: u16 = 0;
+}
+
+/// Central Resource Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CentralResourceStatus {
+    /// Board is installed in a system slot (SYSEN# is asserted)
+    pub system_slot: bool,
+    /// Board currently owns central resources
+    pub active: bool,
+    /// Partner system slot board is installed
+    pub partner_present: bool,
+    /// Partner system slot board reported a fault
+    pub partner_fault: bool,
+}
+impl From<u16> for CentralResourceStatus {
+    fn from(word: u16) -> Self {
+        let Lsb((system_slot, active, partner_present, partner_fault, ())) =
+            P5::<u16, 1, 1, 1, 1, 12>(word).into();
+        Self {
+            system_slot,
+            active,
+            partner_present,
+            partner_fault,
+        }
+    }
+}
+impl From<CentralResourceStatus> for u16 {
+    fn from(data: CentralResourceStatus) -> Self {
+        (data.system_slot as u16)
+            | (data.active as u16) << 1
+            | (data.partner_present as u16) << 2
+            | (data.partner_fault as u16) << 3
+    }
+}
+impl Register for CentralResourceStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0008;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{capabilities::CapabilityError, Capabilities, Header, DDR_OFFSET};
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[test]
+    fn parse() {
+        // Standby system slot board, partner board is active and reported a fault
+        let data = [0x0b, 0x00, 0x00, 0x80, 0x0d, 0x00];
+        let result: CompactPciResourceControl = data[2..].try_into().unwrap();
+        let sample = CompactPciResourceControl {
+            control: CentralResourceControl {
+                clock_enable: false,
+                arbiter_enable: false,
+                reset_enable: false,
+                interrupt_enable: false,
+                enum_enable: false,
+                takeover: true,
+            },
+            status: CentralResourceStatus {
+                system_slot: true,
+                active: false,
+                partner_present: true,
+                partner_fault: true,
+            },
+        };
+        assert_eq!(sample, result);
+        assert_eq!(0x8000, u16::from(result.control));
+        assert_eq!(0x000d, u16::from(result.status));
+    }
+
+    #[test]
+    fn partner_fault_write_back() {
+        let current = 0x000f;
+        let mut status = CentralResourceStatus::from(current);
+        // Read-only bits are written as read, Partner Fault is not cleared
+        assert_eq!(0x0007, status.clone().write_value(current));
+        assert_eq!(0x000f, status.clone().clear_value(current));
+        status.partner_fault = false;
+        assert_eq!(0x0007, status.clear_value(current));
+        // Reserved bits are preserved, RW bits are taken from the value
+        let control = CentralResourceControl::from(0x001f);
+        assert_eq!(0x7fff, control.write_value(0x7fe0));
+    }
+
+    #[test]
+    fn short_data() {
+        let result = CompactPciResourceControl::try_from(&[0x1f][..]);
+        let sample = CapabilityDataError {
+            name: "CompactPCI central resource control",
+            size: 4,
+        };
+        assert_eq!(Err(sample), result);
+
+        // Capability at the end of DDR has Control register only
+        let data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_9dc8/config"
+        ));
+        let mut header: Header = data.as_slice().try_into().unwrap();
+        header.capabilities_pointer = 0xfc;
+        let mut ddr = [0u8; 0x100 - DDR_OFFSET];
+        ddr[0xfc - DDR_OFFSET] = 0x0b;
+        let result = Capabilities::new(&ddr, &header).collect::<Vec<_>>();
+        let sample = vec![Err(CapabilityError::Data {
+            ptr: 0xfc,
+            source: sample,
+        })];
+        assert_eq!(sample, result);
+    }
+}
@@ -16,9 +16,11 @@ assert_eq!(DebugPort { offset: 0x00a0, bar_number: 1 }, dp);
 use heterob::{endianness::LeBytesTryInto, Seq};
 
 use super::CapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 /// Debug port
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DebugPort {
     /// Indicates the byte offset (up to 4K) within the BAR indicated by BAR#
     pub offset: u16,
@@ -41,3 +43,10 @@ impl TryFrom<&[u8]> for DebugPort {
         })
     }
 }
+
+/// Debug Port Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1), // Capability ID
+    Attr::new(0x01, 1), // Next Capability Pointer
+    Attr::new(0x02, 2), // Debug Port
+];
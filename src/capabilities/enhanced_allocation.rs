@@ -52,12 +52,20 @@ use heterob::{
     Seq, P3, P8,
 };
 
-use crate::{header::HeaderType, Header};
+use crate::{
+    header::HeaderType,
+    register::{RegisterAttributes as Attr, RegisterMap},
+    Header,
+};
 
 use snafu::Snafu;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Enhanced Allocation Errors
 #[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EnhancedAllocationError {
     #[snafu(display("number of entries is unreadable"))]
     NumEntries,
@@ -67,6 +75,7 @@ pub enum EnhancedAllocationError {
 
 /// Enhanced Allocation (EA) Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EnhancedAllocation<'a> {
     pub num_entries: u8,
     pub type_1_second_dw: Option<Type1SecondDw>,
@@ -103,6 +112,50 @@ impl<'a> EnhancedAllocation<'a> {
     }
 }
 
+impl<'a> EnhancedAllocation<'a> {
+    /// Access attributes of registers preceding entries
+    ///
+    /// Entries have variable size and are not described.
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        if self.type_1_second_dw.is_some() {
+            map
+        } else {
+            map.truncate(0x04)
+        }
+    }
+}
+
+/// Owned [EnhancedAllocation] with decoded entries
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedEnhancedAllocation {
+    pub num_entries: u8,
+    pub type_1_second_dw: Option<Type1SecondDw>,
+    pub entries: Vec<EnhancedAllocationEntry>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&EnhancedAllocation<'a>> for OwnedEnhancedAllocation {
+    fn from(ea: &EnhancedAllocation<'a>) -> Self {
+        Self {
+            num_entries: ea.num_entries,
+            type_1_second_dw: ea.type_1_second_dw.clone(),
+            entries: ea.entries.clone().collect(),
+        }
+    }
+}
+
+/// Enhanced Allocation Capability registers, second DW is present only for Type 1 functions
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),             // Capability ID
+    Attr::new(0x01, 1),             // Next Capability Pointer
+    Attr::new(0x02, 1).rsvdp(0xc0), // Num Entries
+    Attr::new(0x03, 1).rsvdp(0xff),
+    Attr::new(0x04, 4), // Fixed Secondary/Subordinate Bus Numbers
+];
+
 /// An iterator through Enhanced Allocation entries
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnhancedAllocationEntries<'a> {
@@ -193,8 +246,16 @@ impl<'a> Iterator for EnhancedAllocationEntries<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for EnhancedAllocationEntries<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 /// For Type 1 functions only, there is a second DW in the capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Type1SecondDw {
     /// Bus number of the PCI bus segment to which the secondary interface of
     /// this Function is connected
@@ -205,6 +266,7 @@ pub struct Type1SecondDw {
 }
 /// Entry for Enhanced Allocation Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnhancedAllocationEntry {
     /// Entry Size
     pub entry_size: u8,
@@ -228,6 +290,7 @@ pub struct EnhancedAllocationEntry {
 
 /// BAR Equivalent Indicator (BEI)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BarEquivalentIndicator {
     /// Entry is equivalent to BAR at location 10h
     Location10h,
@@ -292,6 +355,7 @@ impl From<u8> for BarEquivalentIndicator {
 /// Enhanced Allocation Entry Field Value Definitions for both the Primary
 /// Properties and Secondary Properties Fields
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ResourceDefinition {
     /// Memory Space, Non-Prefetchable
     MemorySpaceNonPrefetchable,
@@ -345,6 +409,7 @@ impl From<u8> for ResourceDefinition {
 
 /// 32b or 64b address type
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ResourceRangeAddress {
     U32(u32),
     U64(u64),
@@ -79,9 +79,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P10, P3, P4, P5, P9};
 
 use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// Flattening Portal Bridge (FPB) Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FlatteningPortalBridge {
     pub reserved: u16,
     pub fpb_capabilities: FpbCapabilities,
@@ -112,6 +114,21 @@ impl TryFrom<&[u8]> for FlatteningPortalBridge {
     }
 }
 
+/// FPB Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1), // Capability ID
+    Attr::new(0x01, 1), // Next Capability Pointer
+    Attr::new(0x02, 2).rsvdp(0xffff),
+    Attr::new(0x04, 4),                                    // FPB Capabilities
+    Attr::new(0x08, 4).rw(0xfff8_00f1).rsvdp(0x0007_ff0e), // FPB RID Vector Control 1
+    Attr::new(0x0c, 4).rw(0x0000_fff8).rsvdp(0xffff_0007), // FPB RID Vector Control 2
+    Attr::new(0x10, 4).rw(0xfff0_00f1).rsvdp(0x000f_ff0e), // FPB MEM Low Vector Control
+    Attr::new(0x14, 4).rw(0xf000_00f1).rsvdp(0x0fff_ff0e), // FPB MEM High Vector Control 1
+    Attr::new(0x18, 4).rw(0xffff_ffff),                    // FPB MEM High Vector Control 2
+    Attr::new(0x1c, 4).rw(0x0000_c0ff).rsvdp(0xffff_3f00), // FPB Vector Access Control
+    Attr::new(0x20, 4).rw(0xffff_ffff),                    // FPB Vector Access Data
+];
+
 impl From<[u8; Self::SIZE]> for FlatteningPortalBridge {
     fn from(bytes: [u8; Self::SIZE]) -> Self {
         let Le((
@@ -145,6 +162,7 @@ impl From<[u8; Self::SIZE]> for FlatteningPortalBridge {
 
 /// FPB Capabilities Register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FpbCapabilities {
     /// Indicates that the FPB RID Vector mechanism is supported
     pub fpb_rid_decode_mechanism_supported: bool,
@@ -191,6 +209,7 @@ impl From<u32> for FpbCapabilities {
 /// allowed values software is permitted to write to the FPB RID Vector Granularity
 /// field
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FpbRidVectorSizeSupported {
     /// 256 bits, allowed granularities in RID units: 8, 64, 256
     Size256bits,
@@ -217,6 +236,7 @@ impl From<u8> for FpbRidVectorSizeSupported {
 /// constrains the allowed values software is permitted to write to the FPB MEM Low
 /// Vector Start field
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FpbMemLowVectorSizeSupported {
     /// 256 bits, allowed granularities in MB units: 1, 2, 4, 8, 16
     Size256bits,
@@ -247,6 +267,7 @@ impl From<u8> for FpbMemLowVectorSizeSupported {
 
 /// Indicates the size of the FPB MEM High Vector implemented in hardware
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FpbMemHighVectorSizeSupported {
     /// 256 bits
     Size256bits,
@@ -280,6 +301,7 @@ impl From<u8> for FpbMemHighVectorSizeSupported {
 
 /// FPB RID Vector Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FpbRidVectorControl {
     /// Enables the FPB RID Decode mechanism
     pub fpb_rid_decode_mechanism_enable: bool,
@@ -305,8 +327,20 @@ impl FpbRidVectorControl {
     }
 }
 
+/// FPB RID Vector Control 1 and 2 register values
+impl From<FpbRidVectorControl> for [u32; 2] {
+    fn from(data: FpbRidVectorControl) -> Self {
+        let control_1 = (data.fpb_rid_decode_mechanism_enable as u32)
+            | (u8::from(data.fpb_rid_vector_granularity) as u32 & 0b1111) << 4
+            | (data.fpb_rid_vector_start as u32 & !0b111) << 16;
+        let control_2 = (data.rid_secondary_start as u32 & 0x1fff) << 3;
+        [control_1, control_2]
+    }
+}
+
 /// Controls the granularity and the required alignment of the FPB RID Vector
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FpbRidVectorGranularity {
     /// 8 RIDs
     Granularity8RIDs,
@@ -329,8 +363,20 @@ impl From<u8> for FpbRidVectorGranularity {
     }
 }
 
+impl From<FpbRidVectorGranularity> for u8 {
+    fn from(data: FpbRidVectorGranularity) -> Self {
+        match data {
+            FpbRidVectorGranularity::Granularity8RIDs => 0b0000,
+            FpbRidVectorGranularity::Granularity64RIDs => 0b0011,
+            FpbRidVectorGranularity::Granularity256RIDs => 0b0101,
+            FpbRidVectorGranularity::Reserved(v) => v,
+        }
+    }
+}
+
 /// FPB MEM Low Vector Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FpbMemLowVectorControl {
     /// Enables the FPB MEM Low Decode mechanism
     pub fpb_mem_low_decode_mechanism_enable: bool,
@@ -352,8 +398,23 @@ impl From<u32> for FpbMemLowVectorControl {
     }
 }
 
+impl From<FpbMemLowVectorControl> for u32 {
+    fn from(data: FpbMemLowVectorControl) -> Self {
+        (data.fpb_mem_low_decode_mechanism_enable as u32)
+            | (u8::from(data.fpb_mem_low_vector_granularity) as u32 & 0b1111) << 4
+            | (data.fpb_mem_low_vector_start & 0xfff0_0000)
+    }
+}
+
+impl Register for FpbMemLowVectorControl {
+    type Value = u32;
+    const RSVDP: u32 = 0x000f_ff0e;
+    const RW1C: u32 = 0;
+}
+
 /// Controls the granularity and the required alignment of the FPB MEM Low Vector
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FpbMemLowVectorGranularity {
     /// 1 MB
     Granularity1MB,
@@ -382,8 +443,22 @@ impl From<u8> for FpbMemLowVectorGranularity {
     }
 }
 
+impl From<FpbMemLowVectorGranularity> for u8 {
+    fn from(data: FpbMemLowVectorGranularity) -> Self {
+        match data {
+            FpbMemLowVectorGranularity::Granularity1MB => 0b0000,
+            FpbMemLowVectorGranularity::Granularity2MB => 0b0001,
+            FpbMemLowVectorGranularity::Granularity4MB => 0b0010,
+            FpbMemLowVectorGranularity::Granularity8MB => 0b0011,
+            FpbMemLowVectorGranularity::Granularity16MB => 0b0100,
+            FpbMemLowVectorGranularity::Reserved(v) => v,
+        }
+    }
+}
+
 /// FPB MEM High Vector Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FpbMemHighVectorControl {
     pub fpb_mem_high_decode_mechanism_enable: bool,
     pub fpb_mem_high_vector_granularity: FpbMemHighVectorGranularity,
@@ -406,8 +481,20 @@ impl FpbMemHighVectorControl {
     }
 }
 
+/// FPB MEM High Vector Control 1 and 2 register values
+impl From<FpbMemHighVectorControl> for [u32; 2] {
+    fn from(data: FpbMemHighVectorControl) -> Self {
+        let start = data.fpb_mem_high_vector_start;
+        let control_1 = (data.fpb_mem_high_decode_mechanism_enable as u32)
+            | (u8::from(data.fpb_mem_high_vector_granularity) as u32 & 0b1111) << 4
+            | (start as u32 & 0xf000_0000);
+        [control_1, (start >> 32) as u32]
+    }
+}
+
 /// Controls the granularity and the required alignment of the FPB MEM High Vector
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FpbMemHighVectorGranularity {
     /// 256 MB
     Granularity256MB,
@@ -445,8 +532,25 @@ impl From<u8> for FpbMemHighVectorGranularity {
     }
 }
 
+impl From<FpbMemHighVectorGranularity> for u8 {
+    fn from(data: FpbMemHighVectorGranularity) -> Self {
+        match data {
+            FpbMemHighVectorGranularity::Granularity256MB => 0b0000,
+            FpbMemHighVectorGranularity::Granularity512MB => 0b0001,
+            FpbMemHighVectorGranularity::Granularity1GB => 0b0010,
+            FpbMemHighVectorGranularity::Granularity2GB => 0b0011,
+            FpbMemHighVectorGranularity::Granularity4GB => 0b0100,
+            FpbMemHighVectorGranularity::Granularity8GB => 0b0101,
+            FpbMemHighVectorGranularity::Granularity16GB => 0b0110,
+            FpbMemHighVectorGranularity::Granularity32GB => 0b0111,
+            FpbMemHighVectorGranularity::Reserved(v) => v,
+        }
+    }
+}
+
 /// FPB Vector Access Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FpbVectorAccessControl {
     /// Indicates the offset of the DWORD portion of the FPB RID, MEM Low or
     /// MEM High, Vector that can be read or written
@@ -464,8 +568,21 @@ impl From<u32> for FpbVectorAccessControl {
     }
 }
 
+impl From<FpbVectorAccessControl> for u32 {
+    fn from(data: FpbVectorAccessControl) -> Self {
+        (data.fpb_vector_access_offset as u32) | (u8::from(data.fpb_vector_select) as u32) << 14
+    }
+}
+
+impl Register for FpbVectorAccessControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_3f00;
+    const RW1C: u32 = 0;
+}
+
 /// Selects the Vector to be accessed at the indicated FPB Vector Access Offset
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FpbVectorSelect {
     /// RID
     Rid,
@@ -488,3 +605,14 @@ impl From<u8> for FpbVectorSelect {
         }
     }
 }
+
+impl From<FpbVectorSelect> for u8 {
+    fn from(data: FpbVectorSelect) -> Self {
+        match data {
+            FpbVectorSelect::Rid => 0b00,
+            FpbVectorSelect::MemLow => 0b01,
+            FpbVectorSelect::MemHigh => 0b10,
+            FpbVectorSelect::Reserved => 0b11,
+        }
+    }
+}
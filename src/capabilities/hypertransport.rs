@@ -26,8 +26,11 @@
 use heterob::{bit_numbering::Lsb, endianness::Le, P10, P11, P13, P16, P17, P2, P3, P5, P6, P8};
 use snafu::Snafu;
 
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
 /// HyperTransport errors
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum HypertransportError {
     CapabilityType,
     SlaveOrPrimaryInterface,
@@ -52,6 +55,7 @@ pub enum HypertransportError {
 /// The layout of the capabilities block is determined by the value in the Capability Type field in
 /// the Command register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Hypertransport {
     /// Slave or Primary Interface
     SlaveOrPrimaryInterface(SlaveOrPrimaryInterface),
@@ -95,6 +99,7 @@ pub enum Hypertransport {
 impl<'a> TryFrom<&'a [u8]> for Hypertransport {
     type Error = HypertransportError;
 
+    #[allow(clippy::unnecessary_cast)]
     fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
         let cmd = slice
             .get(..2)
@@ -173,8 +178,81 @@ impl<'a> From<&'a Hypertransport> for u8 {
     }
 }
 
+impl Hypertransport {
+    /// Access attributes of registers of the actual capability type
+    ///
+    /// Only the Command register is described for capability types without decoded registers.
+    pub fn registers(&self) -> RegisterMap {
+        match self {
+            Self::SlaveOrPrimaryInterface(_) => SLAVE_OR_PRIMARY_REGISTERS.into(),
+            Self::HostOrSecondaryInterface(_) => HOST_OR_SECONDARY_REGISTERS.into(),
+            Self::MsiMapping(_) => MSI_MAPPING_REGISTERS.into(),
+            _ => REGISTERS.into(),
+        }
+    }
+}
+
+/// HyperTransport Capability common registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1), // Capability ID
+    Attr::new(0x01, 1), // Next Capability Pointer
+    Attr::new(0x02, 2), // Command
+];
+
+/// Slave/Primary Interface registers
+pub const SLAVE_OR_PRIMARY_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                         // Capability ID
+    Attr::new(0x01, 1),                         // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x181f),              // Command
+    Attr::new(0x04, 2).rw(0xf08f).rw1c(0x0f10), // Link Control 0
+    Attr::new(0x06, 2).rw(0xff00),              // Link Config 0
+    Attr::new(0x08, 2).rw(0xf08f).rw1c(0x0f10), // Link Control 1
+    Attr::new(0x0a, 2).rw(0xff00),              // Link Config 1
+    Attr::new(0x0c, 1),                         // Revision ID
+    Attr::new(0x0d, 1).rw(0x0f).rw1c(0xf0),     // Link Freq 0/Link Error 0
+    Attr::new(0x0e, 2),                         // Link Freq Cap 0
+    Attr::new(0x10, 1),                         // Feature
+    Attr::new(0x11, 1).rw(0x0f).rw1c(0xf0),     // Link Freq 1/Link Error 1
+    Attr::new(0x12, 2),                         // Link Freq Cap 1
+    Attr::new(0x14, 2).rw(0xffff),              // Enumeration Scratchpad
+    Attr::new(0x16, 2).rw(0xfcff).rw1c(0x0200), // Error Handling
+    Attr::new(0x18, 1).rw(0xff),                // Mem Base Upper
+    Attr::new(0x19, 1).rw(0xff),                // Mem Limit Upper
+    Attr::new(0x1a, 1).rw(0xff),                // Bus Number
+    Attr::new(0x1b, 1).rsvdp(0xff),
+];
+
+/// Host/Secondary Interface registers
+pub const HOST_OR_SECONDARY_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                       // Capability ID
+    Attr::new(0x01, 1),                                       // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x157f).rw1c(0x0800).rsvdp(0x0200), // Command
+    Attr::new(0x04, 2).rw(0xf08f).rw1c(0x0f10),               // Link Control
+    Attr::new(0x06, 2).rw(0xff00),                            // Link Config
+    Attr::new(0x08, 1),                                       // Revision ID
+    Attr::new(0x09, 1).rw(0x0f).rw1c(0xf0),                   // Link Freq/Link Error
+    Attr::new(0x0a, 2),                                       // Link Freq Cap
+    Attr::new(0x0c, 2),                                       // Feature
+    Attr::new(0x0e, 2).rsvdp(0xffff),
+    Attr::new(0x10, 2).rw(0xffff), // Enumeration Scratchpad
+    Attr::new(0x12, 2).rw(0xfcff).rw1c(0x0200), // Error Handling
+    Attr::new(0x14, 1).rw(0xff),   // Mem Base Upper
+    Attr::new(0x15, 1).rw(0xff),   // Mem Limit Upper
+    Attr::new(0x16, 2).rsvdp(0xffff),
+];
+
+/// MSI Mapping registers
+pub const MSI_MAPPING_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                    // Capability ID
+    Attr::new(0x01, 1),                                    // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x0001),                         // Command
+    Attr::new(0x04, 4).rw(0xfff0_0000).rsvdp(0x000f_ffff), // Mapping Address Lower
+    Attr::new(0x08, 4).rw(0xffff_ffff),                    // Mapping Address Upper
+];
+
 /// Slave/Primary Interface
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlaveOrPrimaryInterface {
     /// Command
     pub command: SlaveOrPrimaryCommand,
@@ -284,6 +362,7 @@ impl From<[u8; Self::SIZE]> for SlaveOrPrimaryInterface {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlaveOrPrimaryCommand {
     /// Base UnitID
     pub base_unitid: u8,
@@ -317,7 +396,24 @@ impl From<u16> for SlaveOrPrimaryCommand {
     }
 }
 
+impl From<SlaveOrPrimaryCommand> for u16 {
+    fn from(data: SlaveOrPrimaryCommand) -> Self {
+        (data.base_unitid as u16 & 0b11111)
+            | (data.unit_count as u16 & 0b11111) << 5
+            | (data.master_host as u16) << 10
+            | (data.default_direction as u16) << 11
+            | (data.drop_on_uninitialized_link as u16) << 12
+    }
+}
+
+impl Register for SlaveOrPrimaryCommand {
+    type Value = u16;
+    const RSVDP: u16 = 0xe000;
+    const RW1C: u16 = 0;
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkControl {
     /// Source ID Enable
     pub source_id_enable: bool,
@@ -382,7 +478,32 @@ impl From<u16> for LinkControl {
     }
 }
 
+impl From<LinkControl> for u16 {
+    fn from(data: LinkControl) -> Self {
+        (data.source_id_enable as u16)
+            | (data.crc_flood_enable as u16) << 1
+            | (data.crc_start_test as u16) << 2
+            | (data.crc_force_error as u16) << 3
+            | (data.link_failure as u16) << 4
+            | (data.initialization_complete as u16) << 5
+            | (data.end_of_chain as u16) << 6
+            | (data.transmitter_off as u16) << 7
+            | (data.crc_error as u16 & 0b1111) << 8
+            | (data.isochronous_flow_control_enable as u16) << 12
+            | (data.ldtstop_tristate_enable as u16) << 13
+            | (data.extended_ctl_time as u16) << 14
+            | (data.enable_64_bit_addressing as u16) << 15
+    }
+}
+
+impl Register for LinkControl {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0f10;
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkConfiguration {
     /// Max Link Width In
     pub max_link_width_in: LinkWidth,
@@ -427,9 +548,29 @@ impl From<u16> for LinkConfiguration {
     }
 }
 
+impl From<LinkConfiguration> for u16 {
+    fn from(data: LinkConfiguration) -> Self {
+        (u8::from(data.max_link_width_in) as u16 & 0b111)
+            | (data.doubleword_flow_control_in as u16) << 3
+            | (u8::from(data.max_link_width_out) as u16 & 0b111) << 4
+            | (data.doubleword_flow_control_out as u16) << 7
+            | (u8::from(data.link_width_in) as u16 & 0b111) << 8
+            | (data.doubleword_flow_control_in_enable as u16) << 11
+            | (u8::from(data.link_width_out) as u16 & 0b111) << 12
+            | (data.doubleword_flow_control_out_enable as u16) << 15
+    }
+}
+
+impl Register for LinkConfiguration {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
+
 /// Indicate the physical width of the incoming side of the HyperTransport link implemented by this
 /// device. Unganged links indicate a maximum width of 8 bits.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkWidth {
     /// 8 bits
     Width8bits,
@@ -459,13 +600,28 @@ impl From<u8> for LinkWidth {
         }
     }
 }
+#[allow(clippy::derivable_impls)]
 impl Default for LinkWidth {
     fn default() -> Self {
         Self::Width8bits
     }
 }
+impl From<LinkWidth> for u8 {
+    fn from(data: LinkWidth) -> Self {
+        match data {
+            LinkWidth::Width8bits => 0b000,
+            LinkWidth::Width16bits => 0b001,
+            LinkWidth::Width32bits => 0b011,
+            LinkWidth::Width2bits => 0b100,
+            LinkWidth::Width4bits => 0b101,
+            LinkWidth::NotConnected => 0b111,
+            LinkWidth::Reserved(v) => v,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RevisionId {
     pub minor: u8,
     pub major: u8,
@@ -488,6 +644,7 @@ impl<'a> From<&'a RevisionId> for u8 {
 /// The Link Frequency register specifies the operating frequency of the link’s transmitter
 /// clock—the data rate is twice this value.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkFrequency {
     Rate200MHz,
     Rate300MHz,
@@ -538,6 +695,7 @@ impl LinkFrequency {
         }
     }
 }
+#[allow(clippy::derivable_impls)]
 impl Default for LinkFrequency {
     fn default() -> Self {
         Self::Rate200MHz
@@ -545,6 +703,7 @@ impl Default for LinkFrequency {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkError {
     /// Protocol Error
     pub protocol_error: bool,
@@ -557,6 +716,7 @@ pub struct LinkError {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkFrequencyCapability {
     pub supports_200mhz: bool,
     pub supports_300mhz: bool,
@@ -618,6 +778,7 @@ impl From<u16> for LinkFrequencyCapability {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FeatureCapability {
     /// Isochronous Flow Control Mode
     pub isochronous_flow_control_mode: bool,
@@ -669,6 +830,7 @@ impl From<u16> for FeatureCapability {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ErrorHandling {
     /// Protocol Error Flood Enable
     pub protocol_error_flood_enable: bool,
@@ -745,8 +907,36 @@ impl From<u16> for ErrorHandling {
     }
 }
 
+impl From<ErrorHandling> for u16 {
+    fn from(data: ErrorHandling) -> Self {
+        (data.protocol_error_flood_enable as u16)
+            | (data.overflow_error_flood_enable as u16) << 1
+            | (data.protocol_error_fatal_enable as u16) << 2
+            | (data.overflow_error_fatal_enable as u16) << 3
+            | (data.end_of_chain_error_fatal_enable as u16) << 4
+            | (data.response_error_fatal_enable as u16) << 5
+            | (data.crc_error_fatal_enable as u16) << 6
+            | (data.system_error_fatal_enable as u16) << 7
+            | (data.chain_fail as u16) << 8
+            | (data.response_error as u16) << 9
+            | (data.protocol_error_nonfatal_enable as u16) << 10
+            | (data.overflow_error_nonfatal_enable as u16) << 11
+            | (data.end_of_chain_error_nonfatal_enable as u16) << 12
+            | (data.response_error_nonfatal_enable as u16) << 13
+            | (data.crc_error_nonfatal_enable as u16) << 14
+            | (data.system_error_nonfatal_enable as u16) << 15
+    }
+}
+
+impl Register for ErrorHandling {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0200;
+}
+
 /// Host/Secondary Interface
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HostOrSecondaryInterface {
     /// Command
     pub command: HostOrSecondaryCommand,
@@ -824,6 +1014,7 @@ impl From<[u8; Self::SIZE]> for HostOrSecondaryInterface {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HostOrSecondaryCommand {
     /// Warm Reset
     pub warm_reset: bool,
@@ -870,26 +1061,52 @@ impl From<u16> for HostOrSecondaryCommand {
     }
 }
 
+impl From<HostOrSecondaryCommand> for u16 {
+    fn from(data: HostOrSecondaryCommand) -> Self {
+        (data.warm_reset as u16)
+            | (data.double_ended as u16) << 1
+            | (data.device_number as u16 & 0b11111) << 2
+            | (data.chain_side as u16) << 7
+            | (data.host_hide as u16) << 8
+            | (data.act_as_slave as u16) << 10
+            | (data.host_inbound_end_of_chain_error as u16) << 11
+            | (data.drop_on_uninitialized_link as u16) << 12
+    }
+}
+
+impl Register for HostOrSecondaryCommand {
+    type Value = u16;
+    const RSVDP: u16 = 0xe200;
+    const RW1C: u16 = 0x0800;
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Switch {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReservedHost {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterruptDiscoveryAndConfiguration {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnitIdClumping {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtendedConfigurationSpaceAccess {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AddressMapping {}
 
 /// MSI Mapping Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MsiMapping {
     /// Indicating if the mapping is active
     pub enabled: bool,
@@ -924,27 +1141,35 @@ impl From<[u8; Self::SIZE]> for MsiMapping {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DirectRoute {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VCSet {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RetryMode {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct X86Encoding {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gen3 {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionLevelExtension {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PowerManagement {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HighNodeCount {}
 
 #[cfg(test)]
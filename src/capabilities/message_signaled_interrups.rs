@@ -9,6 +9,8 @@
 use heterob::{bit_numbering::Lsb, endianness::Le, P3, P4, P5, P6, P8};
 use snafu::prelude::*;
 
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
 /// MSI Capability Structure for 32-bit Message Address
 pub const MSI_32BIT_SIZE: usize = 2 + 4 + 2 + 2;
 /// MSI Capability Structure for 64-bit Message Address
@@ -20,6 +22,7 @@ pub const MSI_64BIT_PVM_SIZE: usize = 2 + 4 + 4 + 2 + 2 + 4 + 4;
 
 /// MSI Errors
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum MessageSignaledInterrupsError {
     MessageControl,
     Size32bit,
@@ -32,7 +35,8 @@ pub enum MessageSignaledInterrupsError {
 /// address specified by the contents of the Message Address register (and, optionally, the Message
 /// Upper Address register for a 64-bit message address). A read of the address specified by the
 /// contents of the Message Address register produces undefined results.
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MessageSignaledInterrups {
     pub message_control: MessageControl,
     pub message_address: MessageAddress,
@@ -56,26 +60,11 @@ impl<'a> TryFrom<&'a [u8]> for MessageSignaledInterrups {
                 Some(u16::from_le_bytes(bytes))
             })
             .ok_or(MessageSignaledInterrupsError::MessageControl)?;
-        let Lsb((
-            msi_enable,
-            mmc,
-            mme,
-            a_64_bit_address_capable,
-            per_vector_masking_capable,
-            extended_message_data_capable,
-            extended_message_data_enable,
-            (),
-        )) = P8::<_, 1, 3, 3, 1, 1, 1, 1, 5>(mc).into();
-        let message_control = MessageControl {
-            msi_enable,
-            multiple_message_capable: MultipleMessage(mmc),
-            multiple_message_enable: MultipleMessage(mme),
-            a_64_bit_address_capable,
-            per_vector_masking_capable,
-            extended_message_data_capable,
-            extended_message_data_enable,
-        };
-        let msi = match (a_64_bit_address_capable, per_vector_masking_capable) {
+        let message_control = MessageControl::from(mc);
+        let msi = match (
+            message_control.a_64_bit_address_capable,
+            message_control.per_vector_masking_capable,
+        ) {
             (false, false) => {
                 let Le((addr, message_data, extended_message_data)) = slice
                     .get(MessageControl::SIZE..MSI_32BIT_SIZE)
@@ -159,8 +148,68 @@ impl<'a> TryFrom<&'a [u8]> for MessageSignaledInterrups {
     }
 }
 
+impl MessageSignaledInterrups {
+    /// Access attributes of registers of the actual structure layout
+    pub fn registers(&self) -> RegisterMap {
+        let mc = &self.message_control;
+        match (mc.a_64_bit_address_capable, mc.per_vector_masking_capable) {
+            (false, false) => MSI_32BIT_REGISTERS.into(),
+            (true, false) => MSI_64BIT_REGISTERS.into(),
+            (false, true) => MSI_32BIT_PVM_REGISTERS.into(),
+            (true, true) => MSI_64BIT_PVM_REGISTERS.into(),
+        }
+    }
+}
+
+/// MSI Capability registers for 32-bit Message Address
+pub const MSI_32BIT_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                    // Capability ID
+    Attr::new(0x01, 1),                                    // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x0471).rsvdp(0xf800),           // Message Control
+    Attr::new(0x04, 4).rw(0xffff_fffc).rsvdp(0x0000_0003), // Message Address
+    Attr::new(0x08, 2).rw(0xffff),                         // Message Data
+    Attr::new(0x0a, 2).rw(0xffff),                         // Extended Message Data
+];
+
+/// MSI Capability registers for 64-bit Message Address
+pub const MSI_64BIT_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                    // Capability ID
+    Attr::new(0x01, 1),                                    // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x0471).rsvdp(0xf800),           // Message Control
+    Attr::new(0x04, 4).rw(0xffff_fffc).rsvdp(0x0000_0003), // Message Address
+    Attr::new(0x08, 4).rw(0xffff_ffff),                    // Message Upper Address
+    Attr::new(0x0c, 2).rw(0xffff),                         // Message Data
+    Attr::new(0x0e, 2).rw(0xffff),                         // Extended Message Data
+];
+
+/// MSI Capability registers for 32-bit Message Address and PVM
+pub const MSI_32BIT_PVM_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                    // Capability ID
+    Attr::new(0x01, 1),                                    // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x0471).rsvdp(0xf800),           // Message Control
+    Attr::new(0x04, 4).rw(0xffff_fffc).rsvdp(0x0000_0003), // Message Address
+    Attr::new(0x08, 2).rw(0xffff),                         // Message Data
+    Attr::new(0x0a, 2).rw(0xffff),                         // Extended Message Data
+    Attr::new(0x0c, 4).rw(0xffff_ffff),                    // Mask Bits
+    Attr::new(0x10, 4),                                    // Pending Bits
+];
+
+/// MSI Capability registers for 64-bit Message Address and PVM
+pub const MSI_64BIT_PVM_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                    // Capability ID
+    Attr::new(0x01, 1),                                    // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x0471).rsvdp(0xf800),           // Message Control
+    Attr::new(0x04, 4).rw(0xffff_fffc).rsvdp(0x0000_0003), // Message Address
+    Attr::new(0x08, 4).rw(0xffff_ffff),                    // Message Upper Address
+    Attr::new(0x0c, 2).rw(0xffff),                         // Message Data
+    Attr::new(0x0e, 2).rw(0xffff),                         // Extended Message Data
+    Attr::new(0x10, 4).rw(0xffff_ffff),                    // Mask Bits
+    Attr::new(0x14, 4),                                    // Pending Bits
+];
+
 /// Provides system software control over MSI.
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MessageControl {
     pub msi_enable: bool,
     pub multiple_message_capable: MultipleMessage,
@@ -173,9 +222,49 @@ pub struct MessageControl {
 impl MessageControl {
     pub const SIZE: usize = 2;
 }
+impl From<u16> for MessageControl {
+    fn from(word: u16) -> Self {
+        let Lsb((
+            msi_enable,
+            mmc,
+            mme,
+            a_64_bit_address_capable,
+            per_vector_masking_capable,
+            extended_message_data_capable,
+            extended_message_data_enable,
+            (),
+        )) = P8::<_, 1, 3, 3, 1, 1, 1, 1, 5>(word).into();
+        Self {
+            msi_enable,
+            multiple_message_capable: MultipleMessage(mmc),
+            multiple_message_enable: MultipleMessage(mme),
+            a_64_bit_address_capable,
+            per_vector_masking_capable,
+            extended_message_data_capable,
+            extended_message_data_enable,
+        }
+    }
+}
+impl From<MessageControl> for u16 {
+    fn from(data: MessageControl) -> Self {
+        (data.msi_enable as u16)
+            | (data.multiple_message_capable.0 as u16 & 0b111) << 1
+            | (data.multiple_message_enable.0 as u16 & 0b111) << 4
+            | (data.a_64_bit_address_capable as u16) << 7
+            | (data.per_vector_masking_capable as u16) << 8
+            | (data.extended_message_data_capable as u16) << 9
+            | (data.extended_message_data_enable as u16) << 10
+    }
+}
+impl Register for MessageControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xf800;
+    const RW1C: u16 = 0;
+}
 
 /// System-specified message address
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageAddress {
     Dword(u32),
     Qword(u64),
@@ -188,7 +277,8 @@ impl Default for MessageAddress {
 
 /// The number of requested vectors must be aligned to a power of two (if a function requires three
 /// vectors, it requests four by initializing this field to “010”).
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultipleMessage(pub u8);
 impl MultipleMessage {
     pub fn number_of_vectors(&self) -> u8 {
@@ -202,6 +292,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn message_address_32bit() {
         let mut data =
             include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/random/4k")).clone();
@@ -229,6 +320,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn message_address_64bit() {
         let mut data =
             include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/random/4k")).clone();
@@ -256,6 +348,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn message_address_32bit_per_vector_masking() {
         let mut data =
             include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/random/4k")).clone();
@@ -283,6 +376,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn message_address_64bit_per_vector_masking() {
         let mut data =
             include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/random/4k")).clone();
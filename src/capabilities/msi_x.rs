@@ -9,12 +9,14 @@
 use heterob::{bit_numbering::Lsb, endianness::Le, P2, P3, P4};
 
 use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// In contrast to the [MSI](super::MessageSignaledInterrups) capability, which directly contains all of
 /// the control/status information for the function's vectors, the MSI-X capability structure
 /// instead points to an (MSI-X Table)[Table] structure and a (MSI-X Pending Bit Array
 /// (PBA))[PendingBitArray] structure, each residing in Memory Space.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MsiX {
     pub message_control: MessageControl,
     pub table: Table,
@@ -47,8 +49,18 @@ impl<'a> TryFrom<&'a [u8]> for MsiX {
     }
 }
 
+/// MSI-X Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                          // Capability ID
+    Attr::new(0x01, 1),                          // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0xc000).rsvdp(0x3800), // Message Control
+    Attr::new(0x04, 4),                          // Table Offset/Table BIR
+    Attr::new(0x08, 4),                          // PBA Offset/PBA BIR
+];
+
 /// Message Control for MSI-X
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MessageControl {
     /// Table Size
     pub table_size: u16,
@@ -67,10 +79,23 @@ impl From<u16> for MessageControl {
         }
     }
 }
+impl From<MessageControl> for u16 {
+    fn from(data: MessageControl) -> Self {
+        (data.table_size & 0x7ff)
+            | (data.function_mask as u16) << 14
+            | (data.msi_x_enable as u16) << 15
+    }
+}
+impl Register for MessageControl {
+    type Value = u16;
+    const RSVDP: u16 = 0x3800;
+    const RW1C: u16 = 0;
+}
 
 /// BAR Indicator register (BIR) indicates which BAR, and a QWORD-aligned Offset indicates where
 /// the structure begins relative to the base address associated with the BAR
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bir {
     Bar10h,
     Bar14h,
@@ -96,6 +121,7 @@ impl From<u8> for Bir {
 
 /// Table Offset/Table BIR for MSI-X
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table {
     pub bir: Bir,
     /// Used as an offset from the address contained by one of the function’s Base Address
@@ -115,6 +141,7 @@ impl From<u32> for Table {
 
 /// PBA Offset/PBA BIR for MSI-X
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PendingBitArray {
     pub bir: Bir,
     /// Used as an offset from the address contained by one of the function’s Base Address
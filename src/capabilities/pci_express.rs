@@ -228,7 +228,10 @@ use heterob::{
 };
 use snafu::prelude::*;
 
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PciExpressError {
     #[snafu(display("can't read mandatory (0x1a) bytes from slice"))]
     RequiredBytesSlice,
@@ -238,6 +241,7 @@ pub enum PciExpressError {
 
 /// PCI Express Capability Structure
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PciExpress {
     /// Indicates PCI-SIG defined PCI Express Capability structure version number
     pub version: u8,
@@ -338,10 +342,53 @@ impl<'a> TryFrom<&'a [u8]> for PciExpress {
     }
 }
 
+impl PciExpress {
+    /// Access attributes of registers of the actual capability version
+    ///
+    /// Registers of the second version are absent in version 1 capability.
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        if self.version > 1 {
+            map
+        } else {
+            map.truncate(0x24)
+        }
+    }
+}
+
+/// PCI Express Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                      // Capability ID
+    Attr::new(0x01, 1),                                      // Next Capability Pointer
+    Attr::new(0x02, 2).hwinit(0x0100),                       // PCI Express Capabilities
+    Attr::new(0x04, 4),                                      // Device Capabilities
+    Attr::new(0x08, 2).rw(0xffff),                           // Device Control
+    Attr::new(0x0a, 2).rw1c(0x004f).rsvdz(0xff80),           // Device Status
+    Attr::new(0x0c, 4).hwinit(0xff00_0000),                  // Link Capabilities
+    Attr::new(0x10, 2).rw(0x0ffb).rsvdp(0xf004),             // Link Control
+    Attr::new(0x12, 2).hwinit(0x1000).rw1c(0xc000),          // Link Status
+    Attr::new(0x14, 4).hwinit(0xffff_ffff),                  // Slot Capabilities
+    Attr::new(0x18, 2).rw(0x1fff).rsvdp(0xe000),             // Slot Control
+    Attr::new(0x1a, 2).rw1c(0x011f).rsvdz(0xfe00),           // Slot Status
+    Attr::new(0x1c, 2).rw(0x001f).rsvdp(0xffe0),             // Root Control
+    Attr::new(0x1e, 2).rsvdp(0xfffe),                        // Root Capabilities
+    Attr::new(0x20, 4).rw1c(0x0001_0000).rsvdz(0xfffc_0000), // Root Status
+    Attr::new(0x24, 4),                                      // Device Capabilities 2
+    Attr::new(0x28, 2).rw(0xffff),                           // Device Control 2
+    Attr::new(0x2a, 2).rsvdz(0xffff),                        // Device Status 2
+    Attr::new(0x2c, 4),                                      // Link Capabilities 2
+    Attr::new(0x30, 2).hwinit(0x0040).rw(0xffbf),            // Link Control 2
+    Attr::new(0x32, 2).rw1c(0x8020),                         // Link Status 2
+    Attr::new(0x34, 4),                                      // Slot Capabilities 2
+    Attr::new(0x38, 2).rsvdp(0xffff),                        // Slot Control 2
+    Attr::new(0x3a, 2).rsvdz(0xffff),                        // Slot Status 2
+];
+
 type DeviceTypeArgs = (u8, Link, Slot, Option<Root>, Option<Link2>, Option<Slot2>);
 
 /// Indicates the specific type of this PCI Express Function
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeviceType {
     /// PCI Express Endpoint
     Endpoint { link: Link, link_2: Option<Link2> },
@@ -467,6 +514,7 @@ impl TryFrom<DeviceTypeArgs> for DeviceType {
 /// The Device Capabilities, Device Status, and Device Control registers are required for all PCI
 /// Express device Functions
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Device {
     pub capabilities: DeviceCapabilities,
     pub control: DeviceControl,
@@ -483,6 +531,7 @@ impl Device {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceCapabilities {
     /// Max_Payload_Size Supported
     pub max_payload_size_supported: MaxSize,
@@ -544,6 +593,7 @@ impl From<u32> for DeviceCapabilities {
 
 /// Max_Payload_Size Supported / Max_Payload_Size / Max_Read_Request_Size
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaxSize {
     /// 128 bytes max size
     B128,
@@ -577,11 +627,26 @@ impl From<u8> for MaxSize {
         }
     }
 }
+impl From<MaxSize> for u8 {
+    fn from(data: MaxSize) -> Self {
+        match data {
+            MaxSize::B128 => 0b000,
+            MaxSize::B256 => 0b001,
+            MaxSize::B512 => 0b010,
+            MaxSize::B1024 => 0b011,
+            MaxSize::B2048 => 0b100,
+            MaxSize::B4096 => 0b101,
+            MaxSize::Reserved0 => 0b110,
+            MaxSize::Reserved1 => 0b111,
+        }
+    }
+}
 
 /// Support for use of unclaimed Function Numbers to extend the number of outstanding transactions
 /// allowed by logically combining unclaimed Function Numbers (called Phantom Functions) with the
 /// Tag identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PhantomFunctionsSupported {
     /// No Function Number bits are used
     NoBits,
@@ -607,6 +672,7 @@ impl From<u8> for PhantomFunctionsSupported {
 
 /// Maximum supported size of the Tag field as a Requester
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExtendedTagFieldSupported {
     /// 5-bit Tag field supported
     Five,
@@ -626,6 +692,7 @@ impl From<bool> for ExtendedTagFieldSupported {
 /// Acceptable total latency that an Endpoint can withstand due to the transition from L0s state to
 /// the L0 state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EndpointL0sAcceptableLatency {
     /// Maximum of 64 ns
     Max64ns,
@@ -663,6 +730,7 @@ impl From<u8> for EndpointL0sAcceptableLatency {
 /// Aacceptable latency that an Endpoint can withstand due to the transition from L1 state to the
 /// L0 state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EndpointL1AcceptableLatency {
     /// Maximum of 1 µs
     Max1us,
@@ -700,6 +768,7 @@ impl From<u8> for EndpointL1AcceptableLatency {
 /// Slot Power Limit (Captured)
 /// Specifies the upper limit on power available/supplied to the adapter
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotPowerLimit {
     /// Slot Power Limit Value
     pub value: u8,
@@ -726,6 +795,7 @@ impl From<SlotPowerLimit> for f32 {
         cspl.scale * cspl.value as f32
     }
 }
+#[allow(clippy::needless_lifetimes)]
 impl<'a> From<&'a SlotPowerLimit> for f32 {
     fn from(cspl: &SlotPowerLimit) -> Self {
         cspl.scale * cspl.value as f32
@@ -734,6 +804,7 @@ impl<'a> From<&'a SlotPowerLimit> for f32 {
 
 /// The Device Control register controls PCI Express device specific parameters
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceControl {
     /// Correctable Error Reporting Enable
     pub correctable_error_reporting_enable: bool,
@@ -796,9 +867,31 @@ impl From<u16> for DeviceControl {
         }
     }
 }
+impl From<DeviceControl> for u16 {
+    fn from(data: DeviceControl) -> Self {
+        (data.correctable_error_reporting_enable as u16)
+            | (data.non_fatal_error_reporting_enable as u16) << 1
+            | (data.fatal_error_reporting_enable as u16) << 2
+            | (data.unsupported_request_reporting_enable as u16) << 3
+            | (data.enable_relaxed_ordering as u16) << 4
+            | (u8::from(data.max_payload_size) as u16) << 5
+            | (data.extended_tag_field_enable as u16) << 8
+            | (data.phantom_functions_enable as u16) << 9
+            | (data.aux_power_pm_enable as u16) << 10
+            | (data.enable_no_snoop as u16) << 11
+            | (u8::from(data.max_read_request_size) as u16) << 12
+            | (data.bcre_or_flreset as u16) << 15
+    }
+}
+impl Register for DeviceControl {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
 
 /// Provides information about PCI Express device (Function) specific parameters
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceStatus {
     /// Correctable Error Detected
     pub correctable_error_detected: bool,
@@ -839,10 +932,26 @@ impl From<u16> for DeviceStatus {
         }
     }
 }
+impl From<DeviceStatus> for u16 {
+    fn from(data: DeviceStatus) -> Self {
+        (data.correctable_error_detected as u16)
+            | (data.non_fatal_error_detected as u16) << 1
+            | (data.fatal_error_detected as u16) << 2
+            | (data.unsupported_request_detected as u16) << 3
+            | (data.aux_power_detected as u16) << 4
+            | (data.transactions_pending as u16) << 5
+    }
+}
+impl Register for DeviceStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x004f;
+}
 
 /// The Link Capabilities, Link Status, and Link Control registers are required for all Root Ports,
 /// Switch Ports, Bridges, and Endpoints that are not Root Complex Integrated Endpoints
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Link {
     pub capabilities: LinkCapabilities,
     pub control: LinkControl,
@@ -860,6 +969,7 @@ impl Link {
 
 /// The Link Capabilities register identifies PCI Express Link specific capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkCapabilities {
     pub max_link_speed: LinkSpeed,
     pub maximum_link_width: LinkWidth,
@@ -915,6 +1025,7 @@ impl From<u32> for LinkCapabilities {
 /// Max/Current/Target Link Speed
 /// Speeds should be taken from [SupportedLinkSpeedsVector]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkSpeed {
     /// 2.5 GT/s
     Rate2GTps,
@@ -964,6 +1075,7 @@ impl From<LinkSpeed> for u8 {
 
 /// Maximum/Negotiated Link Width
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkWidth {
     Reserved(u8),
     X1,
@@ -1005,6 +1117,7 @@ impl From<LinkWidth> for u8 {
 
 /// Active State Power Management (ASPM) Support/Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ActiveStatePowerManagement {
     NoAspm,
     L0s,
@@ -1022,9 +1135,20 @@ impl From<u8> for ActiveStatePowerManagement {
         }
     }
 }
+impl From<ActiveStatePowerManagement> for u8 {
+    fn from(data: ActiveStatePowerManagement) -> Self {
+        match data {
+            ActiveStatePowerManagement::NoAspm => 0b00,
+            ActiveStatePowerManagement::L0s => 0b01,
+            ActiveStatePowerManagement::L1 => 0b10,
+            ActiveStatePowerManagement::L0sAndL1 => 0b11,
+        }
+    }
+}
 
 /// L0s Exit Latency
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum L0sExitLatency {
     /// Less than 64 ns
     Lt64ns,
@@ -1061,6 +1185,7 @@ impl From<u8> for L0sExitLatency {
 
 /// L1 Exit Latency
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum L1ExitLatency {
     /// Less than 1 µs
     Lt1us,
@@ -1096,6 +1221,7 @@ impl From<u8> for L1ExitLatency {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkControl {
     /// Active State Power Management (ASPM) Control
     pub active_state_power_management_control: ActiveStatePowerManagement,
@@ -1148,8 +1274,28 @@ impl From<u16> for LinkControl {
         }
     }
 }
+impl From<LinkControl> for u16 {
+    fn from(data: LinkControl) -> Self {
+        (u8::from(data.active_state_power_management_control) as u16)
+            | (bool::from(data.read_completion_boundary) as u16) << 3
+            | (data.link_disable as u16) << 4
+            | (data.retrain_link as u16) << 5
+            | (data.common_clock_configuration as u16) << 6
+            | (data.extended_synch as u16) << 7
+            | (data.enable_clock_power_management as u16) << 8
+            | (data.hardware_autonomous_width_disable as u16) << 9
+            | (data.link_bandwidth_management_interrupt_enable as u16) << 10
+            | (data.link_autonomous_bandwidth_interrupt_enable as u16) << 11
+    }
+}
+impl Register for LinkControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xf004;
+    const RW1C: u16 = 0;
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadCompletionBoundary {
     B64 = 64,
     B128 = 128,
@@ -1163,9 +1309,18 @@ impl From<bool> for ReadCompletionBoundary {
         }
     }
 }
+impl From<ReadCompletionBoundary> for bool {
+    fn from(data: ReadCompletionBoundary) -> Self {
+        match data {
+            ReadCompletionBoundary::B64 => false,
+            ReadCompletionBoundary::B128 => true,
+        }
+    }
+}
 
 /// The Link Status register provides information about PCI Express Link specific parameters
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkStatus {
     /// Current Link Speed
     pub current_link_speed: LinkSpeed,
@@ -1208,11 +1363,29 @@ impl From<u16> for LinkStatus {
         }
     }
 }
+impl From<LinkStatus> for u16 {
+    fn from(data: LinkStatus) -> Self {
+        (u8::from(data.current_link_speed) as u16 & 0b1111)
+            | (u8::from(data.negotiated_link_width) as u16 & 0b11_1111) << 4
+            | (data.link_training_error as u16) << 10
+            | (data.link_training as u16) << 11
+            | (data.slot_clock_configuration as u16) << 12
+            | (data.data_link_layer_link_active as u16) << 13
+            | (data.link_bandwidth_management_status as u16) << 14
+            | (data.link_autonomous_bandwidth_status as u16) << 15
+    }
+}
+impl Register for LinkStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0xc000;
+}
 
 /// Slot Capabilities, Slot Status, and Slot Control registers are required for Switch Downstream
 /// and Root Ports if a slot is implemented on the Port (indicated by the Slot Implemented bit in
 /// the PCI Express Capabilities register)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Slot {
     pub capabilities: SlotCapabilities,
     pub control: SlotControl,
@@ -1230,6 +1403,7 @@ impl Slot {
 
 /// The Slot Capabilities register identifies PCI Express slot specific capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotCapabilities {
     /// Attention Button Present
     pub attention_button_present: bool,
@@ -1287,6 +1461,7 @@ impl From<u32> for SlotCapabilities {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotControl {
     /// Attention Button Pressed Enable
     pub attention_button_pressed_enable: bool,
@@ -1342,9 +1517,30 @@ impl From<u16> for SlotControl {
         }
     }
 }
+impl From<SlotControl> for u16 {
+    fn from(data: SlotControl) -> Self {
+        (data.attention_button_pressed_enable as u16)
+            | (data.power_fault_detected_enable as u16) << 1
+            | (data.mrl_sensor_changed_enable as u16) << 2
+            | (data.presence_detect_changed_enable as u16) << 3
+            | (data.command_completed_interrupt_enable as u16) << 4
+            | (data.hot_plug_interrupt_enable as u16) << 5
+            | (u8::from(data.attention_indicator_control) as u16) << 6
+            | (u8::from(data.power_indicator_control) as u16) << 8
+            | (data.power_controller_control as u16) << 10
+            | (data.electromechanical_interlock_control as u16) << 11
+            | (data.data_link_layer_state_changed_enable as u16) << 12
+    }
+}
+impl Register for SlotControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xe000;
+    const RW1C: u16 = 0;
+}
 
 /// Attention/Power Indicator Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndicatorControl {
     Reserved,
     On,
@@ -1362,8 +1558,19 @@ impl From<u8> for IndicatorControl {
         }
     }
 }
+impl From<IndicatorControl> for u8 {
+    fn from(data: IndicatorControl) -> Self {
+        match data {
+            IndicatorControl::Reserved => 0b00,
+            IndicatorControl::On => 0b01,
+            IndicatorControl::Blink => 0b10,
+            IndicatorControl::Off => 0b11,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotStatus {
     /// Attention Button Pressed
     pub attention_button_pressed: bool,
@@ -1411,10 +1618,29 @@ impl From<u16> for SlotStatus {
         }
     }
 }
+impl From<SlotStatus> for u16 {
+    fn from(data: SlotStatus) -> Self {
+        (data.attention_button_pressed as u16)
+            | (data.power_fault_detected as u16) << 1
+            | (data.mrl_sensor_changed as u16) << 2
+            | (data.presence_detect_changed as u16) << 3
+            | (data.command_completed as u16) << 4
+            | (data.mrl_sensor_state as u16) << 5
+            | (data.presence_detect_state as u16) << 6
+            | (data.electromechanical_interlock_status as u16) << 7
+            | (data.data_link_layer_state_changed as u16) << 8
+    }
+}
+impl Register for SlotStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x011f;
+}
 
 /// Root Ports and Root Complex Event Collectors must implement the Root Capabilities, Root Status,
 /// and Root Control registers
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Root {
     pub control: RootControl,
     pub capabilities: RootCapabilities,
@@ -1432,6 +1658,7 @@ impl Root {
 
 /// The Root Control register controls PCI Express Root Complex specific parameters
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootControl {
     /// System Error on Correctable Error Enable
     pub system_error_on_correctable_error_enable: bool,
@@ -1463,9 +1690,24 @@ impl From<u16> for RootControl {
         }
     }
 }
+impl From<RootControl> for u16 {
+    fn from(data: RootControl) -> Self {
+        (data.system_error_on_correctable_error_enable as u16)
+            | (data.system_error_on_non_fatal_error_enable as u16) << 1
+            | (data.system_error_on_fatal_error_enable as u16) << 2
+            | (data.pme_interrupt_enable as u16) << 3
+            | (data.crs_software_visibility_enable as u16) << 4
+    }
+}
+impl Register for RootControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xffe0;
+    const RW1C: u16 = 0;
+}
 
 /// The Root Capabilities register identifies PCI Express Root Port specific capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootCapabilities {
     /// CRS Software Visibility
     pub crs_software_visibility: bool,
@@ -1481,6 +1723,7 @@ impl From<u16> for RootCapabilities {
 
 /// The Root Status register provides information about PCI Express device specific parameters
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootStatus {
     /// PME Requester ID
     pub pme_requester_id: u16,
@@ -1500,8 +1743,21 @@ impl From<u32> for RootStatus {
         }
     }
 }
+impl From<RootStatus> for u32 {
+    fn from(data: RootStatus) -> Self {
+        (data.pme_requester_id as u32)
+            | (data.pme_status as u32) << 16
+            | (data.pme_pending as u32) << 17
+    }
+}
+impl Register for RootStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0001_0000;
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Device2 {
     pub capabilities: DeviceCapabilities2,
     pub control: DeviceControl2,
@@ -1518,6 +1774,7 @@ impl Device2 {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceCapabilities2 {
     /// Completion Timeout Ranges Supported
     pub completion_timeout_ranges_supported: CompletionTimeoutRanges,
@@ -1613,6 +1870,7 @@ impl From<u32> for DeviceCapabilities2 {
 
 /// Indicates device Function support for the optional Completion Timeout programmability mechanism
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CompletionTimeoutRanges {
     /// Completion Timeout programming not supported – the Function must implement a timeout value
     /// in the range 50 µs to 50 ms.
@@ -1673,6 +1931,7 @@ impl From<CompletionTimeoutRanges> for u8 {
 
 /// Value indicates Completer support for TPH or Extended TPH
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TphCompleter {
     /// TPH and Extended TPH Completer not supported.
     NotSupported,
@@ -1708,6 +1967,7 @@ impl From<TphCompleter> for u8 {
 /// Indicates if the Root Port or RCRB supports LN protocol as an LN Completer, and if so, what
 /// cacheline size is in effect
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LnSystemCls {
     /// LN Completer either not supported or not in effect
     NotSupported,
@@ -1742,6 +2002,7 @@ impl From<LnSystemCls> for u8 {
 
 /// Indicates if OBFF is supported and, if so, what signaling mechanism is used
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Obff {
     /// OBFF Not Supported
     NotSupported,
@@ -1776,6 +2037,7 @@ impl From<Obff> for u8 {
 
 /// Indicates the maximum number of End-End TLP Prefixes supported by this Function
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaxEndEndTlpPrefixes {
     /// 4 End-End TLP Prefixes
     Max4,
@@ -1810,6 +2072,7 @@ impl From<MaxEndEndTlpPrefixes> for u8 {
 
 /// Indicates support level of the optional Emergency Power Reduction State feature
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EmergencyPowerReduction {
     /// Emergency Power Reduction State not supported
     NotSupported,
@@ -1846,6 +2109,7 @@ impl From<EmergencyPowerReduction> for u8 {
 
 /// Device Control 2 Register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceControl2 {
     /// Completion Timeout Value
     pub completion_timeout_value: CompletionTimeoutValue,
@@ -1904,8 +2168,30 @@ impl From<u16> for DeviceControl2 {
         }
     }
 }
+impl From<DeviceControl2> for u16 {
+    fn from(data: DeviceControl2) -> Self {
+        (u8::from(data.completion_timeout_value) as u16 & 0b1111)
+            | (data.completion_timeout_disable as u16) << 4
+            | (data.ari_forwarding_enable as u16) << 5
+            | (data.atomic_op_requester_enable as u16) << 6
+            | (data.atomic_op_egress_blocking as u16) << 7
+            | (data.ido_request_enable as u16) << 8
+            | (data.ido_completion_enable as u16) << 9
+            | (data.ltr_mechanism_enable as u16) << 10
+            | (data.emergency_power_reduction_request as u16) << 11
+            | (data.enable_10bit_tag_requester as u16) << 12
+            | (u8::from(data.obff_enable) as u16) << 13
+            | (bool::from(data.end_end_tlp_prefix_blocking) as u16) << 15
+    }
+}
+impl Register for DeviceControl2 {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CompletionTimeoutValue {
     /// Default range: 50 µs to 50 ms
     DefaultRange50usTo50ms,
@@ -1961,6 +2247,7 @@ impl From<CompletionTimeoutValue> for u8 {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObffEnable {
     /// Disabled
     Disabled,
@@ -1996,16 +2283,28 @@ impl From<ObffEnable> for u8 {
 /// Device Status 2 Register is a placeholder
 /// There are no capabilities that require this register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceStatus2 {}
 impl From<u16> for DeviceStatus2 {
     fn from(_word: u16) -> Self {
         Self {}
     }
 }
+impl From<DeviceStatus2> for u16 {
+    fn from(_data: DeviceStatus2) -> Self {
+        0
+    }
+}
+impl Register for DeviceStatus2 {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
 
 /// Controls whether the routing function is permitted to forward TLPs containing an End-End TLP
 /// Prefix
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EndEndTlpPrefixBlocking {
     /// – Function is permitted to send TLPs with End-End TLP Prefixes
     ForwardingEnabled,
@@ -2031,6 +2330,7 @@ impl From<EndEndTlpPrefixBlocking> for bool {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Link2 {
     pub capabilities: LinkCapabilities2,
     pub control: LinkControl2,
@@ -2048,6 +2348,7 @@ impl Link2 {
 
 /// Link Capabilities 2 Register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkCapabilities2 {
     /// Supported Link Speeds Vector
     pub supported_link_speeds_vector: SupportedLinkSpeedsVector,
@@ -2091,6 +2392,7 @@ impl From<u32> for LinkCapabilities2 {
 
 /// Indicates the supported Link speed(s) of the associated Port
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SupportedLinkSpeedsVector {
     /// 2.5 GT/s
     pub speed_2_5_gtps: bool,
@@ -2130,7 +2432,20 @@ impl From<u8> for SupportedLinkSpeedsVector {
     }
 }
 
+impl From<SupportedLinkSpeedsVector> for u8 {
+    fn from(data: SupportedLinkSpeedsVector) -> Self {
+        (data.speed_2_5_gtps as u8)
+            | (data.speed_5_0_gtps as u8) << 1
+            | (data.speed_8_0_gtps as u8) << 2
+            | (data.speed_16_0_gtps as u8) << 3
+            | (data.speed_32_0_gtps as u8) << 4
+            | (data.speed_64_0_gtps as u8) << 5
+            | (data.reserved as u8) << 6
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkControl2 {
     /// Target Link Speed
     pub target_link_speed: LinkSpeed,
@@ -2172,11 +2487,29 @@ impl From<u16> for LinkControl2 {
         }
     }
 }
+impl From<LinkControl2> for u16 {
+    fn from(data: LinkControl2) -> Self {
+        (u8::from(data.target_link_speed) as u16 & 0b1111)
+            | (data.enter_compliance as u16) << 4
+            | (data.hardware_autonomous_speed_disable as u16) << 5
+            | (bool::from(data.selectable_de_emphasis) as u16) << 6
+            | (u8::from(data.transmit_margin) as u16 & 0b111) << 7
+            | (data.enter_modified_compliance as u16) << 10
+            | (data.compliance_sos as u16) << 11
+            | (u8::from(data.compliance_preset_or_de_emphasis) as u16 & 0b1111) << 12
+    }
+}
+impl Register for LinkControl2 {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
 
 /// Selectable De-emphasis
 ///
 /// Used to control the transmit deemphasis of the link in specific situations
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeEmphasis {
     Minus3_5dB,
     Minus6dB,
@@ -2190,17 +2523,31 @@ impl From<bool> for DeEmphasis {
         }
     }
 }
+impl From<DeEmphasis> for bool {
+    fn from(data: DeEmphasis) -> Self {
+        match data {
+            DeEmphasis::Minus3_5dB => true,
+            DeEmphasis::Minus6dB => false,
+        }
+    }
+}
 
 /// Controls the value of the nondeemphasized voltage level at the Transmitter pins
 ///
 /// 0b000 - Normal operating range
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransmitMargin(pub u8);
 impl From<u8> for TransmitMargin {
     fn from(byte: u8) -> Self {
         Self(byte)
     }
 }
+impl From<TransmitMargin> for u8 {
+    fn from(data: TransmitMargin) -> Self {
+        data.0
+    }
+}
 
 /// Compliance Preset/De-emphasis
 ///
@@ -2211,14 +2558,21 @@ impl From<u8> for TransmitMargin {
 ///   if the entry occurred due to the Enter Compliance bit being 1b.
 /// - **2.5 GT/s Data Rate:** The setting of this field has no effect.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CompliancePresetOrDeEmphasis(pub u8);
 impl From<u8> for CompliancePresetOrDeEmphasis {
     fn from(byte: u8) -> Self {
         Self(byte)
     }
 }
+impl From<CompliancePresetOrDeEmphasis> for u8 {
+    fn from(data: CompliancePresetOrDeEmphasis) -> Self {
+        data.0
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkStatus2 {
     /// Current De-emphasis Level
     pub current_de_emphasis_level: DeEmphasis,
@@ -2274,9 +2628,30 @@ impl From<u16> for LinkStatus2 {
         }
     }
 }
+impl From<LinkStatus2> for u16 {
+    fn from(data: LinkStatus2) -> Self {
+        (bool::from(data.current_de_emphasis_level) as u16)
+            | (data.equalization_complete as u16) << 1
+            | (data.equalization_phase_1_successful as u16) << 2
+            | (data.equalization_phase_2_successful as u16) << 3
+            | (data.equalization_phase_3_successful as u16) << 4
+            | (data.link_equalization_request as u16) << 5
+            | (data.retimer_presence_detected as u16) << 6
+            | (data.two_retimers_presence_detected as u16) << 7
+            | (u8::from(data.crosslink_resolution) as u16) << 8
+            | (u8::from(data.downstream_component_presence) as u16 & 0b111) << 12
+            | (data.drs_message_received as u16) << 15
+    }
+}
+impl Register for LinkStatus2 {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x8020;
+}
 
 /// Indicates the state of the Crosslink negotiation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CrosslinkResolution {
     /// Crosslink Resolution is not supported
     NotSupported,
@@ -2298,10 +2673,21 @@ impl From<u8> for CrosslinkResolution {
         }
     }
 }
+impl From<CrosslinkResolution> for u8 {
+    fn from(data: CrosslinkResolution) -> Self {
+        match data {
+            CrosslinkResolution::NotSupported => 0b00,
+            CrosslinkResolution::UpstreamPort => 0b01,
+            CrosslinkResolution::DownstreamPort => 0b10,
+            CrosslinkResolution::NotCompleted => 0b11,
+        }
+    }
+}
 
 /// Indicates the presence and DRS status for the Downstream Component, if any, connected to the
 /// Link
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DownstreamComponentPresence {
     /// Link Down – Presence Not Determined
     DownNotDetermined,
@@ -2328,8 +2714,21 @@ impl From<u8> for DownstreamComponentPresence {
         }
     }
 }
+impl From<DownstreamComponentPresence> for u8 {
+    fn from(data: DownstreamComponentPresence) -> Self {
+        match data {
+            DownstreamComponentPresence::DownNotDetermined => 0b000,
+            DownstreamComponentPresence::DownNotPresent => 0b001,
+            DownstreamComponentPresence::DownPresent => 0b010,
+            DownstreamComponentPresence::UpPresent => 0b100,
+            DownstreamComponentPresence::UpPresentAndDrsReceived => 0b101,
+            DownstreamComponentPresence::Reserved(v) => v,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Slot2 {
     pub capabilities: SlotCapabilities2,
     pub control: SlotControl2,
@@ -2349,6 +2748,7 @@ impl Slot2 {
 ///
 /// This section is a placeholder. There are no capabilities that require this register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotCapabilities2 {}
 impl From<u32> for SlotCapabilities2 {
     fn from(_dword: u32) -> Self {
@@ -2360,26 +2760,49 @@ impl From<u32> for SlotCapabilities2 {
 ///
 /// This section is a placeholder. There are no capabilities that require this register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotControl2 {}
 impl From<u16> for SlotControl2 {
     fn from(_word: u16) -> Self {
         Self {}
     }
 }
+impl From<SlotControl2> for u16 {
+    fn from(_data: SlotControl2) -> Self {
+        0
+    }
+}
+impl Register for SlotControl2 {
+    type Value = u16;
+    const RSVDP: u16 = 0xffff;
+    const RW1C: u16 = 0;
+}
 
 /// Slot Status 2 Register
 ///
 /// This section is a placeholder. There are no capabilities that require this register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotStatus2 {}
 impl From<u16> for SlotStatus2 {
     fn from(_word: u16) -> Self {
         Self {}
     }
 }
+impl From<SlotStatus2> for u16 {
+    fn from(_data: SlotStatus2) -> Self {
+        0
+    }
+}
+impl Register for SlotStatus2 {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
 
 /// Transmitter Preset
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransmitterPreset {
     P0,
     P1,
@@ -2413,8 +2836,28 @@ impl From<u8> for TransmitterPreset {
     }
 }
 
+impl From<TransmitterPreset> for u8 {
+    fn from(data: TransmitterPreset) -> Self {
+        match data {
+            TransmitterPreset::P0 => 0b0000,
+            TransmitterPreset::P1 => 0b0001,
+            TransmitterPreset::P2 => 0b0010,
+            TransmitterPreset::P3 => 0b0011,
+            TransmitterPreset::P4 => 0b0100,
+            TransmitterPreset::P5 => 0b0101,
+            TransmitterPreset::P6 => 0b0110,
+            TransmitterPreset::P7 => 0b0111,
+            TransmitterPreset::P8 => 0b1000,
+            TransmitterPreset::P9 => 0b1001,
+            TransmitterPreset::P10 => 0b1010,
+            TransmitterPreset::Reserved(v) => v,
+        }
+    }
+}
+
 /// Receiver Preset Hint
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReceiverPresetHint {
     Minus6dB = -6,
     Minus7dB = -7,
@@ -2440,6 +2883,21 @@ impl From<u8> for ReceiverPresetHint {
     }
 }
 
+impl From<ReceiverPresetHint> for u8 {
+    fn from(data: ReceiverPresetHint) -> Self {
+        match data {
+            ReceiverPresetHint::Minus6dB => 0b000,
+            ReceiverPresetHint::Minus7dB => 0b001,
+            ReceiverPresetHint::Minus8dB => 0b010,
+            ReceiverPresetHint::Minus9dB => 0b011,
+            ReceiverPresetHint::Minus10dB => 0b100,
+            ReceiverPresetHint::Minus11dB => 0b101,
+            ReceiverPresetHint::Minus12dB => 0b110,
+            ReceiverPresetHint::Reserved => 0b111,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
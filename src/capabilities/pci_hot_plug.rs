@@ -0,0 +1,812 @@
+/*!
+# PCI Hot-Plug
+
+Indicates that the associated device conforms to the Standard Hot-Plug Controller (SHPC) model.
+SHPC registers are not mapped into configuration space, the capability provides an indirect
+access to them: DWORD Select register holds the index of SHPC DWORD that is accessed through the
+DWORD Data register.
+
+[Shpc::read_from] walks the SHPC register set through a user supplied [ShpcAccess].
+
+## Struct diagram
+[PciHotPlug]
+- [Pending]
+
+[Shpc]
+- [SlotsAvailable]
+- [SlotConfiguration]
+- [SecondaryBusConfiguration]
+- [Command]
+- [CommandStatus]
+- [SerrIntEnable]
+- [Slots]
+  - [SlotRegister]
+    - [SlotState]
+    - [IndicatorState]
+
+## Examples
+
+```rust
+# use pcics::capabilities::pci_hot_plug::*;
+let data = [0x0c, 0x00, 0x09, 0x01, 0x3a, 0x02, 0x00, 0x00];
+let result = data[2..].try_into().unwrap();
+let sample = PciHotPlug {
+    dword_select: 9,
+    pending: Pending {
+        interrupt_pending: true,
+        controller_serr_pending: false,
+    },
+    dword_data: 0x23a,
+};
+assert_eq!(sample, result);
+```
+
+Walk recorded register image of a two slot controller:
+
+```rust
+# use pcics::capabilities::pci_hot_plug::*;
+let image: [u32; 11] = [
+    0x0000_0000, // Base Offset
+    0x0000_0002, // Slots Available I: two 33 MHz conventional slots
+    0x0000_0002, // Slots Available II: two 66 MHz conventional slots
+    0x0001_0102, // Slot Configuration: 2 slots starting with device 1, PSN 1
+    0x0100_0000, // Secondary Bus Configuration, PI 1
+    0x0000_0000, // Command / Command Status
+    0x0000_0004, // Interrupt Locator: slot 2
+    0x0000_0000, // SERR Locator
+    0x0000_0002, // Controller SERR-INT Enable
+    0x0000_03fa, // Slot 1: enabled, indicators on, card present
+    0x0001_0f3f, // Slot 2: disabled, indicators off, card removed
+];
+let shpc = Shpc::read_from(&image[..]).unwrap();
+assert_eq!(2, shpc.slot_configuration.number_of_slots);
+let slots = shpc.slots.collect::<Vec<_>>();
+assert_eq!(SlotState::Enabled, slots[0].slot_state);
+assert_eq!(SlotState::Disabled, slots[1].slot_state);
+assert!(slots[1].presence_changed);
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P14, P3, P5, P7, P8, P9};
+use snafu::prelude::*;
+
+use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+/// PCI Hot-Plug
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PciHotPlug {
+    /// DWORD Select. Index of SHPC DWORD accessed through [DWORD Data](Self::dword_data).
+    pub dword_select: u8,
+    pub pending: Pending,
+    /// DWORD Data. Contents of the SHPC DWORD selected by [DWORD Select](Self::dword_select).
+    pub dword_data: u32,
+}
+impl TryFrom<&[u8]> for PciHotPlug {
+    type Error = CapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((dword_select, pending, dword_data)),
+            ..
+        } = P3(slice).try_into().map_err(|_| CapabilityDataError {
+            name: "PCI Hot-Plug",
+            size: 6,
+        })?;
+        let Lsb((interrupt_pending, controller_serr_pending, ())) =
+            P3::<u8, 1, 1, 6>(pending).into();
+        Ok(Self {
+            dword_select,
+            pending: Pending {
+                interrupt_pending,
+                controller_serr_pending,
+            },
+            dword_data,
+        })
+    }
+}
+
+/// PCI Hot-Plug Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                 // Capability ID
+    Attr::new(0x01, 1),                 // Next Capability Pointer
+    Attr::new(0x02, 1).rw(0xff),        // DWORD Select
+    Attr::new(0x03, 1).rsvdz(0xfc),     // Pending
+    Attr::new(0x04, 4).rw(0xffff_ffff), // DWORD Data
+];
+
+/// Pending
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pending {
+    /// SHPC has a pending interrupt
+    pub interrupt_pending: bool,
+    /// SHPC has a pending SERR
+    pub controller_serr_pending: bool,
+}
+
+/// SHPC register set reader
+///
+/// SHPC DWORD is read by writing its `index` to DWORD Select register and reading DWORD Data
+/// register of the [PciHotPlug] capability.
+pub trait ShpcAccess {
+    type Error;
+
+    /// Read SHPC DWORD `index`
+    fn read_dword(&self, index: u8) -> Result<u32, Self::Error>;
+}
+
+impl<A: ShpcAccess + ?Sized> ShpcAccess for &A {
+    type Error = A::Error;
+
+    fn read_dword(&self, index: u8) -> Result<u32, Self::Error> {
+        (**self).read_dword(index)
+    }
+}
+
+/// Recorded SHPC register image indexed by DWORD Select value
+impl ShpcAccess for [u32] {
+    type Error = ShpcOutOfBoundsError;
+
+    fn read_dword(&self, index: u8) -> Result<u32, Self::Error> {
+        self.get(index as usize)
+            .copied()
+            .context(ShpcOutOfBoundsSnafu { index })
+    }
+}
+
+/// SHPC DWORD lies outside of recorded register image
+#[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[snafu(display("SHPC DWORD {index:#04x} is out of register image"))]
+pub struct ShpcOutOfBoundsError {
+    pub index: u8,
+}
+
+/// Standard Hot-Plug Controller register set
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Shpc {
+    /// Base Offset. Offset of SHPC registers in memory space relative to BAR0.
+    pub base_offset: u32,
+    pub slots_available: SlotsAvailable,
+    pub slot_configuration: SlotConfiguration,
+    pub secondary_bus_configuration: SecondaryBusConfiguration,
+    pub command: Command,
+    pub command_status: CommandStatus,
+    /// Interrupt Locator. Bit 0 is Command Completion interrupt pending, bit N is slot N
+    /// interrupt pending.
+    pub interrupt_locator: u32,
+    /// SERR Locator. Bit 0 is Arbiter SERR pending, bit N is slot N SERR pending.
+    pub serr_locator: u32,
+    pub serr_int_enable: SerrIntEnable,
+    pub slots: Slots,
+}
+impl Shpc {
+    /// DWORD index of the first Slot register
+    pub const SLOT_INDEX: u8 = 9;
+
+    /// Read SHPC registers, slot registers are read for implemented slots only
+    pub fn read_from<A: ShpcAccess + ?Sized>(access: &A) -> Result<Self, A::Error> {
+        let read = |index| access.read_dword(index);
+        let base_offset = read(0x00)?;
+        let slots_available = SlotsAvailable::new(read(0x01)?, read(0x02)?);
+        let slot_configuration = SlotConfiguration::from(read(0x03)?);
+        let secondary_bus_configuration = From::<u32>::from(read(0x04)?);
+        let command = read(0x05)?;
+        let interrupt_locator = read(0x06)?;
+        let serr_locator = read(0x07)?;
+        let serr_int_enable = From::<u32>::from(read(0x08)?);
+        let mut slots = Slots {
+            registers: [0; Slots::MAX_NUMBER],
+            count: 0,
+            position: 0,
+        };
+        let count = (slot_configuration.number_of_slots as usize).min(Slots::MAX_NUMBER);
+        for n in 0..count {
+            slots.registers[n] = read(Self::SLOT_INDEX + n as u8)?;
+        }
+        slots.count = count;
+        Ok(Self {
+            base_offset,
+            slots_available,
+            slot_configuration,
+            secondary_bus_configuration,
+            command: From::<u16>::from(command as u16),
+            command_status: From::<u16>::from((command >> 16) as u16),
+            interrupt_locator,
+            serr_locator,
+            serr_int_enable,
+            slots,
+        })
+    }
+
+    /// Access attributes of SHPC registers with offsets relative to SHPC register set
+    pub fn registers(&self) -> RegisterMap {
+        RegisterMap::new(SHPC_REGISTERS).repeat(SLOT_REGISTERS, 4, self.slots.count as u16)
+    }
+}
+
+/// SHPC registers preceding Slot registers
+pub const SHPC_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4).hwinit(0xffff_ffff), // Base Offset
+    Attr::new(0x04, 4).hwinit(0x1f1f_1f1f).rsvdp(0xe0e0_e0e0), // Slots Available I
+    Attr::new(0x08, 4).hwinit(0xffff_ff1f).rsvdp(0x0000_00e0), // Slots Available II
+    Attr::new(0x0c, 4).hwinit(0xe7ff_1f1f).rsvdp(0x1800_e0e0), // Slot Configuration
+    Attr::new(0x10, 2).rsvdp(0xfff0),       // Secondary Bus Configuration
+    Attr::new(0x12, 1).hwinit(0x1f).rsvdp(0xe0), // MSI Control
+    Attr::new(0x13, 1),                     // Programming Interface
+    Attr::new(0x14, 2).rw(0x1fff).rsvdp(0xe000), // Command
+    Attr::new(0x16, 2).rsvdz(0xfff0),       // Command Status
+    Attr::new(0x18, 4),                     // Interrupt Locator
+    Attr::new(0x1c, 4),                     // SERR Locator
+    Attr::new(0x20, 4)
+        .rw(0x0000_000f)
+        .rw1c(0x0003_0000)
+        .rsvdz(0xfffc_fff0), // Controller SERR-INT Enable
+];
+
+/// Slot register, repeated for every implemented slot
+pub const SLOT_REGISTERS: &[Attr] = &[
+    Attr::new(0x24, 4)
+        .rw(0x7f00_0000)
+        .rw1c(0x001f_0000)
+        .rsvdz(0x80e0_8000), // Slot
+];
+
+/// Number of slots supporting each bus speed and mode (Slots Available I and II registers)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotsAvailable {
+    pub conventional_33mhz: u8,
+    pub pci_x_66mhz: u8,
+    pub pci_x_100mhz: u8,
+    pub pci_x_133mhz: u8,
+    pub conventional_66mhz: u8,
+    pub pci_x_266_66mhz: u8,
+    pub pci_x_266_100mhz: u8,
+    pub pci_x_266_133mhz: u8,
+    pub pci_x_533_66mhz: u8,
+    pub pci_x_533_100mhz: u8,
+    pub pci_x_533_133mhz: u8,
+}
+impl SlotsAvailable {
+    fn new(dword_1: u32, dword_2: u32) -> Self {
+        let Lsb((conventional_33mhz, (), pci_x_66mhz, (), pci_x_100mhz, (), pci_x_133mhz, ())) =
+            P8::<u32, 5, 3, 5, 3, 5, 3, 5, 3>(dword_1).into();
+        let Lsb((
+            conventional_66mhz,
+            (),
+            pci_x_266_66mhz,
+            pci_x_266_100mhz,
+            pci_x_266_133mhz,
+            pci_x_533_66mhz,
+            pci_x_533_100mhz,
+            pci_x_533_133mhz,
+        )) = P8::<u32, 5, 3, 4, 4, 4, 4, 4, 4>(dword_2).into();
+        Self {
+            conventional_33mhz,
+            pci_x_66mhz,
+            pci_x_100mhz,
+            pci_x_133mhz,
+            conventional_66mhz,
+            pci_x_266_66mhz,
+            pci_x_266_100mhz,
+            pci_x_266_133mhz,
+            pci_x_533_66mhz,
+            pci_x_533_100mhz,
+            pci_x_533_133mhz,
+        }
+    }
+}
+
+/// Slot Configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotConfiguration {
+    /// Number of Slots Implemented (NSI)
+    pub number_of_slots: u8,
+    /// First Device Number (FDN). Device number assigned to the first slot.
+    pub first_device_number: u8,
+    /// Physical Slot Number (PSN) of the first slot
+    pub physical_slot_number: u16,
+    /// PSN Up/Down. Physical slot numbers increase with device numbers.
+    pub physical_slot_number_up: bool,
+    /// MRL Sensor Implemented
+    pub mrl_sensor_implemented: bool,
+    /// Attention Button Implemented
+    pub attention_button_implemented: bool,
+}
+impl From<u32> for SlotConfiguration {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            number_of_slots,
+            (),
+            first_device_number,
+            (),
+            physical_slot_number,
+            (),
+            physical_slot_number_up,
+            mrl_sensor_implemented,
+            attention_button_implemented,
+        )) = P9::<u32, 5, 3, 5, 3, 11, 2, 1, 1, 1>(dword).into();
+        Self {
+            number_of_slots,
+            first_device_number,
+            physical_slot_number,
+            physical_slot_number_up,
+            mrl_sensor_implemented,
+            attention_button_implemented,
+        }
+    }
+}
+
+/// Secondary Bus Configuration, MSI Control and Programming Interface registers
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecondaryBusConfiguration {
+    /// Current Bus Segment Speed/Mode
+    pub current_speed_mode: u8,
+    /// MSI Message Number used by SHPC
+    pub msi_message_number: u8,
+    /// Programming Interface. SHPC revision 1.0 reports 01h.
+    pub programming_interface: u8,
+}
+impl From<u32> for SecondaryBusConfiguration {
+    fn from(dword: u32) -> Self {
+        let Lsb((current_speed_mode, (), msi_message_number, (), programming_interface)) =
+            P5::<u32, 4, 12, 5, 3, 8>(dword).into();
+        Self {
+            current_speed_mode,
+            msi_message_number,
+            programming_interface,
+        }
+    }
+}
+
+/// Command
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Command {
+    /// Command Code
+    pub code: u8,
+    /// Target Slot of the slot commands
+    pub target_slot: u8,
+}
+impl From<u16> for Command {
+    fn from(word: u16) -> Self {
+        let Lsb((code, target_slot, ())) = P3::<u16, 8, 5, 3>(word).into();
+        Self { code, target_slot }
+    }
+}
+impl From<Command> for u16 {
+    fn from(data: Command) -> Self {
+        data.code as u16 | (data.target_slot as u16 & 0x1f) << 8
+    }
+}
+impl Register for Command {
+    type Value = u16;
+    const RSVDP: u16 = 0xe000;
+    const RW1C: u16 = 0;
+}
+
+/// Command Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommandStatus {
+    /// Controller Busy. SHPC is executing a command.
+    pub busy: bool,
+    /// MRL Open. Last command was rejected because MRL of the target slot is open.
+    pub mrl_open: bool,
+    /// Invalid Command
+    pub invalid_command: bool,
+    /// Invalid Speed/Mode
+    pub invalid_speed_mode: bool,
+}
+impl From<u16> for CommandStatus {
+    fn from(word: u16) -> Self {
+        let Lsb((busy, mrl_open, invalid_command, invalid_speed_mode, ())) =
+            P5::<u16, 1, 1, 1, 1, 12>(word).into();
+        Self {
+            busy,
+            mrl_open,
+            invalid_command,
+            invalid_speed_mode,
+        }
+    }
+}
+
+/// Controller SERR-INT Enable
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerrIntEnable {
+    /// Global Interrupt Mask
+    pub global_interrupt_mask: bool,
+    /// Global SERR Mask
+    pub global_serr_mask: bool,
+    /// Command Completion Interrupt Mask
+    pub command_completion_interrupt_mask: bool,
+    /// Arbiter SERR Mask
+    pub arbiter_serr_mask: bool,
+    /// Command Completion Detected
+    pub command_completion_detected: bool,
+    /// Arbiter Timeout Detected
+    pub arbiter_timeout_detected: bool,
+}
+impl From<u32> for SerrIntEnable {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            global_interrupt_mask,
+            global_serr_mask,
+            command_completion_interrupt_mask,
+            arbiter_serr_mask,
+            (),
+            command_completion_detected,
+            arbiter_timeout_detected,
+            (),
+        )) = P8::<u32, 1, 1, 1, 1, 12, 1, 1, 14>(dword).into();
+        Self {
+            global_interrupt_mask,
+            global_serr_mask,
+            command_completion_interrupt_mask,
+            arbiter_serr_mask,
+            command_completion_detected,
+            arbiter_timeout_detected,
+        }
+    }
+}
+impl From<SerrIntEnable> for u32 {
+    fn from(data: SerrIntEnable) -> Self {
+        (data.global_interrupt_mask as u32)
+            | (data.global_serr_mask as u32) << 1
+            | (data.command_completion_interrupt_mask as u32) << 2
+            | (data.arbiter_serr_mask as u32) << 3
+            | (data.command_completion_detected as u32) << 16
+            | (data.arbiter_timeout_detected as u32) << 17
+    }
+}
+impl Register for SerrIntEnable {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0003_0000;
+}
+
+/// An iterator through Slot registers of implemented slots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slots {
+    registers: [u32; Self::MAX_NUMBER],
+    count: usize,
+    position: usize,
+}
+impl Slots {
+    /// SHPC supports up to 31 slots
+    pub const MAX_NUMBER: usize = 31;
+}
+impl Iterator for Slots {
+    type Item = SlotRegister;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.count {
+            return None;
+        }
+        let dword = self.registers[self.position];
+        self.position += 1;
+        Some(dword.into())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Slots {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+/// Slot register: status, event latches and event masks of one slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotRegister {
+    /// Slot State
+    pub slot_state: SlotState,
+    /// Power Indicator State
+    pub power_indicator: IndicatorState,
+    /// Attention Indicator State
+    pub attention_indicator: IndicatorState,
+    /// Power Fault Detected
+    pub power_fault: bool,
+    /// Attention Button is pressed
+    pub attention_button_pressed: bool,
+    /// MRL Sensor reports open MRL
+    pub mrl_open: bool,
+    /// 66 MHz Capable (M66EN) of the installed card
+    pub m66en: bool,
+    /// PRSNT1# and PRSNT2# pins of the slot. 11b means empty slot.
+    pub presence: u8,
+    /// PCI-X Capability of the installed card
+    pub pci_x_capability: u8,
+    /// Card Presence Change Detected
+    pub presence_changed: bool,
+    /// Isolated Power Fault Detected
+    pub isolated_power_fault_detected: bool,
+    /// Attention Button Press Detected
+    pub attention_button_press_detected: bool,
+    /// MRL Sensor Change Detected
+    pub mrl_sensor_changed: bool,
+    /// Connected Power Fault Detected
+    pub connected_power_fault_detected: bool,
+    /// Card Presence Change Interrupt Mask
+    pub presence_change_interrupt_mask: bool,
+    /// Isolated Power Fault Interrupt Mask
+    pub isolated_power_fault_interrupt_mask: bool,
+    /// Attention Button Press Interrupt Mask
+    pub attention_button_press_interrupt_mask: bool,
+    /// MRL Sensor Change Interrupt Mask
+    pub mrl_sensor_change_interrupt_mask: bool,
+    /// Connected Power Fault Interrupt Mask
+    pub connected_power_fault_interrupt_mask: bool,
+    /// MRL Sensor Change SERR Mask
+    pub mrl_sensor_change_serr_mask: bool,
+    /// Connected Power Fault SERR Mask
+    pub connected_power_fault_serr_mask: bool,
+}
+impl From<u32> for SlotRegister {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            slot_state,
+            power_indicator,
+            attention_indicator,
+            power_fault,
+            attention_button_pressed,
+            mrl_open,
+            m66en,
+            presence,
+            pci_x_capability,
+            (),
+            events,
+            (),
+            masks,
+            (),
+        )) = P14::<u32, 2, 2, 2, 1, 1, 1, 1, 2, 3, 1, 5, 3, 7, 1>(dword).into();
+        let Lsb((
+            presence_changed,
+            isolated_power_fault_detected,
+            attention_button_press_detected,
+            mrl_sensor_changed,
+            connected_power_fault_detected,
+        )) = P5::<u8, 1, 1, 1, 1, 1>(events).into();
+        let Lsb((
+            presence_change_interrupt_mask,
+            isolated_power_fault_interrupt_mask,
+            attention_button_press_interrupt_mask,
+            mrl_sensor_change_interrupt_mask,
+            connected_power_fault_interrupt_mask,
+            mrl_sensor_change_serr_mask,
+            connected_power_fault_serr_mask,
+        )) = P7::<u8, 1, 1, 1, 1, 1, 1, 1>(masks).into();
+        Self {
+            slot_state: From::<u8>::from(slot_state),
+            power_indicator: From::<u8>::from(power_indicator),
+            attention_indicator: From::<u8>::from(attention_indicator),
+            power_fault,
+            attention_button_pressed,
+            mrl_open,
+            m66en,
+            presence,
+            pci_x_capability,
+            presence_changed,
+            isolated_power_fault_detected,
+            attention_button_press_detected,
+            mrl_sensor_changed,
+            connected_power_fault_detected,
+            presence_change_interrupt_mask,
+            isolated_power_fault_interrupt_mask,
+            attention_button_press_interrupt_mask,
+            mrl_sensor_change_interrupt_mask,
+            connected_power_fault_interrupt_mask,
+            mrl_sensor_change_serr_mask,
+            connected_power_fault_serr_mask,
+        }
+    }
+}
+impl From<SlotRegister> for u32 {
+    fn from(data: SlotRegister) -> Self {
+        u8::from(data.slot_state) as u32
+            | (u8::from(data.power_indicator) as u32) << 2
+            | (u8::from(data.attention_indicator) as u32) << 4
+            | (data.power_fault as u32) << 6
+            | (data.attention_button_pressed as u32) << 7
+            | (data.mrl_open as u32) << 8
+            | (data.m66en as u32) << 9
+            | (data.presence as u32 & 0b11) << 10
+            | (data.pci_x_capability as u32 & 0b111) << 12
+            | (data.presence_changed as u32) << 16
+            | (data.isolated_power_fault_detected as u32) << 17
+            | (data.attention_button_press_detected as u32) << 18
+            | (data.mrl_sensor_changed as u32) << 19
+            | (data.connected_power_fault_detected as u32) << 20
+            | (data.presence_change_interrupt_mask as u32) << 24
+            | (data.isolated_power_fault_interrupt_mask as u32) << 25
+            | (data.attention_button_press_interrupt_mask as u32) << 26
+            | (data.mrl_sensor_change_interrupt_mask as u32) << 27
+            | (data.connected_power_fault_interrupt_mask as u32) << 28
+            | (data.mrl_sensor_change_serr_mask as u32) << 29
+            | (data.connected_power_fault_serr_mask as u32) << 30
+    }
+}
+impl Register for SlotRegister {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x001f_0000;
+}
+
+/// Slot State
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SlotState {
+    Reserved,
+    /// Slot is powered, but not connected to the bus
+    PowerOnly,
+    /// Slot is powered and connected to the bus
+    Enabled,
+    /// Slot is powered off and disconnected
+    Disabled,
+}
+impl From<u8> for SlotState {
+    fn from(byte: u8) -> Self {
+        match byte & 0b11 {
+            0b01 => Self::PowerOnly,
+            0b10 => Self::Enabled,
+            0b11 => Self::Disabled,
+            _ => Self::Reserved,
+        }
+    }
+}
+impl From<SlotState> for u8 {
+    fn from(data: SlotState) -> Self {
+        match data {
+            SlotState::Reserved => 0b00,
+            SlotState::PowerOnly => 0b01,
+            SlotState::Enabled => 0b10,
+            SlotState::Disabled => 0b11,
+        }
+    }
+}
+
+/// Power and Attention Indicator State
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndicatorState {
+    Reserved,
+    On,
+    Blink,
+    Off,
+}
+impl From<u8> for IndicatorState {
+    fn from(byte: u8) -> Self {
+        match byte & 0b11 {
+            0b01 => Self::On,
+            0b10 => Self::Blink,
+            0b11 => Self::Off,
+            _ => Self::Reserved,
+        }
+    }
+}
+impl From<IndicatorState> for u8 {
+    fn from(data: IndicatorState) -> Self {
+        match data {
+            IndicatorState::Reserved => 0b00,
+            IndicatorState::On => 0b01,
+            IndicatorState::Blink => 0b10,
+            IndicatorState::Off => 0b11,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    // Recorded register image of a four slot controller, slot 3 is empty
+    const IMAGE: [u32; 13] = [
+        0x0000_1000, // Base Offset
+        0x0004_0404, // Slots Available I
+        0x0000_0004, // Slots Available II
+        0x6001_0204, // Slot Configuration
+        0x0100_0001, // Secondary Bus Configuration
+        0x0001_0000, // Command / Command Status
+        0x0000_0008, // Interrupt Locator
+        0x0000_0000, // SERR Locator
+        0x0001_0003, // Controller SERR-INT Enable
+        0x0000_2236, // Slot 1
+        0x0000_2236, // Slot 2
+        0x0005_0c3f, // Slot 3
+        0x1f00_10ff, // Slot 4
+    ];
+
+    #[test]
+    fn read_from_image() {
+        let shpc = Shpc::read_from(&IMAGE[..]).unwrap();
+        assert_eq!(0x1000, shpc.base_offset);
+        assert_eq!(
+            SlotsAvailable {
+                conventional_33mhz: 4,
+                pci_x_66mhz: 4,
+                pci_x_100mhz: 4,
+                pci_x_133mhz: 0,
+                conventional_66mhz: 4,
+                pci_x_266_66mhz: 0,
+                pci_x_266_100mhz: 0,
+                pci_x_266_133mhz: 0,
+                pci_x_533_66mhz: 0,
+                pci_x_533_100mhz: 0,
+                pci_x_533_133mhz: 0,
+            },
+            shpc.slots_available
+        );
+        assert_eq!(
+            SlotConfiguration {
+                number_of_slots: 4,
+                first_device_number: 2,
+                physical_slot_number: 1,
+                physical_slot_number_up: true,
+                mrl_sensor_implemented: true,
+                attention_button_implemented: false,
+            },
+            shpc.slot_configuration
+        );
+        assert_eq!(
+            SecondaryBusConfiguration {
+                current_speed_mode: 1,
+                msi_message_number: 0,
+                programming_interface: 1,
+            },
+            shpc.secondary_bus_configuration
+        );
+        assert_eq!(
+            CommandStatus {
+                busy: true,
+                mrl_open: false,
+                invalid_command: false,
+                invalid_speed_mode: false,
+            },
+            shpc.command_status
+        );
+        assert!(shpc.serr_int_enable.command_completion_detected);
+        assert_eq!(0x34, shpc.registers().size());
+
+        let states = shpc
+            .slots
+            .map(|slot| (slot.slot_state, slot.power_indicator, slot.presence))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (SlotState::Enabled, IndicatorState::On, 0b00),
+                (SlotState::Enabled, IndicatorState::On, 0b00),
+                (SlotState::Disabled, IndicatorState::Off, 0b11),
+                (SlotState::Disabled, IndicatorState::Off, 0b00),
+            ],
+            states
+        );
+    }
+
+    #[test]
+    fn slot_register_round_trip() {
+        for dword in &IMAGE[9..] {
+            let slot = SlotRegister::from(*dword);
+            assert_eq!(*dword, u32::from(slot));
+        }
+        let slot = SlotRegister::from(IMAGE[11]);
+        assert!(slot.presence_changed && slot.attention_button_press_detected);
+        assert_eq!(IMAGE[11], slot.clone().clear_value(IMAGE[11]));
+        assert_eq!(IMAGE[11] & !0x001f_0000, slot.write_value(IMAGE[11]));
+    }
+
+    #[test]
+    fn truncated_image() {
+        let result = Shpc::read_from(&IMAGE[..11]);
+        assert_eq!(Err(ShpcOutOfBoundsError { index: 11 }), result);
+    }
+}
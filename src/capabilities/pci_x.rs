@@ -167,8 +167,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P11, P13, P14, P2, P4, P5, P6, P8};
 use snafu::Snafu;
 
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
 /// PCI-X Errors
 #[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PciXError {
     #[snafu(display("command and status registers are unreadable"))]
     CommandAndStatus,
@@ -180,6 +183,7 @@ pub enum PciXError {
 
 /// Type 00h Configuration Space header PCI-X Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PciX {
     pub command: Command,
     pub status: Status,
@@ -236,8 +240,35 @@ impl TryFrom<&[u8]> for PciX {
     }
 }
 
+/// PCI-X Capability registers, ECC registers are present only for non-zero capability version
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                          // Capability ID
+    Attr::new(0x01, 1),                          // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0x007f).rsvdp(0xcf80), // PCI-X Command
+    Attr::new(0x04, 4).rw1c(0x200c_0000),        // PCI-X Status
+    Attr::new(0x08, 4)
+        .rw(0xd000_0001)
+        .rw1c(0x0000_000c)
+        .rsvdp(0x2000_0000), // ECC Control and Status
+    Attr::new(0x0c, 4),                          // ECC First Address
+    Attr::new(0x10, 4),                          // ECC Second Address
+    Attr::new(0x14, 4),                          // ECC Attribute
+];
+
+impl PciX {
+    /// Access attributes of registers of the actual structure layout
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        match self.ecc {
+            Ecc::None => map.truncate(0x08),
+            _ => map,
+        }
+    }
+}
+
 /// Controls various modes and features of the PCI-X device
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Command {
     /// Enable the device to attempt to recover from uncorrectable data errors
     pub uncorrectable_data_error_recovery_enable: bool,
@@ -268,9 +299,25 @@ impl From<u16> for Command {
     }
 }
 
+impl From<Command> for u16 {
+    fn from(data: Command) -> Self {
+        (data.uncorrectable_data_error_recovery_enable as u16)
+            | (data.enable_relaxed_ordering as u16) << 1
+            | (data.maximum_memory_read_byte_count.0 as u16 & 0b11) << 2
+            | (data.maximum_outstanding_split_transactions.0 as u16 & 0b111) << 4
+    }
+}
+
+impl Register for Command {
+    type Value = u16;
+    const RSVDP: u16 = 0xff80;
+    const RW1C: u16 = 0;
+}
+
 /// Maximum byte count the device uses (device-function is designed to use)
 /// when initiating a Sequence with one of the burst memory read commands
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaximumByteCount(pub u8);
 impl MaximumByteCount {
     pub fn value(&self) -> usize {
@@ -281,6 +328,7 @@ impl MaximumByteCount {
 /// Indicates a number that is greater than or equal to the maximum number of
 /// Split Transactions
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaximumOutstandingSplitTransactions(pub u8);
 impl MaximumOutstandingSplitTransactions {
     pub fn value(&self) -> u8 {
@@ -300,6 +348,7 @@ impl MaximumOutstandingSplitTransactions {
 
 /// Capabilities and current operating mode of the device
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Status {
     /// Function Number
     pub function_number: u8,
@@ -372,8 +421,34 @@ impl From<u32> for Status {
         }
     }
 }
+
+impl From<Status> for u32 {
+    fn from(data: Status) -> Self {
+        (data.function_number as u32 & 0b111)
+            | (data.device_number as u32 & 0b11111) << 3
+            | (data.bus_number as u32) << 8
+            | (data.device_64_bit as u32) << 16
+            | (data.pci_x_133_capable as u32) << 17
+            | (data.slit_completion_discarded as u32) << 18
+            | (data.unexpected_split_completion as u32) << 19
+            | (bool::from(data.device_complexity) as u32) << 20
+            | (data.designed_maximum_memory_read_byte_count.0 as u32 & 0b11) << 21
+            | (data.designed_maximum_outstanding_split_transactions.0 as u32 & 0b111) << 23
+            | (data.designed_maximum_cumulative_read_size.0 as u32 & 0b111) << 26
+            | (data.received_split_completion_error_message as u32) << 29
+            | (data.pci_x_266_capable as u32) << 30
+            | (data.pci_x_533_capable as u32) << 31
+    }
+}
+
+impl Register for Status {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x200c_0000;
+}
 /// Indicates whether this device is a simple device or a bridge device
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeviceComplexity {
     Simple,
     Bridge,
@@ -387,11 +462,20 @@ impl From<bool> for DeviceComplexity {
         }
     }
 }
+impl From<DeviceComplexity> for bool {
+    fn from(data: DeviceComplexity) -> Self {
+        match data {
+            DeviceComplexity::Simple => false,
+            DeviceComplexity::Bridge => true,
+        }
+    }
+}
 
 /// Indicates a number that is greater than or equal to the maximum cumulative
 /// size of all burst memory read transactions the devicefunction is designed to
 /// have outstanding at one time as a requester
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaximumCumulativeReadSize(pub u8);
 impl MaximumCumulativeReadSize {
     pub fn adqs(&self) -> usize {
@@ -405,6 +489,7 @@ impl MaximumCumulativeReadSize {
 /// Indicate the format of the PCI-X Capabilities List item, and whether the
 /// device supports ECC in Mode 1
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Ecc {
     None,
     Mode2Only {
@@ -424,6 +509,7 @@ pub enum Ecc {
 
 /// Provides information about detected ECC errors
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EccControlAndStatus {
     /// Select Secondary ECC Registers
     pub select_secondary_ecc_registers: bool,
@@ -486,8 +572,33 @@ impl From<u32> for EccControlAndStatus {
     }
 }
 
+impl From<EccControlAndStatus> for u32 {
+    fn from(data: EccControlAndStatus) -> Self {
+        (data.select_secondary_ecc_registers as u32)
+            | (data.error_present_in_other_ecc_register_bank as u32) << 1
+            | (data.additional_correctable_ecc_error as u32) << 2
+            | (data.additional_uncorrectable_ecc_error as u32) << 3
+            | (u8::from(data.ecc_error_phase) as u32) << 4
+            | (data.ecc_error_corrected as u32) << 7
+            | (u8::from(data.syndrome) as u32) << 8
+            | (data.error_first_command as u32 & 0b1111) << 16
+            | (data.error_second_command as u32 & 0b1111) << 20
+            | (data.error_upper_attributes as u32 & 0b1111) << 24
+            | (data.ecc_control_update_enable as u32) << 28
+            | (data.disable_single_bit_error_correction as u32) << 30
+            | (data.ecc_mode as u32) << 31
+    }
+}
+
+impl Register for EccControlAndStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0x2000_0000;
+    const RW1C: u32 = 0x0000_000c;
+}
+
 /// ECC Error Phase
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EccErrorPhase {
     /// No error
     NoError,
@@ -518,9 +629,23 @@ impl From<u8> for EccErrorPhase {
         }
     }
 }
+impl From<EccErrorPhase> for u8 {
+    fn from(data: EccErrorPhase) -> Self {
+        match data {
+            EccErrorPhase::NoError => 0,
+            EccErrorPhase::First32bits => 1,
+            EccErrorPhase::Second32bits => 2,
+            EccErrorPhase::AttributePhase => 3,
+            EccErrorPhase::Phase32or16bit => 4,
+            EccErrorPhase::Phase64bit => 5,
+            EccErrorPhase::Reserved => 6,
+        }
+    }
+}
 
 /// The syndrome indicates information about the bit or bits that are in error
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Syndrome {
     /// E0
     pub e0: bool,
@@ -556,8 +681,22 @@ impl From<u8> for Syndrome {
     }
 }
 
+impl From<Syndrome> for u8 {
+    fn from(data: Syndrome) -> Self {
+        (data.e0 as u8)
+            | (data.e1 as u8) << 1
+            | (data.e2 as u8) << 2
+            | (data.e3 as u8) << 3
+            | (data.e4 as u8) << 4
+            | (data.e5 as u8) << 5
+            | (data.e6 as u8) << 6
+            | (data.e7 as u8) << 7
+    }
+}
+
 /// PCI-X Bridge Errors
 #[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PciXBridgeError {
     #[snafu(display("statuses and split trx control registers are unreadable"))]
     Mandatory,
@@ -569,6 +708,7 @@ pub enum PciXBridgeError {
 
 /// Type 01h Configuration Space header (Bridge) PCI-X Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PciXBridge {
     pub secondary_status: SecondaryStatus,
     pub bridge_status: BridgeStatus,
@@ -646,8 +786,37 @@ impl TryFrom<&[u8]> for PciXBridge {
     }
 }
 
+/// PCI-X Bridge Capability registers, ECC registers are present only for non-zero capability version
+pub const BRIDGE_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                      // Capability ID
+    Attr::new(0x01, 1),                                      // Next Capability Pointer
+    Attr::new(0x02, 2).rw1c(0x003c).rsvdz(0x0c00),           // PCI-X Secondary Status
+    Attr::new(0x04, 4).rw1c(0x003c_0000).rsvdz(0x1fc0_0000), // PCI-X Bridge Status
+    Attr::new(0x08, 4).rw(0xffff_0000),                      // Upstream Split Transaction Control
+    Attr::new(0x0c, 4).rw(0xffff_0000),                      // Downstream Split Transaction Control
+    Attr::new(0x10, 4)
+        .rw(0xd000_0001)
+        .rw1c(0x0000_000c)
+        .rsvdp(0x2000_0000), // ECC Control and Status
+    Attr::new(0x14, 4),                                      // ECC First Address
+    Attr::new(0x18, 4),                                      // ECC Second Address
+    Attr::new(0x1c, 4),                                      // ECC Attribute
+];
+
+impl PciXBridge {
+    /// Access attributes of registers of the actual structure layout
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(BRIDGE_REGISTERS);
+        match self.ecc {
+            Ecc::None => map.truncate(0x10),
+            _ => map,
+        }
+    }
+}
+
 /// Reports status information about the secondary bus
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SecondaryStatus {
     /// 64-bit Device
     pub device_64_bit: bool,
@@ -732,8 +901,29 @@ impl From<u16> for SecondaryStatus {
     }
 }
 
+impl From<SecondaryStatus> for u16 {
+    fn from(data: SecondaryStatus) -> Self {
+        (data.device_64_bit as u16)
+            | (data.pci_x_133_capable as u16) << 1
+            | (data.slit_completion_discarded as u16) << 2
+            | (data.unexpected_split_completion as u16) << 3
+            | (data.split_completion_overrun as u16) << 4
+            | (data.split_request_delayed as u16) << 5
+            | (data.secondary_bus_mode_and_frequency() as u16) << 6
+            | (data.pci_x_266_capable as u16) << 14
+            | (data.pci_x_533_capable as u16) << 15
+    }
+}
+
+impl Register for SecondaryStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x003c;
+}
+
 /// Secondary Bus Mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SecondaryBusMode {
     /// Conventional
     Conventional,
@@ -759,6 +949,7 @@ impl From<u8> for SecondaryBusMode {
 
 /// Error Protection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ErrorProtection {
     /// Parity
     Parity,
@@ -778,6 +969,7 @@ impl From<u8> for ErrorProtection {
 
 /// Secondary Bus Max Clock Freq and Min Clock Period
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SecondaryBusFrequency {
     /// N/A
     NotAvailable,
@@ -807,6 +999,7 @@ impl From<u8> for SecondaryBusFrequency {
 /// Identifies the capabilities and current operating mode of the bridge on its
 /// primary bus as listed in the following table
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BridgeStatus {
     /// Function Number
     pub function_number: u8,
@@ -868,8 +1061,32 @@ impl From<u32> for BridgeStatus {
     }
 }
 
+impl From<BridgeStatus> for u32 {
+    fn from(data: BridgeStatus) -> Self {
+        (data.function_number as u32 & 0b111)
+            | (data.device_number as u32 & 0b11111) << 3
+            | (data.bus_number as u32) << 8
+            | (data.device_64_bit as u32) << 16
+            | (data.pci_x_133_capable as u32) << 17
+            | (data.slit_completion_discarded as u32) << 18
+            | (data.unexpected_split_completion as u32) << 19
+            | (data.split_completion_overrun as u32) << 20
+            | (data.split_request_delayed as u32) << 21
+            | (data.device_id_messaging_capable as u32) << 29
+            | (data.pci_x_266_capable as u32) << 30
+            | (data.pci_x_533_capable as u32) << 31
+    }
+}
+
+impl Register for BridgeStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x003c_0000;
+}
+
 /// Controls behavior of the bridge buffers for forwarding Split Transactions
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SplitTransactionControl {
     /// Split Transaction Capacity
     pub split_transaction_capacity: u16,
@@ -877,6 +1094,13 @@ pub struct SplitTransactionControl {
     pub split_transaction_commitment_limit: u16,
 }
 
+impl From<SplitTransactionControl> for u32 {
+    fn from(data: SplitTransactionControl) -> Self {
+        (data.split_transaction_capacity as u32)
+            | (data.split_transaction_commitment_limit as u32) << 16
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
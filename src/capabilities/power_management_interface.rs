@@ -60,13 +60,16 @@ let sample = PowerManagementInterface {
 assert_eq!(sample, result);
 ```
 */
+#![allow(clippy::doc_lazy_continuation)]
 
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P12, P3, P4, P8};
 
 use super::CapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PowerManagementInterface {
     pub capabilities: Capabilities,
     pub control: Control,
@@ -111,16 +114,6 @@ impl TryFrom<&[u8]> for PowerManagementInterface {
             d3_hot,
             d3_cold,
         )) = P12::<u16, 3, 1, 1, 1, 3, 1, 1, 1, 1, 1, 1, 1>(capabilities).into();
-        let Lsb((
-            power_state,
-            (),
-            no_soft_reset,
-            (),
-            pme_enabled,
-            data_select,
-            data_scale,
-            pme_status,
-        )) = P8::<u16, 2, 1, 1, 4, 1, 4, 2, 1>(control).into();
         let Lsb((reserved, b2_b3, bpcc_enabled)) = P3::<u8, 6, 1, 1>(bridge).into();
         Ok(Self {
             capabilities: Capabilities {
@@ -139,14 +132,7 @@ impl TryFrom<&[u8]> for PowerManagementInterface {
                     d3_cold,
                 },
             },
-            control: Control {
-                power_state: From::<u8>::from(power_state),
-                no_soft_reset,
-                pme_enabled,
-                data_select: From::<u8>::from(data_select),
-                data_scale: From::<u8>::from(data_scale),
-                pme_status,
-            },
+            control: From::<u16>::from(control),
             bridge: Bridge {
                 reserved,
                 b2_b3,
@@ -157,8 +143,19 @@ impl TryFrom<&[u8]> for PowerManagementInterface {
     }
 }
 
+/// Power Management Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                                       // Capability ID
+    Attr::new(0x01, 1),                                       // Next Capability Pointer
+    Attr::new(0x02, 2),                                       // Power Management Capabilities
+    Attr::new(0x04, 2).rw(0x1f03).rsvdp(0x00f4).rw1c(0x8000), // Power Management Control/Status
+    Attr::new(0x06, 1),                                       // PMCSR_BSE Bridge Extensions
+    Attr::new(0x07, 1),                                       // Data
+];
+
 /// Provides information on the capabilities of the function related to power management
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Capabilities {
     /// Default value of 0b10 indicates that this function complies with Revision 1.1 of the PCI
     /// Power Management Interface Specification.
@@ -182,6 +179,7 @@ pub struct Capabilities {
 /// This 3 bit field reports the 3.3Vaux auxiliary current requirements for the PCI function.
 /// he [Data] Register takes precedence over this field for 3.3Vaux current and value must be 0.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AuxCurrent {
     /// 0mA
     SelfPowered,
@@ -218,6 +216,7 @@ impl From<u8> for AuxCurrent {
 
 /// Indicates the power states in which the function may assert PME#.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PmeSupport {
     /// PME# can be asserted from D0
     pub d0: bool,
@@ -233,6 +232,7 @@ pub struct PmeSupport {
 
 /// Used to manage the PCI function’s power management state as well as to enable/monitor PMEs.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Control {
     pub power_state: PowerState,
     /// State of the Function after writing the PowerState field to transition the Function
@@ -246,9 +246,47 @@ pub struct Control {
     /// state of the [Control.pme_enabled] bit.
     pub pme_status: bool,
 }
+impl From<u16> for Control {
+    fn from(word: u16) -> Self {
+        let Lsb((
+            power_state,
+            (),
+            no_soft_reset,
+            (),
+            pme_enabled,
+            data_select,
+            data_scale,
+            pme_status,
+        )) = P8::<u16, 2, 1, 1, 4, 1, 4, 2, 1>(word).into();
+        Self {
+            power_state: From::<u8>::from(power_state),
+            no_soft_reset,
+            pme_enabled,
+            data_select: From::<u8>::from(data_select),
+            data_scale: From::<u8>::from(data_scale),
+            pme_status,
+        }
+    }
+}
+impl From<Control> for u16 {
+    fn from(data: Control) -> Self {
+        (u8::from(data.power_state) as u16)
+            | (data.no_soft_reset as u16) << 3
+            | (data.pme_enabled as u16) << 8
+            | (u8::from(data.data_select) as u16 & 0b1111) << 9
+            | (u8::from(data.data_scale) as u16) << 13
+            | (data.pme_status as u16) << 15
+    }
+}
+impl Register for Control {
+    type Value = u16;
+    const RSVDP: u16 = 0x00f4;
+    const RW1C: u16 = 0x8000;
+}
 
 /// Current power state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerState {
     D0,
     D1,
@@ -266,9 +304,20 @@ impl From<u8> for PowerState {
         }
     }
 }
+impl From<PowerState> for u8 {
+    fn from(data: PowerState) -> Self {
+        match data {
+            PowerState::D0 => 0b00,
+            PowerState::D1 => 0b01,
+            PowerState::D2 => 0b10,
+            PowerState::D3Hot => 0b11,
+        }
+    }
+}
 
 /// PCI bridge specific functionality and is required for all PCI-toPCI bridges
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bridge {
     /// Value at reset 0b000000
     pub reserved: u8,
@@ -286,6 +335,7 @@ pub struct Bridge {
 /// Register that provides a mechanism for the function to report state dependent operating data
 /// such as power consumed or heat dissipation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Data {
     pub value: u8,
     pub select: DataSelect,
@@ -294,6 +344,7 @@ pub struct Data {
 
 /// Used to select which data is to be reported through the [Data] register and [DataScale].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataSelect {
     /// D0 Power Consumed
     PowerConsumedD0,
@@ -351,6 +402,7 @@ impl From<DataSelect> for u8 {
 
 /// Scaling factor indicated to arrive at the value for the desired measurement.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataScale {
     Unknown,
     /// 0.1x
@@ -371,6 +423,16 @@ impl From<u8> for DataScale {
         }
     }
 }
+impl From<DataScale> for u8 {
+    fn from(data: DataScale) -> Self {
+        match data {
+            DataScale::Unknown => 0b00,
+            DataScale::Tenth => 0b01,
+            DataScale::Hundredth => 0b10,
+            DataScale::Thousandth => 0b11,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
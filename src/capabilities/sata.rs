@@ -29,9 +29,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3};
 
 use super::CapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 /// Slave/Primary Interface
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sata {
     pub revision: Revision,
     /// BAR Offset
@@ -61,7 +63,17 @@ impl TryFrom<&[u8]> for Sata {
     }
 }
 
+/// SATA Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1), // Capability ID
+    Attr::new(0x01, 1), // Next Capability Pointer
+    Attr::new(0x02, 1), // Revision
+    Attr::new(0x03, 1).rsvdp(0xff),
+    Attr::new(0x04, 4), // BAR Offset/BAR Location
+];
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Revision {
     /// Minor Revision
     pub minor: u8,
@@ -72,6 +84,7 @@ pub struct Revision {
 /// Indicates the absolute PCI Configuration Register address of the BAR containing the Index-Data
 /// Pair in Dword granularity
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BarLocation {
     /// 10h (BAR0)
     Bar0,
@@ -111,6 +124,7 @@ impl From<u8> for BarLocation {
 ///  - Maximum if Index-Data Pair is implemented in IO Space from 0 – 64 KB
 ///  - Maximum if Index-Data Pair is memory mapped in the 0 – (1MB – 4) range)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BarOffset(pub u32);
 impl BarOffset {
     pub fn value(&self) -> u32 {
@@ -0,0 +1,260 @@
+/*!
+# Secure Device
+
+AMD I/O Virtualization Technology (IOMMU) capability block. It locates IOMMU control registers
+in memory space and describes devices translated by the IOMMU.
+
+## Struct diagram
+[SecureDevice]
+- [SecureDeviceHeader]
+- [IommuRange]
+- [MiscInformation0]
+- [MiscInformation1]
+
+## Examples
+
+```rust
+# use pcics::capabilities::secure_device::*;
+let data = [
+    0x0f, 0x00, 0x0b, 0x09, // Capability Header
+    0x01, 0x00, 0xd8, 0xfe, // Base Address Low
+    0x00, 0x00, 0x00, 0x00, // Base Address High
+    0x00, 0x00, 0x00, 0xff, // Range
+    0x00, 0x30, 0x20, 0x00, // Misc Information 0
+];
+let result = data[2..].try_into().unwrap();
+let sample = SecureDevice {
+    header: SecureDeviceHeader {
+        capability_type: 3,
+        capability_revision: 1,
+        iotlb_support: true,
+        ht_tunnel: false,
+        np_cache: false,
+        efr_support: true,
+        misc_information_1_present: false,
+    },
+    enable: true,
+    base_address: 0xfed8_0000,
+    range: IommuRange {
+        unit_id: 0,
+        range_valid: false,
+        bus_number: 0,
+        first_device: 0,
+        last_device: 0xff,
+    },
+    misc_information_0: MiscInformation0 {
+        msi_number: 0,
+        guest_virtual_address_size: 0,
+        physical_address_size: 48,
+        virtual_address_size: 64,
+        ht_ats_reserved: false,
+        msi_number_ppr: 0,
+    },
+    misc_information_1: None,
+};
+assert_eq!(sample, result);
+```
+*/
+
+use heterob::{
+    bit_numbering::Lsb,
+    endianness::{Le, LeBytesTryInto},
+    Seq, P2, P3, P4, P6, P7, P8,
+};
+
+use super::CapabilityDataError;
+use crate::register::{RegisterAttributes as Attr, RegisterMap};
+
+/// Secure Device
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecureDevice {
+    pub header: SecureDeviceHeader,
+    /// IOMMU registers are decoded at [base address](Self::base_address)
+    pub enable: bool,
+    /// 16 KiB aligned base address of IOMMU control registers
+    pub base_address: u64,
+    pub range: IommuRange,
+    pub misc_information_0: MiscInformation0,
+    /// Present if [SecureDeviceHeader::misc_information_1_present] is set
+    pub misc_information_1: Option<MiscInformation1>,
+}
+impl TryFrom<&[u8]> for SecureDevice {
+    type Error = CapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((header, base_address, range, misc_0)),
+            tail,
+        } = P4(slice).try_into().map_err(|_| CapabilityDataError {
+            name: "Secure Device",
+            size: 18,
+        })?;
+        let header: SecureDeviceHeader = From::<u16>::from(header);
+        let misc_information_1 = if header.misc_information_1_present {
+            let Seq { head, .. } = tail.le_bytes_try_into().map_err(|_| CapabilityDataError {
+                name: "Secure Device",
+                size: 22,
+            })?;
+            Some(From::<u32>::from(head))
+        } else {
+            None
+        };
+        let Lsb((enable, (), base_address)): Lsb<(bool, (), u64)> =
+            P3::<u64, 1, 13, 50>(base_address).into();
+        Ok(Self {
+            header,
+            enable,
+            base_address: base_address << 14,
+            range: From::<u32>::from(range),
+            misc_information_0: From::<u32>::from(misc_0),
+            misc_information_1,
+        })
+    }
+}
+impl SecureDevice {
+    /// Access attributes of registers present in the decoded structure
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        if self.misc_information_1.is_some() {
+            map
+        } else {
+            map.truncate(0x14)
+        }
+    }
+}
+
+/// Secure Device Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                                        // Capability Header
+    Attr::new(0x04, 4).rw(0xffff_c001).rsvdp(0x0000_3ffe),     // Base Address Low
+    Attr::new(0x08, 4).rw(0xffff_ffff),                        // Base Address High
+    Attr::new(0x0c, 4).hwinit(0xffff_ff9f).rsvdp(0x0000_0060), // Range
+    Attr::new(0x10, 4).hwinit(0xf87f_ffff).rsvdp(0x0780_0000), // Misc Information 0
+    Attr::new(0x14, 4).hwinit(0x0000_001f).rsvdp(0xffff_ffe0), // Misc Information 1
+];
+
+/// Upper half of Capability Header
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecureDeviceHeader {
+    /// Capability Type (CapType). IOMMU reports 011b.
+    pub capability_type: u8,
+    /// Capability Revision (CapRev)
+    pub capability_revision: u8,
+    /// IOTLB Support (IotlbSup). IOMMU supports remote IOTLBs.
+    pub iotlb_support: bool,
+    /// HyperTransport Tunnel translation support (HtTunnel)
+    pub ht_tunnel: bool,
+    /// Not Present table entries are cached (NpCache)
+    pub np_cache: bool,
+    /// IOMMU Extended Feature Register is supported (EFRSup)
+    pub efr_support: bool,
+    /// Miscellaneous Information Register 1 is present (CapExt)
+    pub misc_information_1_present: bool,
+}
+impl From<u16> for SecureDeviceHeader {
+    fn from(word: u16) -> Self {
+        let Lsb((
+            capability_type,
+            capability_revision,
+            iotlb_support,
+            ht_tunnel,
+            np_cache,
+            efr_support,
+            misc_information_1_present,
+            (),
+        )) = P8::<u16, 3, 5, 1, 1, 1, 1, 1, 3>(word).into();
+        Self {
+            capability_type,
+            capability_revision,
+            iotlb_support,
+            ht_tunnel,
+            np_cache,
+            efr_support,
+            misc_information_1_present,
+        }
+    }
+}
+
+/// IOMMU Range. Devices on the bus translated by the IOMMU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IommuRange {
+    /// HyperTransport UnitID of the IOMMU
+    pub unit_id: u8,
+    /// Range Valid (RngValid). Bus and device range fields are valid.
+    pub range_valid: bool,
+    /// Bus Number of the devices
+    pub bus_number: u8,
+    /// First Device and Function number
+    pub first_device: u8,
+    /// Last Device and Function number
+    pub last_device: u8,
+}
+impl From<u32> for IommuRange {
+    fn from(dword: u32) -> Self {
+        let Lsb((unit_id, (), range_valid, bus_number, first_device, last_device)) =
+            P6::<u32, 5, 2, 1, 8, 8, 8>(dword).into();
+        Self {
+            unit_id,
+            range_valid,
+            bus_number,
+            first_device,
+            last_device,
+        }
+    }
+}
+
+/// IOMMU Miscellaneous Information Register 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MiscInformation0 {
+    /// MSI Message Number used for event and command interrupts (MsiNum)
+    pub msi_number: u8,
+    /// Guest Virtual Address Size (GVAsize)
+    pub guest_virtual_address_size: u8,
+    /// Maximum guest physical address size in bits (PAsize)
+    pub physical_address_size: u8,
+    /// Maximum virtual address size in bits (VAsize)
+    pub virtual_address_size: u8,
+    /// HyperTransport ATS reserved address range is not used (HtAtsResv)
+    pub ht_ats_reserved: bool,
+    /// MSI Message Number used for peripheral page request interrupts (MsiNumPPR)
+    pub msi_number_ppr: u8,
+}
+impl From<u32> for MiscInformation0 {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            msi_number,
+            guest_virtual_address_size,
+            physical_address_size,
+            virtual_address_size,
+            ht_ats_reserved,
+            (),
+            msi_number_ppr,
+        )) = P7::<u32, 5, 3, 7, 7, 1, 4, 5>(dword).into();
+        Self {
+            msi_number,
+            guest_virtual_address_size,
+            physical_address_size,
+            virtual_address_size,
+            ht_ats_reserved,
+            msi_number_ppr,
+        }
+    }
+}
+
+/// IOMMU Miscellaneous Information Register 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MiscInformation1 {
+    /// MSI Message Number used for guest virtual APIC log interrupts (MsiNumGA)
+    pub msi_number_ga: u8,
+}
+impl From<u32> for MiscInformation1 {
+    fn from(dword: u32) -> Self {
+        let Lsb((msi_number_ga, ())) = P2::<u32, 5, 27>(dword).into();
+        Self { msi_number_ga }
+    }
+}
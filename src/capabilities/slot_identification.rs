@@ -29,9 +29,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3};
 
 use super::CapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 /// Slot Identification
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlotIdentification {
     pub expansion_slot: ExpansionSlot,
     /// Contains the physical chassis number for the slots on this bridge’s secondary interface
@@ -61,9 +63,18 @@ impl<'a> TryFrom<&'a [u8]> for SlotIdentification {
     }
 }
 
+/// Slot Identification Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),              // Capability ID
+    Attr::new(0x01, 1),              // Next Capability Pointer
+    Attr::new(0x02, 1).hwinit(0xff), // Expansion Slot
+    Attr::new(0x03, 1).rw(0xff),     // Chassis Number
+];
+
 /// Provides information used by system software in calculating the slot number of a device plugged
 /// into a PCI slot in an expansion chassis
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExpansionSlot {
     /// Number of PCI expansion slots located directly on the secondary interface of this bridge
     pub expansion_slots_provided: u8,
@@ -48,9 +48,14 @@ assert_eq!(sample, result);
 use snafu::prelude::*;
 use heterob::{endianness::Le, P5};
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::header::Header;
+use crate::register::RegisterAttributes as Attr;
 
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum VendorSpecificError {
     #[snafu(display("length byte is unreadable"))]
     LengthUnreadable,
@@ -63,6 +68,7 @@ pub enum VendorSpecificError {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum VendorSpecific<'a> {
     /// Only vendor-specific data. Without Cap ID, Next Ptr and length itself
     Unspecified(&'a [u8]),
@@ -92,12 +98,51 @@ impl<'a> VendorSpecific<'a> {
     }
 }
 
+/// Owned [VendorSpecific]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedVendorSpecific {
+    /// Only vendor-specific data. Without Cap ID, Next Ptr and length itself
+    Unspecified(Vec<u8>),
+    /// Known vendor-specific capabilities
+    Virtio(Virtio),
+}
 
+#[cfg(feature = "alloc")]
+impl<'a> From<&VendorSpecific<'a>> for OwnedVendorSpecific {
+    fn from(vs: &VendorSpecific<'a>) -> Self {
+        match vs {
+            VendorSpecific::Unspecified(data) => Self::Unspecified(data.to_vec()),
+            VendorSpecific::Virtio(virtio) => Self::Virtio(virtio.clone()),
+        }
+    }
+}
 
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedVendorSpecific> for VendorSpecific<'a> {
+    fn from(vs: &'a OwnedVendorSpecific) -> Self {
+        match vs {
+            OwnedVendorSpecific::Unspecified(data) => Self::Unspecified(data),
+            OwnedVendorSpecific::Virtio(virtio) => Self::Virtio(virtio.clone()),
+        }
+    }
+}
+
+/// Vendor Specific Capability registers, vendor defined registers are not described
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1), // Capability ID
+    Attr::new(0x01, 1), // Next Capability Pointer
+    Attr::new(0x02, 1), // Capability Length
+];
 
 
 
-#[derive(Debug, PartialEq, Eq)]
+
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Virtio {
     CommonCfg { bar: u8, offset: u32, size: u32 },
     Notify {
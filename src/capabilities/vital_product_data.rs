@@ -25,9 +25,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2};
 
 use super::CapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 /// Vital Product Data
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VitalProductData {
     /// DWORD-aligned byte address of the VPD to be accessed
     pub vpd_address: u16,
@@ -56,3 +58,11 @@ impl TryFrom<&[u8]> for VitalProductData {
         })
     }
 }
+
+/// VPD Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 1),                 // Capability ID
+    Attr::new(0x01, 1),                 // Next Capability Pointer
+    Attr::new(0x02, 2).rw(0xffff),      // VPD Address
+    Attr::new(0x04, 4).rw(0xffff_ffff), // VPD Data
+];
@@ -0,0 +1,268 @@
+/*!
+## Owned configuration space
+
+Decoded capabilities borrow configuration space data, so they could not outlive the buffer.
+[ConfigurationSpace] owns the decoded [Header], [owned capabilities](OwnedCapability),
+[owned extended capabilities](OwnedExtendedCapability) and parse errors of both capabilities
+lists. It has no lifetime parameters, so it could be kept in a long-lived inventory or sent to
+another thread. Every borrowed capability type converts into its owned counterpart with
+[From].
+
+## Example
+```rust
+# use pcics::{
+#     capabilities::OwnedCapabilityKind,
+#     configuration_space::{ConfigurationSpace, ConfigurationSpaceError},
+#     extended_capabilities::{ExtendedCapabilityError, OwnedExtendedCapabilityKind},
+# };
+let data = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/device/8086_2030/config"
+));
+let cs = std::thread::spawn(move || ConfigurationSpace::try_from(&data[..]).unwrap())
+    .join()
+    .unwrap();
+assert_eq!((0x8086, 0x2030), (cs.header.vendor_id, cs.header.device_id));
+assert!(cs
+    .capabilities
+    .iter()
+    .any(|cap| matches!(cap.kind, OwnedCapabilityKind::PciExpress(_))));
+assert_eq!(8, cs.extended_capabilities.len());
+assert!(matches!(
+    cs.extended_capabilities[0].kind,
+    OwnedExtendedCapabilityKind::VendorSpecificExtendedCapability(_)
+));
+assert!(cs.errors.is_empty());
+
+// Extended capability at 0x110 points back to the first one
+let mut data = data.to_vec();
+data[0x112..0x114].copy_from_slice(&0x1001u16.to_le_bytes());
+let cs = ConfigurationSpace::try_from(data.as_slice()).unwrap();
+assert_eq!(2, cs.extended_capabilities.len());
+assert_eq!(
+    vec![ConfigurationSpaceError::ExtendedCapability {
+        offset: 0x100,
+        source: ExtendedCapabilityError::Loop { offset: 0x100 },
+    }],
+    cs.errors
+);
+```
+*/
+
+use alloc::vec::Vec;
+use core::array::TryFromSliceError;
+
+use snafu::prelude::*;
+
+use crate::{
+    capabilities::{CapabilityError, OwnedCapability},
+    extended_capabilities::{ExtendedCapabilityError, OwnedExtendedCapability},
+    Capabilities, ExtendedCapabilities, Header, Lspci, DDR_OFFSET, ECS_OFFSET,
+};
+
+/// Capabilities list parsing error with the offset of the failed structure
+#[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+pub enum ConfigurationSpaceError {
+    #[snafu(display("[{offset:02x}] {source}"))]
+    Capability { offset: u8, source: CapabilityError },
+    #[snafu(display("[{offset:03x}] {source}"))]
+    ExtendedCapability {
+        offset: u16,
+        source: ExtendedCapabilityError,
+    },
+}
+
+/// Decoded configuration space owning its data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurationSpace {
+    pub header: Header,
+    /// Successfully decoded capabilities, empty if device dependent region is not available
+    pub capabilities: Vec<OwnedCapability>,
+    /// Successfully decoded extended capabilities, empty if extended configuration space is not
+    /// available
+    pub extended_capabilities: Vec<OwnedExtendedCapability>,
+    /// Errors of capabilities and extended capabilities lists in order of occurrence
+    pub errors: Vec<ConfigurationSpaceError>,
+    /// Raw configuration space (64, 256 or 4096 bytes)
+    data: Vec<u8>,
+}
+
+impl ConfigurationSpace {
+    fn new(header: Header, data: Vec<u8>) -> Self {
+        let mut cs = Self {
+            header,
+            capabilities: Vec::new(),
+            extended_capabilities: Vec::new(),
+            errors: Vec::new(),
+            data,
+        };
+        cs.decode();
+        cs
+    }
+
+    /// Raw configuration space data
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// `lspci -vvv` style view of the function
+    pub fn lspci(&self) -> Lspci<'_> {
+        Lspci::new(self.header.clone(), &self.data)
+    }
+
+    fn capabilities_list(&self) -> Option<Capabilities<'_>> {
+        self.data
+            .get(DDR_OFFSET..ECS_OFFSET)
+            .map(|ddr| Capabilities::new(ddr, &self.header))
+    }
+
+    fn extended_capabilities_list(&self) -> Option<ExtendedCapabilities<'_>> {
+        self.data
+            .get(ECS_OFFSET..)
+            .filter(|ecs| !ecs.is_empty())
+            .map(ExtendedCapabilities::new)
+    }
+
+    fn decode(&mut self) {
+        let mut capabilities = Vec::new();
+        let mut extended_capabilities = Vec::new();
+        let mut errors = Vec::new();
+        if let Some(mut caps) = self.capabilities_list() {
+            loop {
+                let offset = caps.pointer();
+                match caps.next() {
+                    Some(Ok(cap)) => capabilities.push(OwnedCapability::from(&cap)),
+                    Some(Err(source)) => {
+                        errors.push(ConfigurationSpaceError::Capability { offset, source })
+                    }
+                    None => break,
+                }
+            }
+        }
+        if let Some(mut ecaps) = self.extended_capabilities_list() {
+            loop {
+                let offset = ecaps.next_capability_offset();
+                match ecaps.next() {
+                    Some(Ok(ecap)) => {
+                        extended_capabilities.push(OwnedExtendedCapability::from(&ecap))
+                    }
+                    Some(Err(source)) => {
+                        errors.push(ConfigurationSpaceError::ExtendedCapability { offset, source })
+                    }
+                    None => break,
+                }
+            }
+        }
+        self.capabilities = capabilities;
+        self.extended_capabilities = extended_capabilities;
+        self.errors = errors;
+    }
+}
+
+impl TryFrom<&[u8]> for ConfigurationSpace {
+    type Error = TryFromSliceError;
+
+    /// Copy configuration space data starting at offset 0
+    ///
+    /// Fails only if `slice` is shorter than [Header::TOTAL_SIZE].
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let header = Header::try_from(slice)?;
+        Ok(Self::new(header, slice.to_vec()))
+    }
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+impl From<crate::sysfs::Device> for ConfigurationSpace {
+    fn from(device: crate::sysfs::Device) -> Self {
+        Self::new(device.header, device.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::OwnedCapabilityKind;
+    use crate::extended_capabilities::{
+        secondary_pci_express::OwnedSecondaryPciExpress, ExtendedCapabilityKind,
+        OwnedExtendedCapabilityKind, SecondaryPciExpress,
+    };
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    const DATA: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/device/8086_2030/config"
+    ));
+
+    fn assert_send_sync_static<T: Send + Sync + 'static>(_: &T) {}
+
+    #[test]
+    fn owned() {
+        let cs = ConfigurationSpace::try_from(DATA).unwrap();
+        assert_send_sync_static(&cs);
+        let result = cs
+            .capabilities
+            .iter()
+            .map(|cap| cap.pointer)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0x40, 0x60, 0x90, 0xe0], result);
+        let result = cs
+            .extended_capabilities
+            .iter()
+            .map(|ecap| ecap.offset)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![0x100, 0x110, 0x148, 0x1d0, 0x250, 0x280, 0x298, 0x300],
+            result
+        );
+        assert_eq!(Vec::<ConfigurationSpaceError>::new(), cs.errors);
+    }
+
+    #[test]
+    fn regions() {
+        let cs = ConfigurationSpace::try_from(&DATA[..ECS_OFFSET]).unwrap();
+        assert_eq!(4, cs.capabilities.len());
+        assert_eq!(0, cs.extended_capabilities.len());
+        let cs = ConfigurationSpace::try_from(&DATA[..DDR_OFFSET]).unwrap();
+        assert_eq!(0, cs.capabilities.len());
+        assert!(cs.errors.is_empty());
+        assert!(ConfigurationSpace::try_from(&DATA[..0x20]).is_err());
+    }
+
+    #[test]
+    fn errors() {
+        let mut data = DATA[..ECS_OFFSET].to_vec();
+        // MSI at 0x60 points to misaligned 0x92 instead of PCI Express at 0x90
+        data[0x61] = 0x92;
+        let cs = ConfigurationSpace::try_from(data.as_slice()).unwrap();
+        let result = cs
+            .capabilities
+            .iter()
+            .map(|cap| matches!(cap.kind, OwnedCapabilityKind::MessageSignaledInterrups(_)))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![false, true], result);
+        let sample = vec![ConfigurationSpaceError::Capability {
+            offset: 0x92,
+            source: CapabilityError::Misaligned { ptr: 0x92 },
+        }];
+        assert_eq!(sample, cs.errors);
+    }
+
+    #[test]
+    fn owned_converts_back() {
+        let cs = ConfigurationSpace::try_from(DATA).unwrap();
+        let spe = ExtendedCapabilities::new(&DATA[ECS_OFFSET..])
+            .filter_map(Result::ok)
+            .find_map(|ecap| match ecap.kind {
+                ExtendedCapabilityKind::SecondaryPciExpress(spe) => Some(spe),
+                _ => None,
+            })
+            .unwrap();
+        let owned = OwnedSecondaryPciExpress::from(&spe);
+        assert_eq!(
+            OwnedExtendedCapabilityKind::SecondaryPciExpress(owned.clone()),
+            cs.extended_capabilities[4].kind
+        );
+        assert_eq!(spe, SecondaryPciExpress::from(&owned));
+    }
+}
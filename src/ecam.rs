@@ -0,0 +1,482 @@
+/*!
+## Enhanced Configuration Access Mechanism (ECAM)
+
+ECAM maps configuration space of every function into a memory window. Each PCI Segment Group
+has its own window, which base address and bus range are described by the ACPI
+[MCFG](Mcfg) table. Function configuration space (4 KiB) is located at
+*base + ((bus - start bus) << 20 | device << 15 | function << 12)*.
+
+## Example
+```rust
+# use pcics::{
+#     access::Address,
+#     ecam::{Ecam, Mcfg, McfgEntry},
+#     ExtendedCapabilities, Header, ECS_LENGTH,
+# };
+let mcfg = [
+    // Header
+    b'M', b'C', b'F', b'G', 0x3c, 0x00, 0x00, 0x00, 0x01, 0x00, b'O', b'E', b'M', b'I', b'D', b' ',
+    b'T', b'A', b'B', b'L', b'E', b'I', b'D', b' ', 0x01, 0x00, 0x00, 0x00, b'C', b'R', b'T', b'R',
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    // Configuration space base address allocation structure
+    0x00, 0x00, 0x00, 0xe0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+let mut mcfg = mcfg;
+mcfg[9] = 0u8.wrapping_sub(mcfg.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)));
+let mcfg = Mcfg::try_from(mcfg.as_slice()).unwrap();
+assert_eq!(b"OEMID ", &mcfg.header.oem_id);
+let entries = mcfg.collect::<Vec<_>>();
+assert_eq!(
+    vec![McfgEntry { base_address: 0xe0000000, segment_group: 0, start_bus: 0, end_bus: 0 }],
+    entries
+);
+
+// Memory window of bus 00h
+let mut window = vec![0xffffffffu32; Ecam::BUS_SIZE / 4];
+let config = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/device/8086_2030/config"
+));
+// Function 00:03.0
+let start = (3 << 15) / 4;
+for (dword, bytes) in window[start..].iter_mut().zip(config.chunks(4)) {
+    *dword = u32::from_ne_bytes(bytes.try_into().unwrap());
+}
+
+let ecam = Ecam::from_slice(&window, &entries[0]).unwrap();
+let function = ecam.function("00:03.0".parse().unwrap()).unwrap();
+
+let header = Header::read_from(&function).unwrap();
+assert_eq!((0x8086, 0x2030), (header.vendor_id, header.device_id));
+let mut ecs = [0; ECS_LENGTH];
+let ecaps = ExtendedCapabilities::read_from(&function, &mut ecs).unwrap();
+assert_eq!(8, ecaps.count());
+```
+*/
+
+use core::{marker::PhantomData, slice::Chunks};
+
+use heterob::{endianness::Le, Seq, P5, P9};
+use snafu::prelude::*;
+
+use crate::access::{Address, ConfigAccess};
+
+/// ECAM access error
+#[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcamError {
+    #[snafu(display(
+        "{address} is out of ECAM window {segment:04x}:[{start_bus:02x}-{end_bus:02x}]"
+    ))]
+    Address {
+        address: Address,
+        segment: u16,
+        start_bus: u8,
+        end_bus: u8,
+    },
+    #[snafu(display("[{offset:03x}] register offset should be DWORD aligned and less than 4096"))]
+    Offset { offset: u16 },
+    #[snafu(display("ECAM window should be at least {required} bytes ({len} bytes)"))]
+    Window { len: usize, required: usize },
+}
+
+/// ECAM memory window of a PCI Segment Group
+#[derive(Debug, Clone, Copy)]
+pub struct Ecam<'a> {
+    base: *const u32,
+    segment: u16,
+    start_bus: u8,
+    end_bus: u8,
+    _window: PhantomData<&'a [u32]>,
+}
+
+impl<'a> Ecam<'a> {
+    /// Every function has 4 KiB of configuration space
+    pub const FUNCTION_SIZE: usize = 1 << 12;
+    /// 32 devices by 8 functions
+    pub const BUS_SIZE: usize = Self::FUNCTION_SIZE << 8;
+
+    /// ECAM window mapped at `base`
+    ///
+    /// # Safety
+    ///
+    /// `base` should be 4 KiB aligned and valid for volatile reads of
+    /// *(end_bus - start_bus + 1) * [Ecam::BUS_SIZE]* bytes during `'a`.
+    pub unsafe fn new(base: *const u32, entry: &McfgEntry) -> Self {
+        Self {
+            base,
+            segment: entry.segment_group,
+            start_bus: entry.start_bus,
+            end_bus: entry.end_bus,
+            _window: PhantomData,
+        }
+    }
+
+    /// ECAM window stored in memory, e.g. buffer standing in for the mapped window
+    pub fn from_slice(window: &'a [u32], entry: &McfgEntry) -> Result<Self, EcamError> {
+        let required = entry.size();
+        let len = window.len() * 4;
+        ensure!(len >= required, WindowSnafu { len, required });
+        // Safety: window length checked above
+        Ok(unsafe { Self::new(window.as_ptr(), entry) })
+    }
+
+    /// Byte offset of function configuration space from window base
+    pub fn offset(&self, address: Address) -> Result<usize, EcamError> {
+        let Address {
+            segment,
+            bus,
+            device,
+            function,
+        } = address;
+        let is_valid = segment == self.segment
+            && (self.start_bus..=self.end_bus).contains(&bus)
+            && device < 32
+            && function < 8;
+        ensure!(
+            is_valid,
+            AddressSnafu {
+                address,
+                segment: self.segment,
+                start_bus: self.start_bus,
+                end_bus: self.end_bus,
+            }
+        );
+        let bus = (bus - self.start_bus) as usize;
+        Ok(bus << 20 | (device as usize) << 15 | (function as usize) << 12)
+    }
+
+    /// Configuration space of the function
+    pub fn function(&self, address: Address) -> Result<EcamFunction<'a>, EcamError> {
+        let offset = self.offset(address)?;
+        Ok(EcamFunction {
+            // Safety: offset is inside the window
+            base: unsafe { self.base.add(offset / 4) },
+            _window: PhantomData,
+        })
+    }
+}
+
+/// Configuration space of a function in ECAM window
+#[derive(Debug, Clone, Copy)]
+pub struct EcamFunction<'a> {
+    base: *const u32,
+    _window: PhantomData<&'a [u32]>,
+}
+
+impl<'a> ConfigAccess for EcamFunction<'a> {
+    type Error = EcamError;
+
+    fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+        ensure!(
+            offset & 0b11 == 0 && (offset as usize) < Ecam::FUNCTION_SIZE,
+            OffsetSnafu { offset }
+        );
+        // Safety: offset is inside the function configuration space
+        let dword = unsafe { self.base.add(offset as usize / 4).read_volatile() };
+        Ok(u32::from_le(dword))
+    }
+}
+
+/// MCFG table parsing error
+#[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McfgError {
+    #[snafu(display("MCFG table header is too short"))]
+    Header,
+    #[snafu(display("unexpected table signature {signature:02x?}"))]
+    Signature { signature: [u8; 4] },
+    #[snafu(display("table length {length} is out of {len} bytes of data"))]
+    Length { length: u32, len: usize },
+    #[snafu(display("table checksum mismatch"))]
+    Checksum,
+}
+
+/// PCI Express memory mapped configuration space base address description table (MCFG)
+///
+/// An iterator through [configuration space base address allocation structures](McfgEntry)
+#[derive(Debug, Clone)]
+pub struct Mcfg<'a> {
+    pub header: AcpiTableHeader,
+    entries: Chunks<'a, u8>,
+}
+
+impl<'a> Mcfg<'a> {
+    /// Table header and 8 reserved bytes
+    pub const ENTRIES_OFFSET: usize = AcpiTableHeader::SIZE + 8;
+    pub const SIGNATURE: [u8; 4] = *b"MCFG";
+}
+
+impl<'a> PartialEq for Mcfg<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.header == other.header && self.entries.clone().eq(other.entries.clone())
+    }
+}
+
+impl<'a> Eq for Mcfg<'a> {}
+
+impl<'a> TryFrom<&'a [u8]> for Mcfg<'a> {
+    type Error = McfgError;
+
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        let header = slice
+            .get(..AcpiTableHeader::SIZE)
+            .and_then(|slice| <[u8; AcpiTableHeader::SIZE]>::try_from(slice).ok())
+            .map(AcpiTableHeader::from)
+            .ok_or(McfgError::Header)?;
+        ensure!(
+            header.signature == Self::SIGNATURE,
+            SignatureSnafu {
+                signature: header.signature
+            }
+        );
+        let table = slice
+            .get(..header.length as usize)
+            .filter(|table| table.len() >= Self::ENTRIES_OFFSET)
+            .context(LengthSnafu {
+                length: header.length,
+                len: slice.len(),
+            })?;
+        let checksum = table.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+        ensure!(checksum == 0, ChecksumSnafu);
+        Ok(Self {
+            header,
+            entries: table[Self::ENTRIES_OFFSET..].chunks(McfgEntry::SIZE),
+        })
+    }
+}
+
+impl<'a> Iterator for Mcfg<'a> {
+    type Item = McfgEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Seq {
+            head: Le((base_address, segment_group, start_bus, end_bus, reserved)),
+            ..
+        } = P5(self.entries.next()?).try_into().ok()?;
+        let _: u32 = reserved;
+        Some(McfgEntry {
+            base_address,
+            segment_group,
+            start_bus,
+            end_bus,
+        })
+    }
+}
+
+/// System description table header common for all ACPI tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcpiTableHeader {
+    pub signature: [u8; 4],
+    /// Length of the entire table in bytes
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: [u8; 6],
+    pub oem_table_id: [u8; 8],
+    pub oem_revision: u32,
+    pub creator_id: [u8; 4],
+    pub creator_revision: u32,
+}
+
+impl AcpiTableHeader {
+    pub const SIZE: usize = 36;
+}
+
+impl From<[u8; AcpiTableHeader::SIZE]> for AcpiTableHeader {
+    fn from(bytes: [u8; AcpiTableHeader::SIZE]) -> Self {
+        let Le((
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+        )) = P9::<_, 4, 4, 1, 1, 6, 8, 4, 4, 4>(bytes).into();
+        Self {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+        }
+    }
+}
+
+/// Configuration space base address allocation structure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McfgEntry {
+    /// Base address of enhanced configuration mechanism
+    pub base_address: u64,
+    /// PCI Segment Group Number
+    pub segment_group: u16,
+    /// Start PCI bus number decoded by this host bridge
+    pub start_bus: u8,
+    /// End PCI bus number decoded by this host bridge
+    pub end_bus: u8,
+}
+
+impl McfgEntry {
+    pub const SIZE: usize = 16;
+
+    /// ECAM window size in bytes
+    pub fn size(&self) -> usize {
+        (self.end_bus.saturating_sub(self.start_bus) as usize + 1) * Ecam::BUS_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExtendedCapabilities, Header, ECS_LENGTH};
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    const DATA: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/device/8086_2030/config"
+    ));
+
+    fn mcfg(entries: &[[u8; McfgEntry::SIZE]]) -> Vec<u8> {
+        let mut table = b"MCFG\0\0\0\0\x01\0OEMID TABLEID \x01\0\0\0CRTR\x01\0\0\0".to_vec();
+        table.extend_from_slice(&[0; 8]);
+        entries.iter().for_each(|e| table.extend_from_slice(e));
+        let len = table.len() as u32;
+        table[4..8].copy_from_slice(&len.to_le_bytes());
+        table[9] = 0u8.wrapping_sub(table.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)));
+        table
+    }
+
+    #[test]
+    fn mcfg_entries() {
+        let data = mcfg(&[
+            [
+                0x00, 0x00, 0x00, 0xe0, 0, 0, 0, 0, 0x00, 0x00, 0x00, 0x3f, 0, 0, 0, 0,
+            ],
+            [
+                0x00, 0x00, 0x00, 0x80, 0x03, 0, 0, 0, 0x01, 0x00, 0x80, 0xff, 0, 0, 0, 0,
+            ],
+        ]);
+        let result = Mcfg::try_from(data.as_slice()).unwrap();
+        assert_eq!(b"CRTR", &result.header.creator_id);
+        let sample = vec![
+            McfgEntry {
+                base_address: 0xe0000000,
+                segment_group: 0,
+                start_bus: 0,
+                end_bus: 0x3f,
+            },
+            McfgEntry {
+                base_address: 0x380000000,
+                segment_group: 1,
+                start_bus: 0x80,
+                end_bus: 0xff,
+            },
+        ];
+        assert_eq!(sample, result.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn mcfg_errors() {
+        let data = mcfg(&[]);
+        assert_eq!(Err(McfgError::Header), Mcfg::try_from(&data[..35]));
+
+        let mut wrong = data.clone();
+        wrong[0] = b'X';
+        assert_eq!(
+            Err(McfgError::Signature {
+                signature: *b"XCFG"
+            }),
+            Mcfg::try_from(wrong.as_slice())
+        );
+
+        assert_eq!(
+            Err(McfgError::Length {
+                length: 44,
+                len: 40
+            }),
+            Mcfg::try_from(&data[..40])
+        );
+
+        let mut wrong = data;
+        wrong[10] ^= 1;
+        assert_eq!(Err(McfgError::Checksum), Mcfg::try_from(wrong.as_slice()));
+    }
+
+    #[test]
+    fn function_offset() {
+        let entry = McfgEntry {
+            base_address: 0,
+            segment_group: 1,
+            start_bus: 0x10,
+            end_bus: 0x11,
+        };
+        let window = vec![0u32; entry.size() / 4];
+        let ecam = Ecam::from_slice(&window, &entry).unwrap();
+        assert_eq!(Ok(0x1d_5000), ecam.offset("0001:11:1a.5".parse().unwrap()));
+        assert_eq!(Ok(0), ecam.offset("0001:10:00.0".parse().unwrap()));
+        for address in ["0000:10:00.0", "0001:0f:00.0", "0001:12:00.0"] {
+            let address = address.parse().unwrap();
+            assert!(matches!(
+                ecam.offset(address),
+                Err(EcamError::Address { .. })
+            ));
+        }
+        let address = Address {
+            segment: 1,
+            bus: 0x10,
+            device: 32,
+            function: 0,
+        };
+        assert!(matches!(
+            ecam.offset(address),
+            Err(EcamError::Address { .. })
+        ));
+
+        assert_eq!(
+            Err(EcamError::Window {
+                len: 4,
+                required: 2 * Ecam::BUS_SIZE
+            }),
+            Ecam::from_slice(&[0], &entry).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn function_access() {
+        let entry = McfgEntry {
+            base_address: 0,
+            segment_group: 0,
+            start_bus: 2,
+            end_bus: 2,
+        };
+        let mut window = vec![0xffff_ffffu32; entry.size() / 4];
+        let start = Ecam::FUNCTION_SIZE * 9 / 4;
+        for (dword, bytes) in window[start..].iter_mut().zip(DATA.chunks(4)) {
+            *dword = u32::from_ne_bytes(bytes.try_into().unwrap());
+        }
+        let ecam = Ecam::from_slice(&window, &entry).unwrap();
+
+        let function = ecam.function("02:01.1".parse().unwrap()).unwrap();
+        let header = Header::read_from(&function).unwrap();
+        assert_eq!(Header::try_from(DATA).unwrap(), header);
+        let mut ecs = [0; ECS_LENGTH];
+        let ecaps = ExtendedCapabilities::read_from(&function, &mut ecs).unwrap();
+        assert_eq!(8, ecaps.count());
+        assert_eq!(Ok(0x2030), function.read_u16(2));
+        assert_eq!(Err(EcamError::Offset { offset: 2 }), function.read_u32(2));
+        assert_eq!(
+            Err(EcamError::Offset { offset: 0x1000 }),
+            function.read_u32(0x1000)
+        );
+
+        // Not present function
+        let function = ecam.function("02:01.0".parse().unwrap()).unwrap();
+        assert_eq!(Ok(0xffff), function.read_u16(0));
+    }
+}
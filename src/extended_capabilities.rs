@@ -24,7 +24,7 @@ Extended Capabilities list:
 - [x] [Alternative Routing-ID Interpretation (ARI)](alternative_routing_id_interpolation) (000Eh)
 - [x] [Address Translation Services (ATS)](address_translation_services) (000Fh)
 - [x] [Single Root I/O Virtualization (SR-IOV)](single_root_io_virtualization) (0010h)
-- [ ] [Multi-Root I/O Virtualization (MR-IOV)](multi_root_io_virtualization) (0011h)
+- [x] [Multi-Root I/O Virtualization (MR-IOV)](multi_root_io_virtualization) (0011h)
 - [x] [Multicast](multicast) (0012h)
 - [x] [Page Request Interface (PRI)](page_request_interface) (0013h)
 - [x] [Reserved for AMD](reserved_for_amd) (0014h)
@@ -44,19 +44,19 @@ Extended Capabilities list:
 - [x] [Readiness Time Reporting](readiness_time_reporting) (0022h)
 - [x] [Designated Vendor-Specific Extended Capability](designated_vendor_specific_extended_capability) (0023h)
 - [x] [VF Resizable BAR](vf_resizable_bar) (0024h)
-- [ ] [Data Link Feature](data_link_feature) (0025h)
-- [ ] [Physical Layer 16.0 GT/s](physical_layer_16_gtps) (0026h)
-- [ ] [Lane Margining at the Receiver](lane_margining_at_the_receiver) (0027h)
-- [ ] [Hierarchy ID](hierarchy_id) (0028h)
-- [ ] [Native PCIe Enclosure Management (NPEM)](native_pcie_enclosure_management) (0029h)
-- [ ] [Physical Layer 32.0 GT/s](physical_layer_32_gtps) (002Ah)
-- [ ] [Alternate Protocol](alternate_protocol) (002Bh)
-- [ ] [System Firmware Intermediary (SFI)](system_firmware_intermediary) (002Ch)
-- [ ] [Shadow Functions](shadow_functions) (002Dh)
-- [ ] [Data Object Exchange](data_object_exchange) (002Eh)
+- [x] [Data Link Feature](data_link_feature) (0025h)
+- [x] [Physical Layer 16.0 GT/s](physical_layer_16_gtps) (0026h)
+- [x] [Lane Margining at the Receiver](lane_margining_at_the_receiver) (0027h)
+- [x] [Hierarchy ID](hierarchy_id) (0028h)
+- [x] [Native PCIe Enclosure Management (NPEM)](native_pcie_enclosure_management) (0029h)
+- [x] [Physical Layer 32.0 GT/s](physical_layer_32_gtps) (002Ah)
+- [x] [Alternate Protocol](alternate_protocol) (002Bh)
+- [x] [System Firmware Intermediary (SFI)](system_firmware_intermediary) (002Ch)
+- [x] [Shadow Functions](shadow_functions) (002Dh)
+- [x] [Data Object Exchange](data_object_exchange) (002Eh)
 - [ ] [Device 3](device_3) (002Fh)
 - [ ] [Integrity and Data Encryption (IDE)](integrity_and_data_encryption) (0030h)
-- [ ] [Physical Layer 64.0 GT/s Capability](physical_layer_64_gtps) (0031h)
+- [x] [Physical Layer 64.0 GT/s Capability](physical_layer_64_gtps) (0031h)
 - [ ] [Flit Logging](flit_logging) (0032h)
 - [ ] [Flit Performance Measurement](flit_performance_measurement) (0033h)
 - [ ] [Flit Error Injection](flit_error_injection) (0034h)
@@ -101,7 +101,9 @@ let sample = vec![
 use heterob::{P3, bit_numbering::{LsbInto, Lsb}, endianness::FromLeBytes};
 use snafu::prelude::*;
 
-use super::ECS_OFFSET;
+use super::{ECS_LENGTH, ECS_OFFSET};
+use crate::access::{self, ConfigAccess};
+use crate::register::RegisterMap;
 
 /// Extended Capability Header length in bytes
 pub const ECH_BYTES: usize = 4;
@@ -109,6 +111,7 @@ pub const ECH_BYTES: usize = 4;
 
 /// Extended capability parsing error
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ExtendedCapabilityError {
     #[snafu(display("extended capability offset should be greater than 0xFF"))]
     Offset,
@@ -116,6 +119,17 @@ pub enum ExtendedCapabilityError {
     Header { offset: u16 },
     #[snafu(display("[{offset:03x}] extended capability has empty header"))]
     EmptyHeader { offset: u16 },
+    #[snafu(display("[{offset:03x}] extended capability offset is not DWORD aligned"))]
+    Misaligned { offset: u16 },
+    #[snafu(display("[{offset:03x}] extended capability was already visited, list is looped"))]
+    Loop { offset: u16 },
+    #[snafu(display(
+        "extended capabilities list is longer than {} entries",
+        ExtendedCapabilities::MAX_ENTRIES
+    ))]
+    Limit,
+    #[snafu(display("[{offset:03x}] extended capability overlaps previous extended capabilities"))]
+    Overlap { offset: u16 },
     #[snafu(display("[{offset:03x}] {source} data read error"))]
     Data { offset: u16, source: ExtendedCapabilityDataError },
     #[snafu(display("[{offset:03x}] Root Complex Link Declaration error: {source}"))]
@@ -173,6 +187,7 @@ pub enum ExtendedCapabilityError {
 
 /// Common error for reading capability data
 #[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[snafu(display("{name} ({size} bytes)"))]
 pub struct ExtendedCapabilityDataError {
     name: &'static str,
@@ -180,17 +195,110 @@ pub struct ExtendedCapabilityDataError {
 }
 
 /// An iterator through *Extended Capabilities List*
+///
+/// Iteration stops after a misaligned or already visited offset or after
+/// [ExtendedCapabilities::MAX_ENTRIES] extended capabilities. Extended capabilities overlapping
+/// previous ones are reported as [ExtendedCapabilityError::Overlap].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ExtendedCapabilities<'a> {
     /// Extended Configuration Space
     ecs: &'a [u8],
     /// PCI Configuration Space offset
     next_capability_offset: u16,
+    /// Bit per DWORD of extended configuration space with already visited offsets
+    visited: [u64; ECS_DWORDS / 64],
+    /// Bit per DWORD of extended configuration space occupied by already parsed capabilities
+    occupied: [u64; ECS_DWORDS / 64],
+    /// Number of already visited extended capabilities
+    count: usize,
 }
+
+/// Number of DWORDs in extended configuration space
+const ECS_DWORDS: usize = ECS_LENGTH / 4;
+
 impl<'a> ExtendedCapabilities<'a> {
+    /// Maximum number of extended capabilities that fit into extended configuration space
+    pub const MAX_NUMBER: usize = ECS_LENGTH / ECH_BYTES;
+    /// Maximum number of extended capabilities followed before the list is reported as too long
+    pub const MAX_ENTRIES: usize = 128;
+
     pub fn new(ecs: &'a [u8]) -> Self {
-        Self { ecs, next_capability_offset: ECS_OFFSET as u16 }
+        Self {
+            ecs,
+            next_capability_offset: ECS_OFFSET as u16,
+            visited: [0; ECS_DWORDS / 64],
+            occupied: [0; ECS_DWORDS / 64],
+            count: 0,
+        }
+    }
+
+    /// Offset of the extended capability which will be parsed next, 0 at the end of the list
+    pub fn next_capability_offset(&self) -> u16 {
+        self.next_capability_offset
     }
+
+    /// Walk the extended capabilities list through [ConfigAccess] and read the extended
+    /// capability structures into extended configuration space buffer `ecs`
+    ///
+    /// Registers not belonging to any extended capability are left untouched.
+    pub fn read_from<A: ConfigAccess + ?Sized>(
+        access: &A,
+        ecs: &'a mut [u8; ECS_LENGTH],
+    ) -> Result<Self, A::Error> {
+        let mut offsets = [0u16; Self::MAX_NUMBER];
+        let mut count = 0;
+        let mut offset = ECS_OFFSET;
+        while (ECS_OFFSET..ECS_OFFSET + ECS_LENGTH).contains(&offset) && count < offsets.len() {
+            if offsets[..count].contains(&(offset as u16)) {
+                break;
+            }
+            offsets[count] = offset as u16;
+            count += 1;
+            access::read_range(access, ecs, ECS_OFFSET, offset..offset + ECH_BYTES)?;
+            let ExtendedCapabilityHeader {
+                extended_capability_id,
+                next_capability_offset,
+                ..
+            } = dword_at(ecs, offset).into();
+            if dword_at(ecs, offset) == 0 {
+                break;
+            }
+            // VSEC and DVSEC have length in the DWORD following Extended Capability Header
+            if let 0x000B | 0x0023 = extended_capability_id {
+                let start = offset + ECH_BYTES;
+                access::read_range(access, ecs, ECS_OFFSET, start..start + 4)?;
+            }
+            offset = next_capability_offset as usize;
+        }
+        let has_length = |ecs: &[u8], offset| {
+            let ExtendedCapabilityHeader {
+                extended_capability_id,
+                ..
+            } = dword_at(ecs, offset).into();
+            matches!(extended_capability_id, 0x000B | 0x0023)
+        };
+        access::read_structures(
+            access,
+            ecs,
+            ECS_OFFSET,
+            &mut offsets[..count],
+            |ecs, offset| {
+                has_length(ecs, offset)
+                    .then(|| (dword_at(ecs, offset + ECH_BYTES) >> 20) as usize)
+                    .filter(|&length| length >= ECH_BYTES + 4)
+            },
+            |ecs, offset| ECH_BYTES + if has_length(ecs, offset) { 4 } else { 0 },
+        )?;
+        Ok(Self::new(ecs))
+    }
+}
+
+/// DWORD at configuration space `offset` of extended configuration space data
+fn dword_at(ecs: &[u8], offset: usize) -> u32 {
+    offset
+        .checked_sub(ECS_OFFSET)
+        .and_then(|start| ecs.get(start..start + 4))
+        .map_or(0, |bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
 }
 impl<'a> Iterator for ExtendedCapabilities<'a> {
     type Item = ExtendedCapabilityResult<'a>;
@@ -199,9 +307,71 @@ impl<'a> Iterator for ExtendedCapabilities<'a> {
         if self.next_capability_offset == 0 {
             return None;
         }
-        match parse_ecap(self.ecs, &mut self.next_capability_offset) {
-            Err(ExtendedCapabilityError::EmptyHeader { .. }) => None,
-            v => Some(v),
+        let offset = self.next_capability_offset;
+        if let Err(err) = self.visit(offset) {
+            // There is no sense to follow broken list
+            self.next_capability_offset = 0;
+            return Some(Err(err));
+        }
+        let result = match parse_ecap(self.ecs, &mut self.next_capability_offset) {
+            Err(ExtendedCapabilityError::EmptyHeader { .. }) => return None,
+            v => v,
+        };
+        let size = match &result {
+            Ok(ecap) => ecap.kind.registers().map_or(0, |map| map.size() as usize),
+            Err(_) => 0,
+        };
+        let overlap = self.occupy(offset, size.max(ECH_BYTES));
+        Some(result.and_then(|ecap| overlap.map(|_| ecap)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ExtendedCapabilities<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(*self)
+    }
+}
+
+impl<'a> ExtendedCapabilities<'a> {
+    /// Check extended capability offset and mark it as visited
+    fn visit(&mut self, offset: u16) -> Result<(), ExtendedCapabilityError> {
+        if offset & 0b11 != 0 {
+            return Err(ExtendedCapabilityError::Misaligned { offset });
+        }
+        if self.count >= Self::MAX_ENTRIES {
+            return Err(ExtendedCapabilityError::Limit);
+        }
+        self.count += 1;
+        // Offsets outside of extended configuration space are reported by parse_ecap
+        if (offset as usize) < ECS_OFFSET {
+            return Ok(());
+        }
+        let n = (offset as usize - ECS_OFFSET) / 4;
+        let (word, bit) = (n / 64, 1 << (n % 64));
+        if self.visited[word] & bit != 0 {
+            return Err(ExtendedCapabilityError::Loop { offset });
+        }
+        self.visited[word] |= bit;
+        Ok(())
+    }
+    /// Mark DWORDs of `size` bytes extended capability structure as occupied
+    fn occupy(&mut self, offset: u16, size: usize) -> Result<(), ExtendedCapabilityError> {
+        if (offset as usize) < ECS_OFFSET {
+            return Ok(());
+        }
+        let start = offset as usize - ECS_OFFSET;
+        let end = (start + size).min(ECS_LENGTH);
+        let mut overlapped = false;
+        for n in start / 4..end.div_ceil(4) {
+            let (word, bit) = (n / 64, 1 << (n % 64));
+            overlapped |= self.occupied[word] & bit != 0;
+            self.occupied[word] |= bit;
+        }
+        if overlapped {
+            Err(ExtendedCapabilityError::Overlap { offset })
+        } else {
+            Ok(())
         }
     }
 }
@@ -211,6 +381,7 @@ impl<'a> Iterator for ExtendedCapabilities<'a> {
 /// All PCI Express Extended Capabilities must begin with a PCI Express
 /// Extended Capability header
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtendedCapabilityHeader {
     /// PCI Express Extended Capability ID
     ///
@@ -282,6 +453,9 @@ fn parse_ecap<'a>(
     let (id, version, next_cap_offset) = P3::<_, 16, 4, 12>(*dword).lsb_into();
     *next_capability_offset = next_cap_offset;
 
+    // Extended capability data with PCI Express Extended Capability Header, the capabilities
+    // parsing it count offsets from their own header rather than from ECS_OFFSET
+    let ecap_bytes = &bytes[ecs_offset..];
     let ecap_data = &bytes[ecap_data_offset..];
 
     use ExtendedCapabilityKind as Kind;
@@ -316,7 +490,7 @@ fn parse_ecap<'a>(
             .map(Kind::RootComplexEventCollectorEndpointAssociation)
             .context(DataSnafu { offset })?,
         // MFVC use data with PCI Express Extended Capability Header for simpler calculations
-        0x0008 => bytes
+        0x0008 => ecap_bytes
             .try_into()
             .map(Kind::MultifunctionVirtualChannel)
             .context(MultifunctionVirtualChannelSnafu { offset })?,
@@ -324,7 +498,7 @@ fn parse_ecap<'a>(
             .try_into()
             .map(Kind::VirtualChannelMfvcPresent)
             .context(DataSnafu { offset })?,
-        0x000A => bytes
+        0x000A => ecap_bytes
             .try_into()
             .map(Kind::RootComplexRegisterBlockHeader)
             .context(DataSnafu { offset })?,
@@ -332,7 +506,7 @@ fn parse_ecap<'a>(
             .try_into()
             .map(Kind::VendorSpecificExtendedCapability)
             .context(DataSnafu { offset })?,
-        0x000C => bytes
+        0x000C => ecap_bytes
             .try_into()
             .map(Kind::ConfigurationAccessCorrelation)
             .context(DataSnafu { offset })?,
@@ -352,8 +526,11 @@ fn parse_ecap<'a>(
             .try_into()
             .map(Kind::SingleRootIoVirtualization)
             .context(DataSnafu { offset })?,
-        0x0011 => Kind::MultiRootIoVirtualization(MultiRootIoVirtualization),
-        0x0012 => bytes
+        0x0011 => ecap_data
+            .try_into()
+            .map(Kind::MultiRootIoVirtualization)
+            .context(DataSnafu { offset })?,
+        0x0012 => ecap_bytes
             .try_into()
             .map(Kind::Multicast)
             .context(DataSnafu { offset })?,
@@ -362,11 +539,11 @@ fn parse_ecap<'a>(
             .map(Kind::PageRequestInterface)
             .context(DataSnafu { offset })?,
         0x0014 => Kind::ReservedForAmd(ReservedForAmd),
-        0x0015 => bytes
+        0x0015 => ecap_bytes
             .try_into()
             .map(Kind::ResizableBar)
             .context(ResizableBarSnafu { offset })?,
-        0x0016 => bytes
+        0x0016 => ecap_bytes
             .try_into()
             .map(Kind::DynamicPowerAllocation)
             .context(DynamicPowerAllocationSnafu { offset })?,
@@ -382,7 +559,7 @@ fn parse_ecap<'a>(
             .try_into()
             .map(Kind::SecondaryPciExpress)
             .context(DataSnafu { offset })?,
-        0x001A => bytes
+        0x001A => ecap_bytes
             .try_into()
             .map(Kind::ProtocolMultiplexing)
             .context(ProtocolMultiplexingSnafu { offset })?,
@@ -390,7 +567,7 @@ fn parse_ecap<'a>(
             .try_into()
             .map(Kind::ProcessAddressSpaceId)
             .context(DataSnafu { offset })?,
-        0x001C => bytes
+        0x001C => ecap_bytes
             .try_into()
             .map(Kind::LnRequester)
             .context(DataSnafu { offset })?,
@@ -406,34 +583,70 @@ fn parse_ecap<'a>(
             .try_into()
             .map(Kind::PrecisionTimeMeasurement)
             .context(DataSnafu { offset })?,
-        0x0020 => bytes
+        0x0020 => ecap_bytes
             .try_into()
             .map(Kind::PciExpressOverMphy)
             .context(DataSnafu { offset })?,
-        0x0021 => bytes
+        0x0021 => ecap_bytes
             .try_into()
             .map(Kind::FrsQueuing)
             .context(DataSnafu { offset })?,
-        0x0022 => bytes
+        0x0022 => ecap_bytes
             .try_into()
             .map(Kind::ReadinessTimeReporting)
             .context(DataSnafu { offset })?,
-        0x0023 => bytes
+        0x0023 => ecap_bytes
             .try_into()
             .map(Kind::DesignatedVendorSpecificExtendedCapability)
             .context(DesignatedVendorSpecificExtendedCapabilitySnafu { offset })?,
-        0x0024 => bytes
+        0x0024 => ecap_bytes
             .try_into()
             .map(Kind::VfResizableBar)
             .context(VfResizableBarSnafu { offset })?,
-        0x0025 => Kind::DataLinkFeature(DataLinkFeature),
-        0x0026 => Kind::PhysicalLayer16GTps(PhysicalLayer16GTps),
-        0x0027 => Kind::LaneMarginingAtTheReceiver(LaneMarginingAtTheReceiver),
-        0x0028 => Kind::HierarchyId(HierarchyId),
-        0x0029 => Kind::NativePcieEnclosureManagement(NativePcieEnclosureManagement),
-        0x002A => Kind::PhysicalLayer32GTps(PhysicalLayer32GTps),
-        0x002B => Kind::AlternateProtocol(AlternateProtocol),
-        0x002C => Kind::SystemFirmwareIntermediary(SystemFirmwareIntermediary),
+        0x0025 => ecap_data
+            .try_into()
+            .map(Kind::DataLinkFeature)
+            .context(DataSnafu { offset })?,
+        0x0026 => ecap_data
+            .try_into()
+            .map(Kind::PhysicalLayer16GTps)
+            .context(DataSnafu { offset })?,
+        0x0027 => ecap_data
+            .try_into()
+            .map(Kind::LaneMarginingAtTheReceiver)
+            .context(DataSnafu { offset })?,
+        0x0028 => ecap_data
+            .try_into()
+            .map(Kind::HierarchyId)
+            .context(DataSnafu { offset })?,
+        0x0029 => ecap_data
+            .try_into()
+            .map(Kind::NativePcieEnclosureManagement)
+            .context(DataSnafu { offset })?,
+        0x002A => ecap_data
+            .try_into()
+            .map(Kind::PhysicalLayer32GTps)
+            .context(DataSnafu { offset })?,
+        0x002B => ecap_data
+            .try_into()
+            .map(Kind::AlternateProtocol)
+            .context(DataSnafu { offset })?,
+        0x002C => ecap_data
+            .try_into()
+            .map(Kind::SystemFirmwareIntermediary)
+            .context(DataSnafu { offset })?,
+        0x002D => ecap_data
+            .try_into()
+            .map(Kind::ShadowFunctions)
+            .context(DataSnafu { offset })?,
+        0x002E => ecap_data
+            .try_into()
+            .map(Kind::DataObjectExchange)
+            .context(DataSnafu { offset })?,
+        0x0031 => ecap_data
+            .try_into()
+            .map(Kind::PhysicalLayer64GTps)
+            .context(DataSnafu { offset })?,
         v => Kind::Reserved(v),
     };
     Ok(ExtendedCapability {
@@ -446,6 +659,7 @@ fn parse_ecap<'a>(
 
 /// Extended Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ExtendedCapability<'a> {
     pub kind: ExtendedCapabilityKind<'a>,
     pub version: u8,
@@ -515,6 +729,7 @@ impl<'a> ExtendedCapability<'a> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ExtendedCapabilityKind<'a> {
     /// Null Capability – This capability contains no registers other than those in the Extended
     /// Capability Header
@@ -596,21 +811,138 @@ pub enum ExtendedCapabilityKind<'a> {
     /// Data Link Feature
     DataLinkFeature(DataLinkFeature),
     /// Physical Layer 16.0 GT/s
-    PhysicalLayer16GTps(PhysicalLayer16GTps),
+    PhysicalLayer16GTps(PhysicalLayer16GTps<'a>),
+    /// Lane Margining at the Receiver
+    LaneMarginingAtTheReceiver(LaneMarginingAtTheReceiver<'a>),
+    /// Hierarchy ID
+    HierarchyId(HierarchyId),
+    /// Native PCIe Enclosure Management (NPEM)
+    NativePcieEnclosureManagement(NativePcieEnclosureManagement),
+    /// Physical Layer 32.0 GT/s
+    PhysicalLayer32GTps(PhysicalLayer32GTps<'a>),
+    /// Alternate Protocol
+    AlternateProtocol(AlternateProtocol),
+    /// System Firmware Intermediary (SFI)
+    SystemFirmwareIntermediary(SystemFirmwareIntermediary),
+    /// Shadow Functions
+    ShadowFunctions(ShadowFunctions<'a>),
+    /// Data Object Exchange
+    DataObjectExchange(DataObjectExchange),
+    /// Device 3
+    Device3(Device3),
+    /// Integrity and Data Encryption (IDE)
+    IntegrityAndDataEncryption(IntegrityAndDataEncryption),
+    /// Physical Layer 64.0 GT/s
+    PhysicalLayer64GTps(PhysicalLayer64GTps<'a>),
+    /// Flit Logging
+    FlitLogging(FlitLogging),
+    /// Flit Performance Measurement
+    FlitPerformanceMeasurement(FlitPerformanceMeasurement),
+    /// Flit Error Injection
+    FlitErrorInjection(FlitErrorInjection),
+    Reserved(u16),
+}
+
+/// Owned [ExtendedCapabilityKind]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedExtendedCapabilityKind {
+    /// Null Capability – This capability contains no registers other than those in the Extended
+    /// Capability Header
+    Null,
+    /// Advanced Error Reporting (AER)
+    AdvancedErrorReporting(AdvancedErrorReporting),
+    /// Virtual Channel (VC) – used if an MFVC Extended Cap structure is not present in the device
+    VirtualChannel(OwnedVirtualChannel),
+    /// Device Serial Number
+    DeviceSerialNumber(DeviceSerialNumber),
+    /// Power Budgeting
+    PowerBudgeting(PowerBudgeting),
+    /// Root Complex Link Declaration
+    RootComplexLinkDeclaration(OwnedRootComplexLinkDeclaration),
+    /// Root Complex Internal Link Control
+    RootComplexInternalLinkControl(RootComplexInternalLinkControl),
+    /// Root Complex Event Collector Endpoint Association
+    RootComplexEventCollectorEndpointAssociation(RootComplexEventCollectorEndpointAssociation),
+    /// Multi-Function Virtual Channel (MFVC)
+    MultifunctionVirtualChannel(OwnedMultifunctionVirtualChannel),
+    /// Virtual Channel (VC) – used if an MFVC Extended Cap structure is present in the device
+    VirtualChannelMfvcPresent(OwnedVirtualChannel),
+    /// Root Complex Register Block (RCRB) Header
+    RootComplexRegisterBlockHeader(RootComplexRegisterBlockHeader),
+    /// Vendor-Specific Extended Capability (VSEC)
+    VendorSpecificExtendedCapability(OwnedVendorSpecificExtendedCapability),
+    /// Configuration Access Correlation (CAC) – defined by the Trusted Configuration Space (TCS)
+    /// for PCI Express ECN, which is no longer supporte(ECNd
+    ConfigurationAccessCorrelation(ConfigurationAccessCorrelation),
+    /// Access Control Services (ACS)
+    AccessControlServices(OwnedAccessControlServices),
+    /// Alternative Routing-ID Interpretation (ARI)
+    AlternativeRoutingIdInterpretation(AlternativeRoutingIdInterpretation),
+    /// Address Translation Services (ATS)
+    AddressTranslationServices(AddressTranslationServices),
+    /// Single Root I/O Virtualization (SR-IOV)
+    SingleRootIoVirtualization(SingleRootIoVirtualization),
+    /// Multi-Root I/O Virtualization (MR-IOV) – defined in the Multi-Root I/O Virtualization and
+    /// Sharing Specification
+    MultiRootIoVirtualization(MultiRootIoVirtualization),
+    /// Multicast
+    Multicast(Multicast),
+    /// Page Request Interface (PRI)
+    PageRequestInterface(PageRequestInterface),
+    /// Reserved for AMD
+    ReservedForAmd(ReservedForAmd),
+    /// Resizable BAR
+    ResizableBar(OwnedResizableBar),
+    /// Dynamic Power Allocation (DPA)
+    DynamicPowerAllocation(OwnedDynamicPowerAllocation),
+    /// TPH Requester
+    TphRequester(OwnedTphRequester),
+    /// Latency Tolerance Reporting (LTR)
+    LatencyToleranceReporting(LatencyToleranceReporting),
+    /// Secondary PCI Express
+    SecondaryPciExpress(OwnedSecondaryPciExpress),
+    /// Protocol Multiplexing (PMUX)
+    ProtocolMultiplexing(OwnedProtocolMultiplexing),
+    /// Process Address Space ID (PASID)
+    ProcessAddressSpaceId(ProcessAddressSpaceId),
+    /// LN Requester (LNR)
+    LnRequester(LnRequester),
+    /// Downstream Port Containment (DPC)
+    DownstreamPortContainment(DownstreamPortContainment),
+    /// L1 PM Substates
+    L1PmSubstates(L1PmSubstates),
+    /// Precision Time Measurement (PTM)
+    PrecisionTimeMeasurement(PrecisionTimeMeasurement),
+    /// PCI Express over M-PHY (M-PCIe)
+    PciExpressOverMphy(PciExpressOverMphy),
+    /// FRS Queuing
+    FrsQueuing(FrsQueuing),
+    /// Readiness Time Reporting
+    ReadinessTimeReporting(ReadinessTimeReporting),
+    /// Designated Vendor-Specific Extended Capability
+    DesignatedVendorSpecificExtendedCapability(OwnedDesignatedVendorSpecificExtendedCapability),
+    /// VF Resizable BAR
+    VfResizableBar(OwnedVfResizableBar),
+    /// Data Link Feature
+    DataLinkFeature(DataLinkFeature),
+    /// Physical Layer 16.0 GT/s
+    PhysicalLayer16GTps(OwnedPhysicalLayer16GTps),
     /// Lane Margining at the Receiver
-    LaneMarginingAtTheReceiver(LaneMarginingAtTheReceiver),
+    LaneMarginingAtTheReceiver(OwnedLaneMarginingAtTheReceiver),
     /// Hierarchy ID
     HierarchyId(HierarchyId),
     /// Native PCIe Enclosure Management (NPEM)
     NativePcieEnclosureManagement(NativePcieEnclosureManagement),
     /// Physical Layer 32.0 GT/s
-    PhysicalLayer32GTps(PhysicalLayer32GTps),
+    PhysicalLayer32GTps(OwnedPhysicalLayer32GTps),
     /// Alternate Protocol
     AlternateProtocol(AlternateProtocol),
     /// System Firmware Intermediary (SFI)
     SystemFirmwareIntermediary(SystemFirmwareIntermediary),
     /// Shadow Functions
-    ShadowFunctions(ShadowFunctions),
+    ShadowFunctions(OwnedShadowFunctions),
     /// Data Object Exchange
     DataObjectExchange(DataObjectExchange),
     /// Device 3
@@ -618,7 +950,7 @@ pub enum ExtendedCapabilityKind<'a> {
     /// Integrity and Data Encryption (IDE)
     IntegrityAndDataEncryption(IntegrityAndDataEncryption),
     /// Physical Layer 64.0 GT/s
-    PhysicalLayer64GTps(PhysicalLayer64GTps),
+    PhysicalLayer64GTps(OwnedPhysicalLayer64GTps),
     /// Flit Logging
     FlitLogging(FlitLogging),
     /// Flit Performance Measurement
@@ -628,6 +960,201 @@ pub enum ExtendedCapabilityKind<'a> {
     Reserved(u16),
 }
 
+#[cfg(feature = "alloc")]
+impl<'a> From<&ExtendedCapabilityKind<'a>> for OwnedExtendedCapabilityKind {
+    fn from(kind: &ExtendedCapabilityKind<'a>) -> Self {
+        use ExtendedCapabilityKind as Kind;
+        match kind {
+            Kind::Null => Self::Null,
+            Kind::AdvancedErrorReporting(data) => Self::AdvancedErrorReporting(data.clone()),
+            Kind::VirtualChannel(data) => Self::VirtualChannel(data.into()),
+            Kind::DeviceSerialNumber(data) => Self::DeviceSerialNumber(data.clone()),
+            Kind::PowerBudgeting(data) => Self::PowerBudgeting(data.clone()),
+            Kind::RootComplexLinkDeclaration(data) => Self::RootComplexLinkDeclaration(data.into()),
+            Kind::RootComplexInternalLinkControl(data) => {
+                Self::RootComplexInternalLinkControl(data.clone())
+            }
+            Kind::RootComplexEventCollectorEndpointAssociation(data) => {
+                Self::RootComplexEventCollectorEndpointAssociation(data.clone())
+            }
+            Kind::MultifunctionVirtualChannel(data) => {
+                Self::MultifunctionVirtualChannel(data.into())
+            }
+            Kind::VirtualChannelMfvcPresent(data) => Self::VirtualChannelMfvcPresent(data.into()),
+            Kind::RootComplexRegisterBlockHeader(data) => {
+                Self::RootComplexRegisterBlockHeader(data.clone())
+            }
+            Kind::VendorSpecificExtendedCapability(data) => {
+                Self::VendorSpecificExtendedCapability(data.into())
+            }
+            Kind::ConfigurationAccessCorrelation(data) => {
+                Self::ConfigurationAccessCorrelation(data.clone())
+            }
+            Kind::AccessControlServices(data) => Self::AccessControlServices(data.into()),
+            Kind::AlternativeRoutingIdInterpretation(data) => {
+                Self::AlternativeRoutingIdInterpretation(data.clone())
+            }
+            Kind::AddressTranslationServices(data) => {
+                Self::AddressTranslationServices(data.clone())
+            }
+            Kind::SingleRootIoVirtualization(data) => {
+                Self::SingleRootIoVirtualization(data.clone())
+            }
+            Kind::MultiRootIoVirtualization(data) => Self::MultiRootIoVirtualization(data.clone()),
+            Kind::Multicast(data) => Self::Multicast(data.clone()),
+            Kind::PageRequestInterface(data) => Self::PageRequestInterface(data.clone()),
+            Kind::ReservedForAmd(data) => Self::ReservedForAmd(data.clone()),
+            Kind::ResizableBar(data) => Self::ResizableBar(data.into()),
+            Kind::DynamicPowerAllocation(data) => Self::DynamicPowerAllocation(data.into()),
+            Kind::TphRequester(data) => Self::TphRequester(data.into()),
+            Kind::LatencyToleranceReporting(data) => Self::LatencyToleranceReporting(data.clone()),
+            Kind::SecondaryPciExpress(data) => Self::SecondaryPciExpress(data.into()),
+            Kind::ProtocolMultiplexing(data) => Self::ProtocolMultiplexing(data.into()),
+            Kind::ProcessAddressSpaceId(data) => Self::ProcessAddressSpaceId(data.clone()),
+            Kind::LnRequester(data) => Self::LnRequester(data.clone()),
+            Kind::DownstreamPortContainment(data) => Self::DownstreamPortContainment(data.clone()),
+            Kind::L1PmSubstates(data) => Self::L1PmSubstates(data.clone()),
+            Kind::PrecisionTimeMeasurement(data) => Self::PrecisionTimeMeasurement(data.clone()),
+            Kind::PciExpressOverMphy(data) => Self::PciExpressOverMphy(data.clone()),
+            Kind::FrsQueuing(data) => Self::FrsQueuing(data.clone()),
+            Kind::ReadinessTimeReporting(data) => Self::ReadinessTimeReporting(data.clone()),
+            Kind::DesignatedVendorSpecificExtendedCapability(data) => {
+                Self::DesignatedVendorSpecificExtendedCapability(data.into())
+            }
+            Kind::VfResizableBar(data) => Self::VfResizableBar(data.into()),
+            Kind::DataLinkFeature(data) => Self::DataLinkFeature(data.clone()),
+            Kind::PhysicalLayer16GTps(data) => Self::PhysicalLayer16GTps(data.into()),
+            Kind::LaneMarginingAtTheReceiver(data) => Self::LaneMarginingAtTheReceiver(data.into()),
+            Kind::HierarchyId(data) => Self::HierarchyId(data.clone()),
+            Kind::NativePcieEnclosureManagement(data) => {
+                Self::NativePcieEnclosureManagement(data.clone())
+            }
+            Kind::PhysicalLayer32GTps(data) => Self::PhysicalLayer32GTps(data.into()),
+            Kind::AlternateProtocol(data) => Self::AlternateProtocol(data.clone()),
+            Kind::SystemFirmwareIntermediary(data) => {
+                Self::SystemFirmwareIntermediary(data.clone())
+            }
+            Kind::ShadowFunctions(data) => Self::ShadowFunctions(data.into()),
+            Kind::DataObjectExchange(data) => Self::DataObjectExchange(data.clone()),
+            Kind::Device3(data) => Self::Device3(data.clone()),
+            Kind::IntegrityAndDataEncryption(data) => {
+                Self::IntegrityAndDataEncryption(data.clone())
+            }
+            Kind::PhysicalLayer64GTps(data) => Self::PhysicalLayer64GTps(data.into()),
+            Kind::FlitLogging(data) => Self::FlitLogging(data.clone()),
+            Kind::FlitPerformanceMeasurement(data) => {
+                Self::FlitPerformanceMeasurement(data.clone())
+            }
+            Kind::FlitErrorInjection(data) => Self::FlitErrorInjection(data.clone()),
+            Kind::Reserved(v) => Self::Reserved(*v),
+        }
+    }
+}
+
+/// Owned [ExtendedCapability]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedExtendedCapability {
+    pub kind: OwnedExtendedCapabilityKind,
+    pub version: u8,
+    pub offset: u16,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ExtendedCapability<'a>> for OwnedExtendedCapability {
+    fn from(ecap: &ExtendedCapability<'a>) -> Self {
+        Self {
+            kind: (&ecap.kind).into(),
+            version: ecap.version,
+            offset: ecap.offset,
+        }
+    }
+}
+
+impl<'a> ExtendedCapabilityKind<'a> {
+    /// Access attributes of capability registers with offsets relative to the capability offset
+    ///
+    /// Returns `None` for capabilities which registers are not decoded. Lane Equalization Control
+    /// registers of [SecondaryPciExpress] and Physical Layer 16.0/32.0/64.0 GT/s capabilities
+    /// and Margining Lane registers of [LaneMarginingAtTheReceiver] depend on link width and
+    /// should be taken from [SecondaryPciExpress::registers], [PhysicalLayer16GTps::registers],
+    /// [PhysicalLayer32GTps::registers], [PhysicalLayer64GTps::registers] and
+    /// [LaneMarginingAtTheReceiver::registers].
+    pub fn registers(&self) -> Option<RegisterMap> {
+        let map = match self {
+            Self::AdvancedErrorReporting(aer) => aer.registers(),
+            Self::VirtualChannel(vc) | Self::VirtualChannelMfvcPresent(vc) => vc.registers(),
+            Self::DeviceSerialNumber(_) => device_serial_number::REGISTERS.into(),
+            Self::PowerBudgeting(_) => power_budgeting::REGISTERS.into(),
+            Self::RootComplexLinkDeclaration(rcld) => rcld.registers(),
+            Self::RootComplexInternalLinkControl(_) => {
+                root_complex_internal_link_control::REGISTERS.into()
+            }
+            Self::RootComplexEventCollectorEndpointAssociation(_) => {
+                root_complex_event_collector_endpoint_association::REGISTERS.into()
+            }
+            Self::MultifunctionVirtualChannel(mfvc) => mfvc.registers(),
+            Self::RootComplexRegisterBlockHeader(_) => {
+                root_complex_register_block_header::REGISTERS.into()
+            }
+            Self::VendorSpecificExtendedCapability(_) => {
+                vendor_specific_extended_capability::REGISTERS.into()
+            }
+            Self::ConfigurationAccessCorrelation(_) => {
+                configuration_access_correlation::REGISTERS.into()
+            }
+            Self::AccessControlServices(acs) => acs.registers(),
+            Self::AlternativeRoutingIdInterpretation(_) => {
+                alternative_routing_id_interpolation::REGISTERS.into()
+            }
+            Self::AddressTranslationServices(_) => address_translation_services::REGISTERS.into(),
+            Self::SingleRootIoVirtualization(_) => single_root_io_virtualization::REGISTERS.into(),
+            Self::MultiRootIoVirtualization(_) => multi_root_io_virtualization::REGISTERS.into(),
+            Self::Multicast(mc) => mc.registers(),
+            Self::PageRequestInterface(_) => page_request_interface::REGISTERS.into(),
+            Self::ResizableBar(rebar) => rebar.registers(),
+            Self::VfResizableBar(vf_rebar) => vf_rebar.registers(),
+            Self::DataLinkFeature(_) => data_link_feature::REGISTERS.into(),
+            Self::DynamicPowerAllocation(dpa) => dpa.registers(),
+            Self::TphRequester(tph) => tph.registers(),
+            Self::LatencyToleranceReporting(_) => latency_tolerance_reporting::REGISTERS.into(),
+            Self::SecondaryPciExpress(_) => secondary_pci_express::REGISTERS.into(),
+            Self::PhysicalLayer16GTps(_) => physical_layer_16_gtps::REGISTERS.into(),
+            Self::LaneMarginingAtTheReceiver(_) => lane_margining_at_the_receiver::REGISTERS.into(),
+            Self::HierarchyId(_) => hierarchy_id::REGISTERS.into(),
+            Self::NativePcieEnclosureManagement(_) => {
+                native_pcie_enclosure_management::REGISTERS.into()
+            }
+            Self::PhysicalLayer32GTps(_) => physical_layer_32_gtps::REGISTERS.into(),
+            Self::PhysicalLayer64GTps(_) => physical_layer_64_gtps::REGISTERS.into(),
+            Self::AlternateProtocol(ap) => ap.registers(),
+            Self::SystemFirmwareIntermediary(_) => system_firmware_intermediary::REGISTERS.into(),
+            Self::ShadowFunctions(sf) => sf.registers(),
+            Self::DataObjectExchange(_) => data_object_exchange::REGISTERS.into(),
+            Self::ProtocolMultiplexing(pmux) => pmux.registers(),
+            Self::ProcessAddressSpaceId(_) => process_address_space_id::REGISTERS.into(),
+            Self::LnRequester(_) => ln_requester::REGISTERS.into(),
+            Self::DownstreamPortContainment(dpc) => dpc.registers(),
+            Self::L1PmSubstates(_) => l1_pm_substates::REGISTERS.into(),
+            Self::PrecisionTimeMeasurement(_) => precision_time_measurement::REGISTERS.into(),
+            Self::PciExpressOverMphy(_) => pci_express_over_m_phy::REGISTERS.into(),
+            Self::FrsQueuing(_) => frs_queuing::REGISTERS.into(),
+            Self::ReadinessTimeReporting(_) => readiness_time_reporting::REGISTERS.into(),
+            Self::DesignatedVendorSpecificExtendedCapability(dvsec) => dvsec.registers(),
+            Self::Null
+            | Self::ReservedForAmd(_)
+            | Self::Device3(_)
+            | Self::IntegrityAndDataEncryption(_)
+            | Self::FlitLogging(_)
+            | Self::FlitPerformanceMeasurement(_)
+            | Self::FlitErrorInjection(_)
+            | Self::Reserved(_) => return None,
+        };
+        Some(map)
+    }
+}
+
 
 // 0001h Advanced Error Reporting (AER)
 pub mod advanced_error_reporting;
@@ -635,6 +1162,8 @@ pub use advanced_error_reporting::AdvancedErrorReporting;
 
 // 0002h/0009h Virtual Channel (VC)
 pub mod virtual_channel;
+#[cfg(feature = "alloc")]
+pub use virtual_channel::OwnedVirtualChannel;
 pub use virtual_channel::VirtualChannel;
 
 // 0003h Device Serial Number
@@ -647,6 +1176,8 @@ pub use power_budgeting::PowerBudgeting;
 
 // 0005h Root Complex Link Declaration
 pub mod root_complex_link_declaration;
+#[cfg(feature = "alloc")]
+pub use root_complex_link_declaration::OwnedRootComplexLinkDeclaration;
 pub use root_complex_link_declaration::RootComplexLinkDeclaration;
 
 // 0006h Root Complex Internal Link Control
@@ -660,6 +1191,8 @@ pub use root_complex_event_collector_endpoint_association::RootComplexEventColle
 // 0008h Multi-Function Virtual Channel (MFVC)
 pub mod multifunction_virtual_channel;
 pub use multifunction_virtual_channel::MultifunctionVirtualChannel;
+#[cfg(feature = "alloc")]
+pub use multifunction_virtual_channel::OwnedMultifunctionVirtualChannel;
 
 // 000Ah Root Complex Register Block (RCRB) Header
 pub mod root_complex_register_block_header;
@@ -667,6 +1200,8 @@ pub use root_complex_register_block_header::RootComplexRegisterBlockHeader;
 
 // 000Bh Vendor-Specific Extended Capability (VSEC)
 pub mod vendor_specific_extended_capability;
+#[cfg(feature = "alloc")]
+pub use vendor_specific_extended_capability::OwnedVendorSpecificExtendedCapability;
 pub use vendor_specific_extended_capability::VendorSpecificExtendedCapability;
 
 // 000Ch Configuration Access Correlation (CAC)
@@ -677,6 +1212,8 @@ pub use configuration_access_correlation::ConfigurationAccessCorrelation;
 // 000Dh Access Control Services (ACS)
 pub mod access_control_services;
 pub use access_control_services::AccessControlServices;
+#[cfg(feature = "alloc")]
+pub use access_control_services::OwnedAccessControlServices;
 
 // 000Eh Alternative Routing-ID Interpretation (ARI)
 pub mod alternative_routing_id_interpolation;
@@ -692,10 +1229,7 @@ pub use single_root_io_virtualization::SingleRootIoVirtualization;
 
 // 0011h Multi-Root I/O Virtualization (MR-IOV)
 // defined in the Multi-Root I/O Virtualization and Sharing Specification
-pub mod multi_root_io_virtualization {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct MultiRootIoVirtualization;
-}
+pub mod multi_root_io_virtualization;
 pub use multi_root_io_virtualization::MultiRootIoVirtualization;
 
 // 0012h Multicast
@@ -709,20 +1243,27 @@ pub use page_request_interface::PageRequestInterface;
 // 0014h Reserved for AMD
 pub mod reserved_for_amd {
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ReservedForAmd;
 }
 pub use reserved_for_amd::ReservedForAmd;
 
 // 0015h Resizable BAR
 pub mod resizable_bar;
+#[cfg(feature = "alloc")]
+pub use resizable_bar::OwnedResizableBar;
 pub use resizable_bar::ResizableBar;
 
 // 0016h Dynamic Power Allocation (DPA)
 pub mod dynamic_power_allocation;
 pub use dynamic_power_allocation::DynamicPowerAllocation;
+#[cfg(feature = "alloc")]
+pub use dynamic_power_allocation::OwnedDynamicPowerAllocation;
 
 // 0017h TPH Requester
 pub mod tph_requester;
+#[cfg(feature = "alloc")]
+pub use tph_requester::OwnedTphRequester;
 pub use tph_requester::TphRequester;
 
 // 0018h Latency Tolerance Reporting (LTR)
@@ -731,10 +1272,14 @@ pub use latency_tolerance_reporting::LatencyToleranceReporting;
 
 // 0019h Secondary PCI Express
 pub mod secondary_pci_express;
+#[cfg(feature = "alloc")]
+pub use secondary_pci_express::OwnedSecondaryPciExpress;
 pub use secondary_pci_express::SecondaryPciExpress;
 
 // 001Ah Protocol Multiplexing (PMUX)
 pub mod protocol_multiplexing;
+#[cfg(feature = "alloc")]
+pub use protocol_multiplexing::OwnedProtocolMultiplexing;
 pub use protocol_multiplexing::ProtocolMultiplexing;
 
 // 001Bh Process Address Space ID (PASID)
@@ -772,103 +1317,67 @@ pub use readiness_time_reporting::ReadinessTimeReporting;
 // 0023h Designated Vendor-Specific Extended Capability
 pub mod designated_vendor_specific_extended_capability;
 pub use designated_vendor_specific_extended_capability::DesignatedVendorSpecificExtendedCapability;
+#[cfg(feature = "alloc")]
+pub use designated_vendor_specific_extended_capability::OwnedDesignatedVendorSpecificExtendedCapability;
 
 // 0024h VF Resizable BAR
-pub mod vf_resizable_bar {
-    /*!
-    # VF Resizable BAR
-
-    The VF Resizable BAR capability permits hardware to communicate the
-    resource sizes that are acceptable for operation via the VF Resizable BAR Capability and Control
-    registers and system software to communicate the optimal size back to the hardware via the VF
-    BAR Size field of the VF Resizable BAR Control register.
-
-    ## Struct diagram
-
-    VF Resizable BAR has same fields as [ResizableBar](super::ResizableBar)
-    */
-
-    /// VF Resizable BAR
-    pub type VfResizableBar<'a> = super::ResizableBar<'a>;
-
-    /// VF Resizable BAR Error
-    pub type VfResizableBarError = super::resizable_bar::ResizableBarError;
-}
+pub mod vf_resizable_bar;
+#[cfg(feature = "alloc")]
+pub use vf_resizable_bar::OwnedVfResizableBar;
 pub use vf_resizable_bar::VfResizableBar;
 
 // 0025h Data Link Feature
-pub mod data_link_feature {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct DataLinkFeature;
-}
+pub mod data_link_feature;
 pub use data_link_feature::DataLinkFeature;
 
 // 0026h Physical Layer 16.0 GT/s
-pub mod physical_layer_16_gtps {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct PhysicalLayer16GTps;
-}
+pub mod physical_layer_16_gtps;
+#[cfg(feature = "alloc")]
+pub use physical_layer_16_gtps::OwnedPhysicalLayer16GTps;
 pub use physical_layer_16_gtps::PhysicalLayer16GTps;
 
 // 0027h Lane Margining at the Receiver
-pub mod lane_margining_at_the_receiver {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct LaneMarginingAtTheReceiver;
-}
+pub mod lane_margining_at_the_receiver;
 pub use lane_margining_at_the_receiver::LaneMarginingAtTheReceiver;
+#[cfg(feature = "alloc")]
+pub use lane_margining_at_the_receiver::OwnedLaneMarginingAtTheReceiver;
 
 // 0028h Hierarchy ID
-pub mod hierarchy_id {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct HierarchyId;
-}
+pub mod hierarchy_id;
 pub use hierarchy_id::HierarchyId;
 
 // 0029h Native PCIe Enclosure Management (NPEM)
-pub mod native_pcie_enclosure_management {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct NativePcieEnclosureManagement;
-}
+pub mod native_pcie_enclosure_management;
 pub use native_pcie_enclosure_management::NativePcieEnclosureManagement;
 
 // 002Ah Physical Layer 32.0 GT/s
-pub mod physical_layer_32_gtps {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct PhysicalLayer32GTps;
-}
+pub mod physical_layer_32_gtps;
+#[cfg(feature = "alloc")]
+pub use physical_layer_32_gtps::OwnedPhysicalLayer32GTps;
 pub use physical_layer_32_gtps::PhysicalLayer32GTps;
 
 // 002Bh Alternate Protocol
-pub mod alternate_protocol {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct AlternateProtocol;
-}
+pub mod alternate_protocol;
 pub use alternate_protocol::AlternateProtocol;
 
 // 002Ch System Firmware Intermediary (SFI)
-pub mod system_firmware_intermediary {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct SystemFirmwareIntermediary;
-}
+pub mod system_firmware_intermediary;
 pub use system_firmware_intermediary::SystemFirmwareIntermediary;
 
 // 002Dh Shadow Functions
-pub mod shadow_functions {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct ShadowFunctions;
-}
+pub mod shadow_functions;
+#[cfg(feature = "alloc")]
+pub use shadow_functions::OwnedShadowFunctions;
 pub use shadow_functions::ShadowFunctions;
 
 // 002Eh Data Object Exchange
-pub mod data_object_exchange {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct DataObjectExchange;
-}
+pub mod data_object_exchange;
 pub use data_object_exchange::DataObjectExchange;
 
 // 002Fh Device 3
 pub mod device_3 {
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Device3;
 }
 pub use device_3::Device3;
@@ -876,20 +1385,21 @@ pub use device_3::Device3;
 // 0030h Integrity and Data Encryption (IDE)
 pub mod integrity_and_data_encryption {
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct IntegrityAndDataEncryption;
 }
 pub use integrity_and_data_encryption::IntegrityAndDataEncryption;
 
 // 0031h Physical Layer 64.0 GT/s
-pub mod physical_layer_64_gtps {
-    #[derive(Debug, Clone, PartialEq, Eq)]
-    pub struct PhysicalLayer64GTps;
-}
+pub mod physical_layer_64_gtps;
+#[cfg(feature = "alloc")]
+pub use physical_layer_64_gtps::OwnedPhysicalLayer64GTps;
 pub use physical_layer_64_gtps::PhysicalLayer64GTps;
 
 // 0032h Flit Logging
 pub mod flit_logging {
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct FlitLogging;
 }
 pub use flit_logging::FlitLogging;
@@ -897,6 +1407,7 @@ pub use flit_logging::FlitLogging;
 // 0033h Flit Performance Measurement
 pub mod flit_performance_measurement {
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct FlitPerformanceMeasurement;
 }
 pub use flit_performance_measurement::FlitPerformanceMeasurement;
@@ -904,6 +1415,7 @@ pub use flit_performance_measurement::FlitPerformanceMeasurement;
 // 0034h Flit Error Injection
 pub mod flit_error_injection {
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct FlitErrorInjection;
 }
 pub use flit_error_injection::FlitErrorInjection;
@@ -945,4 +1457,85 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(sample, result);
     }
+
+    #[test]
+    fn malformed_lists() {
+        let offsets = |ecs: &[u8]| {
+            ExtendedCapabilities::new(ecs)
+                .map(|ecap| ecap.map(|ecap| ecap.offset))
+                .collect::<Vec<_>>()
+        };
+        // Capability Version 1 keeps Null Capability header nonzero
+        let set_header = |ecs: &mut [u8], offset: usize, id: u32, next: u32| {
+            let dword = id | 1 << 16 | next << 20;
+            ecs[offset - ECS_OFFSET..][..4].copy_from_slice(&dword.to_le_bytes());
+        };
+
+        // [100] Null -> [110] Null -> [100]
+        let mut ecs = [0u8; ECS_LENGTH];
+        set_header(&mut ecs, 0x100, 0x0000, 0x110);
+        set_header(&mut ecs, 0x110, 0x0000, 0x100);
+        let sample = vec![
+            Ok(0x100),
+            Ok(0x110),
+            Err(ExtendedCapabilityError::Loop { offset: 0x100 }),
+        ];
+        assert_eq!(sample, offsets(&ecs));
+
+        // [100] Null -> [112]
+        let mut ecs = [0u8; ECS_LENGTH];
+        set_header(&mut ecs, 0x100, 0x0000, 0x112);
+        let sample = vec![
+            Ok(0x100),
+            Err(ExtendedCapabilityError::Misaligned { offset: 0x112 }),
+        ];
+        assert_eq!(sample, offsets(&ecs));
+
+        // [100] Device Serial Number (12 bytes) -> [108] Null
+        let mut ecs = [0u8; ECS_LENGTH];
+        set_header(&mut ecs, 0x100, 0x0003, 0x108);
+        set_header(&mut ecs, 0x108, 0x0000, 0x000);
+        let sample = vec![
+            Ok(0x100),
+            Err(ExtendedCapabilityError::Overlap { offset: 0x108 }),
+        ];
+        assert_eq!(sample, offsets(&ecs));
+
+        // [100] Null -> [104] Null -> ... -> [ffc] Null, longer than MAX_ENTRIES
+        let mut ecs = [0u8; ECS_LENGTH];
+        for offset in (ECS_OFFSET..ECS_OFFSET + ECS_LENGTH - 4).step_by(4) {
+            set_header(&mut ecs, offset, 0x0000, offset as u32 + 4);
+        }
+        let result = offsets(&ecs);
+        assert_eq!(ExtendedCapabilities::MAX_ENTRIES + 1, result.len());
+        assert!(result[..ExtendedCapabilities::MAX_ENTRIES]
+            .iter()
+            .all(Result::is_ok));
+        assert_eq!(Some(&Err(ExtendedCapabilityError::Limit)), result.last());
+    }
+
+    #[test]
+    fn header_inclusive_data_at_offset() {
+        // Multicast parses data starting from its own Extended Capability Header, not from the
+        // start of extended configuration space
+        let multicast = [
+            0x12, 0x00, 0x01, 0x00, // Capability header
+            0x2a, 0x8f, 0x20, 0x80, // Multicast Capability, Multicast Control
+            0x0f, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, // MC_Base_Address
+            0x00, 0x00, 0x00, 0x00, 0x11, 0x11, 0x11, 0x11, // MC_Receive
+            0x22, 0x22, 0x22, 0x22, 0x33, 0x33, 0x33, 0x33, // MC_Block_All
+            0x44, 0x44, 0x44, 0x44, 0x55, 0x55, 0x55, 0x55, // MC_Block_Untranslated
+            0x0a, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, // MC_Overlay_BAR
+        ];
+        let mut ecs = [0u8; ECS_LENGTH];
+        // [100] Null -> [200] Multicast
+        ecs[..4].copy_from_slice(&(1u32 << 16 | 0x200 << 20).to_le_bytes());
+        ecs[0x100..][..multicast.len()].copy_from_slice(&multicast);
+        let result = ExtendedCapabilities::new(&ecs).nth(1).unwrap().unwrap();
+        assert_eq!(0x200, result.offset);
+        assert_eq!(
+            ExtendedCapabilityKind::Multicast(multicast.as_slice().try_into().unwrap()),
+            result.kind
+        );
+    }
 }
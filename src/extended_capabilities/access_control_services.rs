@@ -47,13 +47,18 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P8, P9};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
 
 use core::slice::Chunks;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Egress Control Vector is DWORD
 const ECV_BYTES: usize = 4;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AccessControlServices<'a> {
     data: &'a [u8],
     /// ACS Capability
@@ -91,8 +96,80 @@ impl<'a> TryFrom<&'a [u8]> for AccessControlServices<'a> {
     }
 }
 
+/// Owned [AccessControlServices]
+///
+/// Converts back into [AccessControlServices] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedAccessControlServices {
+    data: Vec<u8>,
+    /// ACS Capability
+    pub acs_capability: AcsCapability,
+    /// ACS Control
+    pub acs_control: AcsControl,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&AccessControlServices<'a>> for OwnedAccessControlServices {
+    fn from(value: &AccessControlServices<'a>) -> Self {
+        Self {
+            data: value.data.to_vec(),
+            acs_capability: value.acs_capability.clone(),
+            acs_control: value.acs_control.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedAccessControlServices> for AccessControlServices<'a> {
+    fn from(value: &'a OwnedAccessControlServices) -> Self {
+        Self {
+            data: &value.data,
+            acs_capability: value.acs_capability.clone(),
+            acs_control: value.acs_control.clone(),
+        }
+    }
+}
+
+impl<'a> AccessControlServices<'a> {
+    /// Access attributes of ACS registers including Egress Control Vector
+    pub fn registers(&self) -> RegisterMap {
+        let AcsCapability {
+            acs_p2p_egress_control,
+            egress_control_vector_size,
+            ..
+        } = self.acs_capability;
+        let count = if acs_p2p_egress_control {
+            // Size 00h is interpreted as 256 bits
+            let bits = if egress_control_vector_size == 0 {
+                256
+            } else {
+                egress_control_vector_size as u16
+            };
+            bits.div_ceil(u32::BITS as u16)
+        } else {
+            0
+        };
+        RegisterMap::from(REGISTERS).repeat(EGRESS_CONTROL_VECTOR_REGISTERS, 4, count)
+    }
+}
+
+/// ACS Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 2),                          // ACS Capability
+    Attr::new(0x06, 2).rw(0x007f).rsvdp(0xff80), // ACS Control
+];
+
+/// First Egress Control Vector register, other registers follow every 4 bytes
+pub const EGRESS_CONTROL_VECTOR_REGISTERS: &[Attr] = &[
+    Attr::new(0x08, 4).rw(0xffff_ffff), // Egress Control Vector
+];
+
 /// ACS Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AcsCapability {
     /// ACS Source Validation (V)
     pub acs_source_validation: bool,
@@ -140,6 +217,7 @@ impl From<u16> for AcsCapability {
 
 /// ACS Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AcsControl {
     /// ACS Source Validation Enable (V)
     pub acs_source_validation_enable: bool,
@@ -181,6 +259,24 @@ impl From<u16> for AcsControl {
     }
 }
 
+impl From<AcsControl> for u16 {
+    fn from(data: AcsControl) -> Self {
+        (data.acs_source_validation_enable as u16)
+            | (data.acs_translation_blocking_enable as u16) << 1
+            | (data.acs_p2p_request_redirect_enable as u16) << 2
+            | (data.acs_p2p_completion_redirect_enable as u16) << 3
+            | (data.acs_upstream_forwarding_enable as u16) << 4
+            | (data.acs_p2p_egress_control_enable as u16) << 5
+            | (data.acs_direct_translated_p2p_enable as u16) << 6
+    }
+}
+
+impl Register for AcsControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xff80;
+    const RW1C: u16 = 0;
+}
+
 /// An iterator through bits controlled the blocking or redirecting of  peer-to-peer Requests
 /// targeting the associated Port, Function, or Function Group.
 #[derive(Debug, Clone)]
@@ -233,6 +329,13 @@ impl<'a> Iterator for EgressControlVectors<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for EgressControlVectors<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,8 +35,10 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3, P4};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AddressTranslationServices {
     /// ATS Capability
     pub ats_capability: AtsCapability,
@@ -63,8 +65,16 @@ impl TryFrom<&[u8]> for AddressTranslationServices {
     }
 }
 
+/// ATS Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 2),                          // ATS Capability
+    Attr::new(0x06, 2).rw(0x801f).rsvdp(0x7fe0), // ATS Control
+];
+
 /// ATS Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AtsCapability {
     /// Invalidate Queue Depth
     pub invalidate_queue_depth: u8,
@@ -87,6 +97,7 @@ impl From<u16> for AtsCapability {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AtsControl {
     /// Smallest Translation Unit (STU)
     pub smallest_translation_unit: u8,
@@ -103,3 +114,15 @@ impl From<u16> for AtsControl {
         }
     }
 }
+
+impl From<AtsControl> for u16 {
+    fn from(data: AtsControl) -> Self {
+        (data.smallest_translation_unit as u16 & 0x1f) | (data.enable as u16) << 15
+    }
+}
+
+impl Register for AtsControl {
+    type Value = u16;
+    const RSVDP: u16 = 0x7fe0;
+    const RW1C: u16 = 0;
+}
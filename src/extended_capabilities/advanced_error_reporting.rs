@@ -6,11 +6,12 @@ be implemented by PCI Express device Functions supporting advanced error control
 
 ## Struct diagram
 [AdvancedErrorReporting]
-- [UncorrectableError]
-- [UncorrectableError]
-- [UncorrectableError]
-- [CorrectableError]
-- [CorrectableError]
+- [UncorrectableErrorStatus]
+  - [UncorrectableError]
+- [UncorrectableError] (Mask, Severity)
+- [CorrectableErrorStatus]
+  - [CorrectableError]
+- [CorrectableError] (Mask)
 - [AdvancedErrorCapabilitiesAndControl]
 - [HeaderLog]
 - [RootErrorCommand]
@@ -50,7 +51,7 @@ let data = [
 ];
 let result = data[4..].try_into().unwrap();
 let sample = AdvancedErrorReporting {
-    uncorrectable_error_status: UncorrectableError {
+    uncorrectable_error_status: UncorrectableErrorStatus(UncorrectableError {
         link_training_error: false,
         data_link_protocol_error_status: false,
         surprise_down_error_status: false,
@@ -69,7 +70,7 @@ let sample = AdvancedErrorReporting {
         atomicop_egress_blocked_status: false,
         tlp_prefix_blocked_error_status: false,
         poisoned_tlp_egress_blocked_status: false,
-    },
+    }),
     uncorrectable_error_mask: UncorrectableError {
         link_training_error: false,
         data_link_protocol_error_status: false,
@@ -110,7 +111,7 @@ let sample = AdvancedErrorReporting {
         tlp_prefix_blocked_error_status: false,
         poisoned_tlp_egress_blocked_status: false,
     },
-    correctable_error_status: CorrectableError {
+    correctable_error_status: CorrectableErrorStatus(CorrectableError {
         receiver_error_status: false,
         bad_tlp_status: false,
         bad_dllp_status: false,
@@ -119,7 +120,7 @@ let sample = AdvancedErrorReporting {
         advisory_non_fatal_error_status: false,
         corrected_internal_error_status: false,
         header_log_overflow_status: false,
-    },
+    }),
     correctable_error_mask: CorrectableError {
         receiver_error_status: true,
         bad_tlp_status: true,
@@ -170,8 +171,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P10, P11, P2, P21, P4, P7, P9};
 use snafu::Snafu;
 
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
 /// Advanced Error Reporting Error
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AdvancedErrorReportingError {
     #[snafu(display("can't read common registres"))]
     Common,
@@ -180,10 +184,11 @@ pub enum AdvancedErrorReportingError {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdvancedErrorReporting {
     /// The Uncorrectable Error Status register indicates error detection status of individual errors
     /// on a PCI Express device Function.
-    pub uncorrectable_error_status: UncorrectableError,
+    pub uncorrectable_error_status: UncorrectableErrorStatus,
     /// The Uncorrectable Error Mask register controls reporting of individual errors by the device
     /// Function to the PCI Express Root Complex via a PCI Express error Message.
     pub uncorrectable_error_mask: UncorrectableError,
@@ -192,7 +197,7 @@ pub struct AdvancedErrorReporting {
     pub uncorrectable_error_severity: UncorrectableError,
     /// The Correctable Error Status register reports error status of individual correctable error
     /// sources on a PCI Express device Function.
-    pub correctable_error_status: CorrectableError,
+    pub correctable_error_status: CorrectableErrorStatus,
     /// The Correctable Error Mask register controls reporting of individual correctable errors by this
     /// Function to the PCI Express Root Complex via a PCI Express error Message.
     pub correctable_error_mask: CorrectableError,
@@ -289,9 +294,46 @@ impl TryFrom<&[u8]> for AdvancedErrorReporting {
     }
 }
 
+impl AdvancedErrorReporting {
+    /// Access attributes of registers present in the decoded structure
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        if self.tlp_prefix_log.is_some() {
+            map
+        } else if self.root_error_command.is_some() {
+            map.truncate(0x38)
+        } else {
+            map.truncate(0x2c)
+        }
+    }
+}
+
+/// Advanced Error Reporting Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).rw1c(0x07ff_f030).rsvdz(0xf800_0fcf), // Uncorrectable Error Status
+    Attr::new(0x08, 4).rw(0x07ff_f030).rsvdp(0xf800_0fcf), // Uncorrectable Error Mask
+    Attr::new(0x0c, 4).rw(0x07ff_f030).rsvdp(0xf800_0fcf), // Uncorrectable Error Severity
+    Attr::new(0x10, 4).rw1c(0x0000_f1c1).rsvdz(0xffff_0e3e), // Correctable Error Status
+    Attr::new(0x14, 4).rw(0x0000_f1c1).rsvdp(0xffff_0e3e), // Correctable Error Mask
+    Attr::new(0x18, 4).rw(0x0000_0540).rsvdp(0xffff_e000), // Advanced Error Capabilities and Control
+    Attr::new(0x1c, 4),                                    // Header Log 1st DW
+    Attr::new(0x20, 4),                                    // Header Log 2nd DW
+    Attr::new(0x24, 4),                                    // Header Log 3rd DW
+    Attr::new(0x28, 4),                                    // Header Log 4th DW
+    Attr::new(0x2c, 4).rw(0x0000_0007).rsvdp(0xffff_fff8), // Root Error Command
+    Attr::new(0x30, 4).rw1c(0x0000_007f).rsvdz(0x07ff_ff80), // Root Error Status
+    Attr::new(0x34, 4),                                    // Error Source Identification
+    Attr::new(0x38, 4),                                    // TLP Prefix Log 1st DW
+    Attr::new(0x3c, 4),                                    // TLP Prefix Log 2nd DW
+    Attr::new(0x40, 4),                                    // TLP Prefix Log 3rd DW
+    Attr::new(0x44, 4),                                    // TLP Prefix Log 4th DW
+];
+
 /// Uncorrectable Error Status, Uncorrectable Error Mask and Uncorrectable Error Severity has same
 /// fields
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UncorrectableError {
     /// Indicate a Link Training Error (legacy)
     pub link_training_error: bool,
@@ -378,8 +420,60 @@ impl From<u32> for UncorrectableError {
     }
 }
 
+/// Mask and Severity registers bits are RW, Status register is encoded by
+/// [UncorrectableErrorStatus]
+impl From<UncorrectableError> for u32 {
+    fn from(data: UncorrectableError) -> Self {
+        (data.link_training_error as u32)
+            | (data.data_link_protocol_error_status as u32) << 4
+            | (data.surprise_down_error_status as u32) << 5
+            | (data.poisoned_tlp_received_status as u32) << 12
+            | (data.flow_control_protocol_error_status as u32) << 13
+            | (data.completion_timeout_status as u32) << 14
+            | (data.completer_abort_status as u32) << 15
+            | (data.unexpected_completion_status as u32) << 16
+            | (data.receiver_overflow_status as u32) << 17
+            | (data.malformed_tlp_status as u32) << 18
+            | (data.ecrc_error_status as u32) << 19
+            | (data.unsupported_request_error_status as u32) << 20
+            | (data.acs_violation_status as u32) << 21
+            | (data.uncorrectable_internal_error_status as u32) << 22
+            | (data.mc_blocked_tlp_status as u32) << 23
+            | (data.atomicop_egress_blocked_status as u32) << 24
+            | (data.tlp_prefix_blocked_error_status as u32) << 25
+            | (data.poisoned_tlp_egress_blocked_status as u32) << 26
+    }
+}
+
+/// Uncorrectable Error Status register
+///
+/// Wraps [UncorrectableError] to write the status back without clearing RW1C bits by accident.
+/// Link Training Error is not defined for this register (RsvdZ) and is never encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UncorrectableErrorStatus(pub UncorrectableError);
+
+impl From<u32> for UncorrectableErrorStatus {
+    fn from(dword: u32) -> Self {
+        Self(dword.into())
+    }
+}
+
+impl From<UncorrectableErrorStatus> for u32 {
+    fn from(data: UncorrectableErrorStatus) -> Self {
+        u32::from(data.0) & UncorrectableErrorStatus::RW1C
+    }
+}
+
+impl Register for UncorrectableErrorStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x07ff_f030;
+}
+
 /// Correctable Error Status and Correctable Error Mask has same fields
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CorrectableError {
     /// Receiver Error Status
     pub receiver_error_status: bool,
@@ -426,8 +520,48 @@ impl From<u32> for CorrectableError {
     }
 }
 
+/// Mask register bits are RW, Status register is encoded by [CorrectableErrorStatus]
+impl From<CorrectableError> for u32 {
+    fn from(data: CorrectableError) -> Self {
+        (data.receiver_error_status as u32)
+            | (data.bad_tlp_status as u32) << 6
+            | (data.bad_dllp_status as u32) << 7
+            | (data.replay_num_rollover_status as u32) << 8
+            | (data.replay_timer_timeout_status as u32) << 12
+            | (data.advisory_non_fatal_error_status as u32) << 13
+            | (data.corrected_internal_error_status as u32) << 14
+            | (data.header_log_overflow_status as u32) << 15
+    }
+}
+
+/// Correctable Error Status register
+///
+/// Wraps [CorrectableError] to write the status back without clearing RW1C bits by accident.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorrectableErrorStatus(pub CorrectableError);
+
+impl From<u32> for CorrectableErrorStatus {
+    fn from(dword: u32) -> Self {
+        Self(dword.into())
+    }
+}
+
+impl From<CorrectableErrorStatus> for u32 {
+    fn from(data: CorrectableErrorStatus) -> Self {
+        data.0.into()
+    }
+}
+
+impl Register for CorrectableErrorStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0000_f1c1;
+}
+
 /// Advanced Error Capabilities and Control Register
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdvancedErrorCapabilitiesAndControl {
     /// First Error Pointer
     pub first_error_pointer: u8,
@@ -477,14 +611,36 @@ impl From<u32> for AdvancedErrorCapabilitiesAndControl {
     }
 }
 
+impl From<AdvancedErrorCapabilitiesAndControl> for u32 {
+    fn from(data: AdvancedErrorCapabilitiesAndControl) -> Self {
+        (data.first_error_pointer as u32 & 0b11111)
+            | (data.ecrc_generation_capable as u32) << 5
+            | (data.ecrc_generation_enable as u32) << 6
+            | (data.ecrc_check_capable as u32) << 7
+            | (data.ecrc_check_enable as u32) << 8
+            | (data.multiple_header_recording_capable as u32) << 9
+            | (data.multiple_header_recording_enable as u32) << 10
+            | (data.tlp_prefix_log_present as u32) << 11
+            | (data.completion_timeout_prefix_or_header_log_capable as u32) << 12
+    }
+}
+
+impl Register for AdvancedErrorCapabilitiesAndControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_e000;
+    const RW1C: u32 = 0;
+}
+
 /// The Header Log register contains the header for the TLP corresponding to a detected error
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeaderLog(pub [u32; 4]);
 
 /// The Root Error Command register allows further control of Root Complex response to Correctable,
 /// Non-Fatal, and Fatal error Messages than the basic Root Complex capability to generate system
 /// errors in response to error Messages (either received or internally generated).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootErrorCommand {
     /// Correctable Error Reporting Enable
     pub correctable_error_reporting_enable: bool,
@@ -510,10 +666,25 @@ impl From<u32> for RootErrorCommand {
     }
 }
 
+impl From<RootErrorCommand> for u32 {
+    fn from(data: RootErrorCommand) -> Self {
+        (data.correctable_error_reporting_enable as u32)
+            | (data.non_fatal_error_reporting_enable as u32) << 1
+            | (data.fatal_error_reporting_enable as u32) << 2
+    }
+}
+
+impl Register for RootErrorCommand {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_fff8;
+    const RW1C: u32 = 0;
+}
+
 /// The Root Error Status register reports status of error Messages (ERR_COR, ERR_NONFATAL, and
 /// ERR_FATAL) received by the Root Port, and of errors detected by the Root Port itself (which are
 /// treated conceptually as if the Root Port had sent an error Message to itself).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootErrorStatus {
     /// ERR_COR Received
     pub err_cor_received: bool,
@@ -559,10 +730,30 @@ impl From<u32> for RootErrorStatus {
     }
 }
 
+impl From<RootErrorStatus> for u32 {
+    fn from(data: RootErrorStatus) -> Self {
+        (data.err_cor_received as u32)
+            | (data.multiple_err_cor_received as u32) << 1
+            | (data.err_fatal_or_nonfatal_received as u32) << 2
+            | (data.multiple_err_fatal_or_nonfatal_received as u32) << 3
+            | (data.first_uncorrectable_fatal as u32) << 4
+            | (data.non_fatal_error_messages_received as u32) << 5
+            | (data.fatal_error_messages_received as u32) << 6
+            | (data.advanced_error_interrupt_message_number as u32 & 0b11111) << 27
+    }
+}
+
+impl Register for RootErrorStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0000_007f;
+}
+
 /// The Error Source Identification register identifies the source (Requester ID) of first
 /// correctable and uncorrectable (Non-fatal/Fatal) errors reported in the Root Error Status
 /// register.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ErrorSourceIdentification {
     /// ERR_COR Source Identification
     pub err_cor_source_identification: u16,
@@ -573,6 +764,7 @@ pub struct ErrorSourceIdentification {
 /// The TLP Prefix Log register captures the End-End TLP Prefix(s) for the TLP corresponding to the
 /// detected error
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TlpPrefixLog(pub [u32; 4]);
 
 #[cfg(test)]
@@ -580,6 +772,23 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn error_status_write_back() {
+        // Every defined error bit is set
+        let current = 0x07ff_f030;
+        let mut status = UncorrectableErrorStatus::from(current);
+        assert_eq!(0, status.clone().write_value(current), "clears nothing");
+        // Clear only Malformed TLP Status
+        status = UncorrectableErrorStatus::from(0);
+        status.0.malformed_tlp_status = true;
+        assert_eq!(1 << 18, status.clear_value(current));
+
+        let current = 0x0000_f1c1;
+        let mut status = CorrectableErrorStatus::from(current);
+        status.0.bad_tlp_status = false;
+        assert_eq!(0, status.write_value(current), "clears nothing");
+    }
+
     #[test]
     fn uncorrectable_error_status() {
         // UESvrt: DLP+ SDES- TLP- FCP- CmpltTO- CmpltAbrt- UnxCmplt- RxOF+ MalfTLP+ ECRC- UnsupReq- ACSViol-
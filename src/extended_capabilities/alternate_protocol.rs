@@ -0,0 +1,459 @@
+/*!
+# Alternate Protocol
+
+The Alternate Protocol Extended Capability describes the protocols other than PCI Express
+(e.g., CXL or vendor protocols) that a Port may negotiate during Link training. Every protocol
+is described by an entry which is read by writing its index to Alternate Protocol Index Select
+and reading Alternate Protocol Data 1 and Data 2 registers. Negotiation result is reported in
+[Received Modified TS Data](super::physical_layer_32_gtps::ModifiedTsData) of the Physical Layer
+32.0 GT/s capability.
+
+[AlternateProtocol::protocols] walks the entries through an [AlternateProtocolAccess]: recorded
+Data 1 and Data 2 registers or [AlternateProtocolSelector] driving Index Select of a live
+Function.
+
+## Struct diagram
+[AlternateProtocol]
+- [AlternateProtocolCapabilities]
+- [AlternateProtocolControl]
+- [AlternateProtocolData]
+
+[AlternateProtocols]
+- [AlternateProtocolEntry]
+  - [AlternateProtocolData]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::alternate_protocol::*;
+let data = [
+    /* 00h */ 0x2b, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x02, 0x00, 0x00, 0x00, // Alternate Protocol Capabilities
+    /* 08h */ 0x00, 0x00, 0x00, 0x00, // Alternate Protocol Control
+    /* 0Ch */ 0x21, 0x00, 0x98, 0x1e, // Alternate Protocol Data 1
+    /* 10h */ 0x00, 0x00, 0x00, 0x00, // Alternate Protocol Data 2
+];
+let ap: AlternateProtocol = data[4..].try_into().unwrap();
+assert_eq!(2, ap.capabilities.alternate_protocol_count);
+assert_eq!(None, ap.selective_enable_mask);
+
+// Data 1 and Data 2 registers recorded for every Index Select value
+let image = [[0x1e98_0021, 0x0000_0000], [0x8086_0041, 0x0012_3456]];
+let protocols = ap
+    .protocols(image.as_slice())
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+assert!(protocols[0].data.is_compute_express_link());
+assert_eq!(
+    AlternateProtocolEntry {
+        index: 1,
+        data: AlternateProtocolData {
+            usage_information: 1,
+            details: 2,
+            vendor_id: 0x8086,
+            modified_ts_information_2: 0x12_3456,
+        },
+    },
+    protocols[1]
+);
+```
+*/
+
+use heterob::{
+    bit_numbering::Lsb,
+    endianness::{Le, LeBytesTryInto},
+    Seq, P2, P3, P4,
+};
+use snafu::prelude::*;
+
+use super::ExtendedCapabilityDataError;
+use crate::access::ConfigWrite;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+/// Alternate Protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlternateProtocol {
+    pub capabilities: AlternateProtocolCapabilities,
+    pub control: AlternateProtocolControl,
+    /// Entry selected by [Alternate Protocol Index
+    /// Select](AlternateProtocolControl::alternate_protocol_index_select)
+    pub data: AlternateProtocolData,
+    /// Alternate Protocol Selective Enable Mask. Present if [Selective Enable
+    /// Supported](AlternateProtocolCapabilities::alternate_protocol_selective_enable_supported)
+    /// is set, bit N enables protocol N.
+    pub selective_enable_mask: Option<u32>,
+}
+impl AlternateProtocol {
+    /// Iterate over all advertised protocol entries
+    pub fn protocols<'a, A: AlternateProtocolAccess + ?Sized>(
+        &self,
+        access: &'a A,
+    ) -> AlternateProtocols<'a, A> {
+        AlternateProtocols {
+            access,
+            index: 0,
+            count: self.capabilities.alternate_protocol_count,
+        }
+    }
+    /// Access attributes of registers present in the decoded structure
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        if self.selective_enable_mask.is_some() {
+            map
+        } else {
+            map.truncate(0x14)
+        }
+    }
+}
+impl TryFrom<&[u8]> for AlternateProtocol {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capabilities, control, data_1, data_2)),
+            tail,
+        } = P4(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Alternate Protocol",
+                size: 16,
+            })?;
+        let capabilities: AlternateProtocolCapabilities = From::<u32>::from(capabilities);
+        let selective_enable_mask = if capabilities.alternate_protocol_selective_enable_supported {
+            let Seq { head, .. } =
+                tail.le_bytes_try_into()
+                    .map_err(|_| ExtendedCapabilityDataError {
+                        name: "Alternate Protocol",
+                        size: 20,
+                    })?;
+            Some(head)
+        } else {
+            None
+        };
+        Ok(Self {
+            capabilities,
+            control: From::<u32>::from(control),
+            data: From::<[u32; 2]>::from([data_1, data_2]),
+            selective_enable_mask,
+        })
+    }
+}
+
+/// Alternate Protocol Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                    // Extended Capability Header
+    Attr::new(0x04, 4).rsvdp(0xffff_fe00), // Alternate Protocol Capabilities
+    Attr::new(0x08, 4).rw(0x0000_00ff).rsvdp(0xffff_ff00), // Alternate Protocol Control
+    Attr::new(0x0c, 4).rsvdp(0x0000_0018), // Alternate Protocol Data 1
+    Attr::new(0x10, 4).rsvdp(0xff00_0000), // Alternate Protocol Data 2
+    Attr::new(0x14, 4).rw(0xffff_ffff),    // Alternate Protocol Selective Enable Mask
+];
+
+/// Alternate Protocol Capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlternateProtocolCapabilities {
+    /// Alternate Protocol Count. Number of entries.
+    pub alternate_protocol_count: u8,
+    /// Alternate Protocol Selective Enable Supported
+    pub alternate_protocol_selective_enable_supported: bool,
+}
+
+impl From<u32> for AlternateProtocolCapabilities {
+    fn from(dword: u32) -> Self {
+        let Lsb((alternate_protocol_count, alternate_protocol_selective_enable_supported, ())) =
+            P3::<_, 8, 1, 23>(dword).into();
+        Self {
+            alternate_protocol_count,
+            alternate_protocol_selective_enable_supported,
+        }
+    }
+}
+
+/// Alternate Protocol Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlternateProtocolControl {
+    /// Alternate Protocol Index Select. Entry reported by Data 1 and Data 2 registers.
+    pub alternate_protocol_index_select: u8,
+}
+
+impl From<u32> for AlternateProtocolControl {
+    fn from(dword: u32) -> Self {
+        let Lsb((alternate_protocol_index_select, ())) = P2::<_, 8, 24>(dword).into();
+        Self {
+            alternate_protocol_index_select,
+        }
+    }
+}
+
+impl From<AlternateProtocolControl> for u32 {
+    fn from(data: AlternateProtocolControl) -> Self {
+        data.alternate_protocol_index_select as u32
+    }
+}
+
+impl Register for AlternateProtocolControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_ff00;
+    const RW1C: u32 = 0;
+}
+
+/// Alternate Protocol Data 1 and Data 2 registers of an entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlternateProtocolData {
+    /// Alternate Protocol Usage Information
+    pub usage_information: u8,
+    /// Alternate Protocol Details
+    pub details: u16,
+    /// Alternate Protocol Vendor ID. Vendor ID of the entity that defined the protocol.
+    pub vendor_id: u16,
+    /// Modified TS Information 2 exchanged to negotiate the protocol
+    pub modified_ts_information_2: u32,
+}
+
+impl AlternateProtocolData {
+    /// Protocol is defined by the CXL Consortium
+    pub fn is_compute_express_link(&self) -> bool {
+        self.vendor_id == 0x1e98
+    }
+}
+
+impl From<[u32; 2]> for AlternateProtocolData {
+    fn from([data_1, data_2]: [u32; 2]) -> Self {
+        let Lsb((usage_information, (), details, vendor_id)) = P4::<_, 3, 2, 11, 16>(data_1).into();
+        let Lsb((modified_ts_information_2, ())) = P2::<_, 24, 8>(data_2).into();
+        Self {
+            usage_information,
+            details,
+            vendor_id,
+            modified_ts_information_2,
+        }
+    }
+}
+
+/// Alternate Protocol entries reader
+///
+/// Entry is read by writing its `index` to Alternate Protocol Index Select and reading
+/// Alternate Protocol Data 1 and Data 2 registers of the [AlternateProtocol] capability.
+pub trait AlternateProtocolAccess {
+    type Error;
+
+    /// Read Alternate Protocol Data 1 and Data 2 registers of entry `index`
+    fn read_entry(&self, index: u8) -> Result<[u32; 2], Self::Error>;
+}
+
+impl<A: AlternateProtocolAccess + ?Sized> AlternateProtocolAccess for &A {
+    type Error = A::Error;
+
+    fn read_entry(&self, index: u8) -> Result<[u32; 2], Self::Error> {
+        (**self).read_entry(index)
+    }
+}
+
+/// Recorded Data 1 and Data 2 registers indexed by Alternate Protocol Index Select value
+impl AlternateProtocolAccess for [[u32; 2]] {
+    type Error = AlternateProtocolOutOfBoundsError;
+
+    fn read_entry(&self, index: u8) -> Result<[u32; 2], Self::Error> {
+        self.get(index as usize)
+            .copied()
+            .context(AlternateProtocolOutOfBoundsSnafu { index })
+    }
+}
+
+/// Alternate Protocol capability at configuration space `offset` accessed through a
+/// [ConfigWrite] accessor
+///
+/// Entry is selected by writing its index to Alternate Protocol Control, reserved bits of the
+/// register are preserved. Data 1 and Data 2 registers are read after the write.
+#[derive(Debug, Clone)]
+pub struct AlternateProtocolSelector<A> {
+    access: A,
+    offset: u16,
+}
+
+impl<A: ConfigWrite> AlternateProtocolSelector<A> {
+    const CONTROL: u16 = 0x08;
+    const DATA_1: u16 = 0x0c;
+    const DATA_2: u16 = 0x10;
+
+    pub fn new(access: A, offset: u16) -> Self {
+        Self { access, offset }
+    }
+}
+
+impl<A: ConfigWrite> AlternateProtocolAccess for AlternateProtocolSelector<A> {
+    type Error = A::Error;
+
+    fn read_entry(&self, index: u8) -> Result<[u32; 2], Self::Error> {
+        let current = self.access.read_u32(self.offset + Self::CONTROL)?;
+        let control = AlternateProtocolControl {
+            alternate_protocol_index_select: index,
+        };
+        self.access
+            .write_u32(self.offset + Self::CONTROL, control.write_value(current))?;
+        let data_1 = self.access.read_u32(self.offset + Self::DATA_1)?;
+        let data_2 = self.access.read_u32(self.offset + Self::DATA_2)?;
+        Ok([data_1, data_2])
+    }
+}
+
+/// Alternate Protocol entry lies outside of recorded registers
+#[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[snafu(display("Alternate Protocol entry {index} is out of recorded registers"))]
+pub struct AlternateProtocolOutOfBoundsError {
+    pub index: u8,
+}
+
+/// An iterator through [Alternate Protocol entries](AlternateProtocolEntry)
+///
+/// Iteration stops after the first error or the last entry.
+#[derive(Debug)]
+pub struct AlternateProtocols<'a, A: ?Sized> {
+    access: &'a A,
+    index: u8,
+    count: u8,
+}
+
+impl<'a, A: AlternateProtocolAccess + ?Sized> Iterator for AlternateProtocols<'a, A> {
+    type Item = Result<AlternateProtocolEntry, A::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
+        }
+        let index = self.index;
+        self.index += 1;
+        let entry = self
+            .access
+            .read_entry(index)
+            .map(|data| AlternateProtocolEntry {
+                index,
+                data: data.into(),
+            });
+        if entry.is_err() {
+            self.index = self.count;
+        }
+        Some(entry)
+    }
+}
+
+/// Alternate Protocol entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlternateProtocolEntry {
+    /// Alternate Protocol Index Select value
+    pub index: u8,
+    pub data: AlternateProtocolData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::ConfigAccess;
+    use core::cell::{Cell, RefCell};
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[test]
+    fn selective_enable_mask() {
+        let data = [
+            0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x98, 0x1e, 0x00, 0x00,
+            0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        ];
+        let ap: AlternateProtocol = data.as_slice().try_into().unwrap();
+        assert_eq!(Some(1), ap.selective_enable_mask);
+        assert_eq!(6, ap.registers().iter().count());
+        assert_eq!(
+            Err(ExtendedCapabilityDataError {
+                name: "Alternate Protocol",
+                size: 20
+            }),
+            AlternateProtocol::try_from(&data[..16])
+        );
+    }
+
+    #[test]
+    fn walker_stops_on_error() {
+        let data = [0x03, 0x00, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let ap: AlternateProtocol = data.as_slice().try_into().unwrap();
+        let image = [[0x1e98_0000, 0]];
+        let result = ap.protocols(image.as_slice()).collect::<Vec<_>>();
+        assert_eq!(2, result.len());
+        assert!(result[0].is_ok());
+        assert_eq!(
+            Err(AlternateProtocolOutOfBoundsError { index: 1 }),
+            result[1]
+        );
+    }
+
+    const BASE: u16 = 0x180;
+
+    /// In-memory Port with two Alternate Protocol entries
+    struct Port {
+        control: Cell<u32>,
+        writes: RefCell<Vec<u32>>,
+    }
+
+    impl ConfigAccess for Port {
+        type Error = ();
+
+        fn read_u32(&self, offset: u16) -> Result<u32, ()> {
+            const ENTRIES: [[u32; 2]; 2] = [[0x1e98_0021, 0], [0x8086_0041, 0x0012_3456]];
+            let entry = ENTRIES.get((self.control.get() & 0xff) as usize);
+            match offset - BASE {
+                0x04 => Ok(0x0000_0002),
+                0x08 => Ok(self.control.get()),
+                0x0c => entry.map(|[data_1, _]| *data_1).ok_or(()),
+                0x10 => entry.map(|[_, data_2]| *data_2).ok_or(()),
+                _ => Ok(0),
+            }
+        }
+    }
+
+    impl ConfigWrite for Port {
+        fn write_u32(&self, offset: u16, value: u32) -> Result<(), ()> {
+            if offset == BASE + 0x08 {
+                self.writes.borrow_mut().push(value);
+                self.control.set(value);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn selector() {
+        // Reserved bits of Alternate Protocol Control are set
+        let port = Port {
+            control: Cell::new(0xabcd_ef00),
+            writes: Default::default(),
+        };
+        let data = [0x02, 0x00, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let ap: AlternateProtocol = data.as_slice().try_into().unwrap();
+        let selector = AlternateProtocolSelector::new(&port, BASE);
+        let result = ap
+            .protocols(&selector)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(result[0].data.is_compute_express_link());
+        assert_eq!(
+            AlternateProtocolEntry {
+                index: 1,
+                data: AlternateProtocolData {
+                    usage_information: 1,
+                    details: 2,
+                    vendor_id: 0x8086,
+                    modified_ts_information_2: 0x12_3456,
+                },
+            },
+            result[1]
+        );
+        assert_eq!(vec![0xabcd_ef00, 0xabcd_ef01], *port.writes.borrow());
+        // Index Select is not checked by the Port
+        assert_eq!(Err(()), selector.read_entry(2));
+    }
+}
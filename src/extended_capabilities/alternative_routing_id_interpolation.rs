@@ -44,8 +44,10 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P4, P5};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AlternativeRoutingIdInterpretation {
     pub ari_capability: AriCapability,
     pub ari_control: AriControl,
@@ -70,8 +72,16 @@ impl TryFrom<&[u8]> for AlternativeRoutingIdInterpretation {
     }
 }
 
+/// ARI Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 2),                          // ARI Capability
+    Attr::new(0x06, 2).rw(0x0073).rsvdp(0xff8c), // ARI Control
+];
+
 /// ARI Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AriCapability {
     /// MFVC Function Groups Capability (M)
     pub mfvc_function_groups_capability: bool,
@@ -99,6 +109,7 @@ impl From<u16> for AriCapability {
 
 /// ARI Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AriControl {
     /// MFVC Function Groups Enable (M)
     pub mfvc_function_groups_enable: bool,
@@ -119,3 +130,17 @@ impl From<u16> for AriControl {
         }
     }
 }
+
+impl From<AriControl> for u16 {
+    fn from(data: AriControl) -> Self {
+        (data.mfvc_function_groups_enable as u16)
+            | (data.acs_function_groups_enable as u16) << 1
+            | (data.function_group as u16 & 0b111) << 4
+    }
+}
+
+impl Register for AriControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xff8c;
+    const RW1C: u16 = 0;
+}
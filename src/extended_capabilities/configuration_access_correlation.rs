@@ -31,9 +31,11 @@ assert_eq!(sample, result);
 use heterob::{endianness::Le, Seq, P2};
 
 use super::{ExtendedCapabilityDataError, ExtendedCapabilityHeaderPlaceholder};
+use crate::register::RegisterAttributes as Attr;
 
 /// Configuration Access Correlation
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigurationAccessCorrelation {
     /// Device Correlation
     ///
@@ -65,3 +67,9 @@ impl TryFrom<&[u8]> for ConfigurationAccessCorrelation {
         Ok(From::<[u8; Self::SIZE]>::from(head))
     }
 }
+
+/// Configuration Access Correlation Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // Device Correlation
+];
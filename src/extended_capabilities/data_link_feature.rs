@@ -0,0 +1,170 @@
+/*!
+# Data Link Feature
+
+The Data Link Feature Capability is an optional Extended Capability that is required for
+Downstream Ports and Functions associated with an Upstream Port that support 16.0 GT/s or higher
+data rates. Ports exchange supported features with Data Link Feature DLLPs, the only feature
+defined is Scaled Flow Control.
+
+## Struct diagram
+[DataLinkFeature]
+- [DataLinkFeatureCapabilities]
+- [DataLinkFeatureStatus]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::data_link_feature::*;
+let data = [
+    0x25, 0x00, 0x01, 0x00, // Extended Capability Header
+    0x01, 0x00, 0x00, 0x80, // Data Link Feature Capabilities
+    0x03, 0x00, 0x00, 0x80, // Data Link Feature Status
+];
+let result: DataLinkFeature = data[4..].try_into().unwrap();
+let sample = DataLinkFeature {
+    capabilities: DataLinkFeatureCapabilities {
+        local_data_link_feature_supported: 0x000001,
+        data_link_feature_exchange_enable: true,
+    },
+    status: DataLinkFeatureStatus {
+        remote_data_link_feature_supported: 0x000003,
+        remote_data_link_feature_supported_valid: true,
+    },
+};
+assert_eq!(sample, result);
+assert!(result.status.remote_scaled_flow_control_supported());
+assert!(result.is_scaled_flow_control_active());
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3};
+
+use super::ExtendedCapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
+
+/// Data Link Feature
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataLinkFeature {
+    pub capabilities: DataLinkFeatureCapabilities,
+    pub status: DataLinkFeatureStatus,
+}
+impl DataLinkFeature {
+    /// Scaled Flow Control is used on the Link: both Ports support it and the Remote Data Link
+    /// Feature Supported field was received from the Link partner
+    pub fn is_scaled_flow_control_active(&self) -> bool {
+        self.capabilities.data_link_feature_exchange_enable
+            && self.capabilities.local_scaled_flow_control_supported()
+            && self.status.remote_data_link_feature_supported_valid
+            && self.status.remote_scaled_flow_control_supported()
+    }
+}
+impl TryFrom<&[u8]> for DataLinkFeature {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capabilities, status)),
+            ..
+        } = P2(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Data Link Feature",
+                size: 8,
+            })?;
+        Ok(Self {
+            capabilities: From::<u32>::from(capabilities),
+            status: From::<u32>::from(status),
+        })
+    }
+}
+
+/// Data Link Feature Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0x8000_0001).rsvdp(0x7fff_fffe), // DLF Capabilities
+    Attr::new(0x08, 4).rsvdz(0x7f80_0000), // DLF Status
+];
+
+/// Data Link Feature Capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataLinkFeatureCapabilities {
+    /// Local Data Link Feature Supported. Feature Supported value sent in Data Link Feature
+    /// DLLPs, bits other than Scaled Flow Control are reserved for future features.
+    pub local_data_link_feature_supported: u32,
+    /// Data Link Feature Exchange Enable. Port sends Data Link Feature DLLPs during Link
+    /// initialization.
+    pub data_link_feature_exchange_enable: bool,
+}
+impl DataLinkFeatureCapabilities {
+    /// Local Scaled Flow Control Supported. Bit 0 of Local Data Link Feature Supported field.
+    pub fn local_scaled_flow_control_supported(&self) -> bool {
+        self.local_data_link_feature_supported & 1 != 0
+    }
+}
+impl From<u32> for DataLinkFeatureCapabilities {
+    fn from(dword: u32) -> Self {
+        let Lsb((local_data_link_feature_supported, (), data_link_feature_exchange_enable)) =
+            P3::<_, 23, 8, 1>(dword).into();
+        Self {
+            local_data_link_feature_supported,
+            data_link_feature_exchange_enable,
+        }
+    }
+}
+
+/// Data Link Feature Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataLinkFeatureStatus {
+    /// Remote Data Link Feature Supported. Feature Supported value received in Data Link
+    /// Feature DLLP from the Link partner.
+    pub remote_data_link_feature_supported: u32,
+    /// Remote Data Link Feature Supported Valid. Data Link Feature DLLP was received from the
+    /// Link partner.
+    pub remote_data_link_feature_supported_valid: bool,
+}
+impl DataLinkFeatureStatus {
+    /// Remote Scaled Flow Control Supported. Bit 0 of Remote Data Link Feature Supported field.
+    pub fn remote_scaled_flow_control_supported(&self) -> bool {
+        self.remote_data_link_feature_supported & 1 != 0
+    }
+}
+impl From<u32> for DataLinkFeatureStatus {
+    fn from(dword: u32) -> Self {
+        let Lsb((remote_data_link_feature_supported, (), remote_data_link_feature_supported_valid)) =
+            P3::<_, 23, 8, 1>(dword).into();
+        Self {
+            remote_data_link_feature_supported,
+            remote_data_link_feature_supported_valid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn scaled_flow_control_active() {
+        let dlf = |capabilities: u32, status: u32| {
+            let mut data = [0; 8];
+            data[..4].copy_from_slice(&capabilities.to_le_bytes());
+            data[4..].copy_from_slice(&status.to_le_bytes());
+            DataLinkFeature::try_from(data.as_slice()).unwrap()
+        };
+        assert!(dlf(0x8000_0001, 0x8000_0001).is_scaled_flow_control_active());
+        // Feature exchange is disabled
+        assert!(!dlf(0x0000_0001, 0x8000_0001).is_scaled_flow_control_active());
+        // Local Port does not support Scaled Flow Control
+        assert!(!dlf(0x8000_0000, 0x8000_0001).is_scaled_flow_control_active());
+        // No Data Link Feature DLLP was received yet
+        assert!(!dlf(0x8000_0001, 0x0000_0001).is_scaled_flow_control_active());
+        // Link partner supports future features only
+        let result = dlf(0x8000_0001, 0x807f_fffe);
+        assert!(!result.is_scaled_flow_control_active());
+        assert_eq!(0x7f_fffe, result.status.remote_data_link_feature_supported);
+    }
+}
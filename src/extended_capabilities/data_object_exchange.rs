@@ -0,0 +1,794 @@
+/*!
+# Data Object Exchange (DOE)
+
+Data Object Exchange is a mailbox based mechanism for exchanging data objects (e.g., CMA/SPDM
+or CXL CDAT) between system software and a Function. Request is written DWORD by DWORD to the
+DOE Write Data Mailbox and started with DOE Go, response is read DWORD by DWORD from the DOE
+Read Data Mailbox.
+
+[DoeMailbox] drives the mailbox through a [ConfigWrite] accessor.
+
+## Struct diagram
+[DataObjectExchange]
+- [DoeCapabilities]
+- [DoeControl]
+- [DoeStatus]
+
+[DoeMailbox]
+- [DataObjectHeader]
+  - [DataObjectProtocol]
+- [DiscoveryResponse]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::data_object_exchange::*;
+let data = [
+    /* 00h */ 0x2e, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x07, 0x00, 0x00, 0x00, // DOE Capabilities
+    /* 08h */ 0x02, 0x00, 0x00, 0x00, // DOE Control
+    /* 0Ch */ 0x00, 0x00, 0x00, 0x80, // DOE Status
+    /* 10h */ 0x00, 0x00, 0x00, 0x00, // DOE Write Data Mailbox
+    /* 14h */ 0x01, 0x00, 0x00, 0x00, // DOE Read Data Mailbox
+];
+let result: DataObjectExchange = data[4..].try_into().unwrap();
+let sample = DataObjectExchange {
+    capabilities: DoeCapabilities {
+        doe_interrupt_support: true,
+        doe_interrupt_message_number: 3,
+    },
+    control: DoeControl {
+        doe_abort: false,
+        doe_interrupt_enable: true,
+        doe_go: false,
+    },
+    status: DoeStatus {
+        doe_busy: false,
+        doe_interrupt_status: false,
+        doe_error: false,
+        data_object_ready: true,
+    },
+    doe_write_data_mailbox: 0,
+    doe_read_data_mailbox: 1,
+};
+assert_eq!(sample, result);
+```
+
+Listing supported data object protocols of a Function through [DOE
+Discovery](DataObjectProtocol::DISCOVERY):
+
+```rust
+# use pcics::{access::{ConfigAccess, ConfigWrite}, extended_capabilities::data_object_exchange::*};
+# use std::{cell::RefCell, collections::VecDeque};
+/// DOE instance at offset 150h supporting Discovery and CMA/SPDM
+#[derive(Default)]
+struct Device {
+    request: RefCell<Vec<u32>>,
+    response: RefCell<VecDeque<u32>>,
+}
+impl ConfigAccess for Device {
+    type Error = ();
+    fn read_u32(&self, offset: u16) -> Result<u32, ()> {
+        match offset {
+            // Data Object Ready
+            0x15c if !self.response.borrow().is_empty() => Ok(1 << 31),
+            0x164 => Ok(self.response.borrow().front().copied().unwrap_or(0)),
+            _ => Ok(0),
+        }
+    }
+}
+impl ConfigWrite for Device {
+    fn write_u32(&self, offset: u16, value: u32) -> Result<(), ()> {
+        match offset {
+            0x160 => self.request.borrow_mut().push(value),
+            // DOE Go: answer Discovery request
+            0x158 if value & (1 << 31) != 0 => {
+                let index = self.request.take()[2];
+                let entries = [0x0000_0001, 0x0001_0001];
+                let next = (index + 1) % entries.len() as u32;
+                *self.response.borrow_mut() =
+                    [0x0000_0001, 3, entries[index as usize] | next << 24].into();
+            }
+            0x164 => drop(self.response.borrow_mut().pop_front()),
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+let device = Device::default();
+let mailbox = DoeMailbox::new(&device, 0x150);
+let protocols = mailbox.protocols(16).collect::<Result<Vec<_>, _>>().unwrap();
+assert_eq!(
+    vec![
+        DataObjectProtocol::DISCOVERY,
+        DataObjectProtocol { vendor_id: 0x0001, data_object_type: 0x01 },
+    ],
+    protocols
+);
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3, P4, P5};
+use snafu::prelude::*;
+
+use super::ExtendedCapabilityDataError;
+use crate::access::ConfigWrite;
+use crate::register::{Register, RegisterAttributes as Attr};
+
+/// Data Object Exchange
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataObjectExchange {
+    pub capabilities: DoeCapabilities,
+    pub control: DoeControl,
+    pub status: DoeStatus,
+    /// DOE Write Data Mailbox. Next request DWORD.
+    pub doe_write_data_mailbox: u32,
+    /// DOE Read Data Mailbox. Current response DWORD.
+    pub doe_read_data_mailbox: u32,
+}
+impl TryFrom<&[u8]> for DataObjectExchange {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capabilities, control, status, doe_write_data_mailbox, doe_read_data_mailbox)),
+            ..
+        } = P5(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Data Object Exchange",
+                size: 20,
+            })?;
+        Ok(Self {
+            capabilities: From::<u32>::from(capabilities),
+            control: From::<u32>::from(control),
+            status: From::<u32>::from(status),
+            doe_write_data_mailbox,
+            doe_read_data_mailbox,
+        })
+    }
+}
+
+/// Data Object Exchange Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                    // Extended Capability Header
+    Attr::new(0x04, 4).rsvdp(0xffff_f000), // DOE Capabilities
+    Attr::new(0x08, 4).rw(0x8000_0003).rsvdp(0x7fff_fffc), // DOE Control
+    Attr::new(0x0c, 4).rw1c(0x0000_0002).rsvdz(0x7fff_fff8), // DOE Status
+    Attr::new(0x10, 4).rw(0xffff_ffff),    // DOE Write Data Mailbox
+    Attr::new(0x14, 4).rw(0xffff_ffff),    // DOE Read Data Mailbox
+];
+
+/// DOE Capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoeCapabilities {
+    /// DOE Interrupt Support. MSI/MSI-X interrupt is supported.
+    pub doe_interrupt_support: bool,
+    /// DOE Interrupt Message Number. MSI/MSI-X vector used for the DOE interrupt.
+    pub doe_interrupt_message_number: u16,
+}
+
+impl From<u32> for DoeCapabilities {
+    fn from(dword: u32) -> Self {
+        let Lsb((doe_interrupt_support, doe_interrupt_message_number, ())) =
+            P3::<_, 1, 11, 20>(dword).into();
+        Self {
+            doe_interrupt_support,
+            doe_interrupt_message_number,
+        }
+    }
+}
+
+/// DOE Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoeControl {
+    /// DOE Abort. Aborts current data object transfer and clears DOE Error. Always reads 0.
+    pub doe_abort: bool,
+    /// DOE Interrupt Enable
+    pub doe_interrupt_enable: bool,
+    /// DOE Go. Starts processing of the data object in the Write Data Mailbox. Always reads 0.
+    pub doe_go: bool,
+}
+
+impl From<u32> for DoeControl {
+    fn from(dword: u32) -> Self {
+        let Lsb((doe_abort, doe_interrupt_enable, (), doe_go)) = P4::<_, 1, 1, 29, 1>(dword).into();
+        Self {
+            doe_abort,
+            doe_interrupt_enable,
+            doe_go,
+        }
+    }
+}
+
+impl From<DoeControl> for u32 {
+    fn from(data: DoeControl) -> Self {
+        (data.doe_abort as u32)
+            | (data.doe_interrupt_enable as u32) << 1
+            | (data.doe_go as u32) << 31
+    }
+}
+
+impl Register for DoeControl {
+    type Value = u32;
+    const RSVDP: u32 = 0x7fff_fffc;
+    const RW1C: u32 = 0;
+}
+
+/// DOE Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoeStatus {
+    /// DOE Busy. DOE instance is temporarily unable to receive a new data object.
+    pub doe_busy: bool,
+    /// DOE Interrupt Status
+    pub doe_interrupt_status: bool,
+    /// DOE Error. Error occurred during processing of the data object, cleared by DOE Abort.
+    pub doe_error: bool,
+    /// Data Object Ready. Response data object is available in the Read Data Mailbox.
+    pub data_object_ready: bool,
+}
+
+impl From<u32> for DoeStatus {
+    fn from(dword: u32) -> Self {
+        let Lsb((doe_busy, doe_interrupt_status, doe_error, (), data_object_ready)) =
+            P5::<_, 1, 1, 1, 28, 1>(dword).into();
+        Self {
+            doe_busy,
+            doe_interrupt_status,
+            doe_error,
+            data_object_ready,
+        }
+    }
+}
+
+impl From<DoeStatus> for u32 {
+    fn from(data: DoeStatus) -> Self {
+        (data.doe_busy as u32)
+            | (data.doe_interrupt_status as u32) << 1
+            | (data.doe_error as u32) << 2
+            | (data.data_object_ready as u32) << 31
+    }
+}
+
+impl Register for DoeStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0000_0002;
+}
+
+/// Data object protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataObjectProtocol {
+    /// Vendor ID of the entity that defined the data object type
+    pub vendor_id: u16,
+    pub data_object_type: u8,
+}
+
+impl DataObjectProtocol {
+    /// DOE Discovery defined by PCI-SIG
+    pub const DISCOVERY: Self = Self {
+        vendor_id: 0x0001,
+        data_object_type: 0x00,
+    };
+}
+
+/// Data Object Header 1 and Header 2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataObjectHeader {
+    pub protocol: DataObjectProtocol,
+    /// Length of the data object in DWORDs including both headers
+    pub length: usize,
+}
+
+impl DataObjectHeader {
+    /// Header 1 and Header 2 DWORDs
+    pub const SIZE: usize = 2;
+    /// Maximal data object length in DWORDs (256K), encoded as 0
+    pub const MAX_LENGTH: usize = 1 << 18;
+
+    /// Length of the data object in DWORDs without headers
+    pub fn payload_length(&self) -> usize {
+        self.length.saturating_sub(Self::SIZE)
+    }
+}
+
+impl From<[u32; 2]> for DataObjectHeader {
+    fn from([header_1, header_2]: [u32; 2]) -> Self {
+        let Lsb((vendor_id, data_object_type, ())) = P3::<_, 16, 8, 8>(header_1).into();
+        let Lsb((length, ())): Lsb<(u32, ())> = P2::<_, 18, 14>(header_2).into();
+        let length = match length as usize {
+            0 => Self::MAX_LENGTH,
+            n => n,
+        };
+        Self {
+            protocol: DataObjectProtocol {
+                vendor_id,
+                data_object_type,
+            },
+            length,
+        }
+    }
+}
+
+impl From<DataObjectHeader> for [u32; 2] {
+    fn from(data: DataObjectHeader) -> Self {
+        [
+            data.protocol.vendor_id as u32 | (data.protocol.data_object_type as u32) << 16,
+            (data.length % DataObjectHeader::MAX_LENGTH) as u32,
+        ]
+    }
+}
+
+/// DOE Discovery response payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscoveryResponse {
+    /// Protocol at the requested index
+    pub protocol: DataObjectProtocol,
+    /// Index of the next entry, 0 if the requested entry is the last one
+    pub next_index: u8,
+}
+
+impl From<u32> for DiscoveryResponse {
+    fn from(dword: u32) -> Self {
+        let Lsb((vendor_id, data_object_type, next_index)) = P3::<_, 16, 8, 8>(dword).into();
+        Self {
+            protocol: DataObjectProtocol {
+                vendor_id,
+                data_object_type,
+            },
+            next_index,
+        }
+    }
+}
+
+/// DOE mailbox error
+#[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DoeError<E> {
+    #[snafu(display("configuration space access failed"))]
+    Access { error: E },
+    #[snafu(display("DOE instance is busy"))]
+    Busy,
+    #[snafu(display("DOE instance reported an error, DOE Abort is required"))]
+    InstanceError,
+    #[snafu(display("data object length {length} is out of 2..=262144 DWORDs"))]
+    DataObjectLength { length: usize },
+    #[snafu(display("response data object is not ready"))]
+    NotReady,
+    #[snafu(display("response data object ({length} DWORDs) does not fit buffer"))]
+    BufferTooSmall { length: usize },
+    #[snafu(display("no response after {polls} status polls"))]
+    Timeout { polls: usize },
+    #[snafu(display("unexpected response data object {vendor_id:04x}:{data_object_type:02x}"))]
+    UnexpectedResponse {
+        vendor_id: u16,
+        data_object_type: u8,
+    },
+}
+
+/// DOE mailbox driver
+///
+/// Drives the DOE instance at configuration space `offset` (offset of the Extended Capability
+/// Header) through a [ConfigWrite] accessor. Request is sent by [send](Self::send), completion
+/// is polled by [poll](Self::poll) and response is read by [receive](Self::receive).
+/// [exchange](Self::exchange) does all three steps. Methods waiting for the DOE instance take
+/// the maximal number of DOE Status reads `polls` instead of a timeout, so the caller decides
+/// how long a poll is.
+///
+/// After [DoeError::InstanceError] or [DoeError::Timeout] the instance should be reset by
+/// [abort](Self::abort).
+#[derive(Debug, Clone)]
+pub struct DoeMailbox<A> {
+    access: A,
+    offset: u16,
+}
+
+impl<A: ConfigWrite> DoeMailbox<A> {
+    const CONTROL: u16 = 0x08;
+    const STATUS: u16 = 0x0c;
+    const WRITE_DATA_MAILBOX: u16 = 0x10;
+    const READ_DATA_MAILBOX: u16 = 0x14;
+
+    pub fn new(access: A, offset: u16) -> Self {
+        Self { access, offset }
+    }
+    fn read(&self, register: u16) -> Result<u32, DoeError<A::Error>> {
+        self.access
+            .read_u32(self.offset + register)
+            .map_err(|error| DoeError::Access { error })
+    }
+    fn write(&self, register: u16, value: u32) -> Result<(), DoeError<A::Error>> {
+        self.access
+            .write_u32(self.offset + register, value)
+            .map_err(|error| DoeError::Access { error })
+    }
+    fn write_control(&self, abort: bool, go: bool) -> Result<(), DoeError<A::Error>> {
+        let current = self.read(Self::CONTROL)?;
+        let control = DoeControl {
+            doe_abort: abort,
+            doe_go: go,
+            ..From::<u32>::from(current)
+        };
+        self.write(Self::CONTROL, control.write_value(current))
+    }
+    /// Current DOE Status
+    pub fn status(&self) -> Result<DoeStatus, DoeError<A::Error>> {
+        self.read(Self::STATUS).map(From::<u32>::from)
+    }
+    /// Write request data object and set DOE Go
+    pub fn send(
+        &self,
+        protocol: DataObjectProtocol,
+        payload: &[u32],
+    ) -> Result<(), DoeError<A::Error>> {
+        let length = payload.len() + DataObjectHeader::SIZE;
+        ensure!(
+            length <= DataObjectHeader::MAX_LENGTH,
+            DataObjectLengthSnafu { length }
+        );
+        let status = self.status()?;
+        ensure!(!status.doe_busy, BusySnafu);
+        ensure!(!status.doe_error, InstanceSnafu);
+        let header: [u32; 2] = DataObjectHeader { protocol, length }.into();
+        for dword in header.into_iter().chain(payload.iter().copied()) {
+            self.write(Self::WRITE_DATA_MAILBOX, dword)?;
+        }
+        self.write_control(false, true)
+    }
+    /// Response data object is ready
+    pub fn poll(&self) -> Result<bool, DoeError<A::Error>> {
+        let status = self.status()?;
+        ensure!(!status.doe_error, InstanceSnafu);
+        Ok(status.data_object_ready)
+    }
+    /// Read response data object and store its payload at the beginning of `payload`
+    ///
+    /// Response that does not fit `payload` is read out of the mailbox anyway and
+    /// [DoeError::BufferTooSmall] is returned.
+    pub fn receive(&self, payload: &mut [u32]) -> Result<DataObjectHeader, DoeError<A::Error>> {
+        ensure!(self.poll()?, NotReadySnafu);
+        let mut header = [0; DataObjectHeader::SIZE];
+        for dword in header.iter_mut() {
+            *dword = self.read_dword()?;
+        }
+        let header = DataObjectHeader::from(header);
+        let length = header.length;
+        ensure!(
+            length >= DataObjectHeader::SIZE,
+            DataObjectLengthSnafu { length }
+        );
+        for index in 0..header.payload_length() {
+            let dword = self.read_dword()?;
+            if let Some(slot) = payload.get_mut(index) {
+                *slot = dword;
+            }
+        }
+        ensure!(!self.status()?.doe_error, InstanceSnafu);
+        ensure!(
+            header.payload_length() <= payload.len(),
+            BufferTooSmallSnafu { length }
+        );
+        Ok(header)
+    }
+    /// Read current Read Data Mailbox DWORD and move to the next one
+    fn read_dword(&self) -> Result<u32, DoeError<A::Error>> {
+        let dword = self.read(Self::READ_DATA_MAILBOX)?;
+        self.write(Self::READ_DATA_MAILBOX, 0)?;
+        Ok(dword)
+    }
+    /// Send request, wait for the response up to `polls` DOE Status reads and receive it
+    pub fn exchange(
+        &self,
+        protocol: DataObjectProtocol,
+        request: &[u32],
+        response: &mut [u32],
+        polls: usize,
+    ) -> Result<DataObjectHeader, DoeError<A::Error>> {
+        self.send(protocol, request)?;
+        for _ in 0..polls {
+            if self.poll()? {
+                return self.receive(response);
+            }
+        }
+        TimeoutSnafu { polls }.fail()
+    }
+    /// Set DOE Abort and wait up to `polls` DOE Status reads for DOE Busy and DOE Error to clear
+    pub fn abort(&self, polls: usize) -> Result<(), DoeError<A::Error>> {
+        self.write_control(true, false)?;
+        for _ in 0..polls {
+            let status = self.status()?;
+            if !status.doe_busy && !status.doe_error {
+                return Ok(());
+            }
+        }
+        TimeoutSnafu { polls }.fail()
+    }
+    /// DOE Discovery of the protocol at `index`
+    pub fn discovery(
+        &self,
+        index: u8,
+        polls: usize,
+    ) -> Result<DiscoveryResponse, DoeError<A::Error>> {
+        let mut response = [0];
+        let DataObjectHeader { protocol, length } = self.exchange(
+            DataObjectProtocol::DISCOVERY,
+            &[index as u32],
+            &mut response,
+            polls,
+        )?;
+        ensure!(
+            protocol == DataObjectProtocol::DISCOVERY,
+            UnexpectedResponseSnafu {
+                vendor_id: protocol.vendor_id,
+                data_object_type: protocol.data_object_type,
+            }
+        );
+        ensure!(length == 3, DataObjectLengthSnafu { length });
+        Ok(response[0].into())
+    }
+    /// Iterate over all protocols supported by the DOE instance using DOE Discovery
+    pub fn protocols(&self, polls: usize) -> DiscoveryProtocols<'_, A> {
+        DiscoveryProtocols {
+            mailbox: self,
+            index: Some(0),
+            polls,
+        }
+    }
+}
+
+/// An iterator through [protocols](DataObjectProtocol) supported by the DOE instance
+///
+/// Iteration stops after the first error or the last entry.
+#[derive(Debug, Clone)]
+pub struct DiscoveryProtocols<'a, A> {
+    mailbox: &'a DoeMailbox<A>,
+    index: Option<u8>,
+    polls: usize,
+}
+
+impl<'a, A: ConfigWrite> Iterator for DiscoveryProtocols<'a, A> {
+    type Item = Result<DataObjectProtocol, DoeError<A::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index.take()?;
+        let result = self.mailbox.discovery(index, self.polls);
+        if let Ok(DiscoveryResponse { next_index, .. }) = result {
+            // Next index should grow, otherwise entries would repeat forever
+            self.index = Some(next_index).filter(|&next| next > index);
+        }
+        Some(result.map(|response| response.protocol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::ConfigAccess;
+    use core::cell::RefCell;
+    use pretty_assertions::assert_eq;
+    use std::{collections::VecDeque, prelude::v1::*};
+
+    const BASE: u16 = 0x200;
+
+    enum Step {
+        Respond(Vec<u32>),
+        Fail,
+    }
+
+    /// In-memory DOE instance answering requests with scripted steps
+    #[derive(Default)]
+    struct Responder {
+        /// DOE Status reads reporting DOE Busy after DOE Go
+        delay: usize,
+        state: RefCell<State>,
+    }
+
+    #[derive(Default)]
+    struct State {
+        script: VecDeque<Step>,
+        interrupt_enable: bool,
+        control_writes: Vec<u32>,
+        request: Vec<u32>,
+        requests: Vec<Vec<u32>>,
+        pending: Option<(usize, Option<Vec<u32>>)>,
+        response: VecDeque<u32>,
+        error: bool,
+    }
+
+    impl Responder {
+        fn new(delay: usize, script: impl IntoIterator<Item = Step>) -> Self {
+            let state = State {
+                script: script.into_iter().collect(),
+                ..Default::default()
+            };
+            Self {
+                delay,
+                state: RefCell::new(state),
+            }
+        }
+    }
+
+    impl ConfigAccess for Responder {
+        type Error = ();
+
+        fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+            let mut state = self.state.borrow_mut();
+            match offset.checked_sub(BASE).ok_or(())? {
+                0x08 => Ok((state.interrupt_enable as u32) << 1),
+                0x0c => {
+                    match state.pending.take() {
+                        Some((0, Some(response))) => state.response = response.into(),
+                        Some((0, None)) => state.error = true,
+                        Some((n, step)) => state.pending = Some((n - 1, step)),
+                        None => (),
+                    }
+                    Ok(u32::from(DoeStatus {
+                        doe_busy: state.pending.is_some(),
+                        doe_interrupt_status: false,
+                        doe_error: state.error,
+                        data_object_ready: !state.response.is_empty(),
+                    }))
+                }
+                0x14 => Ok(state.response.front().copied().unwrap_or(0)),
+                0x00..=0x17 => Ok(0),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl ConfigWrite for Responder {
+        fn write_u32(&self, offset: u16, value: u32) -> Result<(), Self::Error> {
+            let mut state = self.state.borrow_mut();
+            match offset.checked_sub(BASE).ok_or(())? {
+                0x08 => {
+                    state.control_writes.push(value);
+                    let control = DoeControl::from(value);
+                    state.interrupt_enable = control.doe_interrupt_enable;
+                    if control.doe_abort {
+                        state.request.clear();
+                        state.pending = None;
+                        state.response.clear();
+                        state.error = false;
+                    }
+                    if control.doe_go {
+                        let request = core::mem::take(&mut state.request);
+                        state.requests.push(request);
+                        let step = match state.script.pop_front() {
+                            Some(Step::Respond(response)) => Some(response),
+                            Some(Step::Fail) | None => None,
+                        };
+                        state.pending = Some((self.delay, step));
+                    }
+                }
+                0x10 => state.request.push(value),
+                0x14 => {
+                    state.response.pop_front();
+                }
+                0x00..=0x17 => (),
+                _ => return Err(()),
+            }
+            Ok(())
+        }
+    }
+
+    fn discovery_response(protocol: u32, next_index: u32) -> Step {
+        Step::Respond(vec![0x0000_0001, 3, protocol | next_index << 24])
+    }
+
+    #[test]
+    fn discovery() {
+        let responder = Responder::new(
+            2,
+            [
+                discovery_response(0x00_0001, 1),
+                discovery_response(0x01_0001, 2),
+                discovery_response(0x02_1e98, 0),
+            ],
+        );
+        responder.state.borrow_mut().interrupt_enable = true;
+        let mailbox = DoeMailbox::new(&responder, BASE);
+        let result = mailbox.protocols(3).collect::<Result<Vec<_>, _>>();
+        let protocols = [(0x0001, 0x00), (0x0001, 0x01), (0x1e98, 0x02)]
+            .map(|(vendor_id, data_object_type)| DataObjectProtocol {
+                vendor_id,
+                data_object_type,
+            })
+            .to_vec();
+        assert_eq!(Ok(protocols), result);
+
+        let state = responder.state.borrow();
+        let requests = (0..3).map(|index| vec![0x0000_0001, 3, index]);
+        assert_eq!(requests.collect::<Vec<_>>(), state.requests);
+        // DOE Interrupt Enable is preserved by DOE Go
+        assert_eq!(vec![0x8000_0002; 3], state.control_writes);
+    }
+
+    #[test]
+    fn error_and_abort() {
+        let responder = Responder::new(0, [Step::Fail, discovery_response(0x00_0001, 0)]);
+        let mailbox = DoeMailbox::new(&responder, BASE);
+        assert_eq!(Err(DoeError::InstanceError), mailbox.discovery(0, 1));
+        assert_eq!(
+            Err(DoeError::InstanceError),
+            mailbox.send(DataObjectProtocol::DISCOVERY, &[0])
+        );
+        assert_eq!(Ok(()), mailbox.abort(1));
+        assert_eq!(
+            Ok(DataObjectProtocol::DISCOVERY),
+            mailbox.discovery(0, 1).map(|response| response.protocol)
+        );
+    }
+
+    #[test]
+    fn busy_and_timeout() {
+        let responder = Responder::new(8, [discovery_response(0x00_0001, 0)]);
+        let mailbox = DoeMailbox::new(&responder, BASE);
+        assert_eq!(Err(DoeError::Timeout { polls: 4 }), mailbox.discovery(0, 4));
+        assert_eq!(Err(DoeError::NotReady), mailbox.receive(&mut []));
+        assert_eq!(
+            Err(DoeError::Busy),
+            mailbox.send(DataObjectProtocol::DISCOVERY, &[0])
+        );
+        assert_eq!(Ok(()), mailbox.abort(1));
+        assert!(!mailbox.status().unwrap().doe_busy);
+
+        // Access errors are passed through
+        let mailbox = DoeMailbox::new(&responder, 0xfff0);
+        assert_eq!(Err(DoeError::Access { error: () }), mailbox.status());
+    }
+
+    #[test]
+    fn data_object_length() {
+        let max = DataObjectHeader {
+            protocol: DataObjectProtocol::DISCOVERY,
+            length: DataObjectHeader::MAX_LENGTH,
+        };
+        assert_eq!([0x0000_0001, 0], <[u32; 2]>::from(max));
+        assert_eq!(max, DataObjectHeader::from([0x0000_0001, 0]));
+
+        let responder = Responder::new(0, [Step::Respond(vec![0x0002_0001, 6, 1, 2, 3, 4])]);
+        let mailbox = DoeMailbox::new(&responder, BASE);
+        let payload = vec![0; DataObjectHeader::MAX_LENGTH - 1];
+        assert_eq!(
+            Err(DoeError::DataObjectLength {
+                length: DataObjectHeader::MAX_LENGTH + 1
+            }),
+            mailbox.send(DataObjectProtocol::DISCOVERY, &payload)
+        );
+        assert!(responder.state.borrow().requests.is_empty());
+
+        // Response is drained even if it does not fit
+        let protocol = DataObjectProtocol {
+            vendor_id: 0x0001,
+            data_object_type: 0x02,
+        };
+        let mut response = [0; 2];
+        assert_eq!(
+            Err(DoeError::BufferTooSmall { length: 6 }),
+            mailbox.exchange(protocol, &[], &mut response, 1)
+        );
+        assert_eq!([1, 2], response);
+        assert!(!mailbox.status().unwrap().data_object_ready);
+    }
+
+    #[test]
+    fn unexpected_discovery_response() {
+        let responder = Responder::new(0, [Step::Respond(vec![0x0001_0001, 3, 0])]);
+        let mailbox = DoeMailbox::new(&responder, BASE);
+        assert_eq!(
+            Err(DoeError::UnexpectedResponse {
+                vendor_id: 0x0001,
+                data_object_type: 0x01
+            }),
+            mailbox.discovery(0, 1)
+        );
+    }
+}
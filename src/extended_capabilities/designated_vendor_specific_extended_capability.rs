@@ -42,9 +42,14 @@ use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P3};
 use snafu::prelude::*;
 
 use super::ExtendedCapabilityHeaderPlaceholder;
+use crate::register::{RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// DVSEC Errors
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DesignatedVendorSpecificExtendedCapabilityError {
     #[snafu(display("mandatory fields are unreadable"))]
     Mandatory,
@@ -74,6 +79,7 @@ pub type DvsecError = DesignatedVendorSpecificExtendedCapabilityError;
 
 /// Designated Vendor-Specific Extended Capability (DVSEC)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DesignatedVendorSpecificExtendedCapability<'a> {
     /// Vendor ID associated with the vendor that defined the contents of this capability
     pub dvsec_vendor_id: u16,
@@ -90,11 +96,41 @@ pub struct DesignatedVendorSpecificExtendedCapability<'a> {
 
 /// Contents of capability depends on Vendor ID
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DvsecType<'a> {
     Unspecified(&'a [u8]),
     ComputeExpressLink(ComputeExpressLink),
 }
 
+/// Owned [DvsecType]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedDvsecType {
+    Unspecified(Vec<u8>),
+    ComputeExpressLink(ComputeExpressLink),
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&DvsecType<'a>> for OwnedDvsecType {
+    fn from(dvsec_type: &DvsecType<'a>) -> Self {
+        match dvsec_type {
+            DvsecType::Unspecified(data) => Self::Unspecified(data.to_vec()),
+            DvsecType::ComputeExpressLink(cxl) => Self::ComputeExpressLink(cxl.clone()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedDvsecType> for DvsecType<'a> {
+    fn from(dvsec_type: &'a OwnedDvsecType) -> Self {
+        match dvsec_type {
+            OwnedDvsecType::Unspecified(data) => Self::Unspecified(data),
+            OwnedDvsecType::ComputeExpressLink(cxl) => Self::ComputeExpressLink(cxl.clone()),
+        }
+    }
+}
+
 /// [DesignatedVendorSpecificExtendedCapability] alias
 pub type Dvsec<'a> = DesignatedVendorSpecificExtendedCapability<'a>;
 
@@ -140,5 +176,68 @@ impl<'a> TryFrom<&'a [u8]> for Dvsec<'a> {
     }
 }
 
+/// Owned [DesignatedVendorSpecificExtendedCapability]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedDesignatedVendorSpecificExtendedCapability {
+    /// Vendor ID associated with the vendor that defined the contents of this capability
+    pub dvsec_vendor_id: u16,
+    /// Vendor-defined version number that indicates the version of the DVSEC structure
+    pub dvsec_revision: u8,
+    /// Indicates the number of bytes in the entire DVSEC structure, including
+    /// the PCI Express Extended Capability header, the DVSEC Header 1, DVSEC Header 2,
+    /// and DVSEC vendor-specific registers
+    pub dvsec_length: u16,
+    /// Vendor-defined ID that indicates the nature and format of the DVSEC structure
+    pub dvsec_id: u16,
+    pub dvsec_type: OwnedDvsecType,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&Dvsec<'a>> for OwnedDesignatedVendorSpecificExtendedCapability {
+    fn from(dvsec: &Dvsec<'a>) -> Self {
+        Self {
+            dvsec_vendor_id: dvsec.dvsec_vendor_id,
+            dvsec_revision: dvsec.dvsec_revision,
+            dvsec_length: dvsec.dvsec_length,
+            dvsec_id: dvsec.dvsec_id,
+            dvsec_type: (&dvsec.dvsec_type).into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedDesignatedVendorSpecificExtendedCapability> for Dvsec<'a> {
+    fn from(dvsec: &'a OwnedDesignatedVendorSpecificExtendedCapability) -> Self {
+        Self {
+            dvsec_vendor_id: dvsec.dvsec_vendor_id,
+            dvsec_revision: dvsec.dvsec_revision,
+            dvsec_length: dvsec.dvsec_length,
+            dvsec_id: dvsec.dvsec_id,
+            dvsec_type: (&dvsec.dvsec_type).into(),
+        }
+    }
+}
+
+impl<'a> Dvsec<'a> {
+    /// Access attributes of DVSEC headers and decoded DVSEC registers
+    pub fn registers(&self) -> RegisterMap {
+        match &self.dvsec_type {
+            DvsecType::ComputeExpressLink(ComputeExpressLink::PcieDvsecForCxlDevice(_)) => {
+                compute_express_link::pcie_dvsec_for_cxl_device::REGISTERS.into()
+            }
+            _ => REGISTERS.into(),
+        }
+    }
+}
+
+/// Designated Vendor-Specific Extended Capability registers, vendor-specific registers are not described
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // Designated Vendor-Specific Header 1
+    Attr::new(0x08, 2), // Designated Vendor-Specific Header 2
+];
+
 pub mod compute_express_link;
 pub use compute_express_link::ComputeExpressLink;
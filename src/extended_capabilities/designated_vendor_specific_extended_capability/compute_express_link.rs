@@ -49,6 +49,7 @@ use snafu::Snafu;
 
 /// Compute Express Link (CXL) Errors
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ComputeExpressLinkError {
     #[snafu(display("PCIe DVSEC for CXL Device"))]
     PcieDvsecForCxlDevice,
@@ -56,6 +57,7 @@ pub enum ComputeExpressLinkError {
 
 /// Compute Express Link (CXL)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ComputeExpressLink {
     PcieDvsecForCxlDevice(PcieDvsecForCxlDevice),
     NonCxlFunctionMapDvsec(NonCxlFunctionMapDvsec),
@@ -131,6 +133,7 @@ use self::pcie_dvsec_for_cxl_device::{CxlRangeBase, CxlRangeSize, PcieDvsecForCx
 mod non_cxl_function_map_dvsec {
     /// Non-CXL Function Map DVSEC
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct NonCxlFunctionMapDvsec;
 }
 use non_cxl_function_map_dvsec::NonCxlFunctionMapDvsec;
@@ -138,6 +141,7 @@ use non_cxl_function_map_dvsec::NonCxlFunctionMapDvsec;
 mod cxl_2_0_extensions_dvsec_for_ports {
     /// CXL 2.0 Extensions DVSEC for Ports
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Cxl20ExtensionsDvsecForPorts;
 }
 use cxl_2_0_extensions_dvsec_for_ports::Cxl20ExtensionsDvsecForPorts;
@@ -145,6 +149,7 @@ use cxl_2_0_extensions_dvsec_for_ports::Cxl20ExtensionsDvsecForPorts;
 mod gpf_dvsec_for_cxl_ports {
     /// GPF DVSEC for CXL Ports
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct GpfDvsecForCxlPorts;
 }
 use gpf_dvsec_for_cxl_ports::GpfDvsecForCxlPorts;
@@ -152,6 +157,7 @@ use gpf_dvsec_for_cxl_ports::GpfDvsecForCxlPorts;
 mod gpf_dvsec_for_cxl_devices {
     /// GPF DVSEC for CXL Devices
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct GpfDvsecForCxlDevices;
 }
 use gpf_dvsec_for_cxl_devices::GpfDvsecForCxlDevices;
@@ -159,6 +165,7 @@ use gpf_dvsec_for_cxl_devices::GpfDvsecForCxlDevices;
 mod pcie_dvsec_for_flex_bus_port {
     /// PCIe DVSEC for Flex Bus Port
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct PcieDvsecForFlexBusPort;
 }
 use pcie_dvsec_for_flex_bus_port::PcieDvsecForFlexBusPort;
@@ -166,6 +173,7 @@ use pcie_dvsec_for_flex_bus_port::PcieDvsecForFlexBusPort;
 mod register_locator_dvsec {
     /// Register Locator DVSEC
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct RegisterLocatorDvsec;
 }
 use register_locator_dvsec::RegisterLocatorDvsec;
@@ -173,6 +181,7 @@ use register_locator_dvsec::RegisterLocatorDvsec;
 mod mld_dvsec {
     /// MLD DVSEC
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct MldDvsec;
 }
 use mld_dvsec::MldDvsec;
@@ -180,6 +189,7 @@ use mld_dvsec::MldDvsec;
 mod pcie_dvsec_for_test_capability {
     /// PCIe DVSEC for Test Capability
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct PcieDvsecForTestCapability;
 }
 use pcie_dvsec_for_test_capability::PcieDvsecForTestCapability;
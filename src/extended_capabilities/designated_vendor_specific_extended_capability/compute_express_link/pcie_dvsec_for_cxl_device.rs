@@ -139,8 +139,11 @@ assert_eq!(sample, result);
 
 use heterob::{bit_numbering::Lsb, Bool, P13, P2, P3, P5, P8, P9, U8};
 
+use crate::register::{Register, RegisterAttributes as Attr};
+
 /// PCIe DVSEC for CXL Device
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PcieDvsecForCxlDevice {
     pub cxl_capability: CxlCapability,
     pub cxl_control: CxlControl,
@@ -167,8 +170,31 @@ impl PcieDvsecForCxlDevice {
         + CxlRangeBase::SIZE * 2;
 }
 
+/// PCIe DVSEC for CXL Device registers with offsets from the Extended Capability Header
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                            // Extended Capability Header
+    Attr::new(0x04, 4),                            // Designated Vendor-Specific Header 1
+    Attr::new(0x08, 2),                            // Designated Vendor-Specific Header 2
+    Attr::new(0x0a, 2),                            // DVSEC CXL Capability
+    Attr::new(0x0c, 2).rw(0x4fff).rsvdp(0xb000),   // DVSEC CXL Control
+    Attr::new(0x0e, 2).rw1c(0x4000).rsvdz(0xbfff), // DVSEC CXL Status
+    Attr::new(0x10, 2).rw(0x000f).rsvdp(0xfff0),   // DVSEC CXL Control2
+    Attr::new(0x12, 2),                            // DVSEC CXL Status2
+    Attr::new(0x14, 2).rw(0x0001).rsvdp(0xfffe),   // DVSEC CXL Lock
+    Attr::new(0x16, 2),                            // DVSEC CXL Capability2
+    Attr::new(0x18, 4),                            // DVSEC CXL Range 1 Size High
+    Attr::new(0x1c, 4),                            // DVSEC CXL Range 1 Size Low
+    Attr::new(0x20, 4).rw(0xffff_ffff),            // DVSEC CXL Range 1 Base High
+    Attr::new(0x24, 4).rw(0xf000_0000).rsvdp(0x0fff_ffff), // DVSEC CXL Range 1 Base Low
+    Attr::new(0x28, 4),                            // DVSEC CXL Range 2 Size High
+    Attr::new(0x2c, 4),                            // DVSEC CXL Range 2 Size Low
+    Attr::new(0x30, 4).rw(0xffff_ffff),            // DVSEC CXL Range 2 Base High
+    Attr::new(0x34, 4).rw(0xf000_0000).rsvdp(0x0fff_ffff), // DVSEC CXL Range 2 Base Low
+];
+
 /// DVSEC CXL Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlCapability {
     /// Indicates CXL.cache protocol support when operating in Flex Bus.CXL mode
     pub cache_capable: bool,
@@ -238,6 +264,7 @@ impl From<u16> for CxlCapability {
 
 /// Number of HDM ranges implemented by the CXL device and reported through this function
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HdmCount {
     /// Zero ranges
     ZeroRanges,
@@ -263,6 +290,7 @@ impl From<u8> for HdmCount {
 
 /// Indicates the maximum time that the device may take to complete the CXL Reset
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CxlResetTimeout {
     /// 10 ms
     MaxTime10ms,
@@ -293,6 +321,7 @@ impl From<u8> for CxlResetTimeout {
 
 /// DVSEC CXL Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlControl {
     /// Enables CXL.cache protocol operation when in Flex Bus.CXL mode
     pub cache_enable: bool,
@@ -336,8 +365,27 @@ impl From<u16> for CxlControl {
     }
 }
 
+impl From<CxlControl> for u16 {
+    fn from(data: CxlControl) -> Self {
+        (data.cache_enable as u16)
+            | (data.io_enable as u16) << 1
+            | (data.mem_enable as u16) << 2
+            | (u8::from(data.cache_sf_coverage) as u16 & 0x1f) << 3
+            | (u8::from(data.cache_sf_granularity) as u16 & 0b111) << 8
+            | (bool::from(data.cache_clean_eviction) as u16) << 11
+            | (data.viral_enable as u16) << 14
+    }
+}
+
+impl Register for CxlControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xb000;
+    const RW1C: u16 = 0;
+}
+
 /// Indicates Snoop Filter coverage on the Host
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CacheSfCoverage {
     /// No Snoop Filter coverage on the Host
     NoSnoopFilter,
@@ -366,6 +414,7 @@ impl From<CacheSfCoverage> for u8 {
 
 /// Indicates granularity tracking on the Host
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CacheSfGranularity {
     /// 64B granular tracking on the Host
     Tracking64B,
@@ -400,8 +449,24 @@ impl From<u8> for CacheSfGranularity {
     }
 }
 
+impl From<CacheSfGranularity> for u8 {
+    fn from(data: CacheSfGranularity) -> Self {
+        match data {
+            CacheSfGranularity::Tracking64B => 0b000,
+            CacheSfGranularity::Tracking128B => 0b001,
+            CacheSfGranularity::Tracking256B => 0b010,
+            CacheSfGranularity::Tracking512B => 0b011,
+            CacheSfGranularity::Tracking1KB => 0b100,
+            CacheSfGranularity::Tracking2KB => 0b101,
+            CacheSfGranularity::Tracking4KB => 0b110,
+            CacheSfGranularity::Reserved => 0b111,
+        }
+    }
+}
+
 /// Indicates necessity of clean evictions from device caches for best performance
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CacheCleanEviction {
     /// Clean evictions from device caches are needed for best performance
     Needed,
@@ -419,8 +484,15 @@ impl From<bool> for CacheCleanEviction {
     }
 }
 
+impl From<CacheCleanEviction> for bool {
+    fn from(data: CacheCleanEviction) -> Self {
+        matches!(data, CacheCleanEviction::NotNeeded)
+    }
+}
+
 /// DVSEC CXL Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlStatus {
     /// Indicates that the CXL device has entered Viral
     pub viral_status: bool,
@@ -437,8 +509,21 @@ impl From<u16> for CxlStatus {
     }
 }
 
+impl From<CxlStatus> for u16 {
+    fn from(data: CxlStatus) -> Self {
+        (data.viral_status as u16) << 14
+    }
+}
+
+impl Register for CxlStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x4000;
+}
+
 /// DVSEC CXL Control2
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlControl2 {
     /// When set, device shall no longer cache new modified lines in its local cache
     pub disable_caching: bool,
@@ -473,8 +558,24 @@ impl From<u16> for CxlControl2 {
     }
 }
 
+impl From<CxlControl2> for u16 {
+    fn from(data: CxlControl2) -> Self {
+        (data.disable_caching as u16)
+            | (data.initiate_cache_write_back_and_invalidation as u16) << 1
+            | (data.initiate_cxl_reset as u16) << 2
+            | (data.cxl_reset_mem_clr_enable as u16) << 3
+    }
+}
+
+impl Register for CxlControl2 {
+    type Value = u16;
+    const RSVDP: u16 = 0xfff0;
+    const RW1C: u16 = 0;
+}
+
 /// DVSEC CXL Status2
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlStatus2 {
     /// When set, device guarantees that it does not hold any valid lines and Disable Caching=1
     pub cache_invalid: bool,
@@ -508,8 +609,24 @@ impl From<u16> for CxlStatus2 {
     }
 }
 
+impl From<CxlStatus2> for u16 {
+    fn from(data: CxlStatus2) -> Self {
+        (data.cache_invalid as u16)
+            | (data.cxl_reset_complete as u16) << 1
+            | (data.cxl_reset_error as u16) << 2
+            | (data.power_management_initialization_complete as u16) << 15
+    }
+}
+
+impl Register for CxlStatus2 {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
+
 /// DVSEC CXL Lock
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlLock {
     /// When set, all register fields in the PCIe DVSEC for CXL Devices
     /// Capability with RWL attribute become read onl
@@ -527,8 +644,21 @@ impl From<u16> for CxlLock {
     }
 }
 
+impl From<CxlLock> for u16 {
+    fn from(data: CxlLock) -> Self {
+        data.config_lock as u16
+    }
+}
+
+impl Register for CxlLock {
+    type Value = u16;
+    const RSVDP: u16 = 0xfffe;
+    const RW1C: u16 = 0;
+}
+
 /// DVSEC CXL Capability2
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlCapability2 {
     pub cache_size_unit: CacheSizeUnit,
     pub cache_size: u8,
@@ -550,6 +680,7 @@ impl From<u16> for CxlCapability2 {
 
 /// Cache Size Unit
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CacheSizeUnit {
     /// Cache size is not reported
     NotReported,
@@ -573,6 +704,7 @@ impl From<u8> for CacheSizeUnit {
 
 /// DVSEC CXL Range Size
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlRangeSize {
     pub memory_info_valid: bool,
     pub memory_active: bool,
@@ -615,6 +747,7 @@ impl CxlRangeSize {
 
 /// Indicates the memory media characteristics
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MediaType {
     /// Volatile memory
     Volatile,
@@ -638,6 +771,7 @@ impl From<u8> for MediaType {
 
 /// Indicates the class of memory
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MemoryClass {
     /// Memory Class (e.g., normal DRAM)
     Memory,
@@ -661,6 +795,7 @@ impl From<u8> for MemoryClass {
 
 /// Represents the memory interleaving desired by the device
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DesiredInterleave {
     /// No Interleave
     NoInterleave,
@@ -700,6 +835,7 @@ impl From<u8> for DesiredInterleave {
 /// Indicates the maximum time that the device is permitted to take to set
 /// Memory_Active bit after a hot reset, warm reset or a cold reset
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MemoryActiveTimeout {
     MaxTime1s,
     MaxTime4s,
@@ -724,6 +860,7 @@ impl From<u8> for MemoryActiveTimeout {
 
 /// DVSEC CXL Range Base
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CxlRangeBase {
     pub memory_base: u64,
 }
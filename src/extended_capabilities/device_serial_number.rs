@@ -27,10 +27,12 @@ assert_eq!(sample, result);
 use heterob::{endianness::Le, Seq, P2};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 /// The Serial Number register is a 64-bit field that contains the IEEE defined 64-bit extended
 /// unique identifier (EUI-64™).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceSerialNumber {
     /// PCI Express Device Serial Number (1st DW)
     pub lower_dword: u32,
@@ -56,3 +58,10 @@ impl TryFrom<&[u8]> for DeviceSerialNumber {
         })
     }
 }
+
+/// Device Serial Number Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // Serial Number Register (Lower DW)
+    Attr::new(0x08, 4), // Serial Number Register (Upper DW)
+];
@@ -145,12 +145,15 @@ use heterob::{
     endianness::{Le, LeBytesTryInto},
     Seq, P12, P4, P6, P7, P8,
 };
+
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
 use snafu::Snafu;
 
 pub use super::advanced_error_reporting::{HeaderLog, TlpPrefixLog};
 
 /// Downstream Port Containment Error
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DownstreamPortContainmentError {
     #[snafu(display("can't read manadatory registers (8 bytes)"))]
     Mandatory,
@@ -163,6 +166,7 @@ pub enum DownstreamPortContainmentError {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DownstreamPortContainment {
     /// DPC Capability
     pub dpc_capability: DpcCapability,
@@ -256,9 +260,52 @@ impl TryFrom<&[u8]> for DownstreamPortContainment {
     }
 }
 
+impl DownstreamPortContainment {
+    /// Access attributes of registers present in the decoded structure
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        match &self.rp_extensions {
+            None => map.truncate(0x0c),
+            Some(RpExtensions {
+                rp_pio_tlp_prefix_log: Some(_),
+                ..
+            }) => map,
+            Some(RpExtensions {
+                rp_pio_impspec_log: Some(_),
+                ..
+            }) => map.truncate(0x34),
+            Some(_) => map.truncate(0x30),
+        }
+    }
+}
+
+/// DPC Capability registers, RP PIO registers are present only in Root Ports
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                            // Extended Capability Header
+    Attr::new(0x04, 2),                            // DPC Capability
+    Attr::new(0x06, 2).rw(0x00ff).rsvdp(0xff00),   // DPC Control
+    Attr::new(0x08, 2).rw1c(0x0009).rsvdz(0xe080), // DPC Status
+    Attr::new(0x0a, 2),                            // DPC Error Source ID
+    Attr::new(0x0c, 4).rw1c(0x0007_0707).rsvdz(0xfff8_f8f8), // RP PIO Status
+    Attr::new(0x10, 4).rw(0x0007_0707).rsvdp(0xfff8_f8f8), // RP PIO Mask
+    Attr::new(0x14, 4).rw(0x0007_0707).rsvdp(0xfff8_f8f8), // RP PIO Severity
+    Attr::new(0x18, 4).rw(0x0007_0707).rsvdp(0xfff8_f8f8), // RP PIO SysError
+    Attr::new(0x1c, 4).rw(0x0007_0707).rsvdp(0xfff8_f8f8), // RP PIO Exception
+    Attr::new(0x20, 4),                            // RP PIO Header Log 1st DW
+    Attr::new(0x24, 4),                            // RP PIO Header Log 2nd DW
+    Attr::new(0x28, 4),                            // RP PIO Header Log 3rd DW
+    Attr::new(0x2c, 4),                            // RP PIO Header Log 4th DW
+    Attr::new(0x30, 4),                            // RP PIO ImpSpec Log
+    Attr::new(0x34, 4),                            // RP PIO TLP Prefix Log 1st DW
+    Attr::new(0x38, 4),                            // RP PIO TLP Prefix Log 2nd DW
+    Attr::new(0x3c, 4),                            // RP PIO TLP Prefix Log 3rd DW
+    Attr::new(0x40, 4),                            // RP PIO TLP Prefix Log 4th DW
+];
+
 /// Root Ports that support RP Extensions for DPC  
 /// Switch Downstream Ports must not has this structure.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RpExtensions {
     /// RP PIO Status
     pub rp_pio_status: RpPio,
@@ -285,6 +332,7 @@ pub struct RpExtensions {
 
 /// DPC Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DpcCapability {
     /// DPC Interrupt Message Number
     pub dpc_interrupt_message_number: u8,
@@ -324,6 +372,7 @@ impl From<u16> for DpcCapability {
 
 /// DPC Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DpcControl {
     /// DPC Trigger Enable
     pub dpc_trigger_enable: DpcTrigger,
@@ -365,8 +414,27 @@ impl From<u16> for DpcControl {
     }
 }
 
+impl From<DpcControl> for u16 {
+    fn from(data: DpcControl) -> Self {
+        (u8::from(data.dpc_trigger_enable) as u16 & 0b11)
+            | (data.dpc_completion_control as u16) << 2
+            | (data.dpc_interrupt_enable as u16) << 3
+            | (data.dpc_err_cor_enable as u16) << 4
+            | (data.poisoned_tlp_egress_blocking_enable as u16) << 5
+            | (data.dpc_software_trigger as u16) << 6
+            | (data.dl_active_err_cor_enable as u16) << 7
+    }
+}
+
+impl Register for DpcControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xff00;
+    const RW1C: u16 = 0;
+}
+
 /// Enables DPC and controls the conditions that cause DPC to be triggered
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DpcTrigger {
     Disabled,
     /// Enabled and is triggered when the Downstream Port detects an unmasked uncorrectable error
@@ -388,8 +456,20 @@ impl From<u8> for DpcTrigger {
     }
 }
 
+impl From<DpcTrigger> for u8 {
+    fn from(data: DpcTrigger) -> Self {
+        match data {
+            DpcTrigger::Disabled => 0b00,
+            DpcTrigger::ErrFatalMessage => 0b01,
+            DpcTrigger::ErrNonFatalMessage => 0b10,
+            DpcTrigger::Reserved => 0b11,
+        }
+    }
+}
+
 /// DPC Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DpcStatus {
     /// DPC Trigger Status
     pub dpc_trigger_status: bool,
@@ -428,8 +508,26 @@ impl From<u16> for DpcStatus {
     }
 }
 
+impl From<DpcStatus> for u16 {
+    fn from(data: DpcStatus) -> Self {
+        (data.dpc_trigger_status as u16)
+            | (data.dpc_trigger_reason.value() as u16) << 1
+            | (data.dpc_interrupt_status as u16) << 3
+            | (data.dpc_rp_busy as u16) << 4
+            | (data.dpc_trigger_reason.extension_value() as u16 & 0b11) << 5
+            | (data.rp_pio_first_error_pointer as u16 & 0x1f) << 8
+    }
+}
+
+impl Register for DpcStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0009;
+}
+
 /// Indicates why DPC has been triggered
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DpcTriggerReason {
     /// unmasked uncorrectable error
     UnmaskedUncorrectableError,
@@ -474,6 +572,7 @@ impl DpcTriggerReason {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RpPio {
     /// Configuration Request received UR Completion
     pub cfg_ur_cpl: bool,
@@ -524,3 +623,18 @@ impl From<u32> for RpPio {
         }
     }
 }
+
+/// Status register bits are RW1C, other RP PIO registers bits are RW
+impl From<RpPio> for u32 {
+    fn from(data: RpPio) -> Self {
+        (data.cfg_ur_cpl as u32)
+            | (data.cfg_ca_cpl as u32) << 1
+            | (data.cfg_cto as u32) << 2
+            | (data.io_ur_cpl as u32) << 8
+            | (data.io_ca_cpl as u32) << 9
+            | (data.io_cto as u32) << 10
+            | (data.mem_ur_cpl as u32) << 16
+            | (data.mem_ca_cpl as u32) << 17
+            | (data.mem_cto as u32) << 18
+    }
+}
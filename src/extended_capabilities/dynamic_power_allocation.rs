@@ -60,8 +60,13 @@ use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P4, P8};
 use snafu::Snafu;
 
 use super::ExtendedCapabilityHeader;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DynamicPowerAllocationError {
     #[snafu(display("capability, latency indicator, status and control fields are unreadable"))]
     Mandatory,
@@ -70,6 +75,7 @@ pub enum DynamicPowerAllocationError {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DynamicPowerAllocation<'a> {
     pub dpa_capability: DpaCapability,
     /// Each bit indicates which Transition Latency Value is associated with
@@ -111,8 +117,72 @@ impl<'a> TryFrom<&'a [u8]> for DynamicPowerAllocation<'a> {
     }
 }
 
+/// Owned [DynamicPowerAllocation]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedDynamicPowerAllocation {
+    pub dpa_capability: DpaCapability,
+    /// Each bit indicates which Transition Latency Value is associated with
+    /// the corresponding substate
+    pub dpa_latency_indicator: u32,
+    pub dpa_status: DpaStatus,
+    pub dpa_control: DpaControl,
+    /// Substate Power Allocation registers
+    pub dpa_power_allocation_array: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&DynamicPowerAllocation<'a>> for OwnedDynamicPowerAllocation {
+    fn from(dpa: &DynamicPowerAllocation<'a>) -> Self {
+        Self {
+            dpa_capability: dpa.dpa_capability.clone(),
+            dpa_latency_indicator: dpa.dpa_latency_indicator,
+            dpa_status: dpa.dpa_status.clone(),
+            dpa_control: dpa.dpa_control.clone(),
+            dpa_power_allocation_array: dpa.dpa_power_allocation_array.0.to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedDynamicPowerAllocation> for DynamicPowerAllocation<'a> {
+    fn from(dpa: &'a OwnedDynamicPowerAllocation) -> Self {
+        Self {
+            dpa_capability: dpa.dpa_capability.clone(),
+            dpa_latency_indicator: dpa.dpa_latency_indicator,
+            dpa_status: dpa.dpa_status.clone(),
+            dpa_control: dpa.dpa_control.clone(),
+            dpa_power_allocation_array: DpaPowerAllocationArray(&dpa.dpa_power_allocation_array),
+        }
+    }
+}
+
+impl<'a> DynamicPowerAllocation<'a> {
+    /// Access attributes of registers including Power Allocation Array
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.dpa_capability.substate_max as u16 + 1;
+        RegisterMap::from(REGISTERS).repeat(POWER_ALLOCATION_ARRAY_REGISTERS, 1, count)
+    }
+}
+
+/// DPA Capability registers without Power Allocation Array
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                            // Extended Capability Header
+    Attr::new(0x04, 4),                            // DPA Capability
+    Attr::new(0x08, 4),                            // DPA Latency Indicator
+    Attr::new(0x0c, 2).rw1c(0x0100).rsvdz(0xfee0), // DPA Status
+    Attr::new(0x0e, 2).rw(0x001f).rsvdp(0xffe0),   // DPA Control
+];
+
+/// First DPA Power Allocation Array register, other registers follow every byte
+pub const POWER_ALLOCATION_ARRAY_REGISTERS: &[Attr] = &[
+    Attr::new(0x10, 1), // Substate Power Allocation
+];
+
 /// DPA Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DpaCapability {
     /// Indicates the maximum substate number, which is the total number of
     /// supported substates minus one.
@@ -152,6 +222,7 @@ impl From<u32> for DpaCapability {
 /// A substate’s Transition Latency Value is multiplied by the Transition
 /// Latency Unit to determine the maximum Transition Latency for the substate
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransitionLatencyUnit {
     /// 1 ms
     Unit1ms,
@@ -177,6 +248,7 @@ impl From<u8> for TransitionLatencyUnit {
 
 /// The encodings provide the scale to determine power allocation per substate in Watts
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerAllocationScale {
     /// 10.0x
     Mul10,
@@ -202,6 +274,7 @@ impl From<u8> for PowerAllocationScale {
 
 /// DPA Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DpaStatus {
     /// Indicates current substate for this Function
     pub substate_status: u8,
@@ -221,8 +294,21 @@ impl From<u16> for DpaStatus {
     }
 }
 
+impl From<DpaStatus> for u16 {
+    fn from(data: DpaStatus) -> Self {
+        (data.substate_status as u16 & 0x1f) | (data.substate_control_enabled as u16) << 8
+    }
+}
+
+impl Register for DpaStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0100;
+}
+
 /// DPA Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DpaControl {
     pub substate_control: u8,
 }
@@ -234,9 +320,22 @@ impl From<u16> for DpaControl {
     }
 }
 
+impl From<DpaControl> for u16 {
+    fn from(data: DpaControl) -> Self {
+        data.substate_control as u16 & 0x1f
+    }
+}
+
+impl Register for DpaControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xffe0;
+    const RW1C: u16 = 0;
+}
+
 /// DPA Power Allocation Array
 ///
 /// Each Substate Power Allocation register indicates the power allocation
 /// value for its associated substate
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DpaPowerAllocationArray<'a>(pub &'a [u8]);
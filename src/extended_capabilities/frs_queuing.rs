@@ -53,9 +53,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3, P4};
 
 use super::{ExtendedCapabilityDataError, ExtendedCapabilityHeader};
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// FRS Queueing
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrsQueuing {
     pub frs_queuing_capability: FrsQueuingCapability,
     pub frs_queuing_status: FrsQueuingStatus,
@@ -99,8 +101,18 @@ impl TryFrom<&[u8]> for FrsQueuing {
     }
 }
 
+/// FRS Queuing Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                            // Extended Capability Header
+    Attr::new(0x04, 4),                            // FRS Queuing Capability
+    Attr::new(0x08, 2).rw1c(0x0003).rsvdz(0xfffc), // FRS Queuing Status
+    Attr::new(0x0a, 2).rw(0x0001).rsvdp(0xfffe),   // FRS Queuing Control
+    Attr::new(0x0c, 4),                            // FRS Message Queue
+];
+
 /// FRS Queuing Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrsQueuingCapability {
     /// Indicates the implemented queue depth, with valid values ranging from
     /// 001h (queue depth of 1) to FFFh (queue depth of 4095)
@@ -127,6 +139,7 @@ impl From<u32> for FrsQueuingCapability {
 
 /// FRS Queuing Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrsQueuingStatus {
     /// This bit is Set when a new FRS Message is Received or generated by this
     /// Root Port or Root Complex Event Collector
@@ -150,8 +163,21 @@ impl From<u16> for FrsQueuingStatus {
     }
 }
 
+impl From<FrsQueuingStatus> for u16 {
+    fn from(data: FrsQueuingStatus) -> Self {
+        (data.frs_message_received as u16) | (data.frs_message_overflow as u16) << 1
+    }
+}
+
+impl Register for FrsQueuingStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0003;
+}
+
 /// FRS Queuing Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrsQueuingControl {
     /// When Set and MSI or MSI-X is enabled, the Port must issue an MSI/
     /// MSI-X interrupt to indicate the 0b to 1b transition of either the FRS Message
@@ -172,8 +198,21 @@ impl From<u16> for FrsQueuingControl {
     }
 }
 
+impl From<FrsQueuingControl> for u16 {
+    fn from(data: FrsQueuingControl) -> Self {
+        data.frs_interrupt_enable as u16
+    }
+}
+
+impl Register for FrsQueuingControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xfffe;
+    const RW1C: u16 = 0;
+}
+
 /// FRS Message Queue
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrsMessageQueue {
     /// Recorded from the Requester ID of the oldest FRS Message received or
     /// generated by this Root Port or Root Complex Event Collector and still in the
@@ -0,0 +1,227 @@
+/*!
+# Hierarchy ID
+
+The Hierarchy ID Extended Capability provides the information delivered by the most recent
+Hierarchy ID Message. It identifies the PCI Hierarchy a Function belongs to and the system that
+owns it, so Functions assigned to different partitions or hosts can be correlated.
+
+## Struct diagram
+[HierarchyId]
+- [HierarchyIdStatus]
+- [HierarchyIdData]
+- [SystemGuid]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::hierarchy_id::*;
+let data = [
+    /* 00h */ 0x28, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x03, 0x00, 0x00, 0x80, // Hierarchy ID Status
+    /* 08h */ 0x08, 0x3a, 0x01, 0x00, // Hierarchy ID Data
+    /* 0Ch */ 0xfe, 0xca, 0x01, 0x00, // Hierarchy ID GUID 1
+    /* 10h */ 0x00, 0x11, 0x22, 0x33, // Hierarchy ID GUID 2
+    /* 14h */ 0x44, 0x55, 0x66, 0x77, // Hierarchy ID GUID 3
+    /* 18h */ 0x88, 0x99, 0xaa, 0xbb, // Hierarchy ID GUID 4
+    /* 1Ch */ 0xcc, 0xdd, 0xee, 0xff, // Hierarchy ID GUID 5
+];
+let result: HierarchyId = data[4..].try_into().unwrap();
+let sample = HierarchyId {
+    status: HierarchyIdStatus {
+        function_id: 3,
+        hierarchy_id_writeable: false,
+        hierarchy_id_valid: true,
+    },
+    data: HierarchyIdData {
+        segment: 1,
+        bus: 0x3a,
+        device: 1,
+        function: 0,
+    },
+    system_guid_authority_id: 1,
+    system_guid: SystemGuid([
+        0xca, 0xfe, 0x33, 0x22, 0x11, 0x00, 0x77, 0x66, 0x55, 0x44, 0xbb, 0xaa, 0x99, 0x88, 0xff,
+        0xee, 0xdd, 0xcc,
+    ]),
+};
+assert_eq!(sample, result);
+assert_eq!(
+    "cafe-33221100-7766-5544-bbaa-9988ffeeddcc",
+    result.system_guid.to_string()
+);
+
+// Another Function of the same host
+let mut other = result.clone();
+other.status.function_id = 4;
+assert!(result.is_same_system(&other));
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P3, P4, P5, P7};
+
+use super::ExtendedCapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
+
+/// Hierarchy ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HierarchyId {
+    pub status: HierarchyIdStatus,
+    pub data: HierarchyIdData,
+    /// System GUID Authority ID. Identifies the mechanism used to assign the System GUID.
+    pub system_guid_authority_id: u8,
+    pub system_guid: SystemGuid,
+}
+impl HierarchyId {
+    /// Both Functions received Hierarchy ID from the same system
+    ///
+    /// System GUIDs are unique only within the scope of the same System GUID Authority ID.
+    /// Functions without [valid](HierarchyIdStatus::hierarchy_id_valid) Hierarchy ID never
+    /// match.
+    pub fn is_same_system(&self, other: &Self) -> bool {
+        self.status.hierarchy_id_valid
+            && other.status.hierarchy_id_valid
+            && self.system_guid_authority_id == other.system_guid_authority_id
+            && self.system_guid == other.system_guid
+    }
+}
+impl TryFrom<&[u8]> for HierarchyId {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((status, data, guid_1, guid_2, guid_3, guid_4, guid_5)),
+            ..
+        } = P7(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Hierarchy ID",
+                size: 28,
+            })?;
+        let Lsb((system_guid_high, system_guid_authority_id, ())) =
+            P3::<u32, 16, 4, 12>(guid_1).into();
+        let _: (u16, u32, u32, u32, u32) = (system_guid_high, guid_2, guid_3, guid_4, guid_5);
+        let mut system_guid = [0; 18];
+        system_guid[..2].copy_from_slice(&system_guid_high.to_be_bytes());
+        for (chunk, dword) in system_guid[2..]
+            .chunks_mut(4)
+            .zip([guid_2, guid_3, guid_4, guid_5])
+        {
+            chunk.copy_from_slice(&dword.to_be_bytes());
+        }
+        Ok(Self {
+            status: From::<u32>::from(status),
+            data: From::<u32>::from(data),
+            system_guid_authority_id,
+            system_guid: SystemGuid(system_guid),
+        })
+    }
+}
+
+/// Hierarchy ID Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0x4000_0000).rsvdz(0x3fff_0000), // Hierarchy ID Status
+    Attr::new(0x08, 4).rsvdz(0xff00_0000), // Hierarchy ID Data
+    Attr::new(0x0c, 4).rsvdz(0xfff0_0000), // Hierarchy ID GUID 1
+    Attr::new(0x10, 4), // Hierarchy ID GUID 2
+    Attr::new(0x14, 4), // Hierarchy ID GUID 3
+    Attr::new(0x18, 4), // Hierarchy ID GUID 4
+    Attr::new(0x1c, 4), // Hierarchy ID GUID 5
+];
+
+/// Hierarchy ID Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HierarchyIdStatus {
+    /// Function ID. Distinguishes Functions (including VFs) that share the same Hierarchy ID.
+    pub function_id: u16,
+    /// Hierarchy ID Writeable. Software may write Hierarchy ID Data and GUID registers.
+    pub hierarchy_id_writeable: bool,
+    /// Hierarchy ID Valid. Hierarchy ID Message was received since the last reset.
+    pub hierarchy_id_valid: bool,
+}
+
+impl From<u32> for HierarchyIdStatus {
+    fn from(dword: u32) -> Self {
+        let Lsb((function_id, (), hierarchy_id_writeable, hierarchy_id_valid)) =
+            P4::<_, 16, 14, 1, 1>(dword).into();
+        Self {
+            function_id,
+            hierarchy_id_writeable,
+            hierarchy_id_valid,
+        }
+    }
+}
+
+/// Hierarchy ID Data. Location of the Downstream Port that sent the Hierarchy ID Message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HierarchyIdData {
+    /// Segment Group Number
+    pub segment: u8,
+    pub bus: u8,
+    pub device: u8,
+    pub function: u8,
+}
+
+impl From<u32> for HierarchyIdData {
+    fn from(dword: u32) -> Self {
+        let Lsb((function, device, bus, segment, ())) = P5::<_, 3, 5, 8, 8, 8>(dword).into();
+        Self {
+            segment,
+            bus,
+            device,
+            function,
+        }
+    }
+}
+
+/// 144-bit System GUID, most significant byte first. Globally identifies the system within the
+/// scope of its [Authority ID](HierarchyId::system_guid_authority_id).
+///
+/// System GUID\[143:128\] is held by Hierarchy ID GUID 1 register, System GUID\[127:0\] by
+/// Hierarchy ID GUID 2 - GUID 5 registers. Displayed as System GUID\[143:128\] followed by
+/// System GUID\[127:0\] in the canonical form: `xxxx-xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemGuid(pub [u8; 18]);
+
+impl core::fmt::Display for SystemGuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if let 2 | 6 | 8 | 10 | 12 = i {
+                write!(f, "-")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[test]
+    fn system_guid_high_bits() {
+        let mut data = [0u8; 28];
+        // Hierarchy ID Valid
+        data[3] = 0x80;
+        // Authority ID 2, System GUID[143:128] = 0x0001
+        data[8..12].copy_from_slice(&0x0002_0001u32.to_le_bytes());
+        let result = HierarchyId::try_from(data.as_slice()).unwrap();
+        assert_eq!(2, result.system_guid_authority_id);
+        assert_eq!(0x01, result.system_guid.0[1]);
+        // Differs from the other system only in System GUID[143:128]
+        data[8..12].copy_from_slice(&0x0002_0002u32.to_le_bytes());
+        let other = HierarchyId::try_from(data.as_slice()).unwrap();
+        assert!(!result.is_same_system(&other));
+        assert_eq!(
+            "0002-00000000-0000-0000-0000-000000000000",
+            other.system_guid.to_string()
+        );
+    }
+}
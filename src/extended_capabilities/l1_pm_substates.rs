@@ -58,14 +58,17 @@ let sample = L1PmSubstates {
 assert_eq!(sample, result);
 ```
 */
+#![allow(clippy::doc_lazy_continuation)]
 
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P11, P3, P4, P9};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 pub use super::latency_tolerance_reporting::MaxLatency;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct L1PmSubstates {
     /// L1 PM Substates Capabilities
     pub l1_pm_substates_capabilities: L1PmSubstatesCapabilities,
@@ -148,8 +151,17 @@ impl TryFrom<&[u8]> for L1PmSubstates {
     }
 }
 
+/// L1 PM Substates Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // L1 PM Substates Capabilities
+    Attr::new(0x08, 4).rw(0xe3ff_ff0f).rsvdp(0x1c00_00f0), // L1 PM Substates Control 1
+    Attr::new(0x0c, 4).rw(0x0000_00fb).rsvdp(0xffff_ff04), // L1 PM Substates Control 2
+];
+
 /// L1 PM Substates Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct L1PmSubstatesCapabilities {
     /// PCI-PM L1.2 Supported
     pub pci_pm_l1_2_supported: bool,
@@ -180,6 +192,7 @@ impl From<L1PmSubstatesCapabilities> for u32 {
 /// Sets the time (in μs) that this Port requires the port on the opposite side
 /// of Link to wait in L1.2
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortTPowerOn {
     pub value: u8,
     pub scale: PortTPowerOnScale,
@@ -200,6 +213,7 @@ impl From<PortTPowerOn> for u8 {
 
 /// Specifies the scale used for the Port T_POWER_ON Value
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PortTPowerOnScale {
     /// 2 µs
     Time2us = 2,
@@ -233,6 +247,7 @@ impl From<PortTPowerOnScale> for u8 {
 
 /// L1 PM Substates Control 1
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct L1PmSubstatesControl1 {
     /// PCI-PM L1.2 Enable
     pub pci_pm_l1_2_enable: bool,
@@ -259,9 +274,15 @@ impl From<L1PmSubstatesControl1> for u32 {
         u32::from_le_bytes([b0, data.common_mode_restore_time, b2, b3])
     }
 }
+impl Register for L1PmSubstatesControl1 {
+    type Value = u32;
+    const RSVDP: u32 = 0x1c00_00f0;
+    const RW1C: u32 = 0;
+}
 
 /// L1 PM Substates Control 2
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct L1PmSubstatesControl2 {
     /// T_POWER_ON
     pub t_power_on: PortTPowerOn,
@@ -271,6 +292,11 @@ impl From<L1PmSubstatesControl2> for u32 {
         u32::from_le_bytes([data.t_power_on.into(), 0x00, 0x00, 0x00])
     }
 }
+impl Register for L1PmSubstatesControl2 {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_ff04;
+    const RW1C: u32 = 0;
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,1242 @@
+/*!
+# Lane Margining at the Receiver
+
+The Margining Extended Capability structure must be implemented by Downstream Ports that
+support 16.0 GT/s or higher data rates and by Upstream Ports that support Lane Margining at the
+Receiver. Software issues margin commands through the per-Lane Margining Lane Control register
+and polls the response in the Margining Lane Status register.
+
+[MarginingLane::write_command] and [MarginingLane::poll_response] do both through a
+[ConfigWrite] accessor.
+
+## Struct diagram
+[LaneMarginingAtTheReceiver]
+- [MarginingPortCapabilities]
+- [MarginingPortStatus]
+- [MarginingLanes]
+  - [MarginingLane]
+    - [MarginingLaneControl] ⇄ [MarginCommand]
+    - [MarginingLaneStatus] ⇄ [MarginResponse]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::lane_margining_at_the_receiver::*;
+# use pcics::capabilities::pci_express::LinkWidth;
+let data = [
+    /* 00h */ 0x27, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x00, 0x00, 0x01, 0x00, // Margining Port Capabilities / Status
+    /* 08h */ 0x38, 0x9c, 0x38, 0x9c, // Margining Lane Control / Status (0)
+    /* 0Ch */ 0x09, 0x89, 0x09, 0x89, // Margining Lane Control / Status (1)
+];
+let result: LaneMarginingAtTheReceiver = data[4..].try_into().unwrap();
+assert!(result.port_status.margining_ready);
+
+let lanes = result.lanes(LinkWidth::X2).collect::<Vec<_>>();
+assert_eq!(MarginCommand::NoCommand, lanes[0].control.clone().into());
+
+// Report MNumVoltageSteps of the Upstream Port Receiver
+let command = MarginCommand::Report {
+    receiver_number: ReceiverNumber::ReceiverF,
+    report: MarginReport::NumVoltageSteps,
+};
+assert_eq!(0x890e, u16::from(MarginingLaneControl::from(command.clone())));
+// Lane 1 status still echoes a command for another Receiver
+assert_eq!(None, command.response(&lanes[1].status));
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3, P5, P6};
+
+use super::ExtendedCapabilityDataError;
+
+use crate::access::{ConfigAccess, ConfigWrite};
+use crate::capabilities::pci_express::LinkWidth;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Margining Lane Control of Lane 0 offset
+pub const LANES_OFFSET: usize = 0x08;
+
+/// Lane Margining at the Receiver
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LaneMarginingAtTheReceiver<'a> {
+    lanes_data: &'a [u8],
+    pub port_capabilities: MarginingPortCapabilities,
+    pub port_status: MarginingPortStatus,
+}
+impl<'a> LaneMarginingAtTheReceiver<'a> {
+    /// Margining Lane Control and Status registers of `link_width` lanes
+    pub fn lanes(&self, link_width: LinkWidth) -> MarginingLanes<'a> {
+        MarginingLanes::new(self.lanes_data, link_width)
+    }
+    /// Access attributes of registers including Margining Lane registers for `link_width` lanes
+    pub fn registers(&self, link_width: LinkWidth) -> RegisterMap {
+        let count = u8::from(link_width) as u16;
+        RegisterMap::from(REGISTERS).repeat(LANE_REGISTERS, MarginingLane::SIZE as u16, count)
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for LaneMarginingAtTheReceiver<'a> {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((port_capabilities, port_status)),
+            tail,
+        } = P2(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Lane Margining at the Receiver",
+                size: 4,
+            })?;
+        Ok(Self {
+            lanes_data: tail,
+            port_capabilities: From::<u16>::from(port_capabilities),
+            port_status: From::<u16>::from(port_status),
+        })
+    }
+}
+
+/// Owned [LaneMarginingAtTheReceiver]
+///
+/// Converts back into [LaneMarginingAtTheReceiver] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedLaneMarginingAtTheReceiver {
+    lanes_data: Vec<u8>,
+    pub port_capabilities: MarginingPortCapabilities,
+    pub port_status: MarginingPortStatus,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&LaneMarginingAtTheReceiver<'a>> for OwnedLaneMarginingAtTheReceiver {
+    fn from(value: &LaneMarginingAtTheReceiver<'a>) -> Self {
+        Self {
+            lanes_data: value.lanes_data.to_vec(),
+            port_capabilities: value.port_capabilities.clone(),
+            port_status: value.port_status.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedLaneMarginingAtTheReceiver> for LaneMarginingAtTheReceiver<'a> {
+    fn from(value: &'a OwnedLaneMarginingAtTheReceiver) -> Self {
+        Self {
+            lanes_data: &value.lanes_data,
+            port_capabilities: value.port_capabilities.clone(),
+            port_status: value.port_status.clone(),
+        }
+    }
+}
+
+/// Lane Margining at the Receiver Capability registers without Margining Lane registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                              // Extended Capability Header
+    Attr::new(0x04, 2).hwinit(0x0001).rsvdp(0xfffe), // Margining Port Capabilities
+    Attr::new(0x06, 2).rsvdz(0xfffc),                // Margining Port Status
+];
+
+/// Margining Lane registers of Lane 0, other Lanes follow every 4 bytes
+pub const LANE_REGISTERS: &[Attr] = &[
+    Attr::new(0x08, 2).rw(0xff7f).rsvdp(0x0080), // Margining Lane Control
+    Attr::new(0x0a, 2).rsvdz(0x0080),            // Margining Lane Status
+];
+
+/// Margining Port Capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginingPortCapabilities {
+    /// Margining uses Driver Software. Device specific software is required to margin the
+    /// Receivers of this Port.
+    pub margining_uses_driver_software: bool,
+}
+
+impl From<u16> for MarginingPortCapabilities {
+    fn from(word: u16) -> Self {
+        let Lsb((margining_uses_driver_software, ())) = P2::<_, 1, 15>(word).into();
+        Self {
+            margining_uses_driver_software,
+        }
+    }
+}
+
+/// Margining Port Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginingPortStatus {
+    /// Margining Ready. Port is ready to accept margin commands.
+    pub margining_ready: bool,
+    /// Margining Software Ready. Device specific software has been loaded when it is required.
+    pub margining_software_ready: bool,
+}
+
+impl From<u16> for MarginingPortStatus {
+    fn from(word: u16) -> Self {
+        let Lsb((margining_ready, margining_software_ready, ())) = P3::<_, 1, 1, 14>(word).into();
+        Self {
+            margining_ready,
+            margining_software_ready,
+        }
+    }
+}
+
+/// An iterator through [Margining Lanes](MarginingLane)
+#[derive(Debug, Clone)]
+pub struct MarginingLanes<'a> {
+    chunks: core::slice::Chunks<'a, u8>,
+}
+impl<'a> MarginingLanes<'a> {
+    /// Margining Lane registers for each of `link_width` lanes
+    pub fn new(bytes: &'a [u8], link_width: LinkWidth) -> Self {
+        let end = (u8::from(link_width) as usize * MarginingLane::SIZE).min(bytes.len());
+        Self {
+            chunks: bytes[..end].chunks(MarginingLane::SIZE),
+        }
+    }
+}
+impl<'a> Iterator for MarginingLanes<'a> {
+    type Item = MarginingLane;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes: [u8; MarginingLane::SIZE] = self.chunks.next()?.try_into().ok()?;
+        Some(u32::from_le_bytes(bytes).into())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for MarginingLanes<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+/// Margining Lane Control and Status registers of a single Lane
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginingLane {
+    pub control: MarginingLaneControl,
+    pub status: MarginingLaneStatus,
+}
+impl MarginingLane {
+    /// Margining Lane Control size + Margining Lane Status size
+    pub const SIZE: usize = 2 + 2;
+
+    /// Offset of `lane` Margining Lane Control register relative to the capability offset
+    pub const fn offset(lane: u8) -> u16 {
+        LANES_OFFSET as u16 + lane as u16 * Self::SIZE as u16
+    }
+    /// Read Margining Lane registers of `lane` from the capability at configuration space
+    /// `offset`
+    pub fn read_from<A: ConfigAccess + ?Sized>(
+        access: &A,
+        offset: u16,
+        lane: u8,
+    ) -> Result<Self, A::Error> {
+        access.read_u32(offset + Self::offset(lane)).map(From::from)
+    }
+    /// Issue margin `command` to `lane` of the capability at configuration space `offset`
+    ///
+    /// Margining Lane Status of the Lane is read-only, so the whole DWORD is written with
+    /// only Margining Lane Control bits set. Status keeps reflecting the previous command until
+    /// the Receiver processes the new one, so [MarginCommand::NoCommand] should be issued and
+    /// polled before a command of the same Margin Type.
+    pub fn write_command<A: ConfigWrite + ?Sized>(
+        access: &A,
+        offset: u16,
+        lane: u8,
+        command: MarginCommand,
+    ) -> Result<(), A::Error> {
+        let offset = offset + Self::offset(lane);
+        let current = access.read_u32(offset)? as u16;
+        let control = MarginingLaneControl::from(command);
+        access.write_u32(offset, control.write_value(current) as u32)
+    }
+    /// Poll Margining Lane Status of `lane` until it reflects `command`
+    ///
+    /// Status is read at most `polls` times, `None` is returned if the Receiver did not respond
+    /// in time.
+    pub fn poll_response<A: ConfigAccess + ?Sized>(
+        access: &A,
+        offset: u16,
+        lane: u8,
+        command: &MarginCommand,
+        polls: usize,
+    ) -> Result<Option<MarginResponse>, A::Error> {
+        for _ in 0..polls {
+            let Self { status, .. } = Self::read_from(access, offset, lane)?;
+            if let Some(response) = command.response(&status) {
+                return Ok(Some(response));
+            }
+        }
+        Ok(None)
+    }
+}
+impl From<u32> for MarginingLane {
+    fn from(dword: u32) -> Self {
+        Self {
+            control: From::<u16>::from(dword as u16),
+            status: From::<u16>::from((dword >> 16) as u16),
+        }
+    }
+}
+
+/// Margining Lane Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginingLaneControl {
+    pub receiver_number: ReceiverNumber,
+    pub margin_type: MarginType,
+    pub usage_model: UsageModel,
+    pub margin_payload: u8,
+}
+
+impl From<u16> for MarginingLaneControl {
+    fn from(word: u16) -> Self {
+        let Lsb((receiver_number, margin_type, usage_model, (), margin_payload)) =
+            P5::<_, 3, 3, 1, 1, 8>(word).into();
+        Self {
+            receiver_number: From::<u8>::from(receiver_number),
+            margin_type: From::<u8>::from(margin_type),
+            usage_model: From::<bool>::from(usage_model),
+            margin_payload,
+        }
+    }
+}
+
+impl From<MarginingLaneControl> for u16 {
+    fn from(data: MarginingLaneControl) -> Self {
+        (u8::from(data.receiver_number) as u16 & 0b111)
+            | (u8::from(data.margin_type) as u16 & 0b111) << 3
+            | (bool::from(data.usage_model) as u16) << 6
+            | (data.margin_payload as u16) << 8
+    }
+}
+
+impl Register for MarginingLaneControl {
+    type Value = u16;
+    const RSVDP: u16 = 0x0080;
+    const RW1C: u16 = 0;
+}
+
+/// Margining Lane Status. Fields reflect the last margin command processed by the Receiver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginingLaneStatus {
+    /// Receiver Number Status
+    pub receiver_number: ReceiverNumber,
+    /// Margin Type Status
+    pub margin_type: MarginType,
+    /// Usage Model Status
+    pub usage_model: UsageModel,
+    /// Margin Payload Status
+    pub margin_payload: u8,
+}
+
+impl From<u16> for MarginingLaneStatus {
+    fn from(word: u16) -> Self {
+        let MarginingLaneControl {
+            receiver_number,
+            margin_type,
+            usage_model,
+            margin_payload,
+        } = word.into();
+        Self {
+            receiver_number,
+            margin_type,
+            usage_model,
+            margin_payload,
+        }
+    }
+}
+
+impl From<MarginingLaneStatus> for u16 {
+    fn from(data: MarginingLaneStatus) -> Self {
+        MarginingLaneControl {
+            receiver_number: data.receiver_number,
+            margin_type: data.margin_type,
+            usage_model: data.usage_model,
+            margin_payload: data.margin_payload,
+        }
+        .into()
+    }
+}
+
+/// Receiver Number. Receivers are named from the Downstream Port towards the Upstream Port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReceiverNumber {
+    /// Broadcast to all Receivers
+    Broadcast,
+    /// Downstream Port Receiver
+    ReceiverA,
+    /// Retimer X or Z Upstream Pseudo Port Receiver
+    ReceiverB,
+    /// Retimer X or Z Downstream Pseudo Port Receiver
+    ReceiverC,
+    /// Retimer Y Upstream Pseudo Port Receiver
+    ReceiverD,
+    /// Retimer Y Downstream Pseudo Port Receiver
+    ReceiverE,
+    /// Upstream Port Receiver
+    ReceiverF,
+    Reserved,
+}
+
+impl From<u8> for ReceiverNumber {
+    fn from(byte: u8) -> Self {
+        match byte {
+            0b000 => Self::Broadcast,
+            0b001 => Self::ReceiverA,
+            0b010 => Self::ReceiverB,
+            0b011 => Self::ReceiverC,
+            0b100 => Self::ReceiverD,
+            0b101 => Self::ReceiverE,
+            0b110 => Self::ReceiverF,
+            _ => Self::Reserved,
+        }
+    }
+}
+
+impl From<ReceiverNumber> for u8 {
+    fn from(data: ReceiverNumber) -> Self {
+        match data {
+            ReceiverNumber::Broadcast => 0b000,
+            ReceiverNumber::ReceiverA => 0b001,
+            ReceiverNumber::ReceiverB => 0b010,
+            ReceiverNumber::ReceiverC => 0b011,
+            ReceiverNumber::ReceiverD => 0b100,
+            ReceiverNumber::ReceiverE => 0b101,
+            ReceiverNumber::ReceiverF => 0b110,
+            ReceiverNumber::Reserved => 0b111,
+        }
+    }
+}
+
+/// Margin Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarginType {
+    /// Report capabilities and parameters, Access Retimer register
+    Report,
+    /// Set error count limit, Go to Normal Settings, Clear Error Log
+    Set,
+    /// Step Margin to timing offset to right/left of default
+    StepMarginTiming,
+    /// Step Margin to voltage offset to up/down of default
+    StepMarginVoltage,
+    VendorDefined,
+    NoCommand,
+    Reserved(u8),
+}
+
+impl From<u8> for MarginType {
+    fn from(byte: u8) -> Self {
+        match byte {
+            0b001 => Self::Report,
+            0b010 => Self::Set,
+            0b011 => Self::StepMarginTiming,
+            0b100 => Self::StepMarginVoltage,
+            0b101 => Self::VendorDefined,
+            0b111 => Self::NoCommand,
+            v => Self::Reserved(v),
+        }
+    }
+}
+
+impl From<MarginType> for u8 {
+    fn from(data: MarginType) -> Self {
+        match data {
+            MarginType::Report => 0b001,
+            MarginType::Set => 0b010,
+            MarginType::StepMarginTiming => 0b011,
+            MarginType::StepMarginVoltage => 0b100,
+            MarginType::VendorDefined => 0b101,
+            MarginType::NoCommand => 0b111,
+            MarginType::Reserved(v) => v,
+        }
+    }
+}
+
+/// Usage Model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UsageModel {
+    LaneMargining,
+    Reserved,
+}
+
+impl From<bool> for UsageModel {
+    fn from(b: bool) -> Self {
+        if b {
+            Self::Reserved
+        } else {
+            Self::LaneMargining
+        }
+    }
+}
+
+impl From<UsageModel> for bool {
+    fn from(data: UsageModel) -> Self {
+        matches!(data, UsageModel::Reserved)
+    }
+}
+
+/// Margin command written to [Margining Lane Control](MarginingLaneControl)
+///
+/// Commands are encoded with [Lane Margining](UsageModel::LaneMargining) usage model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarginCommand {
+    NoCommand,
+    /// Read Retimer register at `register_offset` (00h-87h)
+    AccessRetimerRegister {
+        receiver_number: ReceiverNumber,
+        register_offset: u8,
+    },
+    Report {
+        receiver_number: ReceiverNumber,
+        report: MarginReport,
+    },
+    /// Set Error Count Limit (0-63)
+    SetErrorCountLimit {
+        receiver_number: ReceiverNumber,
+        error_count_limit: u8,
+    },
+    GoToNormalSettings {
+        receiver_number: ReceiverNumber,
+    },
+    ClearErrorLog {
+        receiver_number: ReceiverNumber,
+    },
+    /// Step Margin to timing offset of `steps` (0-63) from default
+    StepMarginTiming {
+        receiver_number: ReceiverNumber,
+        direction: TimingDirection,
+        steps: u8,
+    },
+    /// Step Margin to voltage offset of `steps` (0-127) from default
+    StepMarginVoltage {
+        receiver_number: ReceiverNumber,
+        direction: VoltageDirection,
+        steps: u8,
+    },
+    VendorDefined {
+        receiver_number: ReceiverNumber,
+        margin_payload: u8,
+    },
+    /// Encoding not defined by specification
+    Reserved(MarginingLaneControl),
+}
+
+impl MarginCommand {
+    /// Decode Receiver response to this command from [Margining Lane
+    /// Status](MarginingLaneStatus)
+    ///
+    /// Returns `None` while status does not reflect this command.
+    pub fn response(&self, status: &MarginingLaneStatus) -> Option<MarginResponse> {
+        let control = MarginingLaneControl::from(self.clone());
+        let echo = status.receiver_number == control.receiver_number
+            && status.margin_type == control.margin_type
+            && status.usage_model == control.usage_model;
+        if !echo {
+            return None;
+        }
+        let payload = status.margin_payload;
+        let response = match self {
+            Self::NoCommand => {
+                return (payload == control.margin_payload).then_some(MarginResponse::NoCommand)
+            }
+            Self::AccessRetimerRegister { .. } => MarginResponse::RetimerRegister(payload),
+            Self::Report { report, .. } => match report {
+                MarginReport::Capabilities => MarginResponse::Capabilities(payload.into()),
+                MarginReport::NumVoltageSteps => MarginResponse::NumVoltageSteps(payload & 0x7f),
+                MarginReport::NumTimingSteps => MarginResponse::NumTimingSteps(payload & 0x3f),
+                MarginReport::MaxTimingOffset => MarginResponse::MaxTimingOffset(payload & 0x7f),
+                MarginReport::MaxVoltageOffset => MarginResponse::MaxVoltageOffset(payload & 0x7f),
+                MarginReport::SamplingRateVoltage => {
+                    MarginResponse::SamplingRateVoltage(payload & 0x3f)
+                }
+                MarginReport::SamplingRateTiming => {
+                    MarginResponse::SamplingRateTiming(payload & 0x3f)
+                }
+                MarginReport::SampleCount => MarginResponse::SampleCount(payload & 0x7f),
+                MarginReport::MaxLanes => MarginResponse::MaxLanes(payload & 0x1f),
+                MarginReport::Reserved(_) => MarginResponse::Reserved(payload),
+            },
+            Self::SetErrorCountLimit {
+                error_count_limit, ..
+            } if payload == control.margin_payload => {
+                MarginResponse::ErrorCountLimit(*error_count_limit & 0x3f)
+            }
+            Self::GoToNormalSettings { .. } if payload == control.margin_payload => {
+                MarginResponse::GoToNormalSettings
+            }
+            Self::ClearErrorLog { .. } if payload == control.margin_payload => {
+                MarginResponse::ClearErrorLog
+            }
+            Self::StepMarginTiming { .. } | Self::StepMarginVoltage { .. } => {
+                MarginResponse::StepMargin(payload.into())
+            }
+            Self::VendorDefined { .. } => MarginResponse::VendorDefined(payload),
+            _ => return None,
+        };
+        Some(response)
+    }
+    /// Encode Receiver `response` to this command as [Margining Lane
+    /// Status](MarginingLaneStatus)
+    pub fn response_status(&self, response: MarginResponse) -> MarginingLaneStatus {
+        let control = MarginingLaneControl::from(self.clone());
+        MarginingLaneStatus {
+            receiver_number: control.receiver_number,
+            margin_type: control.margin_type,
+            usage_model: control.usage_model,
+            margin_payload: response.into(),
+        }
+    }
+}
+
+impl From<MarginCommand> for MarginingLaneControl {
+    fn from(data: MarginCommand) -> Self {
+        let (receiver_number, margin_type, margin_payload) = match data {
+            MarginCommand::NoCommand => (ReceiverNumber::Broadcast, MarginType::NoCommand, 0x9c),
+            MarginCommand::AccessRetimerRegister {
+                receiver_number,
+                register_offset,
+            } => (receiver_number, MarginType::Report, register_offset),
+            MarginCommand::Report {
+                receiver_number,
+                report,
+            } => (receiver_number, MarginType::Report, report.into()),
+            MarginCommand::SetErrorCountLimit {
+                receiver_number,
+                error_count_limit,
+            } => (
+                receiver_number,
+                MarginType::Set,
+                0b1100_0000 | (error_count_limit & 0x3f),
+            ),
+            MarginCommand::GoToNormalSettings { receiver_number } => {
+                (receiver_number, MarginType::Set, 0x0f)
+            }
+            MarginCommand::ClearErrorLog { receiver_number } => {
+                (receiver_number, MarginType::Set, 0x55)
+            }
+            MarginCommand::StepMarginTiming {
+                receiver_number,
+                direction,
+                steps,
+            } => (
+                receiver_number,
+                MarginType::StepMarginTiming,
+                (matches!(direction, TimingDirection::Left) as u8) << 6 | (steps & 0x3f),
+            ),
+            MarginCommand::StepMarginVoltage {
+                receiver_number,
+                direction,
+                steps,
+            } => (
+                receiver_number,
+                MarginType::StepMarginVoltage,
+                (matches!(direction, VoltageDirection::Down) as u8) << 7 | (steps & 0x7f),
+            ),
+            MarginCommand::VendorDefined {
+                receiver_number,
+                margin_payload,
+            } => (receiver_number, MarginType::VendorDefined, margin_payload),
+            MarginCommand::Reserved(control) => return control,
+        };
+        Self {
+            receiver_number,
+            margin_type,
+            usage_model: UsageModel::LaneMargining,
+            margin_payload,
+        }
+    }
+}
+
+impl From<MarginingLaneControl> for MarginCommand {
+    fn from(control: MarginingLaneControl) -> Self {
+        let MarginingLaneControl {
+            receiver_number,
+            margin_type,
+            usage_model,
+            margin_payload: payload,
+        } = control.clone();
+        if usage_model != UsageModel::LaneMargining {
+            return Self::Reserved(control);
+        }
+        match (margin_type, payload) {
+            (MarginType::NoCommand, 0x9c) if receiver_number == ReceiverNumber::Broadcast => {
+                Self::NoCommand
+            }
+            (MarginType::Report, 0x00..=0x87) => Self::AccessRetimerRegister {
+                receiver_number,
+                register_offset: payload,
+            },
+            (MarginType::Report, _) => Self::Report {
+                receiver_number,
+                report: payload.into(),
+            },
+            (MarginType::Set, 0b1100_0000..=0b1111_1111) => Self::SetErrorCountLimit {
+                receiver_number,
+                error_count_limit: payload & 0x3f,
+            },
+            (MarginType::Set, 0x0f) => Self::GoToNormalSettings { receiver_number },
+            (MarginType::Set, 0x55) => Self::ClearErrorLog { receiver_number },
+            (MarginType::StepMarginTiming, _) => Self::StepMarginTiming {
+                receiver_number,
+                direction: if payload & 0x40 != 0 {
+                    TimingDirection::Left
+                } else {
+                    TimingDirection::Right
+                },
+                steps: payload & 0x3f,
+            },
+            (MarginType::StepMarginVoltage, _) => Self::StepMarginVoltage {
+                receiver_number,
+                direction: if payload & 0x80 != 0 {
+                    VoltageDirection::Down
+                } else {
+                    VoltageDirection::Up
+                },
+                steps: payload & 0x7f,
+            },
+            (MarginType::VendorDefined, _) => Self::VendorDefined {
+                receiver_number,
+                margin_payload: payload,
+            },
+            _ => Self::Reserved(control),
+        }
+    }
+}
+
+/// Report margin command payloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarginReport {
+    /// Report Margin Control Capabilities
+    Capabilities,
+    /// Report MNumVoltageSteps
+    NumVoltageSteps,
+    /// Report MNumTimingSteps
+    NumTimingSteps,
+    /// Report MMaxTimingOffset
+    MaxTimingOffset,
+    /// Report MMaxVoltageOffset
+    MaxVoltageOffset,
+    /// Report MSamplingRateVoltage
+    SamplingRateVoltage,
+    /// Report MSamplingRateTiming
+    SamplingRateTiming,
+    /// Report MSampleCount
+    SampleCount,
+    /// Report MMaxLanes
+    MaxLanes,
+    Reserved(u8),
+}
+
+impl From<u8> for MarginReport {
+    fn from(byte: u8) -> Self {
+        match byte {
+            0x88 => Self::Capabilities,
+            0x89 => Self::NumVoltageSteps,
+            0x8a => Self::NumTimingSteps,
+            0x8b => Self::MaxTimingOffset,
+            0x8c => Self::MaxVoltageOffset,
+            0x8d => Self::SamplingRateVoltage,
+            0x8e => Self::SamplingRateTiming,
+            0x8f => Self::SampleCount,
+            0x90 => Self::MaxLanes,
+            v => Self::Reserved(v),
+        }
+    }
+}
+
+impl From<MarginReport> for u8 {
+    fn from(data: MarginReport) -> Self {
+        match data {
+            MarginReport::Capabilities => 0x88,
+            MarginReport::NumVoltageSteps => 0x89,
+            MarginReport::NumTimingSteps => 0x8a,
+            MarginReport::MaxTimingOffset => 0x8b,
+            MarginReport::MaxVoltageOffset => 0x8c,
+            MarginReport::SamplingRateVoltage => 0x8d,
+            MarginReport::SamplingRateTiming => 0x8e,
+            MarginReport::SampleCount => 0x8f,
+            MarginReport::MaxLanes => 0x90,
+            MarginReport::Reserved(v) => v,
+        }
+    }
+}
+
+/// Timing margin direction. Left is used only with
+/// [independent left/right timing](MarginingCapabilities::independent_left_right_timing).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimingDirection {
+    Right,
+    Left,
+}
+
+/// Voltage margin direction. Down is used only with
+/// [independent up/down voltage](MarginingCapabilities::independent_up_down_voltage).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoltageDirection {
+    Up,
+    Down,
+}
+
+/// Receiver response reported in [Margining Lane Status](MarginingLaneStatus)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarginResponse {
+    NoCommand,
+    /// Retimer register value
+    RetimerRegister(u8),
+    Capabilities(MarginingCapabilities),
+    /// MNumVoltageSteps
+    NumVoltageSteps(u8),
+    /// MNumTimingSteps
+    NumTimingSteps(u8),
+    /// MMaxTimingOffset in percentage of one UI
+    MaxTimingOffset(u8),
+    /// MMaxVoltageOffset in 10 mV units
+    MaxVoltageOffset(u8),
+    /// MSamplingRateVoltage
+    SamplingRateVoltage(u8),
+    /// MSamplingRateTiming
+    SamplingRateTiming(u8),
+    /// MSampleCount
+    SampleCount(u8),
+    /// MMaxLanes
+    MaxLanes(u8),
+    /// Response to reserved Report payload
+    Reserved(u8),
+    ErrorCountLimit(u8),
+    GoToNormalSettings,
+    ClearErrorLog,
+    StepMargin(StepMarginResponse),
+    VendorDefined(u8),
+}
+
+impl From<MarginResponse> for u8 {
+    fn from(data: MarginResponse) -> Self {
+        match data {
+            MarginResponse::NoCommand => 0x9c,
+            MarginResponse::Capabilities(caps) => caps.into(),
+            MarginResponse::NumVoltageSteps(v)
+            | MarginResponse::MaxTimingOffset(v)
+            | MarginResponse::MaxVoltageOffset(v)
+            | MarginResponse::SampleCount(v) => v & 0x7f,
+            MarginResponse::NumTimingSteps(v)
+            | MarginResponse::SamplingRateVoltage(v)
+            | MarginResponse::SamplingRateTiming(v) => v & 0x3f,
+            MarginResponse::MaxLanes(v) => v & 0x1f,
+            MarginResponse::ErrorCountLimit(v) => 0b1100_0000 | (v & 0x3f),
+            MarginResponse::GoToNormalSettings => 0x0f,
+            MarginResponse::ClearErrorLog => 0x55,
+            MarginResponse::StepMargin(step) => step.into(),
+            MarginResponse::RetimerRegister(v)
+            | MarginResponse::Reserved(v)
+            | MarginResponse::VendorDefined(v) => v,
+        }
+    }
+}
+
+/// Response to Report Margin Control Capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginingCapabilities {
+    /// MVoltageSupported
+    pub voltage_supported: bool,
+    /// MIndUpDownVoltage
+    pub independent_up_down_voltage: bool,
+    /// MIndLeftRightTiming
+    pub independent_left_right_timing: bool,
+    /// MSampleReportingMethod. Sampling rates are reported instead of sample count.
+    pub sample_reporting_method: bool,
+    /// MIndErrorSampler
+    pub independent_error_sampler: bool,
+}
+
+impl From<u8> for MarginingCapabilities {
+    fn from(byte: u8) -> Self {
+        let Lsb((
+            voltage_supported,
+            independent_up_down_voltage,
+            independent_left_right_timing,
+            sample_reporting_method,
+            independent_error_sampler,
+            (),
+        )) = P6::<_, 1, 1, 1, 1, 1, 3>(byte).into();
+        Self {
+            voltage_supported,
+            independent_up_down_voltage,
+            independent_left_right_timing,
+            sample_reporting_method,
+            independent_error_sampler,
+        }
+    }
+}
+
+impl From<MarginingCapabilities> for u8 {
+    fn from(data: MarginingCapabilities) -> Self {
+        (data.voltage_supported as u8)
+            | (data.independent_up_down_voltage as u8) << 1
+            | (data.independent_left_right_timing as u8) << 2
+            | (data.sample_reporting_method as u8) << 3
+            | (data.independent_error_sampler as u8) << 4
+    }
+}
+
+/// Response to Step Margin commands
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepMarginResponse {
+    pub execution_status: MarginExecutionStatus,
+    /// MErrorCount. Number of errors detected since the last Clear Error Log.
+    pub error_count: u8,
+}
+
+impl From<u8> for StepMarginResponse {
+    fn from(byte: u8) -> Self {
+        let Lsb((error_count, execution_status)) = P2::<_, 6, 2>(byte).into();
+        Self {
+            execution_status: From::<u8>::from(execution_status),
+            error_count,
+        }
+    }
+}
+
+impl From<StepMarginResponse> for u8 {
+    fn from(data: StepMarginResponse) -> Self {
+        u8::from(data.execution_status) << 6 | (data.error_count & 0x3f)
+    }
+}
+
+/// Margin Execution Status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarginExecutionStatus {
+    /// Error count exceeded Error Count Limit, Receiver returned to default settings
+    TooManyErrors,
+    /// Receiver is getting ready but has not yet started margining
+    SetUpInProgress,
+    MarginingInProgress,
+    /// Command is not supported or offset is out of range
+    Nak,
+}
+
+impl From<u8> for MarginExecutionStatus {
+    fn from(byte: u8) -> Self {
+        match byte & 0b11 {
+            0b00 => Self::TooManyErrors,
+            0b01 => Self::SetUpInProgress,
+            0b10 => Self::MarginingInProgress,
+            _ => Self::Nak,
+        }
+    }
+}
+
+impl From<MarginExecutionStatus> for u8 {
+    fn from(data: MarginExecutionStatus) -> Self {
+        match data {
+            MarginExecutionStatus::TooManyErrors => 0b00,
+            MarginExecutionStatus::SetUpInProgress => 0b01,
+            MarginExecutionStatus::MarginingInProgress => 0b10,
+            MarginExecutionStatus::Nak => 0b11,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    /// Receiver accepting voltage margining up to 50 steps and reporting 3 errors
+    fn simulated_receiver(control: MarginingLaneControl) -> MarginingLaneStatus {
+        let command = MarginCommand::from(control.clone());
+        let response = match &command {
+            MarginCommand::NoCommand => MarginResponse::NoCommand,
+            MarginCommand::Report {
+                report: MarginReport::Capabilities,
+                ..
+            } => MarginResponse::Capabilities(MarginingCapabilities {
+                voltage_supported: true,
+                independent_up_down_voltage: true,
+                independent_left_right_timing: false,
+                sample_reporting_method: false,
+                independent_error_sampler: false,
+            }),
+            MarginCommand::Report {
+                report: MarginReport::NumVoltageSteps,
+                ..
+            } => MarginResponse::NumVoltageSteps(50),
+            MarginCommand::StepMarginVoltage { steps, .. } => {
+                MarginResponse::StepMargin(StepMarginResponse {
+                    execution_status: if *steps > 50 {
+                        MarginExecutionStatus::Nak
+                    } else {
+                        MarginExecutionStatus::MarginingInProgress
+                    },
+                    error_count: 3,
+                })
+            }
+            MarginCommand::GoToNormalSettings { .. } => MarginResponse::GoToNormalSettings,
+            _ => return u16::from(control).into(),
+        };
+        command.response_status(response)
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let receiver_number = ReceiverNumber::ReceiverA;
+        let commands = [
+            MarginCommand::NoCommand,
+            MarginCommand::AccessRetimerRegister {
+                receiver_number: ReceiverNumber::ReceiverB,
+                register_offset: 0x04,
+            },
+            MarginCommand::Report {
+                receiver_number,
+                report: MarginReport::MaxLanes,
+            },
+            MarginCommand::SetErrorCountLimit {
+                receiver_number,
+                error_count_limit: 4,
+            },
+            MarginCommand::GoToNormalSettings { receiver_number },
+            MarginCommand::ClearErrorLog { receiver_number },
+            MarginCommand::StepMarginTiming {
+                receiver_number,
+                direction: TimingDirection::Left,
+                steps: 7,
+            },
+            MarginCommand::StepMarginVoltage {
+                receiver_number,
+                direction: VoltageDirection::Down,
+                steps: 100,
+            },
+            MarginCommand::VendorDefined {
+                receiver_number,
+                margin_payload: 0xa5,
+            },
+        ];
+        for command in commands {
+            let word = u16::from(MarginingLaneControl::from(command.clone()));
+            assert_eq!(command, MarginingLaneControl::from(word).into());
+        }
+        // Margin Type 000b is reserved
+        let control = MarginingLaneControl::from(0x0001);
+        assert_eq!(
+            MarginCommand::Reserved(control.clone()),
+            control.clone().into()
+        );
+    }
+
+    /// Port with the capability at 100h and a single Lane margined by [simulated_receiver]
+    ///
+    /// Receiver updates Margining Lane Status after `delay` status reads.
+    struct Port {
+        delay: usize,
+        state: RefCell<PortState>,
+    }
+
+    #[derive(Default)]
+    struct PortState {
+        control: u16,
+        status: u16,
+        pending: Option<usize>,
+        writes: Vec<u32>,
+    }
+
+    impl Port {
+        const BASE: u16 = 0x100;
+
+        fn new(delay: usize) -> Self {
+            Self {
+                delay,
+                state: Default::default(),
+            }
+        }
+    }
+
+    impl ConfigAccess for Port {
+        type Error = ();
+
+        fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+            let mut state = self.state.borrow_mut();
+            match offset.checked_sub(Self::BASE).ok_or(())? {
+                0x00 => Ok(0x0001_0027),
+                // Margining Ready
+                0x04 => Ok(1 << 16),
+                0x08 => {
+                    match state.pending {
+                        Some(0) => {
+                            state.status = simulated_receiver(state.control.into()).into();
+                            state.pending = None;
+                        }
+                        Some(n) => state.pending = Some(n - 1),
+                        None => (),
+                    }
+                    Ok(state.control as u32 | (state.status as u32) << 16)
+                }
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl ConfigWrite for Port {
+        fn write_u32(&self, offset: u16, value: u32) -> Result<(), Self::Error> {
+            let mut state = self.state.borrow_mut();
+            match offset.checked_sub(Self::BASE).ok_or(())? {
+                0x08 => {
+                    state.writes.push(value);
+                    state.control = value as u16;
+                    state.pending = Some(self.delay);
+                }
+                0x00..=0x07 => (),
+                _ => return Err(()),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn margining_script() {
+        let receiver_number = ReceiverNumber::ReceiverA;
+        let port = Port::new(2);
+        let issue = |command: MarginCommand| {
+            MarginingLane::write_command(&port, Port::BASE, 0, command.clone()).unwrap();
+            MarginingLane::poll_response(&port, Port::BASE, 0, &command, 4).unwrap()
+        };
+        let run = |command: MarginCommand| {
+            assert_eq!(
+                Some(MarginResponse::NoCommand),
+                issue(MarginCommand::NoCommand)
+            );
+            issue(command)
+        };
+
+        let caps = run(MarginCommand::Report {
+            receiver_number,
+            report: MarginReport::Capabilities,
+        });
+        assert!(matches!(
+            caps,
+            Some(MarginResponse::Capabilities(MarginingCapabilities {
+                voltage_supported: true,
+                independent_up_down_voltage: true,
+                ..
+            }))
+        ));
+
+        let steps = run(MarginCommand::Report {
+            receiver_number,
+            report: MarginReport::NumVoltageSteps,
+        });
+        assert_eq!(Some(MarginResponse::NumVoltageSteps(50)), steps);
+
+        let step = |steps| {
+            run(MarginCommand::StepMarginVoltage {
+                receiver_number,
+                direction: VoltageDirection::Down,
+                steps,
+            })
+        };
+        assert_eq!(
+            Some(MarginResponse::StepMargin(StepMarginResponse {
+                execution_status: MarginExecutionStatus::MarginingInProgress,
+                error_count: 3,
+            })),
+            step(50)
+        );
+        assert!(matches!(
+            step(51),
+            Some(MarginResponse::StepMargin(StepMarginResponse {
+                execution_status: MarginExecutionStatus::Nak,
+                ..
+            }))
+        ));
+
+        assert_eq!(
+            Some(MarginResponse::GoToNormalSettings),
+            run(MarginCommand::GoToNormalSettings { receiver_number })
+        );
+        assert_eq!(
+            Some(MarginResponse::NoCommand),
+            run(MarginCommand::NoCommand)
+        );
+
+        // Margining Lane Status is never written
+        let writes = port.state.borrow().writes.clone();
+        assert_eq!(12, writes.len());
+        assert!(writes.iter().all(|dword| dword >> 16 == 0));
+        assert_eq!(0x0f11, writes[9]);
+
+        // Status of another Receiver is not a response
+        let command = MarginCommand::GoToNormalSettings {
+            receiver_number: ReceiverNumber::ReceiverF,
+        };
+        assert_eq!(
+            Ok(None),
+            MarginingLane::poll_response(&port, Port::BASE, 0, &command, 4)
+        );
+    }
+
+    #[test]
+    fn slow_receiver() {
+        let port = Port::new(8);
+        let command = MarginCommand::Report {
+            receiver_number: ReceiverNumber::ReceiverA,
+            report: MarginReport::NumVoltageSteps,
+        };
+        MarginingLane::write_command(&port, Port::BASE, 0, command.clone()).unwrap();
+        let poll = |polls| MarginingLane::poll_response(&port, Port::BASE, 0, &command, polls);
+        assert_eq!(Ok(None), poll(4));
+        assert_eq!(Ok(Some(MarginResponse::NumVoltageSteps(50))), poll(8));
+        // Lane 1 is not implemented by the Port
+        assert_eq!(
+            Err(()),
+            MarginingLane::write_command(&port, Port::BASE, 1, command.clone())
+        );
+    }
+
+    #[test]
+    fn read_lane_from_config_space() {
+        // Capability at 100h, Lane 1 Receiver F responded with MNumVoltageSteps = 32
+        let mut config = [0u8; 0x110];
+        config[0x100..0x110].copy_from_slice(&[
+            0x27, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x00, 0x38, 0x9c, 0x38, 0x9c, 0x0e, 0x89,
+            0x0e, 0x20,
+        ]);
+        let lane = MarginingLane::read_from(config.as_slice(), 0x100, 1).unwrap();
+        let command = MarginCommand::from(lane.control.clone());
+        assert_eq!(
+            MarginCommand::Report {
+                receiver_number: ReceiverNumber::ReceiverF,
+                report: MarginReport::NumVoltageSteps,
+            },
+            command
+        );
+        assert_eq!(
+            Some(MarginResponse::NumVoltageSteps(32)),
+            command.response(&lane.status)
+        );
+        assert!(MarginingLane::read_from(config.as_slice(), 0x100, 2).is_err());
+
+        let lmr: LaneMarginingAtTheReceiver = config[0x104..].try_into().unwrap();
+        assert!(lmr.port_status.margining_software_ready);
+        assert_eq!(2, lmr.lanes(LinkWidth::X4).count());
+        assert_eq!(3 + 4 * 2, lmr.registers(LinkWidth::X4).iter().count());
+    }
+}
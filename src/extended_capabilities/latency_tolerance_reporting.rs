@@ -33,8 +33,10 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LatencyToleranceReporting {
     /// Max Snoop Latency
     pub max_snoop_latency: MaxLatency,
@@ -61,7 +63,15 @@ impl TryFrom<&[u8]> for LatencyToleranceReporting {
     }
 }
 
+/// LTR Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 2).rw(0x1fff).rsvdp(0xe000), // Max Snoop Latency
+    Attr::new(0x06, 2).rw(0x1fff).rsvdp(0xe000), // Max No-Snoop Latency
+];
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaxLatency {
     /// Specifies the maximum latency that a device is permitted to request
     pub value: u16,
@@ -42,9 +42,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P3, P5};
 
 use super::{ExtendedCapabilityDataError, ExtendedCapabilityHeaderPlaceholder};
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// LN Requester
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LnRequester {
     pub lnr_capability: LnrCapability,
     pub lnr_control: LnrControl,
@@ -78,8 +80,16 @@ impl TryFrom<&[u8]> for LnRequester {
     }
 }
 
+/// LNR Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 2),                          // LNR Capability
+    Attr::new(0x06, 2).rw(0x1f03).rsvdp(0xe0fc), // LNR Control
+];
+
 /// LNR Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LnrCapability {
     /// Endpoint supports LN protocol for 64-byte cachelines as a Requester
     pub lnr_64_supported: bool,
@@ -104,6 +114,7 @@ impl From<u16> for LnrCapability {
 
 /// LNR Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LnrControl {
     /// Endpoint is enabled to operate as an LN Requester
     pub lnr_enable: bool,
@@ -125,3 +136,17 @@ impl From<u16> for LnrControl {
         }
     }
 }
+
+impl From<LnrControl> for u16 {
+    fn from(data: LnrControl) -> Self {
+        (data.lnr_enable as u16)
+            | (data.lnr_cls as u16) << 1
+            | (data.lnr_registration_limit as u16 & 0x1f) << 8
+    }
+}
+
+impl Register for LnrControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xe0fc;
+    const RW1C: u16 = 0;
+}
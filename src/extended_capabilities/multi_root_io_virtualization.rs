@@ -0,0 +1,508 @@
+//! Multi-Root I/O Virtualization (MR-IOV)
+//!
+//! Multi-Root I/O Virtualization and Sharing (MR-IOV) lets several Root Complexes share a Device
+//! through MR-IOV switches. Every Root Complex sees its own Virtual Hierarchy (VH). The capability
+//! of a Base Function enables MR-IOV and locates the VH Table and the Function Table, both
+//! residing in Memory Space.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! # use pcics::extended_capabilities::multi_root_io_virtualization::*;
+//! let data = [
+//!     0x11, 0x00, 0x01, 0x00, // Extended Capability Header
+//!     0x01, 0x00, 0x00, 0x00, // MR-IOV Capabilities
+//!     0x01, 0x00, 0x00, 0x00, // MR-IOV Control, MR-IOV Status
+//!     0x02, 0x00, 0x02, 0x00, // Total VHs, Num VHs
+//!     0x03, 0x00, 0x00, 0x00, // Function Table Size
+//!     0x02, 0x20, 0x00, 0x00, // VH Table Offset/BIR
+//!     0x02, 0x30, 0x00, 0x00, // Function Table Offset/BIR
+//! ];
+//! let mriov: MultiRootIoVirtualization = data[4..].try_into().unwrap();
+//! assert!(mriov.mriov_control.mriov_enable);
+//!
+//! // BAR2 memory containing VH Table at 2000h and Function Table at 3000h
+//! let mut bar = [0u8; 0x3010];
+//! bar[0x2000..0x2008].copy_from_slice(&[0x01, 0x02, 0x00, 0x00, 0x01, 0x01, 0x02, 0x00]);
+//! bar[0x3000..0x300c].copy_from_slice(&[
+//!     0x00, 0x00, 0x03, 0x00, 0x01, 0x00, 0x03, 0x00, 0x00, 0x01, 0x03, 0x00,
+//! ]);
+//! let vhs = mriov.vh_table(&bar).collect::<Vec<_>>();
+//! assert_eq!(2, vhs.len());
+//! assert_eq!(2, vhs[1].first_function);
+//! let functions = mriov.function_table(&bar).collect::<Vec<_>>();
+//! assert_eq!(1, functions[2].vh);
+//! assert_eq!(FunctionState::ActiveAvailable, functions[2].state);
+//! ```
+
+use core::slice::Chunks;
+
+use heterob::{
+    bit_numbering::Lsb,
+    endianness::{FromLeBytes, LeBytesInto},
+    P2, P3, P4, P5, P9,
+};
+
+use super::ExtendedCapabilityDataError;
+use crate::capabilities::msi_x::Bir;
+use crate::register::{Register, RegisterAttributes as Attr};
+
+/// Multi-Root I/O Virtualization (MR-IOV)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiRootIoVirtualization {
+    /// MR-IOV Capabilities
+    pub mriov_capabilities: MriovCapabilities,
+    /// MR-IOV Control
+    pub mriov_control: MriovControl,
+    /// MR-IOV Status
+    pub mriov_status: MriovStatus,
+    /// TotalVHs (RO). Number of VHs the Device supports, equals to VH Table entries count.
+    pub total_vhs: u16,
+    /// NumVHs (RW). Number of VHs enabled by MR-PCIM.
+    pub num_vhs: u16,
+    /// Function Table Size (RO). Number of Function Table entries.
+    pub function_table_size: u16,
+    /// VH Table Offset/BIR (RO)
+    pub vh_table: TableLocation,
+    /// Function Table Offset/BIR (RO)
+    pub function_table: TableLocation,
+}
+impl MultiRootIoVirtualization {
+    pub const BYTES: usize = 0x1c - super::ECH_BYTES;
+
+    /// VH Table entries found in `bar` memory, `bar` starts at the address of BAR indicated by
+    /// [VH Table BIR](TableLocation::bir)
+    pub fn vh_table<'a>(&self, bar: &'a [u8]) -> VhTable<'a> {
+        VhTable::new(
+            self.vh_table
+                .entries(bar, VhTableEntry::SIZE, self.total_vhs),
+        )
+    }
+
+    /// Function Table entries found in `bar` memory, `bar` starts at the address of BAR
+    /// indicated by [Function Table BIR](TableLocation::bir)
+    pub fn function_table<'a>(&self, bar: &'a [u8]) -> FunctionTable<'a> {
+        FunctionTable::new(self.function_table.entries(
+            bar,
+            FunctionTableEntry::SIZE,
+            self.function_table_size,
+        ))
+    }
+}
+
+impl From<[u8; MultiRootIoVirtualization::BYTES]> for MultiRootIoVirtualization {
+    fn from(bytes: [u8; Self::BYTES]) -> Self {
+        let P9((
+            mriov_capabilities,
+            mriov_control,
+            mriov_status,
+            total_vhs,
+            num_vhs,
+            function_table_size,
+            rsvdp,
+            vh_table,
+            function_table,
+        )) = bytes.le_bytes_into();
+        let _: u16 = rsvdp;
+        Self {
+            mriov_capabilities,
+            mriov_control,
+            mriov_status,
+            total_vhs,
+            num_vhs,
+            function_table_size,
+            vh_table: From::<u32>::from(vh_table),
+            function_table: From::<u32>::from(function_table),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for MultiRootIoVirtualization {
+    type Error = ExtendedCapabilityDataError;
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        slice
+            .get(..Self::BYTES)
+            .and_then(|slice| <[u8; Self::BYTES]>::try_from(slice).ok())
+            .ok_or(ExtendedCapabilityDataError {
+                name: "Multi-Root I/O Virtualization",
+                size: Self::BYTES,
+            })
+            .map(Self::from)
+    }
+}
+
+/// MR-IOV Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                            // Extended Capability Header
+    Attr::new(0x04, 4),                            // MR-IOV Capabilities
+    Attr::new(0x08, 2).rw(0x0007).rsvdp(0xfff8),   // MR-IOV Control
+    Attr::new(0x0a, 2).rw1c(0x0001).rsvdz(0xfffe), // MR-IOV Status
+    Attr::new(0x0c, 2),                            // TotalVHs
+    Attr::new(0x0e, 2).rw(0xffff),                 // NumVHs
+    Attr::new(0x10, 2),                            // Function Table Size
+    Attr::new(0x12, 2).rsvdp(0xffff),
+    Attr::new(0x14, 4), // VH Table Offset/BIR
+    Attr::new(0x18, 4), // Function Table Offset/BIR
+];
+
+/// MR-IOV Capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MriovCapabilities {
+    /// VH Migration Capable
+    pub vh_migration_capable: bool,
+    /// VH Migration Interrupt Message Number
+    pub vh_migration_interrupt_message_number: u16,
+}
+impl From<u32> for MriovCapabilities {
+    fn from(dword: u32) -> Self {
+        let Lsb((vh_migration_capable, (), vh_migration_interrupt_message_number)) =
+            P3::<_, 1, 20, 11>(dword).into();
+        Self {
+            vh_migration_capable,
+            vh_migration_interrupt_message_number,
+        }
+    }
+}
+impl FromLeBytes<4> for MriovCapabilities {
+    fn from_le_bytes(bytes: [u8; 4]) -> Self {
+        u32::from_le_bytes(bytes).into()
+    }
+}
+
+/// MR-IOV Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MriovControl {
+    /// MR-IOV Enable
+    pub mriov_enable: bool,
+    /// VH Migration Enable
+    pub vh_migration_enable: bool,
+    /// VH Migration Interrupt Enable
+    pub vh_migration_interrupt_enable: bool,
+}
+impl From<u16> for MriovControl {
+    fn from(word: u16) -> Self {
+        let Lsb((mriov_enable, vh_migration_enable, vh_migration_interrupt_enable, ())) =
+            P4::<_, 1, 1, 1, 13>(word).into();
+        Self {
+            mriov_enable,
+            vh_migration_enable,
+            vh_migration_interrupt_enable,
+        }
+    }
+}
+
+impl From<MriovControl> for u16 {
+    fn from(data: MriovControl) -> Self {
+        (data.mriov_enable as u16)
+            | (data.vh_migration_enable as u16) << 1
+            | (data.vh_migration_interrupt_enable as u16) << 2
+    }
+}
+
+impl Register for MriovControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xfff8;
+    const RW1C: u16 = 0;
+}
+impl FromLeBytes<2> for MriovControl {
+    fn from_le_bytes(bytes: [u8; 2]) -> Self {
+        u16::from_le_bytes(bytes).into()
+    }
+}
+
+/// MR-IOV Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MriovStatus {
+    /// VH Migration Status
+    pub vh_migration_status: bool,
+}
+impl From<u16> for MriovStatus {
+    fn from(word: u16) -> Self {
+        let Lsb((vh_migration_status, ())) = P2::<_, 1, 15>(word).into();
+        Self {
+            vh_migration_status,
+        }
+    }
+}
+
+impl From<MriovStatus> for u16 {
+    fn from(data: MriovStatus) -> Self {
+        data.vh_migration_status as u16
+    }
+}
+
+impl Register for MriovStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0001;
+}
+impl FromLeBytes<2> for MriovStatus {
+    fn from_le_bytes(bytes: [u8; 2]) -> Self {
+        u16::from_le_bytes(bytes).into()
+    }
+}
+
+/// Table Offset/Table BIR. Location of a table residing in Memory Space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableLocation {
+    pub bir: Bir,
+    /// QWORD aligned offset from the address contained by the BAR indicated by
+    /// [BIR](Self::bir)
+    pub offset: u32,
+}
+impl TableLocation {
+    fn entries<'a>(&self, bar: &'a [u8], size: usize, count: u16) -> &'a [u8] {
+        let start = (self.offset as usize).min(bar.len());
+        let end = start.saturating_add(size * count as usize).min(bar.len());
+        &bar[start..end]
+    }
+}
+impl From<u32> for TableLocation {
+    fn from(dword: u32) -> Self {
+        let Lsb((bir, offset)) = P2::<_, 3, 29>(dword).into();
+        let _: u32 = offset;
+        Self {
+            bir: From::<u8>::from(bir),
+            offset: offset << 3,
+        }
+    }
+}
+
+/// An iterator through VH Table entries
+#[derive(Debug, Clone)]
+pub struct VhTable<'a> {
+    chunks: Chunks<'a, u8>,
+}
+impl<'a> VhTable<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            chunks: bytes.chunks(VhTableEntry::SIZE),
+        }
+    }
+}
+impl<'a> Iterator for VhTable<'a> {
+    type Item = VhTableEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes: [u8; VhTableEntry::SIZE] = self.chunks.next()?.try_into().ok()?;
+        Some(u32::from_le_bytes(bytes).into())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for VhTable<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+/// VH Table entry. Describes Functions assigned to a Virtual Hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VhTableEntry {
+    /// VH Enable (RW)
+    pub vh_enable: bool,
+    /// VH Reset (RW)
+    pub vh_reset: bool,
+    /// Number of Functions assigned to the VH (RW)
+    pub number_of_functions: u8,
+    /// Function Table index of the first Function assigned to the VH (RW)
+    pub first_function: u16,
+}
+impl VhTableEntry {
+    pub const SIZE: usize = 4;
+}
+impl From<u32> for VhTableEntry {
+    fn from(dword: u32) -> Self {
+        let Lsb((vh_enable, vh_reset, (), number_of_functions, first_function)) =
+            P5::<_, 1, 1, 6, 8, 16>(dword).into();
+        Self {
+            vh_enable,
+            vh_reset,
+            number_of_functions,
+            first_function,
+        }
+    }
+}
+impl From<VhTableEntry> for u32 {
+    fn from(data: VhTableEntry) -> Self {
+        (data.vh_enable as u32)
+            | (data.vh_reset as u32) << 1
+            | (data.number_of_functions as u32) << 8
+            | (data.first_function as u32) << 16
+    }
+}
+
+/// An iterator through Function Table entries
+#[derive(Debug, Clone)]
+pub struct FunctionTable<'a> {
+    chunks: Chunks<'a, u8>,
+}
+impl<'a> FunctionTable<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            chunks: bytes.chunks(FunctionTableEntry::SIZE),
+        }
+    }
+}
+impl<'a> Iterator for FunctionTable<'a> {
+    type Item = FunctionTableEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes: [u8; FunctionTableEntry::SIZE] = self.chunks.next()?.try_into().ok()?;
+        Some(u32::from_le_bytes(bytes).into())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for FunctionTable<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+/// Function Table entry. Assigns a Function to a Virtual Hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionTableEntry {
+    /// Function Number seen in the VH (RW)
+    pub function_number: u8,
+    /// VH Number the Function is assigned to (RW)
+    pub vh: u8,
+    /// Function State (RW)
+    pub state: FunctionState,
+}
+impl FunctionTableEntry {
+    pub const SIZE: usize = 4;
+}
+impl From<u32> for FunctionTableEntry {
+    fn from(dword: u32) -> Self {
+        let Lsb((function_number, vh, state, ())) = P4::<_, 8, 8, 2, 14>(dword).into();
+        Self {
+            function_number,
+            vh,
+            state: From::<u8>::from(state),
+        }
+    }
+}
+impl From<FunctionTableEntry> for u32 {
+    fn from(data: FunctionTableEntry) -> Self {
+        (data.function_number as u32) | (data.vh as u32) << 8 | (u8::from(data.state) as u32) << 16
+    }
+}
+
+/// Function State
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FunctionState {
+    /// Function is not available to the VH
+    InactiveUnavailable,
+    /// Function is being migrated into the VH
+    DormantMigrateIn,
+    /// Function is being migrated out of the VH
+    ActiveMigrateOut,
+    /// Function is available to the VH
+    ActiveAvailable,
+}
+impl From<u8> for FunctionState {
+    fn from(byte: u8) -> Self {
+        match byte & 0b11 {
+            0b00 => Self::InactiveUnavailable,
+            0b01 => Self::DormantMigrateIn,
+            0b10 => Self::ActiveMigrateOut,
+            _ => Self::ActiveAvailable,
+        }
+    }
+}
+impl From<FunctionState> for u8 {
+    fn from(data: FunctionState) -> Self {
+        match data {
+            FunctionState::InactiveUnavailable => 0b00,
+            FunctionState::DormantMigrateIn => 0b01,
+            FunctionState::ActiveMigrateOut => 0b10,
+            FunctionState::ActiveAvailable => 0b11,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    // MR-IOV Capabilities: VH Migration+, Interrupt Message Number: 002
+    // MR-IOV Control: Enable+ Migration+ Interrupt-
+    // MR-IOV Status: Migration+
+    // Total VHs: 4, Num VHs: 2, Function Table Size: 8
+    // VH Table: BAR 0 offset 00010000, Function Table: BAR 0 offset 00010040
+    const DATA: [u8; 24] = [
+        0x01, 0x00, 0x40, 0x00, 0x03, 0x00, 0x01, 0x00, 0x04, 0x00, 0x02, 0x00, 0x08, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x40, 0x00, 0x01, 0x00,
+    ];
+
+    #[test]
+    fn parse_full_struct() {
+        let result: MultiRootIoVirtualization = DATA.into();
+
+        let sample = MultiRootIoVirtualization {
+            mriov_capabilities: MriovCapabilities {
+                vh_migration_capable: true,
+                vh_migration_interrupt_message_number: 2,
+            },
+            mriov_control: MriovControl {
+                mriov_enable: true,
+                vh_migration_enable: true,
+                vh_migration_interrupt_enable: false,
+            },
+            mriov_status: MriovStatus {
+                vh_migration_status: true,
+            },
+            total_vhs: 4,
+            num_vhs: 2,
+            function_table_size: 8,
+            vh_table: TableLocation {
+                bir: Bir::Bar10h,
+                offset: 0x10000,
+            },
+            function_table: TableLocation {
+                bir: Bir::Bar10h,
+                offset: 0x10040,
+            },
+        };
+        assert_eq!(sample, result);
+    }
+
+    #[test]
+    fn tables_out_of_bar() {
+        let mriov: MultiRootIoVirtualization = DATA.into();
+        let mut bar = vec![0u8; 0x10048];
+        bar[0x10040..].copy_from_slice(&[0x02, 0x01, 0x01, 0x00, 0x03, 0x01, 0x02, 0x00]);
+        // Function Table is truncated by the end of BAR memory
+        let result = mriov.function_table(&bar).collect::<Vec<_>>();
+        let sample = vec![
+            FunctionTableEntry {
+                function_number: 2,
+                vh: 1,
+                state: FunctionState::DormantMigrateIn,
+            },
+            FunctionTableEntry {
+                function_number: 3,
+                vh: 1,
+                state: FunctionState::ActiveMigrateOut,
+            },
+        ];
+        assert_eq!(sample, result);
+        assert_eq!(4, mriov.vh_table(&bar).count());
+        assert_eq!(0, mriov.vh_table(&bar[..0x8000]).count());
+        for entry in sample {
+            let dword = u32::from(entry.clone());
+            assert_eq!(entry, dword.into());
+        }
+    }
+}
@@ -64,9 +64,11 @@ use heterob::{
 };
 
 use super::{ExtendedCapabilityDataError, ExtendedCapabilityHeaderPlaceholder};
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
 
 /// Multicast
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Multicast {
     pub multicast_capability: MulticastCapability,
     pub multicast_control: MulticastControl,
@@ -130,8 +132,38 @@ impl TryFrom<&[u8]> for Multicast {
     }
 }
 
+impl Multicast {
+    /// Access attributes of registers present in the decoded structure
+    pub fn registers(&self) -> RegisterMap {
+        let map = RegisterMap::from(REGISTERS);
+        if self.mc_overlay_bar.is_some() {
+            map
+        } else {
+            map.truncate(0x28)
+        }
+    }
+}
+
+/// Multicast Capability registers, MC Overlay BAR is present only in Switch and Root Ports
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 2),                          // Multicast Capability
+    Attr::new(0x06, 2).rw(0x803f).rsvdp(0x7fc0), // Multicast Control
+    Attr::new(0x08, 4).rw(0xffff_f03f).rsvdp(0x0000_0fc0), // MC_Base_Address (Lower DW)
+    Attr::new(0x0c, 4).rw(0xffff_ffff),          // MC_Base_Address (Upper DW)
+    Attr::new(0x10, 4).rw(0xffff_ffff),          // MC_Receive (Lower DW)
+    Attr::new(0x14, 4).rw(0xffff_ffff),          // MC_Receive (Upper DW)
+    Attr::new(0x18, 4).rw(0xffff_ffff),          // MC_Block_All (Lower DW)
+    Attr::new(0x1c, 4).rw(0xffff_ffff),          // MC_Block_All (Upper DW)
+    Attr::new(0x20, 4).rw(0xffff_ffff),          // MC_Block_Untranslated (Lower DW)
+    Attr::new(0x24, 4).rw(0xffff_ffff),          // MC_Block_Untranslated (Upper DW)
+    Attr::new(0x28, 4).rw(0xffff_ffff),          // MC_Overlay_BAR (Lower DW)
+    Attr::new(0x2c, 4).rw(0xffff_ffff),          // MC_Overlay_BAR (Upper DW)
+];
+
 /// Multicast Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MulticastCapability {
     /// Maximum number of Multicast Groups that the component supports, encoded as M-1
     pub mc_max_group: u8,
@@ -155,6 +187,7 @@ impl From<u16> for MulticastCapability {
 
 /// Multicast Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MulticastControl {
     /// Indicates the number of Multicast Groups configured for use, encoded as N-1
     pub mc_num_group: u8,
@@ -172,8 +205,21 @@ impl From<u16> for MulticastControl {
     }
 }
 
+impl From<MulticastControl> for u16 {
+    fn from(data: MulticastControl) -> Self {
+        (data.mc_num_group as u16 & 0x3f) | (data.mc_enable as u16) << 15
+    }
+}
+
+impl Register for MulticastControl {
+    type Value = u16;
+    const RSVDP: u16 = 0x7fc0;
+    const RW1C: u16 = 0;
+}
+
 /// The MC_Base_Address register contains the MC_Base_Address and the MC_Index_Position
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct McBaseAddress {
     /// The location of the LSB of the Multicast Group number within the address
     pub mc_index_position: u8,
@@ -195,6 +241,7 @@ impl From<u64> for McBaseAddress {
 /// The MC_Overlay_BAR is required in Switch and Root Complex Ports that
 /// support the Multicast Capability and not implemented in Endpoints
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct McOverlayBar {
     pub mc_overlay_size: u8,
     pub mc_overlay_bar: u64,
@@ -117,15 +117,21 @@ use core::slice;
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P11, P3, P4, P5, P7, P8};
 use snafu::Snafu;
 
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 pub use super::virtual_channel::{
     PortVcCapability2, PortVcControl, PortVcStatus, ReferenceClock, VcArbitrationCapability,
-    VcArbitrationSelect, VcArbitrationTable,
+    VcArbitrationSelect, VcArbitrationTable, VcArbitrationTableEntry,
 };
 
 /// Numeral unit for VC Arbitration Table Offset and Function Arbitration Table Offset
 const DQWORD: usize = 0x10;
 
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum MultifunctionVirtualChannelError {
     #[snafu(display("capability 1, capability 2, control and status of Port VC are unreadable"))]
     PortVcData,
@@ -141,6 +147,7 @@ pub enum MultifunctionVirtualChannelError {
 
 /// Multi-Function Virtual Channel
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MultifunctionVirtualChannel<'a> {
     pub port_vc_capability_1: PortVcCapability1,
     /// Port VC Capability 2
@@ -217,11 +224,70 @@ impl<'a> TryFrom<&'a [u8]> for MultifunctionVirtualChannel<'a> {
     }
 }
 
+/// Owned [MultifunctionVirtualChannel] with decoded arbitration tables
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedMultifunctionVirtualChannel {
+    pub port_vc_capability_1: PortVcCapability1,
+    pub port_vc_capability_2: PortVcCapability2,
+    pub port_vc_control: PortVcControl,
+    pub port_vc_status: PortVcStatus,
+    pub vc_arbitration_table: Option<Vec<VcArbitrationTableEntry>>,
+    pub extended_virtual_channels: Vec<OwnedExtendedVirtualChannel>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&MultifunctionVirtualChannel<'a>> for OwnedMultifunctionVirtualChannel {
+    fn from(mfvc: &MultifunctionVirtualChannel<'a>) -> Self {
+        Self {
+            port_vc_capability_1: mfvc.port_vc_capability_1.clone(),
+            port_vc_capability_2: mfvc.port_vc_capability_2.clone(),
+            port_vc_control: mfvc.port_vc_control.clone(),
+            port_vc_status: mfvc.port_vc_status.clone(),
+            vc_arbitration_table: mfvc.vc_arbitration_table.clone().map(Iterator::collect),
+            extended_virtual_channels: mfvc
+                .extended_virtual_channels
+                .clone()
+                .map(|evc| (&evc).into())
+                .collect(),
+        }
+    }
+}
+
+impl<'a> MultifunctionVirtualChannel<'a> {
+    /// Access attributes of Port VC and VC Resource registers
+    ///
+    /// Arbitration tables are not described.
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.port_vc_capability_1.extended_vc_count as u16 + 1;
+        RegisterMap::from(REGISTERS).repeat(VC_RESOURCE_REGISTERS, 0x0c, count)
+    }
+}
+
+/// Multi-Function Virtual Channel Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 4),                          // Port VC Capability 1
+    Attr::new(0x08, 4),                          // Port VC Capability 2
+    Attr::new(0x0c, 2).rw(0x000f).rsvdp(0xfff0), // Port VC Control
+    Attr::new(0x0e, 2).rsvdz(0xfffe),            // Port VC Status
+];
+
+/// VC Resource registers of VC0, resources of other VCs follow every 0Ch bytes
+pub const VC_RESOURCE_REGISTERS: &[Attr] = &[
+    Attr::new(0x10, 4),                                    // VC Resource Capability
+    Attr::new(0x14, 4).rw(0x870f_00ff).rsvdp(0x78f0_ff00), // VC Resource Control
+    Attr::new(0x18, 2).rsvdp(0xffff),
+    Attr::new(0x1a, 2).rsvdz(0xfffc), // VC Resource Status
+];
+
 /// Port VC Capability 1
 ///
 /// Describes the configuration of the Virtual Channels associated with a PCI
 /// Express Port of the Multi-Function Device
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortVcCapability1 {
     /// Indicates the number of (extended) Virtual Channels in addition to the default VC supported
     /// by the device.
@@ -262,6 +328,7 @@ impl From<u32> for PortVcCapability1 {
 ///
 /// Indicates the size (in bits) of Function Arbitration table entry in the device.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionArbitrationTableEntrySize(pub u8);
 impl FunctionArbitrationTableEntrySize {
     pub fn bits(&self) -> usize {
@@ -324,8 +391,16 @@ impl<'a> Iterator for ExtendedVirtualChannels<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ExtendedVirtualChannels<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 /// Extended Virtual Channel
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ExtendedVirtualChannel<'a> {
     pub vc_resource_capability: VcResourceCapability,
     pub vc_resource_control: VcResourceControl,
@@ -362,9 +437,36 @@ impl<'a> From<ExtendedVirtualChannel<'a>> for [u8; ExtendedVirtualChannel::SIZE]
     }
 }
 
+/// Owned [ExtendedVirtualChannel] with decoded Function Arbitration Table
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedExtendedVirtualChannel {
+    pub vc_resource_capability: VcResourceCapability,
+    pub vc_resource_control: VcResourceControl,
+    pub vc_resource_status: VcResourceStatus,
+    pub function_arbitration_table: Option<Vec<FunctionArbitrationTableEntry>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ExtendedVirtualChannel<'a>> for OwnedExtendedVirtualChannel {
+    fn from(evc: &ExtendedVirtualChannel<'a>) -> Self {
+        Self {
+            vc_resource_capability: evc.vc_resource_capability.clone(),
+            vc_resource_control: evc.vc_resource_control.clone(),
+            vc_resource_status: evc.vc_resource_status.clone(),
+            function_arbitration_table: evc
+                .function_arbitration_table
+                .clone()
+                .map(Iterator::collect),
+        }
+    }
+}
+
 /// The VC Resource Capability register describes the capabilities and configuration of a
 /// particular Virtual Channel resource
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcResourceCapability {
     pub function_arbitration_capability: FunctionArbitrationCapability,
     /// Indicates the maximum number of time slots (minus 1) that the VC
@@ -419,6 +521,7 @@ impl From<VcResourceCapability> for u32 {
 
 /// Indicates types of Function Arbitration supported by the VC resource
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionArbitrationCapability {
     /// Non-configurable hardware-fixed arbitration scheme, e.g., Round Robin (RR)
     pub hardware_fixed_arbitration: bool,
@@ -450,6 +553,7 @@ impl From<FunctionArbitrationCapability> for u8 {
 
 /// VC Resource Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcResourceControl {
     /// Indicates the TCs that are mapped to this VC resource
     pub tc_to_vc_map: u8,
@@ -497,9 +601,16 @@ impl From<VcResourceControl> for u32 {
     }
 }
 
+impl Register for VcResourceControl {
+    type Value = u32;
+    const RSVDP: u32 = 0x78f0_ff00;
+    const RW1C: u32 = 0;
+}
+
 /// Corresponding to one of the asserted fields in the [Function Arbitration
 /// Capability](FunctionArbitrationCapability) field of the VC resource
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FunctionArbitrationSelect {
     /// Non-configurable hardware-fixed arbitration scheme, e.g., Round Robin (RR)
     HardwareFixedArbitration,
@@ -559,6 +670,7 @@ impl From<FunctionArbitrationSelect> for u8 {
 
 /// VC Resource Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcResourceStatus {
     /// Indicates the coherency status of the Function Arbitration Table
     /// associated with the VC resource
@@ -588,6 +700,12 @@ impl From<VcResourceStatus> for u16 {
     }
 }
 
+impl Register for VcResourceStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
+
 /// Function Arbitration Table is used to store the WRR or time-based WRR
 /// arbitration table for Function Arbitration for the VC resource
 #[derive(Debug, Clone)]
@@ -628,6 +746,13 @@ impl<'a> Iterator for FunctionArbitrationTable<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for FunctionArbitrationTable<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 impl<'a> PartialEq for FunctionArbitrationTable<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.data.clone().eq(other.data.clone())
@@ -640,6 +765,7 @@ impl<'a> PartialEq for FunctionArbitrationTable<'a> {
 impl<'a> Eq for FunctionArbitrationTable<'a> {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FunctionArbitrationTableEntry(u8);
 
 #[cfg(test)]
@@ -720,6 +846,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unusual_byte_groupings)]
     fn extended_virtual_channels() {
         #[rustfmt::skip]
         let data = [
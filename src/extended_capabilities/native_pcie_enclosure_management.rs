@@ -0,0 +1,340 @@
+/*!
+# Native PCIe Enclosure Management
+
+Native PCIe Enclosure Management (NPEM) provides storage related enclosure control (e.g., LEDs
+of U.2 or E3 drive bays) through the Downstream Port or the Upstream Port of the drive.
+
+## Struct diagram
+[NativePcieEnclosureManagement]
+- [NpemCapability]
+  - [Indications]
+- [NpemControl]
+  - [Indications]
+- [NpemStatus]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::native_pcie_enclosure_management::*;
+let data = [
+    /* 00h */ 0x29, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x1d, 0x00, 0x00, 0x00, // NPEM Capability
+    /* 08h */ 0x05, 0x00, 0x00, 0x00, // NPEM Control
+    /* 0Ch */ 0x01, 0x00, 0x00, 0x00, // NPEM Status
+];
+let npem: NativePcieEnclosureManagement = data[4..].try_into().unwrap();
+assert!(npem.capability.npem_capable);
+assert!(npem.control.indications.get(Indication::Ok));
+
+// Light the Locate LED
+let control = npem.indication_control(Indication::Locate).unwrap();
+assert_eq!(0x0000_0009, u32::from(control.clone()));
+// Rebuild is not supported by this enclosure
+assert_eq!(None, npem.indication_control(Indication::Rebuild));
+// Previous command is completed, but the Control register still holds the old value
+assert!(!npem.is_command_completed(&control));
+// Enclosure processed the new value
+let mut npem = npem;
+npem.control = control.clone();
+assert!(npem.is_command_completed(&control));
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P10, P3, P5};
+
+use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
+
+/// Native PCIe Enclosure Management
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NativePcieEnclosureManagement {
+    pub capability: NpemCapability,
+    pub control: NpemControl,
+    pub status: NpemStatus,
+}
+impl NativePcieEnclosureManagement {
+    /// NPEM Control value that enables NPEM and turns on `indication` only
+    ///
+    /// Enclosure-specific Controls are preserved. Returns `None` if NPEM or `indication` is not
+    /// supported.
+    pub fn indication_control(&self, indication: Indication) -> Option<NpemControl> {
+        let caps = &self.capability;
+        if !caps.npem_capable || !caps.indications.get(indication) {
+            return None;
+        }
+        let mut indications = Indications::default();
+        indications.set(indication, true);
+        Some(NpemControl {
+            npem_enable: true,
+            npem_initiate_reset: false,
+            indications,
+            enclosure_specific_controls: self.control.enclosure_specific_controls,
+        })
+    }
+    /// NPEM Command Completed is set and NPEM Control holds the `written` value
+    ///
+    /// NPEM Command Completed should be cleared before writing a new NPEM Control value.
+    pub fn is_command_completed(&self, written: &NpemControl) -> bool {
+        self.status.npem_command_completed && &self.control == written
+    }
+}
+impl TryFrom<&[u8]> for NativePcieEnclosureManagement {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capability, control, status)),
+            ..
+        } = P3(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Native PCIe Enclosure Management",
+                size: 12,
+            })?;
+        Ok(Self {
+            capability: From::<u32>::from(capability),
+            control: From::<u32>::from(control),
+            status: From::<u32>::from(status),
+        })
+    }
+}
+
+/// Native PCIe Enclosure Management Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0xff00_0fff).rsvdp(0x00ff_f000), // NPEM Capability
+    Attr::new(0x08, 4).rw(0xff00_0fff).rsvdp(0x00ff_f000), // NPEM Control
+    Attr::new(0x0c, 4).rw1c(0x0000_0001).rsvdz(0x00ff_fffe), // NPEM Status
+];
+
+/// NPEM indications
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Indication {
+    /// Drive is functioning normally
+    Ok,
+    /// Identify the drive
+    Locate,
+    /// Drive is failed
+    Fail,
+    /// Drive is part of an array that is being rebuilt
+    Rebuild,
+    /// Predicted Failure Analysis
+    Pfa,
+    /// Drive is marked to be used as a replacement for a failed drive
+    HotSpare,
+    /// In A Critical Array. Array containing the drive has no redundancy left.
+    InACriticalArray,
+    /// In A Failed Array. Array containing the drive failed.
+    InAFailedArray,
+    /// Drive is not the right type for the connector
+    InvalidDeviceType,
+    /// Device Off. Drive is disabled and may be removed.
+    Disabled,
+}
+
+/// OK, Locate, Fail, Rebuild, PFA, Hot Spare, ICA, IFA, IDT and Disabled indications as reported
+/// by [NPEM Capability](NpemCapability) or requested by [NPEM Control](NpemControl)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Indications {
+    pub ok: bool,
+    pub locate: bool,
+    pub fail: bool,
+    pub rebuild: bool,
+    pub pfa: bool,
+    pub hot_spare: bool,
+    pub in_a_critical_array: bool,
+    pub in_a_failed_array: bool,
+    pub invalid_device_type: bool,
+    pub disabled: bool,
+}
+
+impl Indications {
+    pub fn get(&self, indication: Indication) -> bool {
+        *self.field(indication)
+    }
+    pub fn set(&mut self, indication: Indication, value: bool) {
+        *self.field_mut(indication) = value;
+    }
+    fn field(&self, indication: Indication) -> &bool {
+        match indication {
+            Indication::Ok => &self.ok,
+            Indication::Locate => &self.locate,
+            Indication::Fail => &self.fail,
+            Indication::Rebuild => &self.rebuild,
+            Indication::Pfa => &self.pfa,
+            Indication::HotSpare => &self.hot_spare,
+            Indication::InACriticalArray => &self.in_a_critical_array,
+            Indication::InAFailedArray => &self.in_a_failed_array,
+            Indication::InvalidDeviceType => &self.invalid_device_type,
+            Indication::Disabled => &self.disabled,
+        }
+    }
+    fn field_mut(&mut self, indication: Indication) -> &mut bool {
+        match indication {
+            Indication::Ok => &mut self.ok,
+            Indication::Locate => &mut self.locate,
+            Indication::Fail => &mut self.fail,
+            Indication::Rebuild => &mut self.rebuild,
+            Indication::Pfa => &mut self.pfa,
+            Indication::HotSpare => &mut self.hot_spare,
+            Indication::InACriticalArray => &mut self.in_a_critical_array,
+            Indication::InAFailedArray => &mut self.in_a_failed_array,
+            Indication::InvalidDeviceType => &mut self.invalid_device_type,
+            Indication::Disabled => &mut self.disabled,
+        }
+    }
+}
+
+impl From<u16> for Indications {
+    fn from(word: u16) -> Self {
+        let Lsb((
+            ok,
+            locate,
+            fail,
+            rebuild,
+            pfa,
+            hot_spare,
+            in_a_critical_array,
+            in_a_failed_array,
+            invalid_device_type,
+            disabled,
+        )) = P10::<_, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1>(word).into();
+        Self {
+            ok,
+            locate,
+            fail,
+            rebuild,
+            pfa,
+            hot_spare,
+            in_a_critical_array,
+            in_a_failed_array,
+            invalid_device_type,
+            disabled,
+        }
+    }
+}
+
+impl From<Indications> for u16 {
+    fn from(data: Indications) -> Self {
+        (data.ok as u16)
+            | (data.locate as u16) << 1
+            | (data.fail as u16) << 2
+            | (data.rebuild as u16) << 3
+            | (data.pfa as u16) << 4
+            | (data.hot_spare as u16) << 5
+            | (data.in_a_critical_array as u16) << 6
+            | (data.in_a_failed_array as u16) << 7
+            | (data.invalid_device_type as u16) << 8
+            | (data.disabled as u16) << 9
+    }
+}
+
+/// NPEM Capability
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NpemCapability {
+    /// NPEM Capable
+    pub npem_capable: bool,
+    /// NPEM Reset Capable
+    pub npem_reset_capable: bool,
+    /// Supported indications
+    pub indications: Indications,
+    /// Enclosure-specific Capabilities
+    pub enclosure_specific_capabilities: u8,
+}
+
+impl From<u32> for NpemCapability {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            npem_capable,
+            npem_reset_capable,
+            indications,
+            (),
+            enclosure_specific_capabilities,
+        )) = P5::<_, 1, 1, 10, 12, 8>(dword).into();
+        Self {
+            npem_capable,
+            npem_reset_capable,
+            indications: From::<u16>::from(indications),
+            enclosure_specific_capabilities,
+        }
+    }
+}
+
+/// NPEM Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NpemControl {
+    /// NPEM Enable
+    pub npem_enable: bool,
+    /// NPEM Initiate Reset. Reset all NPEM Control fields to their default values.
+    pub npem_initiate_reset: bool,
+    /// Requested indications
+    pub indications: Indications,
+    /// Enclosure-specific Controls
+    pub enclosure_specific_controls: u8,
+}
+
+impl From<u32> for NpemControl {
+    fn from(dword: u32) -> Self {
+        let Lsb((npem_enable, npem_initiate_reset, indications, (), enclosure_specific_controls)) =
+            P5::<_, 1, 1, 10, 12, 8>(dword).into();
+        Self {
+            npem_enable,
+            npem_initiate_reset,
+            indications: From::<u16>::from(indications),
+            enclosure_specific_controls,
+        }
+    }
+}
+
+impl From<NpemControl> for u32 {
+    fn from(data: NpemControl) -> Self {
+        (data.npem_enable as u32)
+            | (data.npem_initiate_reset as u32) << 1
+            | (u16::from(data.indications) as u32) << 2
+            | (data.enclosure_specific_controls as u32) << 24
+    }
+}
+
+impl Register for NpemControl {
+    type Value = u32;
+    const RSVDP: u32 = 0x00ff_f000;
+    const RW1C: u32 = 0;
+}
+
+/// NPEM Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NpemStatus {
+    /// NPEM Command Completed. NPEM Control write has been processed.
+    pub npem_command_completed: bool,
+    /// Enclosure-specific Status
+    pub enclosure_specific_status: u8,
+}
+
+impl From<u32> for NpemStatus {
+    fn from(dword: u32) -> Self {
+        let Lsb((npem_command_completed, (), enclosure_specific_status)) =
+            P3::<_, 1, 23, 8>(dword).into();
+        Self {
+            npem_command_completed,
+            enclosure_specific_status,
+        }
+    }
+}
+
+impl From<NpemStatus> for u32 {
+    fn from(data: NpemStatus) -> Self {
+        (data.npem_command_completed as u32) | (data.enclosure_specific_status as u32) << 24
+    }
+}
+
+impl Register for NpemStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0000_0001;
+}
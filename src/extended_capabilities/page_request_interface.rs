@@ -40,8 +40,10 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P3, P4, P6};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PageRequestInterface {
     /// Page Request Control
     pub page_request_control: PageRequestControl,
@@ -71,30 +73,26 @@ impl TryFrom<&[u8]> for PageRequestInterface {
                 name: "Page Request Interface",
                 size: 12,
             })?;
-        let Lsb((enable, reset, ())) = P3::<u16, 1, 1, 14>(control).into();
-        let Lsb((
-            response_failure,
-            unexpected_page_request_group_index,
-            (),
-            stopped,
-            (),
-            prg_response_pasid_required,
-        )) = P6::<u16, 1, 1, 6, 1, 6, 1>(status).into();
         Ok(Self {
-            page_request_control: PageRequestControl { enable, reset },
-            page_request_status: PageRequestStatus {
-                response_failure,
-                unexpected_page_request_group_index,
-                stopped,
-                prg_response_pasid_required,
-            },
+            page_request_control: From::<u16>::from(control),
+            page_request_status: From::<u16>::from(status),
             outstanding_page_request_capacity,
             outstanding_page_request_allocation,
         })
     }
 }
+
+/// Page Request Interface Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                            // Extended Capability Header
+    Attr::new(0x04, 2).rw(0x0003).rsvdp(0xfffc),   // Page Request Control
+    Attr::new(0x06, 2).rw1c(0x0003).rsvdz(0x7efc), // Page Request Status
+    Attr::new(0x08, 4),                            // Outstanding Page Request Capacity
+    Attr::new(0x0c, 4).rw(0xffff_ffff),            // Outstanding Page Request Allocation
+];
 /// Page Request Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PageRequestControl {
     /// Enable (E)
     pub enable: bool,
@@ -102,8 +100,28 @@ pub struct PageRequestControl {
     pub reset: bool,
 }
 
+impl From<u16> for PageRequestControl {
+    fn from(word: u16) -> Self {
+        let Lsb((enable, reset, ())) = P3::<_, 1, 1, 14>(word).into();
+        Self { enable, reset }
+    }
+}
+
+impl From<PageRequestControl> for u16 {
+    fn from(data: PageRequestControl) -> Self {
+        (data.enable as u16) | (data.reset as u16) << 1
+    }
+}
+
+impl Register for PageRequestControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xfffc;
+    const RW1C: u16 = 0;
+}
+
 /// Page Request Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PageRequestStatus {
     /// Response Failure (RF)
     pub response_failure: bool,
@@ -114,3 +132,37 @@ pub struct PageRequestStatus {
     /// PRG Response PASID Required
     pub prg_response_pasid_required: bool,
 }
+
+impl From<u16> for PageRequestStatus {
+    fn from(word: u16) -> Self {
+        let Lsb((
+            response_failure,
+            unexpected_page_request_group_index,
+            (),
+            stopped,
+            (),
+            prg_response_pasid_required,
+        )) = P6::<_, 1, 1, 6, 1, 6, 1>(word).into();
+        Self {
+            response_failure,
+            unexpected_page_request_group_index,
+            stopped,
+            prg_response_pasid_required,
+        }
+    }
+}
+
+impl From<PageRequestStatus> for u16 {
+    fn from(data: PageRequestStatus) -> Self {
+        (data.response_failure as u16)
+            | (data.unexpected_page_request_group_index as u16) << 1
+            | (data.stopped as u16) << 8
+            | (data.prg_response_pasid_required as u16) << 15
+    }
+}
+
+impl Register for PageRequestStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0003;
+}
@@ -99,9 +99,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P5, P6, P7, P9};
 
 use super::{ExtendedCapabilityDataError, ExtendedCapabilityHeader};
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// M-PCIe Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PciExpressOverMphy {
     pub mpcie_capabilities: MpcieCapabilities,
     pub mpcie_control: MpcieControl,
@@ -152,8 +154,23 @@ impl TryFrom<&[u8]> for PciExpressOverMphy {
     }
 }
 
+/// M-PCIe Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // M-PCIe Capabilities
+    Attr::new(0x08, 4).rw(0x0000_0003).rsvdp(0xffff_fffc), // M-PCIe Control
+    Attr::new(0x0c, 4), // M-PCIe Status
+    Attr::new(0x10, 4).rw1c(0xffff_ffff), // M-PCIe LANE Error Status
+    Attr::new(0x14, 4).rw(0xcf1f_00ff).rsvdp(0x30e0_ff00), // M-PCIe Phy Control Address
+    Attr::new(0x18, 4)
+        .rw(0x0000_00ff)
+        .rw1c(0x2000_0000)
+        .rsvdz(0x1fff_ff00), // M-PCIe Phy Control Data
+];
+
 /// M-PCIe Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpcieCapabilities {
     /// M-PCIe Link Speed Capability
     pub mpcie_link_speed_capability: MpcieLinkSpeed,
@@ -180,6 +197,7 @@ impl From<u32> for MpcieCapabilities {
 
 /// M-PCIe Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpcieControl {
     /// M-PCIe Target Link Speed Control
     pub mpcie_target_link_speed_control: MpcieLinkSpeed,
@@ -194,8 +212,21 @@ impl From<u32> for MpcieControl {
     }
 }
 
+impl From<MpcieControl> for u32 {
+    fn from(data: MpcieControl) -> Self {
+        u8::from(data.mpcie_target_link_speed_control) as u32 & 0b11
+    }
+}
+
+impl Register for MpcieControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_fffc;
+    const RW1C: u32 = 0;
+}
+
 /// M-PCIe LANE Error Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpcieStatus {
     /// M-PCIe Current Link Speed Status
     pub mpcie_current_link_speed_status: MpcieLinkSpeed,
@@ -220,8 +251,24 @@ impl From<u32> for MpcieStatus {
     }
 }
 
+impl From<MpcieStatus> for u32 {
+    fn from(data: MpcieStatus) -> Self {
+        (u8::from(data.mpcie_current_link_speed_status) as u32 & 0b11)
+            | (data.mpcie_configuration_software_status as u32) << 15
+            | (u8::from(data.tx_lane_width_status) as u32) << 16
+            | (u8::from(data.rx_lane_width_status) as u32) << 24
+    }
+}
+
+impl Register for MpcieStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0;
+}
+
 /// M-PCIe Phy Control Address
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpciePhyControlAddress {
     /// Corresponds to Lower Addr\[7:0\] in an RRAP packet.
     pub lower_addr: u8,
@@ -250,8 +297,26 @@ impl From<u32> for MpciePhyControlAddress {
     }
 }
 
+impl From<MpciePhyControlAddress> for u32 {
+    fn from(data: MpciePhyControlAddress) -> Self {
+        (data.lower_addr as u32 & 0xff)
+            | (data.upper_addr as u32 & 0x1f) << 16
+            | ((data.upper_addr >> 5) as u32 & 1) << 24
+            | (u8::from(data.phy_location) as u32 & 0b111) << 25
+            | (data.read as u32) << 30
+            | (data.config as u32) << 31
+    }
+}
+
+impl Register for MpciePhyControlAddress {
+    type Value = u32;
+    const RSVDP: u32 = 0x30e0_ff00;
+    const RW1C: u32 = 0;
+}
+
 /// M-PCIe Phy Control Data
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpciePhyControlData {
     /// Phy Register Data
     pub phy_register_data: u8,
@@ -276,8 +341,24 @@ impl From<u32> for MpciePhyControlData {
     }
 }
 
+impl From<MpciePhyControlData> for u32 {
+    fn from(data: MpciePhyControlData) -> Self {
+        (data.phy_register_data as u32 & 0xff)
+            | (data.phy_control_error as u32) << 29
+            | (data.rrap_abort_a as u32) << 30
+            | (data.phy_control_pending as u32) << 31
+    }
+}
+
+impl Register for MpciePhyControlData {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x2000_0000;
+}
+
 /// M-PHY HS-GEARs
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpcieLinkSpeed {
     /// HS-G1
     pub hs_g1: bool,
@@ -292,8 +373,15 @@ impl From<u8> for MpcieLinkSpeed {
     }
 }
 
+impl From<MpcieLinkSpeed> for u8 {
+    fn from(data: MpcieLinkSpeed) -> Self {
+        (data.hs_g1 as u8) | (data.hs_g2 as u8) << 1
+    }
+}
+
 /// Specifies the supported LANE Width
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LaneWidthSupported {
     pub x1: bool,
     pub x2: bool,
@@ -321,6 +409,7 @@ impl From<u8> for LaneWidthSupported {
 
 /// LANE Width Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LaneWidthStatus {
     X1,
     X2,
@@ -347,8 +436,24 @@ impl From<u8> for LaneWidthStatus {
     }
 }
 
+impl From<LaneWidthStatus> for u8 {
+    fn from(data: LaneWidthStatus) -> Self {
+        match data {
+            LaneWidthStatus::X1 => 0b0000_0001,
+            LaneWidthStatus::X2 => 0b0000_0010,
+            LaneWidthStatus::X4 => 0b0000_0100,
+            LaneWidthStatus::X8 => 0b0000_1000,
+            LaneWidthStatus::X12 => 0b0000_1100,
+            LaneWidthStatus::X16 => 0b0001_0000,
+            LaneWidthStatus::X32 => 0b0010_0000,
+            LaneWidthStatus::Reserved(v) => v,
+        }
+    }
+}
+
 /// Indicates the location along the Link of the Phy
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PhyLocation {
     LocalPhy,
     RemotePhy,
@@ -364,3 +469,13 @@ impl From<u8> for PhyLocation {
         }
     }
 }
+
+impl From<PhyLocation> for u8 {
+    fn from(data: PhyLocation) -> Self {
+        match data {
+            PhyLocation::LocalPhy => 0b000,
+            PhyLocation::RemotePhy => 0b001,
+            PhyLocation::Reserved(v) => v,
+        }
+    }
+}
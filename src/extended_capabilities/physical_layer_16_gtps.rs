@@ -0,0 +1,372 @@
+/*!
+# Physical Layer 16.0 GT/s
+
+The Physical Layer 16.0 GT/s Extended Capability structure must be implemented in a Function
+that supports 16.0 GT/s data rate. It reports the 16.0 GT/s equalization progress, Lane based
+Data Parity Mismatch errors and per-Lane 16.0 GT/s equalization presets.
+
+## Struct diagram
+[PhysicalLayer16GTps]
+- [Status16GTps]
+- [DataParityMismatchStatus] (Local, First Retimer, Second Retimer)
+- [EqualizationControlLanes]
+  - [LaneEqualizationControl]
+
+## Examples
+
+> ```text
+> Phy16Sta: EquComplete+ EquPhase1+ EquPhase2+ EquPhase3+ LinkEquRequest-
+> ```
+
+```rust
+# use pcics::extended_capabilities::physical_layer_16_gtps::*;
+# use pcics::capabilities::pci_express::{LinkWidth, TransmitterPreset};
+let data = [
+    /* 00h */ 0x26, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x00, 0x00, 0x00, 0x00, // 16.0 GT/s Capabilities
+    /* 08h */ 0x00, 0x00, 0x00, 0x00, // 16.0 GT/s Control
+    /* 0Ch */ 0x0f, 0x00, 0x00, 0x00, // 16.0 GT/s Status
+    /* 10h */ 0x02, 0x00, 0x00, 0x00, // 16.0 GT/s Local Data Parity Mismatch Status
+    /* 14h */ 0x00, 0x00, 0x00, 0x00, // 16.0 GT/s First Retimer Data Parity Mismatch Status
+    /* 18h */ 0x00, 0x00, 0x00, 0x00, // 16.0 GT/s Second Retimer Data Parity Mismatch Status
+    /* 1Ch */ 0x00, 0x00, 0x00, 0x00, // Reserved
+    /* 20h */ 0x44, 0x44, 0x00, 0x00, // 16.0 GT/s Lane Equalization Control
+];
+let result: PhysicalLayer16GTps = data[4..].try_into().unwrap();
+
+assert_eq!(
+    Status16GTps {
+        equalization_complete: true,
+        equalization_phase_1_successful: true,
+        equalization_phase_2_successful: true,
+        equalization_phase_3_successful: true,
+        link_equalization_request: false,
+    },
+    result.status
+);
+assert_eq!(DataParityMismatchStatus(0b10), result.local_data_parity_mismatch_status);
+
+let lanes = result.equalization_control_lanes(LinkWidth::X2).collect::<Vec<_>>();
+let sample = LaneEqualizationControl {
+    downstream_port_transmitter_preset: TransmitterPreset::P4,
+    upstream_port_transmitter_preset: TransmitterPreset::P4,
+};
+assert_eq!(vec![sample.clone(), sample], lanes);
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P6, P7};
+
+use super::ExtendedCapabilityDataError;
+
+use crate::capabilities::pci_express::{LinkWidth, TransmitterPreset};
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Lane Equalization Control offset
+pub const LEC_OFFSET: usize = 0x20;
+
+/// Physical Layer 16.0 GT/s
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PhysicalLayer16GTps<'a> {
+    lec_data: &'a [u8],
+    pub status: Status16GTps,
+    /// Data Parity Mismatch detected by the Receiver of this Port
+    pub local_data_parity_mismatch_status: DataParityMismatchStatus,
+    /// Data Parity Mismatch detected by the Retimer adjacent to the Upstream Port
+    pub first_retimer_data_parity_mismatch_status: DataParityMismatchStatus,
+    /// Data Parity Mismatch detected by the Retimer adjacent to the Downstream Port
+    pub second_retimer_data_parity_mismatch_status: DataParityMismatchStatus,
+}
+impl<'a> PhysicalLayer16GTps<'a> {
+    pub fn equalization_control_lanes(
+        &self,
+        link_width: LinkWidth,
+    ) -> EqualizationControlLanes<'a> {
+        EqualizationControlLanes::new(self.lec_data, link_width)
+    }
+    /// Access attributes of registers including Lane Equalization Control for `link_width` lanes
+    pub fn registers(&self, link_width: LinkWidth) -> RegisterMap {
+        let count = u8::from(link_width) as u16;
+        RegisterMap::from(REGISTERS).repeat(LANE_EQUALIZATION_CONTROL_REGISTERS, 1, count)
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for PhysicalLayer16GTps<'a> {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((_capabilities, _control, status, local, first, second, _reserved)),
+            tail,
+        } = P7(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Physical Layer 16.0 GT/s",
+                size: 28,
+            })?;
+        let _: (u32, u32, u32) = (_capabilities, _control, _reserved);
+        Ok(Self {
+            lec_data: tail,
+            status: From::<u32>::from(status),
+            local_data_parity_mismatch_status: DataParityMismatchStatus(local),
+            first_retimer_data_parity_mismatch_status: DataParityMismatchStatus(first),
+            second_retimer_data_parity_mismatch_status: DataParityMismatchStatus(second),
+        })
+    }
+}
+
+/// Owned [PhysicalLayer16GTps]
+///
+/// Converts back into [PhysicalLayer16GTps] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedPhysicalLayer16GTps {
+    lec_data: Vec<u8>,
+    pub status: Status16GTps,
+    /// Data Parity Mismatch detected by the Receiver of this Port
+    pub local_data_parity_mismatch_status: DataParityMismatchStatus,
+    /// Data Parity Mismatch detected by the Retimer adjacent to the Upstream Port
+    pub first_retimer_data_parity_mismatch_status: DataParityMismatchStatus,
+    /// Data Parity Mismatch detected by the Retimer adjacent to the Downstream Port
+    pub second_retimer_data_parity_mismatch_status: DataParityMismatchStatus,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&PhysicalLayer16GTps<'a>> for OwnedPhysicalLayer16GTps {
+    fn from(value: &PhysicalLayer16GTps<'a>) -> Self {
+        Self {
+            lec_data: value.lec_data.to_vec(),
+            status: value.status.clone(),
+            local_data_parity_mismatch_status: value.local_data_parity_mismatch_status.clone(),
+            first_retimer_data_parity_mismatch_status: value
+                .first_retimer_data_parity_mismatch_status
+                .clone(),
+            second_retimer_data_parity_mismatch_status: value
+                .second_retimer_data_parity_mismatch_status
+                .clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedPhysicalLayer16GTps> for PhysicalLayer16GTps<'a> {
+    fn from(value: &'a OwnedPhysicalLayer16GTps) -> Self {
+        Self {
+            lec_data: &value.lec_data,
+            status: value.status.clone(),
+            local_data_parity_mismatch_status: value.local_data_parity_mismatch_status.clone(),
+            first_retimer_data_parity_mismatch_status: value
+                .first_retimer_data_parity_mismatch_status
+                .clone(),
+            second_retimer_data_parity_mismatch_status: value
+                .second_retimer_data_parity_mismatch_status
+                .clone(),
+        }
+    }
+}
+
+/// Physical Layer 16.0 GT/s Capability registers without Lane Equalization Control
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                    // Extended Capability Header
+    Attr::new(0x04, 4).rsvdp(0xffff_ffff), // 16.0 GT/s Capabilities
+    Attr::new(0x08, 4).rsvdp(0xffff_ffff), // 16.0 GT/s Control
+    Attr::new(0x0c, 4).rw1c(0x0000_0010).rsvdz(0xffff_ffe0), // 16.0 GT/s Status
+    Attr::new(0x10, 4).rw1c(0xffff_ffff),  // 16.0 GT/s Local Data Parity Mismatch Status
+    Attr::new(0x14, 4).rw1c(0xffff_ffff),  // 16.0 GT/s First Retimer Data Parity Mismatch Status
+    Attr::new(0x18, 4).rw1c(0xffff_ffff),  // 16.0 GT/s Second Retimer Data Parity Mismatch Status
+    Attr::new(0x1c, 4).rsvdp(0xffff_ffff), // Reserved
+];
+
+/// 16.0 GT/s Lane Equalization Control of Lane 0, other Lanes follow every byte
+pub const LANE_EQUALIZATION_CONTROL_REGISTERS: &[Attr] = &[
+    Attr::new(0x20, 1).hwinit(0xff), // 16.0 GT/s Lane Equalization Control
+];
+
+/// 16.0 GT/s Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Status16GTps {
+    /// Equalization 16.0 GT/s Complete
+    pub equalization_complete: bool,
+    /// Equalization 16.0 GT/s Phase 1 Successful
+    pub equalization_phase_1_successful: bool,
+    /// Equalization 16.0 GT/s Phase 2 Successful
+    pub equalization_phase_2_successful: bool,
+    /// Equalization 16.0 GT/s Phase 3 Successful
+    pub equalization_phase_3_successful: bool,
+    /// Link Equalization Request 16.0 GT/s
+    pub link_equalization_request: bool,
+}
+
+impl From<u32> for Status16GTps {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            equalization_complete,
+            equalization_phase_1_successful,
+            equalization_phase_2_successful,
+            equalization_phase_3_successful,
+            link_equalization_request,
+            (),
+        )) = P6::<_, 1, 1, 1, 1, 1, 27>(dword).into();
+        Self {
+            equalization_complete,
+            equalization_phase_1_successful,
+            equalization_phase_2_successful,
+            equalization_phase_3_successful,
+            link_equalization_request,
+        }
+    }
+}
+
+impl From<Status16GTps> for u32 {
+    fn from(data: Status16GTps) -> Self {
+        (data.equalization_complete as u32)
+            | (data.equalization_phase_1_successful as u32) << 1
+            | (data.equalization_phase_2_successful as u32) << 2
+            | (data.equalization_phase_3_successful as u32) << 3
+            | (data.link_equalization_request as u32) << 4
+    }
+}
+
+impl Register for Status16GTps {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0000_0010;
+}
+
+/// The Data Parity Mismatch Status registers consist of a 32-bit vector, where each bit indicates
+/// if the Lane with the corresponding Lane number detected a Data Parity mismatch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataParityMismatchStatus(pub u32);
+
+impl From<DataParityMismatchStatus> for u32 {
+    fn from(data: DataParityMismatchStatus) -> Self {
+        data.0
+    }
+}
+
+impl Register for DataParityMismatchStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0xffff_ffff;
+}
+
+/// An iterator through 16.0 GT/s and higher Lane Equalization Controls
+#[derive(Debug, Clone)]
+pub struct EqualizationControlLanes<'a> {
+    bytes: core::slice::Iter<'a, u8>,
+}
+impl<'a> EqualizationControlLanes<'a> {
+    /// One Lane Equalization Control byte for each of `link_width` lanes
+    pub fn new(bytes: &'a [u8], link_width: LinkWidth) -> Self {
+        let end = (u8::from(link_width) as usize).min(bytes.len());
+        Self {
+            bytes: bytes[..end].iter(),
+        }
+    }
+}
+impl<'a> Iterator for EqualizationControlLanes<'a> {
+    type Item = LaneEqualizationControl;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.bytes.next().map(|&byte| byte.into())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for EqualizationControlLanes<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+/// The Lane Equalization Control register consists of control fields required for per-Lane
+/// 16.0 GT/s and higher equalization. The number of entries is sized by Maximum Link Width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LaneEqualizationControl {
+    /// Downstream Port Transmitter Preset
+    pub downstream_port_transmitter_preset: TransmitterPreset,
+    /// Upstream Port Transmitter Preset
+    pub upstream_port_transmitter_preset: TransmitterPreset,
+}
+
+impl From<u8> for LaneEqualizationControl {
+    fn from(byte: u8) -> Self {
+        let Lsb((downstream_port_transmitter_preset, upstream_port_transmitter_preset)) =
+            P2::<_, 4, 4>(byte).into();
+        Self {
+            downstream_port_transmitter_preset: From::<u8>::from(
+                downstream_port_transmitter_preset,
+            ),
+            upstream_port_transmitter_preset: From::<u8>::from(upstream_port_transmitter_preset),
+        }
+    }
+}
+
+impl From<LaneEqualizationControl> for u8 {
+    fn from(data: LaneEqualizationControl) -> Self {
+        (u8::from(data.downstream_port_transmitter_preset) & 0b1111)
+            | (u8::from(data.upstream_port_transmitter_preset) & 0b1111) << 4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    // Capabilities: [a60 v1] Physical Layer 16.0 GT/s <?>
+    const DATA: [u8; 0x30] = [
+        0x26, 0x00, 0x01, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x81, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x75, 0x75, 0x75, 0x75, 0x75, 0x75, 0x75, 0x75, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn parse() {
+        let result = DATA[4..].try_into().unwrap();
+        let sample = PhysicalLayer16GTps {
+            lec_data: &DATA[LEC_OFFSET..],
+            status: Status16GTps {
+                equalization_complete: true,
+                equalization_phase_1_successful: true,
+                equalization_phase_2_successful: true,
+                equalization_phase_3_successful: true,
+                link_equalization_request: true,
+            },
+            local_data_parity_mismatch_status: DataParityMismatchStatus(0),
+            first_retimer_data_parity_mismatch_status: DataParityMismatchStatus(0x81),
+            second_retimer_data_parity_mismatch_status: DataParityMismatchStatus(0),
+        };
+        assert_eq!(sample, result);
+    }
+
+    #[test]
+    fn equalization_control_lanes() {
+        let pl: PhysicalLayer16GTps = DATA[4..].try_into().unwrap();
+        let result = pl
+            .equalization_control_lanes(LinkWidth::X8)
+            .collect::<Vec<_>>();
+        let sample = core::iter::repeat_n(
+            LaneEqualizationControl {
+                downstream_port_transmitter_preset: TransmitterPreset::P5,
+                upstream_port_transmitter_preset: TransmitterPreset::P7,
+            },
+            8,
+        )
+        .collect::<Vec<_>>();
+        assert_eq!(sample, result);
+        assert_eq!(0x75, u8::from(result[0].clone()));
+        // Truncated structure limits number of lanes
+        assert_eq!(16, pl.equalization_control_lanes(LinkWidth::X32).count());
+        assert_eq!(8 + 8, pl.registers(LinkWidth::X8).iter().count());
+    }
+}
@@ -0,0 +1,442 @@
+/*!
+# Physical Layer 32.0 GT/s
+
+The Physical Layer 32.0 GT/s Extended Capability structure must be implemented in a Function
+that supports 32.0 GT/s data rate. It reports the 32.0 GT/s equalization progress, Modified TS
+Ordered Sets exchanged during Link training and per-Lane 32.0 GT/s equalization presets.
+
+## Struct diagram
+[PhysicalLayer32GTps]
+- [Capabilities32GTps]
+- [Control32GTps]
+- [Status32GTps]
+- [ModifiedTsData] (Received, Transmitted)
+- [EqualizationControlLanes]
+  - [LaneEqualizationControl]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::physical_layer_32_gtps::*;
+# use pcics::capabilities::pci_express::{LinkWidth, TransmitterPreset};
+let data = [
+    /* 00h */ 0x2a, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x03, 0x03, 0x00, 0x00, // 32.0 GT/s Capabilities
+    /* 08h */ 0x00, 0x01, 0x00, 0x00, // 32.0 GT/s Control
+    /* 0Ch */ 0x2f, 0x00, 0x00, 0x00, // 32.0 GT/s Status
+    /* 10h */ 0x01, 0x00, 0x86, 0x80, // Received Modified TS Data 1
+    /* 14h */ 0x00, 0x00, 0x00, 0x01, // Received Modified TS Data 2
+    /* 18h */ 0x01, 0x00, 0x86, 0x80, // Transmitted Modified TS Data 1
+    /* 1Ch */ 0x00, 0x00, 0x00, 0x01, // Transmitted Modified TS Data 2
+    /* 20h */ 0x44, 0x00, 0x00, 0x00, // 32.0 GT/s Lane Equalization Control
+];
+let result: PhysicalLayer32GTps = data[4..].try_into().unwrap();
+
+assert_eq!(
+    Capabilities32GTps {
+        equalization_bypass_to_highest_rate_supported: true,
+        no_equalization_needed_supported: true,
+        modified_ts_usage_mode_0_supported: true,
+        modified_ts_usage_mode_1_supported: true,
+        modified_ts_usage_mode_2_supported: false,
+    },
+    result.capabilities
+);
+assert_eq!(1, result.control.modified_ts_usage_mode_selected);
+assert!(result.status.equalization_complete && result.status.modified_ts_received);
+assert_eq!(
+    ModifiedTsData {
+        usage_mode: 1,
+        information_1: 0,
+        vendor_id: 0x8086,
+        information_2: 0,
+        alternate_protocol_negotiation_status: 1,
+    },
+    result.received_modified_ts_data
+);
+
+let lane = result.equalization_control_lanes(LinkWidth::X1).next().unwrap();
+assert_eq!(TransmitterPreset::P4, lane.upstream_port_transmitter_preset);
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P11, P3, P5, P7};
+
+use super::ExtendedCapabilityDataError;
+
+use crate::capabilities::pci_express::LinkWidth;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub use super::physical_layer_16_gtps::{EqualizationControlLanes, LaneEqualizationControl};
+
+/// Lane Equalization Control offset
+pub const LEC_OFFSET: usize = 0x20;
+
+/// Physical Layer 32.0 GT/s
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PhysicalLayer32GTps<'a> {
+    lec_data: &'a [u8],
+    pub capabilities: Capabilities32GTps,
+    pub control: Control32GTps,
+    pub status: Status32GTps,
+    /// Modified TS1/TS2 Ordered Set received from the Link partner
+    pub received_modified_ts_data: ModifiedTsData,
+    /// Modified TS1/TS2 Ordered Set transmitted by this Port
+    pub transmitted_modified_ts_data: ModifiedTsData,
+}
+impl<'a> PhysicalLayer32GTps<'a> {
+    pub fn equalization_control_lanes(
+        &self,
+        link_width: LinkWidth,
+    ) -> EqualizationControlLanes<'a> {
+        EqualizationControlLanes::new(self.lec_data, link_width)
+    }
+    /// Access attributes of registers including Lane Equalization Control for `link_width` lanes
+    pub fn registers(&self, link_width: LinkWidth) -> RegisterMap {
+        let count = u8::from(link_width) as u16;
+        RegisterMap::from(REGISTERS).repeat(LANE_EQUALIZATION_CONTROL_REGISTERS, 1, count)
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for PhysicalLayer32GTps<'a> {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capabilities, control, status, rx_1, rx_2, tx_1, tx_2)),
+            tail,
+        } = P7(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Physical Layer 32.0 GT/s",
+                size: 28,
+            })?;
+        Ok(Self {
+            lec_data: tail,
+            capabilities: From::<u32>::from(capabilities),
+            control: From::<u32>::from(control),
+            status: From::<u32>::from(status),
+            received_modified_ts_data: From::<[u32; 2]>::from([rx_1, rx_2]),
+            transmitted_modified_ts_data: From::<[u32; 2]>::from([tx_1, tx_2]),
+        })
+    }
+}
+
+/// Owned [PhysicalLayer32GTps]
+///
+/// Converts back into [PhysicalLayer32GTps] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedPhysicalLayer32GTps {
+    lec_data: Vec<u8>,
+    pub capabilities: Capabilities32GTps,
+    pub control: Control32GTps,
+    pub status: Status32GTps,
+    /// Modified TS1/TS2 Ordered Set received from the Link partner
+    pub received_modified_ts_data: ModifiedTsData,
+    /// Modified TS1/TS2 Ordered Set transmitted by this Port
+    pub transmitted_modified_ts_data: ModifiedTsData,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&PhysicalLayer32GTps<'a>> for OwnedPhysicalLayer32GTps {
+    fn from(value: &PhysicalLayer32GTps<'a>) -> Self {
+        Self {
+            lec_data: value.lec_data.to_vec(),
+            capabilities: value.capabilities.clone(),
+            control: value.control.clone(),
+            status: value.status.clone(),
+            received_modified_ts_data: value.received_modified_ts_data.clone(),
+            transmitted_modified_ts_data: value.transmitted_modified_ts_data.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedPhysicalLayer32GTps> for PhysicalLayer32GTps<'a> {
+    fn from(value: &'a OwnedPhysicalLayer32GTps) -> Self {
+        Self {
+            lec_data: &value.lec_data,
+            capabilities: value.capabilities.clone(),
+            control: value.control.clone(),
+            status: value.status.clone(),
+            received_modified_ts_data: value.received_modified_ts_data.clone(),
+            transmitted_modified_ts_data: value.transmitted_modified_ts_data.clone(),
+        }
+    }
+}
+
+/// Physical Layer 32.0 GT/s Capability registers without Lane Equalization Control
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0x0000_0703).rsvdp(0xffff_f8fc), // 32.0 GT/s Capabilities
+    Attr::new(0x08, 4).rw(0x0000_0703).rsvdp(0xffff_f8fc), // 32.0 GT/s Control
+    Attr::new(0x0c, 4).rw1c(0x0000_0010).rsvdz(0xffff_f800), // 32.0 GT/s Status
+    Attr::new(0x10, 4), // Received Modified TS Data 1
+    Attr::new(0x14, 4).rsvdz(0xfc00_0000), // Received Modified TS Data 2
+    Attr::new(0x18, 4).rw(0xffff_ffff), // Transmitted Modified TS Data 1
+    Attr::new(0x1c, 4).rw(0x03ff_ffff).rsvdp(0xfc00_0000), // Transmitted Modified TS Data 2
+];
+
+/// 32.0 GT/s Lane Equalization Control of Lane 0, other Lanes follow every byte
+pub const LANE_EQUALIZATION_CONTROL_REGISTERS: &[Attr] = &[
+    Attr::new(0x20, 1).hwinit(0xff), // 32.0 GT/s Lane Equalization Control
+];
+
+/// 32.0 GT/s Capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities32GTps {
+    /// Equalization bypass to highest rate Supported
+    pub equalization_bypass_to_highest_rate_supported: bool,
+    /// No Equalization Needed Supported
+    pub no_equalization_needed_supported: bool,
+    /// Modified TS Usage Mode 0 Supported - PCI Express
+    pub modified_ts_usage_mode_0_supported: bool,
+    /// Modified TS Usage Mode 1 Supported - Training Set Message
+    pub modified_ts_usage_mode_1_supported: bool,
+    /// Modified TS Usage Mode 2 Supported - Alternate Protocol
+    pub modified_ts_usage_mode_2_supported: bool,
+}
+
+impl From<u32> for Capabilities32GTps {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            equalization_bypass_to_highest_rate_supported,
+            no_equalization_needed_supported,
+            (),
+            modified_ts_usage_mode_0_supported,
+            modified_ts_usage_mode_1_supported,
+            modified_ts_usage_mode_2_supported,
+            (),
+        )) = P7::<_, 1, 1, 6, 1, 1, 1, 21>(dword).into();
+        Self {
+            equalization_bypass_to_highest_rate_supported,
+            no_equalization_needed_supported,
+            modified_ts_usage_mode_0_supported,
+            modified_ts_usage_mode_1_supported,
+            modified_ts_usage_mode_2_supported,
+        }
+    }
+}
+
+/// 32.0 GT/s Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Control32GTps {
+    /// Equalization bypass to highest rate Disable
+    pub equalization_bypass_to_highest_rate_disable: bool,
+    /// No Equalization Needed Disable
+    pub no_equalization_needed_disable: bool,
+    /// Modified TS Usage Mode Selected
+    pub modified_ts_usage_mode_selected: u8,
+}
+
+impl From<u32> for Control32GTps {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            equalization_bypass_to_highest_rate_disable,
+            no_equalization_needed_disable,
+            (),
+            modified_ts_usage_mode_selected,
+            (),
+        )) = P5::<_, 1, 1, 6, 3, 21>(dword).into();
+        Self {
+            equalization_bypass_to_highest_rate_disable,
+            no_equalization_needed_disable,
+            modified_ts_usage_mode_selected,
+        }
+    }
+}
+
+impl From<Control32GTps> for u32 {
+    fn from(data: Control32GTps) -> Self {
+        (data.equalization_bypass_to_highest_rate_disable as u32)
+            | (data.no_equalization_needed_disable as u32) << 1
+            | (data.modified_ts_usage_mode_selected as u32 & 0b111) << 8
+    }
+}
+
+impl Register for Control32GTps {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_f8fc;
+    const RW1C: u32 = 0;
+}
+
+/// 32.0 GT/s Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Status32GTps {
+    /// Equalization 32.0 GT/s Complete
+    pub equalization_complete: bool,
+    /// Equalization 32.0 GT/s Phase 1 Successful
+    pub equalization_phase_1_successful: bool,
+    /// Equalization 32.0 GT/s Phase 2 Successful
+    pub equalization_phase_2_successful: bool,
+    /// Equalization 32.0 GT/s Phase 3 Successful
+    pub equalization_phase_3_successful: bool,
+    /// Link Equalization Request 32.0 GT/s
+    pub link_equalization_request: bool,
+    /// Modified TS Received
+    pub modified_ts_received: bool,
+    /// Received Enhanced Link Behavior Control
+    pub received_enhanced_link_behavior_control: u8,
+    /// Transmitter Precoding On
+    pub transmitter_precoding_on: bool,
+    /// Transmitter Precode Request
+    pub transmitter_precode_request: bool,
+    /// No Equalization Needed Received
+    pub no_equalization_needed_received: bool,
+}
+
+impl From<u32> for Status32GTps {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            equalization_complete,
+            equalization_phase_1_successful,
+            equalization_phase_2_successful,
+            equalization_phase_3_successful,
+            link_equalization_request,
+            modified_ts_received,
+            received_enhanced_link_behavior_control,
+            transmitter_precoding_on,
+            transmitter_precode_request,
+            no_equalization_needed_received,
+            (),
+        )) = P11::<_, 1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 21>(dword).into();
+        Self {
+            equalization_complete,
+            equalization_phase_1_successful,
+            equalization_phase_2_successful,
+            equalization_phase_3_successful,
+            link_equalization_request,
+            modified_ts_received,
+            received_enhanced_link_behavior_control,
+            transmitter_precoding_on,
+            transmitter_precode_request,
+            no_equalization_needed_received,
+        }
+    }
+}
+
+impl From<Status32GTps> for u32 {
+    fn from(data: Status32GTps) -> Self {
+        (data.equalization_complete as u32)
+            | (data.equalization_phase_1_successful as u32) << 1
+            | (data.equalization_phase_2_successful as u32) << 2
+            | (data.equalization_phase_3_successful as u32) << 3
+            | (data.link_equalization_request as u32) << 4
+            | (data.modified_ts_received as u32) << 5
+            | (data.received_enhanced_link_behavior_control as u32 & 0b11) << 6
+            | (data.transmitter_precoding_on as u32) << 8
+            | (data.transmitter_precode_request as u32) << 9
+            | (data.no_equalization_needed_received as u32) << 10
+    }
+}
+
+impl Register for Status32GTps {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0000_0010;
+}
+
+/// Modified TS Data 1 and 2 registers
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifiedTsData {
+    /// Modified TS Usage Mode
+    pub usage_mode: u8,
+    /// Modified TS Information 1
+    pub information_1: u16,
+    /// Modified TS Vendor ID
+    pub vendor_id: u16,
+    /// Modified TS Information 2
+    pub information_2: u32,
+    /// Alternate Protocol Negotiation Status
+    pub alternate_protocol_negotiation_status: u8,
+}
+
+impl From<[u32; 2]> for ModifiedTsData {
+    fn from([data_1, data_2]: [u32; 2]) -> Self {
+        let Lsb((usage_mode, information_1, vendor_id)) = P3::<_, 3, 13, 16>(data_1).into();
+        let Lsb((information_2, alternate_protocol_negotiation_status, ())) =
+            P3::<_, 24, 2, 6>(data_2).into();
+        Self {
+            usage_mode,
+            information_1,
+            vendor_id,
+            information_2,
+            alternate_protocol_negotiation_status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn control_round_trip() {
+        let dword = 0x0000_0602;
+        let result = Control32GTps::from(dword);
+        assert_eq!(
+            Control32GTps {
+                equalization_bypass_to_highest_rate_disable: false,
+                no_equalization_needed_disable: true,
+                modified_ts_usage_mode_selected: 0b110,
+            },
+            result
+        );
+        assert_eq!(dword, u32::from(result));
+        // Reserved bits are dropped
+        assert_eq!(0x0000_0703, u32::from(Control32GTps::from(u32::MAX)));
+    }
+
+    #[test]
+    fn status_round_trip() {
+        let dword = 0x0000_0595;
+        let result = Status32GTps::from(dword);
+        assert_eq!(
+            Status32GTps {
+                equalization_complete: true,
+                equalization_phase_1_successful: false,
+                equalization_phase_2_successful: true,
+                equalization_phase_3_successful: false,
+                link_equalization_request: true,
+                modified_ts_received: false,
+                received_enhanced_link_behavior_control: 0b10,
+                transmitter_precoding_on: true,
+                transmitter_precode_request: false,
+                no_equalization_needed_received: true,
+            },
+            result
+        );
+        assert_eq!(dword, u32::from(result));
+        assert_eq!(0x0000_07ff, u32::from(Status32GTps::from(u32::MAX)));
+        // Link Equalization Request is the only RW1C bit
+        assert_eq!(
+            0x0000_07ef,
+            Status32GTps::from(0x0000_07ff).write_value(0x0000_07ff)
+        );
+    }
+
+    #[test]
+    fn modified_ts_data() {
+        let result = ModifiedTsData::from([0xabcd_5a5d, 0xfe12_3456]);
+        assert_eq!(
+            ModifiedTsData {
+                usage_mode: 0b101,
+                information_1: 0x0b4b,
+                vendor_id: 0xabcd,
+                information_2: 0x12_3456,
+                alternate_protocol_negotiation_status: 0b10,
+            },
+            result
+        );
+    }
+}
@@ -0,0 +1,210 @@
+/*!
+# Physical Layer 64.0 GT/s
+
+The Physical Layer 64.0 GT/s Extended Capability structure must be implemented in a Function
+that supports 64.0 GT/s data rate. It reports the 64.0 GT/s equalization progress and per-Lane
+64.0 GT/s equalization presets.
+
+## Struct diagram
+[PhysicalLayer64GTps]
+- [Status64GTps]
+- [EqualizationControlLanes]
+  - [LaneEqualizationControl]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::physical_layer_64_gtps::*;
+# use pcics::capabilities::pci_express::LinkWidth;
+let data = [
+    /* 00h */ 0x31, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x00, 0x00, 0x00, 0x00, // 64.0 GT/s Capabilities
+    /* 08h */ 0x00, 0x00, 0x00, 0x00, // 64.0 GT/s Control
+    /* 0Ch */ 0x2f, 0x00, 0x00, 0x00, // 64.0 GT/s Status
+    /* 10h */ 0x00, 0x00, 0x00, 0x00, // 64.0 GT/s Lane Equalization Control
+];
+let result: PhysicalLayer64GTps = data[4..].try_into().unwrap();
+
+assert_eq!(
+    Status64GTps {
+        equalization_complete: true,
+        equalization_phase_1_successful: true,
+        equalization_phase_2_successful: true,
+        equalization_phase_3_successful: true,
+        link_equalization_request: false,
+        transmitter_precoding_on: true,
+        transmitter_precode_request: false,
+        no_equalization_needed_received: false,
+    },
+    result.status
+);
+assert_eq!(4, result.equalization_control_lanes(LinkWidth::X4).count());
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P3, P9};
+
+use super::ExtendedCapabilityDataError;
+
+use crate::capabilities::pci_express::LinkWidth;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub use super::physical_layer_16_gtps::{EqualizationControlLanes, LaneEqualizationControl};
+
+/// Lane Equalization Control offset
+pub const LEC_OFFSET: usize = 0x10;
+
+/// Physical Layer 64.0 GT/s
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PhysicalLayer64GTps<'a> {
+    lec_data: &'a [u8],
+    pub status: Status64GTps,
+}
+impl<'a> PhysicalLayer64GTps<'a> {
+    pub fn equalization_control_lanes(
+        &self,
+        link_width: LinkWidth,
+    ) -> EqualizationControlLanes<'a> {
+        EqualizationControlLanes::new(self.lec_data, link_width)
+    }
+    /// Access attributes of registers including Lane Equalization Control for `link_width` lanes
+    pub fn registers(&self, link_width: LinkWidth) -> RegisterMap {
+        let count = u8::from(link_width) as u16;
+        RegisterMap::from(REGISTERS).repeat(LANE_EQUALIZATION_CONTROL_REGISTERS, 1, count)
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for PhysicalLayer64GTps<'a> {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capabilities, control, status)),
+            tail,
+        } = P3(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Physical Layer 64.0 GT/s",
+                size: 12,
+            })?;
+        let _: (u32, u32) = (capabilities, control);
+        Ok(Self {
+            lec_data: tail,
+            status: From::<u32>::from(status),
+        })
+    }
+}
+
+/// Owned [PhysicalLayer64GTps]
+///
+/// Converts back into [PhysicalLayer64GTps] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedPhysicalLayer64GTps {
+    lec_data: Vec<u8>,
+    pub status: Status64GTps,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&PhysicalLayer64GTps<'a>> for OwnedPhysicalLayer64GTps {
+    fn from(value: &PhysicalLayer64GTps<'a>) -> Self {
+        Self {
+            lec_data: value.lec_data.to_vec(),
+            status: value.status.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedPhysicalLayer64GTps> for PhysicalLayer64GTps<'a> {
+    fn from(value: &'a OwnedPhysicalLayer64GTps) -> Self {
+        Self {
+            lec_data: &value.lec_data,
+            status: value.status.clone(),
+        }
+    }
+}
+
+/// Physical Layer 64.0 GT/s Capability registers without Lane Equalization Control
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                    // Extended Capability Header
+    Attr::new(0x04, 4).rsvdp(0xffff_ffff), // 64.0 GT/s Capabilities
+    Attr::new(0x08, 4).rsvdp(0xffff_ffff), // 64.0 GT/s Control
+    Attr::new(0x0c, 4).rw1c(0x0000_0010).rsvdz(0xffff_ff00), // 64.0 GT/s Status
+];
+
+/// 64.0 GT/s Lane Equalization Control of Lane 0, other Lanes follow every byte
+pub const LANE_EQUALIZATION_CONTROL_REGISTERS: &[Attr] = &[
+    Attr::new(0x10, 1).hwinit(0xff), // 64.0 GT/s Lane Equalization Control
+];
+
+/// 64.0 GT/s Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Status64GTps {
+    /// Equalization 64.0 GT/s Complete
+    pub equalization_complete: bool,
+    /// Equalization 64.0 GT/s Phase 1 Successful
+    pub equalization_phase_1_successful: bool,
+    /// Equalization 64.0 GT/s Phase 2 Successful
+    pub equalization_phase_2_successful: bool,
+    /// Equalization 64.0 GT/s Phase 3 Successful
+    pub equalization_phase_3_successful: bool,
+    /// Link Equalization Request 64.0 GT/s
+    pub link_equalization_request: bool,
+    /// Transmitter Precoding On
+    pub transmitter_precoding_on: bool,
+    /// Transmitter Precode Request
+    pub transmitter_precode_request: bool,
+    /// No Equalization Needed Received
+    pub no_equalization_needed_received: bool,
+}
+
+impl From<u32> for Status64GTps {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            equalization_complete,
+            equalization_phase_1_successful,
+            equalization_phase_2_successful,
+            equalization_phase_3_successful,
+            link_equalization_request,
+            transmitter_precoding_on,
+            transmitter_precode_request,
+            no_equalization_needed_received,
+            (),
+        )) = P9::<_, 1, 1, 1, 1, 1, 1, 1, 1, 24>(dword).into();
+        Self {
+            equalization_complete,
+            equalization_phase_1_successful,
+            equalization_phase_2_successful,
+            equalization_phase_3_successful,
+            link_equalization_request,
+            transmitter_precoding_on,
+            transmitter_precode_request,
+            no_equalization_needed_received,
+        }
+    }
+}
+
+impl From<Status64GTps> for u32 {
+    fn from(data: Status64GTps) -> Self {
+        (data.equalization_complete as u32)
+            | (data.equalization_phase_1_successful as u32) << 1
+            | (data.equalization_phase_2_successful as u32) << 2
+            | (data.equalization_phase_3_successful as u32) << 3
+            | (data.link_equalization_request as u32) << 4
+            | (data.transmitter_precoding_on as u32) << 5
+            | (data.transmitter_precode_request as u32) << 6
+            | (data.no_equalization_needed_received as u32) << 7
+    }
+}
+
+impl Register for Status64GTps {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0x0000_0010;
+}
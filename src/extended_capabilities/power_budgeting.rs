@@ -45,8 +45,10 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P5, P7};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PowerBudgeting {
     /// Data Select
     pub data_select: u8,
@@ -92,8 +94,21 @@ impl TryFrom<&[u8]> for PowerBudgeting {
     }
 }
 
+/// Power Budgeting Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),          // Extended Capability Header
+    Attr::new(0x04, 1).rw(0xff), // Data Select
+    Attr::new(0x05, 1).rsvdp(0xff),
+    Attr::new(0x06, 2).rsvdp(0xffff),
+    Attr::new(0x08, 4),                          // Data
+    Attr::new(0x0c, 1).hwinit(0x01).rsvdp(0xfe), // Power Budget Capability
+    Attr::new(0x0d, 1).rsvdp(0xff),
+    Attr::new(0x0e, 2).rsvdp(0xffff),
+];
+
 /// Power Budgeting Data
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Data {
     pub base_power: BasePower,
     pub data_scale: DataScale,
@@ -105,6 +120,7 @@ pub struct Data {
 
 /// Specifies in watts the base power value in the given operating condition
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BasePower {
     Value(u8),
     Gt239Le250,
@@ -126,6 +142,7 @@ impl From<u8> for BasePower {
 
 /// Specifies the scale to apply to the Base Power value
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataScale {
     /// 1.0x
     One,
@@ -160,6 +177,7 @@ impl From<u8> for DataScale {
 
 /// Specifies the power management sub state of the operating condition being described
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PmSubState {
     /// Default Sub State
     Default,
@@ -177,6 +195,7 @@ impl From<u8> for PmSubState {
 
 /// Specifies the power management state of the operating condition being described
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PmState {
     D0,
     D1,
@@ -197,6 +216,7 @@ impl From<u8> for PmState {
 
 /// Specifies the type of the operating condition being described
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OperationConditionType {
     /// PME Aux
     PmeAux,
@@ -232,6 +252,7 @@ impl From<u8> for OperationConditionType {
 
 /// Specifies the thermal load or power rail of the operating condition being described
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerRail {
     /// Power (12V)
     Power12v,
@@ -258,6 +279,7 @@ impl From<u8> for PowerRail {
 
 /// Power Budget Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PowerBudgetCapability {
     /// System Allocated
     pub system_allocated: bool,
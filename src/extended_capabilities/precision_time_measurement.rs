@@ -44,8 +44,10 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P5, P6};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrecisionTimeMeasurement {
     /// PTM Capability
     pub ptm_capability: PtmCapability,
@@ -72,8 +74,16 @@ impl TryFrom<&[u8]> for PrecisionTimeMeasurement {
     }
 }
 
+/// PTM Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // PTM Capability
+    Attr::new(0x08, 4).rw(0x0000_ff03).rsvdp(0xffff_00fc), // PTM Control
+];
+
 /// Describes a Function’s support for Precision Time Measurement
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PtmCapability {
     /// PTM Requester Capable
     pub ptm_requester_capable: bool,
@@ -105,6 +115,7 @@ impl From<u32> for PtmCapability {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PtmControl {
     /// PTM Enable
     pub ptm_enable: bool,
@@ -125,3 +136,17 @@ impl From<u32> for PtmControl {
         }
     }
 }
+
+impl From<PtmControl> for u32 {
+    fn from(data: PtmControl) -> Self {
+        (data.ptm_enable as u32)
+            | (data.root_select as u32) << 1
+            | (data.effective_granularity as u32 & 0xff) << 8
+    }
+}
+
+impl Register for PtmControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_00fc;
+    const RW1C: u32 = 0;
+}
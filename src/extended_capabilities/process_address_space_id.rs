@@ -36,8 +36,10 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P4, P6};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProcessAddressSpaceId {
     pub pacid_capability: PacidCapability,
     pub pacid_control: PacidControl,
@@ -62,8 +64,16 @@ impl TryFrom<&[u8]> for ProcessAddressSpaceId {
     }
 }
 
+/// PASID Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 2),                          // PASID Capability
+    Attr::new(0x06, 2).rw(0x0007).rsvdp(0xfff8), // PASID Control
+];
+
 /// PASID Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacidCapability {
     /// Execute Permission Supported
     pub execute_permission_supported: bool,
@@ -93,6 +103,7 @@ impl From<u16> for PacidCapability {
 
 /// PASID Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacidControl {
     /// PASID Enable
     pub pasid_enable: bool,
@@ -113,3 +124,17 @@ impl From<u16> for PacidControl {
         }
     }
 }
+
+impl From<PacidControl> for u16 {
+    fn from(data: PacidControl) -> Self {
+        (data.pasid_enable as u16)
+            | (data.execute_permission_enable as u16) << 1
+            | (data.privileged_mode_enable as u16) << 2
+    }
+}
+
+impl Register for PacidControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xfff8;
+    const RW1C: u16 = 0;
+}
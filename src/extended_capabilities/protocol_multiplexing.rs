@@ -89,8 +89,13 @@ use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P16, P2, P3, P4, P5, P8};
 use snafu::Snafu;
 
 use super::ExtendedCapabilityHeader;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ProtocolMultiplexingError {
     #[snafu(display("capability, control and status fields are unreadable"))]
     Mandatory,
@@ -102,6 +107,7 @@ pub enum ProtocolMultiplexingError {
 
 /// Protocol Multiplexing
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ProtocolMultiplexing<'a> {
     pub pmux_capability: PmuxCapability,
     pub pmux_control: PmuxControl,
@@ -142,8 +148,53 @@ impl<'a> TryFrom<&'a [u8]> for ProtocolMultiplexing<'a> {
     }
 }
 
+/// Owned [ProtocolMultiplexing] with decoded PMUX Protocol Array
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedProtocolMultiplexing {
+    pub pmux_capability: PmuxCapability,
+    pub pmux_control: PmuxControl,
+    pub pmux_status: PmuxStatus,
+    pub pmux_protocol_array: Vec<PmuxProtocolArrayEntry>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ProtocolMultiplexing<'a>> for OwnedProtocolMultiplexing {
+    fn from(pmux: &ProtocolMultiplexing<'a>) -> Self {
+        Self {
+            pmux_capability: pmux.pmux_capability.clone(),
+            pmux_control: pmux.pmux_control.clone(),
+            pmux_status: pmux.pmux_status.clone(),
+            pmux_protocol_array: pmux.pmux_protocol_array.clone().collect(),
+        }
+    }
+}
+
+impl<'a> ProtocolMultiplexing<'a> {
+    /// Access attributes of registers including PMUX Protocol Array
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.pmux_capability.pmux_protocol_array_size as u16;
+        RegisterMap::from(REGISTERS).repeat(PROTOCOL_ARRAY_REGISTERS, 4, count)
+    }
+}
+
+/// PMUX Capability registers without PMUX Protocol Array
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // PMUX Capability
+    Attr::new(0x08, 4).rw(0x3f3f_3f3f).rsvdp(0xc0c0_c0c0), // PMUX Control
+    Attr::new(0x0c, 4), // PMUX Status
+];
+
+/// First PMUX Protocol Array entry, other entries follow every 4 bytes
+pub const PROTOCOL_ARRAY_REGISTERS: &[Attr] = &[
+    Attr::new(0x10, 4), // PMUX Protocol Array Entry
+];
+
 /// PMUX Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PmuxCapability {
     /// Indicates the size of this Function’s PMUX Protocol Array
     pub pmux_protocol_array_size: u8,
@@ -163,6 +214,7 @@ impl From<u32> for PmuxCapability {
 
 /// Indicates the Link speed(s) where Protocol Multiplexing is supported
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PmuxSupportedLinkSpeeds {
     /// 2.5 GT/s
     pub speed_2_5_gtps: bool,
@@ -189,6 +241,7 @@ impl From<u8> for PmuxSupportedLinkSpeeds {
 
 /// PMUX Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PmuxControl {
     /// Indicates the protocol assigned to PMUX Channel 0
     pub pmux_channel_0_assignment: u8,
@@ -221,8 +274,24 @@ impl From<u32> for PmuxControl {
     }
 }
 
+impl From<PmuxControl> for u32 {
+    fn from(data: PmuxControl) -> Self {
+        (data.pmux_channel_0_assignment as u32 & 0x3f)
+            | (data.pmux_channel_1_assignment as u32 & 0x3f) << 8
+            | (data.pmux_channel_2_assignment as u32 & 0x3f) << 16
+            | (data.pmux_channel_3_assignment as u32 & 0x3f) << 24
+    }
+}
+
+impl Register for PmuxControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xc0c0_c0c0;
+    const RW1C: u32 = 0;
+}
+
 /// PMUX Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PmuxStatus {
     /// If Set, Channel 0 is disabled because the Current Link Speed is not
     /// supported by Protocol Multiplexing or by the protocol assigned to Channel 0
@@ -295,6 +364,29 @@ impl From<u32> for PmuxStatus {
     }
 }
 
+impl From<PmuxStatus> for u32 {
+    fn from(data: PmuxStatus) -> Self {
+        (data.pmux_channel_0_disabled_link_speed as u32)
+            | (data.pmux_channel_0_disabled_link_width as u32) << 1
+            | (data.pmux_channel_0_disabled_link_protocol_specific as u32) << 2
+            | (data.pmux_channel_1_disabled_link_speed as u32) << 8
+            | (data.pmux_channel_1_disabled_link_width as u32) << 9
+            | (data.pmux_channel_1_disabled_link_protocol_specific as u32) << 10
+            | (data.pmux_channel_2_disabled_link_speed as u32) << 16
+            | (data.pmux_channel_2_disabled_link_width as u32) << 17
+            | (data.pmux_channel_2_disabled_link_protocol_specific as u32) << 18
+            | (data.pmux_channel_3_disabled_link_speed as u32) << 24
+            | (data.pmux_channel_3_disabled_link_width as u32) << 25
+            | (data.pmux_channel_3_disabled_link_protocol_specific as u32) << 26
+    }
+}
+
+impl Register for PmuxStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0;
+}
+
 /// An iterator through [PmuxProtocolArrayEntry]
 #[derive(Debug, Clone)]
 pub struct PmuxProtocolArray<'a>(pub slice::Chunks<'a, u8>);
@@ -329,8 +421,16 @@ impl<'a> Iterator for PmuxProtocolArray<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for PmuxProtocolArray<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 /// PMUX Protocol Array entry
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PmuxProtocolArrayEntry {
     /// Designates a specific protocol and the mechanism by which that protocol
     /// is mapped onto Protocol Multiplexing
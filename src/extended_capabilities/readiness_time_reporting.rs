@@ -48,9 +48,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3, P4};
 
 use super::{ExtendedCapabilityDataError, ExtendedCapabilityHeader};
+use crate::register::RegisterAttributes as Attr;
 
 /// Readiness Time Reporting
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadinessTimeReporting {
     /// The time the Function requires to become Configuration-Ready after the
     /// completion of Conventional Reset
@@ -102,8 +104,16 @@ impl TryFrom<&[u8]> for ReadinessTimeReporting {
     }
 }
 
+/// Readiness Time Reporting Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                     // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0xffff_ffff), // Readiness Time Reporting 1
+    Attr::new(0x08, 4).hwinit(0x00ff_ffff).rsvdp(0xff00_0000), // Readiness Time Reporting 2
+];
+
 /// Readiness Time Encoding
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadinessTime {
     pub value: u16,
     pub scale: u8,
@@ -81,8 +81,13 @@ use heterob::{
 use snafu::Snafu;
 
 use super::ExtendedCapabilityHeader;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ResizableBarError {
     #[snafu(display("should have at least one entry"))]
     FirstEntry,
@@ -134,6 +139,38 @@ impl<'a> TryFrom<&'a [u8]> for ResizableBar<'a> {
     }
 }
 
+/// Owned [ResizableBar] with decoded entries
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedResizableBar(pub Vec<ResizableBarEntry>);
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ResizableBar<'a>> for OwnedResizableBar {
+    fn from(rebar: &ResizableBar<'a>) -> Self {
+        Self(rebar.clone().collect())
+    }
+}
+
+impl<'a> ResizableBar<'a> {
+    /// Access attributes of registers of all entries
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.0.len() as u16;
+        RegisterMap::from(REGISTERS).repeat(ENTRY_REGISTERS, Self::ENTRY_SIZE as u16, count)
+    }
+}
+
+/// Resizable BAR Capability registers without entries
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+];
+
+/// Registers of the first Resizable BAR entry, other entries follow every 8 bytes
+pub const ENTRY_REGISTERS: &[Attr] = &[
+    Attr::new(0x04, 4),                                    // Resizable BAR Capability
+    Attr::new(0x08, 4).rw(0x0000_3f00).rsvdp(0x0000_c018), // Resizable BAR Control
+];
+
 impl<'a> Iterator for ResizableBar<'a> {
     type Item = ResizableBarEntry;
 
@@ -150,8 +187,16 @@ impl<'a> Iterator for ResizableBar<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ResizableBar<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 /// Resizable BAR Entry
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResizableBarEntry {
     pub capability: ResizableBarCapability,
     pub control: ResizableBarControl,
@@ -178,6 +223,7 @@ impl ResizableBarEntry {
 
 /// Resizable BAR Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResizableBarCapability {
     pub support_map_from_1mb_to_128tb: u32,
 }
@@ -192,6 +238,7 @@ impl From<u32> for ResizableBarCapability {
 
 /// Resizable BAR Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResizableBarControl {
     pub bar_index: u8,
     pub number_of_resizable_bars: u8,
@@ -217,3 +264,18 @@ impl From<u32> for ResizableBarControl {
         }
     }
 }
+
+impl From<ResizableBarControl> for u32 {
+    fn from(data: ResizableBarControl) -> Self {
+        (data.bar_index as u32 & 0b111)
+            | (data.number_of_resizable_bars as u32 & 0b111) << 5
+            | (data.bar_size as u32 & 0x3f) << 8
+            | (data.support_map_from_256tb_to_8eb as u32) << 16
+    }
+}
+
+impl Register for ResizableBarControl {
+    type Value = u32;
+    const RSVDP: u32 = 0x0000_c018;
+    const RW1C: u32 = 0;
+}
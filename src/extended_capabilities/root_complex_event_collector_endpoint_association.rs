@@ -32,9 +32,11 @@ assert_eq!(sample, result);
 use heterob::{endianness::Le, Seq};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
 
 /// Root Complex Event Collector Endpoint Association
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootComplexEventCollectorEndpointAssociation {
     /// The Association Bitmap for RCiEPs is a read-only register that sets
     /// the bits corresponding to the Device Numbers of RCiEPs supported by the Root
@@ -66,3 +68,9 @@ impl TryFrom<&[u8]> for RootComplexEventCollectorEndpointAssociation {
         Ok(From::<[u8; Self::SIZE]>::from(head))
     }
 }
+
+/// Root Complex Event Collector Endpoint Association Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                     // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0xffff_ffff), // Association Bitmap for RCiEPs
+];
@@ -72,9 +72,11 @@ use crate::capabilities::pci_express::{
 };
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// Root Complex Internal Link Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootComplexInternalLinkControl {
     /// Root Complex Link Capabilities
     pub root_complex_link_capabilities: RootComplexLinkCapabilities,
@@ -115,8 +117,17 @@ impl TryFrom<&[u8]> for RootComplexInternalLinkControl {
     }
 }
 
+/// Root Complex Internal Link Control Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 4),                          // Root Complex Link Capabilities
+    Attr::new(0x08, 2).rw(0x0083).rsvdp(0xff7c), // Root Complex Link Control
+    Attr::new(0x0a, 2),                          // Root Complex Link Status
+];
+
 /// Root Complex Link Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootComplexLinkCapabilities {
     pub max_link_speed: LinkSpeed,
     pub maximum_link_width: LinkWidth,
@@ -152,6 +163,7 @@ impl From<u32> for RootComplexLinkCapabilities {
 
 /// Root Complex Link Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootComplexLinkControl {
     pub active_state_power_management_control: ActiveStatePowerManagement,
     /// Forces the transmission of additional Ordered Sets when exiting the L0s
@@ -172,8 +184,22 @@ impl From<u16> for RootComplexLinkControl {
     }
 }
 
+impl From<RootComplexLinkControl> for u16 {
+    fn from(data: RootComplexLinkControl) -> Self {
+        (u8::from(data.active_state_power_management_control) as u16 & 0b11)
+            | (data.extended_synch as u16) << 7
+    }
+}
+
+impl Register for RootComplexLinkControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xff7c;
+    const RW1C: u16 = 0;
+}
+
 /// Root Complex Link Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootComplexLinkStatus {
     pub current_link_speed: LinkSpeed,
     pub negotiated_link_width: LinkWidth,
@@ -188,3 +214,16 @@ impl From<u16> for RootComplexLinkStatus {
         }
     }
 }
+
+impl From<RootComplexLinkStatus> for u16 {
+    fn from(data: RootComplexLinkStatus) -> Self {
+        (u8::from(data.current_link_speed) as u16 & 0b1111)
+            | (u8::from(data.negotiated_link_width) as u16 & 0x3f) << 4
+    }
+}
+
+impl Register for RootComplexLinkStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
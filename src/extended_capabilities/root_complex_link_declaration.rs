@@ -63,10 +63,12 @@ use heterob::{
 };
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{RegisterAttributes as Attr, RegisterMap};
 
 /// The Serial Number register is a 64-bit field that contains the IEEE defined 64-bit extended
 /// unique identifier (EUI-64™).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceSerialNumber {
     /// PCI Express Device Serial Number (1st DW)
     pub lower_dword: u32,
@@ -98,8 +100,12 @@ use core::slice::Chunks;
 use heterob::{bit_numbering::Lsb, endianness::FromLeBytes, P4, P5, P6};
 use snafu::prelude::*;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Root Complex Link Declaration Error
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum RootComplexLinkDeclarationError {
     #[snafu(display("can't read Element Self Description (4 bytes) from slice"))]
     ElementSelfDescription,
@@ -119,6 +125,7 @@ pub enum RootComplexLinkDeclarationError {
 
 /// Root Complex Link Declaration
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RootComplexLinkDeclaration<'a> {
     pub element_self_description: ElementSelfDescription,
     pub link_entries: LinkEntries<'a>,
@@ -164,9 +171,55 @@ impl<'a> TryFrom<&'a [u8]> for RootComplexLinkDeclaration<'a> {
     }
 }
 
+/// Owned [RootComplexLinkDeclaration] with decoded link entries
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedRootComplexLinkDeclaration {
+    pub element_self_description: ElementSelfDescription,
+    pub link_entries: Vec<LinkEntry>,
+    pub link_entries_state: LinkEntriesState,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&RootComplexLinkDeclaration<'a>> for OwnedRootComplexLinkDeclaration {
+    fn from(rcld: &RootComplexLinkDeclaration<'a>) -> Self {
+        Self {
+            element_self_description: rcld.element_self_description.clone(),
+            link_entries: rcld.link_entries.clone().collect(),
+            link_entries_state: rcld.link_entries.state.clone(),
+        }
+    }
+}
+
+impl<'a> RootComplexLinkDeclaration<'a> {
+    /// Access attributes of Element Self Description and Link Entries registers
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.element_self_description.number_of_link_entries as u16;
+        RegisterMap::from(REGISTERS).repeat(LINK_ENTRY_REGISTERS, 0x10, count)
+    }
+}
+
+/// Root Complex Link Declaration Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0xffff_ff0f).rsvdp(0x0000_00f0), // Element Self Description
+    Attr::new(0x08, 4).rsvdp(0xffff_ffff),
+    Attr::new(0x0c, 4).rsvdp(0xffff_ffff),
+];
+
+/// Registers of the first Link Entry, other entries follow every 10h bytes
+pub const LINK_ENTRY_REGISTERS: &[Attr] = &[
+    Attr::new(0x10, 4).hwinit(0xffff_ffff), // Link Description
+    Attr::new(0x14, 4).rsvdp(0xffff_ffff),
+    Attr::new(0x18, 4).hwinit(0xffff_ffff), // Link Address (Lower DW)
+    Attr::new(0x1c, 4).hwinit(0xffff_ffff), // Link Address (Upper DW)
+];
+
 /// Provides information about the Root Complex element containing the Root
 /// Complex Link Declaration Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElementSelfDescription {
     /// Element Type
     pub element_type: ElementType,
@@ -204,6 +257,7 @@ impl FromLeBytes<4> for ElementSelfDescription {
 
 /// Indicates the type of the Root Complex Element
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElementType {
     /// Configuration Space Element
     ConfigurationSpaceElement,
@@ -243,6 +297,7 @@ pub struct LinkEntries<'a> {
 }
 impl<'a> LinkEntries<'a> {
     pub const FIRST_ENTRY_OFFSET: usize = 0x10 - super::ECH_BYTES;
+    #[allow(clippy::manual_is_multiple_of)]
     pub fn new(slice: &'a [u8], number_of_link_entries: u8) -> Self {
         let length = (number_of_link_entries as usize) * LinkEntry::SIZE;
         Self {
@@ -283,8 +338,16 @@ impl<'a> Iterator for LinkEntries<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for LinkEntries<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 /// Link Entries State depends on bytes number
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkEntriesState {
     /// Number of entries equal to [ElementSelfDescription::number_of_link_entries]
     Valid,
@@ -296,6 +359,7 @@ pub enum LinkEntriesState {
 
 /// Declares an internal Link to another Root Complex Element
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkEntry {
     /// Link Description
     pub link_description: LinkDescription,
@@ -309,6 +373,7 @@ impl LinkEntry {
 
 /// Link Description
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkDescription {
     /// Link Valid
     pub link_valid: bool,
@@ -348,6 +413,7 @@ impl FromLeBytes<4> for LinkDescription {
 
 /// Identifies the target element for the Link entry
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkAddress {
     /// Pointing to a memory-mapped RCRB
     MemoryMappedSpace(u64),
@@ -428,6 +494,7 @@ mod tests {
     use std::prelude::v1::*;
 
     #[test]
+    #[allow(clippy::unusual_byte_groupings)]
     fn link_description() {
         let result: LinkDescription = 0b10101010_10101010_0000000000000_1_0_1.into();
         let sample = LinkDescription {
@@ -449,6 +516,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unusual_byte_groupings)]
     fn link_address_configuration_space() {
         //           address    bus  dev   fn  reserved  N
         let data = [0b11111111_0101_01010_101_000000000_100u32, 0x33221100];
@@ -45,9 +45,11 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P6};
 
 use super::{ExtendedCapabilityDataError, ExtendedCapabilityHeaderPlaceholder};
+use crate::register::{Register, RegisterAttributes as Attr};
 
 /// RCRB Header
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootComplexRegisterBlockHeader {
     /// Vendor ID – PCI-SIG assigned.
     ///
@@ -100,8 +102,19 @@ impl TryFrom<&[u8]> for RootComplexRegisterBlockHeader {
     }
 }
 
+/// RCRB Header Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 2), // Vendor ID
+    Attr::new(0x06, 2), // Device ID
+    Attr::new(0x08, 4), // RCRB Capabilities
+    Attr::new(0x0c, 4).rw(0x0000_0001).rsvdp(0xffff_fffe), // RCRB Control
+    Attr::new(0x10, 4).rsvdp(0xffff_ffff),
+];
+
 /// RCRB Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RcrbCapabilities {
     /// CRS Software Visibility
     ///
@@ -122,6 +135,7 @@ impl From<u32> for RcrbCapabilities {
 
 /// RCRB Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RcrbControl {
     /// CRS Software Visibility Enable
     ///
@@ -139,3 +153,15 @@ impl From<u32> for RcrbControl {
         }
     }
 }
+
+impl From<RcrbControl> for u32 {
+    fn from(data: RcrbControl) -> Self {
+        data.crs_software_visibility_enable as u32
+    }
+}
+
+impl Register for RcrbControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_fffe;
+    const RW1C: u32 = 0;
+}
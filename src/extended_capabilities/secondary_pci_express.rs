@@ -62,11 +62,16 @@ use core::slice::Chunks;
 use crate::capabilities::pci_express::{
     LinkWidth, ReceiverPresetHint, SupportedLinkSpeedsVector, TransmitterPreset,
 };
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Lane Equalization Control offset
 pub const ECL_OFFSET: usize = 0x0C;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SecondaryPciExpress<'a> {
     ecl_data: &'a [u8],
     pub link_control_3: LinkControl3,
@@ -103,8 +108,63 @@ impl<'a> TryFrom<&'a [u8]> for SecondaryPciExpress<'a> {
     }
 }
 
+/// Owned [SecondaryPciExpress]
+///
+/// Converts back into [SecondaryPciExpress] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedSecondaryPciExpress {
+    ecl_data: Vec<u8>,
+    pub link_control_3: LinkControl3,
+    pub lane_error_status: LaneErrorStatus,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&SecondaryPciExpress<'a>> for OwnedSecondaryPciExpress {
+    fn from(value: &SecondaryPciExpress<'a>) -> Self {
+        Self {
+            ecl_data: value.ecl_data.to_vec(),
+            link_control_3: value.link_control_3.clone(),
+            lane_error_status: value.lane_error_status.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedSecondaryPciExpress> for SecondaryPciExpress<'a> {
+    fn from(value: &'a OwnedSecondaryPciExpress) -> Self {
+        Self {
+            ecl_data: &value.ecl_data,
+            link_control_3: value.link_control_3.clone(),
+            lane_error_status: value.lane_error_status.clone(),
+        }
+    }
+}
+
+impl<'a> SecondaryPciExpress<'a> {
+    /// Access attributes of registers including Lane Equalization Control for `link_width` lanes
+    pub fn registers(&self, link_width: LinkWidth) -> RegisterMap {
+        let count = u8::from(link_width) as u16;
+        RegisterMap::from(REGISTERS).repeat(LANE_EQUALIZATION_CONTROL_REGISTERS, 2, count)
+    }
+}
+
+/// Secondary PCI Express Capability registers without Lane Equalization Control
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).rw(0x0000_fe03).rsvdp(0xffff_01fc), // Link Control 3
+    Attr::new(0x08, 4).rw1c(0xffff_ffff), // Lane Error Status
+];
+
+/// Lane Equalization Control of Lane 0, other Lanes follow every 2 bytes
+pub const LANE_EQUALIZATION_CONTROL_REGISTERS: &[Attr] = &[
+    Attr::new(0x0c, 2).hwinit(0x7f7f).rsvdp(0x8080), // Lane Equalization Control
+];
+
 /// Link Control 3
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkControl3 {
     /// Perform Equalization
     pub perform_equalization: bool,
@@ -146,12 +206,40 @@ impl From<u32> for LinkControl3 {
     }
 }
 
+impl From<LinkControl3> for u32 {
+    fn from(data: LinkControl3) -> Self {
+        (data.perform_equalization as u32)
+            | (data.link_equalization_request_interrupt_enable as u32) << 1
+            | (u8::from(data.lower_skp_os_generation_vector) as u32 & 0x7f) << 9
+    }
+}
+
+impl Register for LinkControl3 {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_01fc;
+    const RW1C: u32 = 0;
+}
+
 /// The Lane Error Status register consists of a 32-bit vector, where each bit indicates if the
 /// Lane with the corresponding Lane number detected an error.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LaneErrorStatus(pub u32);
 
+impl From<LaneErrorStatus> for u32 {
+    fn from(data: LaneErrorStatus) -> Self {
+        data.0
+    }
+}
+
+impl Register for LaneErrorStatus {
+    type Value = u32;
+    const RSVDP: u32 = 0;
+    const RW1C: u32 = 0xffff_ffff;
+}
+
 /// An iterator through Lane Equalization Controls
+#[derive(Debug, Clone)]
 pub struct EqualizationControlLanes<'a> {
     chunks: Chunks<'a, u8>,
 }
@@ -171,9 +259,17 @@ impl<'a> Iterator for EqualizationControlLanes<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for EqualizationControlLanes<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 /// The Lane Equalization Control register consists of control fields required for per-Lane 8.0
 /// GT/s equalization and the number of entries in this register are sized by Maximum Link Width
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LaneEqualizationControl {
     /// Downstream Port 8.0 GT/s Transmitter Preset
     downstream_port_transmitter_preset: TransmitterPreset,
@@ -210,6 +306,21 @@ impl From<u16> for LaneEqualizationControl {
     }
 }
 
+impl From<LaneEqualizationControl> for u16 {
+    fn from(data: LaneEqualizationControl) -> Self {
+        (u8::from(data.downstream_port_transmitter_preset) as u16 & 0b1111)
+            | (u8::from(data.downstream_port_receiver_preset_hint) as u16 & 0b111) << 4
+            | (u8::from(data.upstream_port_transmitter_preset) as u16 & 0b1111) << 8
+            | (u8::from(data.upstream_port_receiver_preset_hint) as u16 & 0b111) << 12
+    }
+}
+
+impl Register for LaneEqualizationControl {
+    type Value = u16;
+    const RSVDP: u16 = 0x8080;
+    const RW1C: u16 = 0;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,6 +361,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_repeat_n)]
     fn equalization_control_lanes() {
         let spe: SecondaryPciExpress = DATA[4..].try_into().unwrap();
         let result = spe
@@ -0,0 +1,284 @@
+/*!
+# Shadow Functions
+
+The Shadow Functions Extended Capability is implemented by Function 0 of a Device that
+responds on behalf of Functions which are not otherwise implemented (shadow Functions). Each
+Shadow Function Instance entry names a Function Number occupied by a shadow Function.
+
+## Struct diagram
+[ShadowFunctions]
+- [ShadowFunctionsCapability]
+- [ShadowFunctionsControl]
+- [ShadowFunctionInstances]
+  - [ShadowFunctionInstance]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::shadow_functions::*;
+let data = [
+    /* 00h */ 0x2d, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x02, 0x01, 0x00, 0x00, // Shadow Functions Capability
+    /* 08h */ 0x01, 0x00, 0x00, 0x00, // Shadow Functions Control
+    /* 0Ch */ 0x02, 0x00, 0x00, 0x00, // Shadow Function Instance [0]
+    /* 10h */ 0x05, 0x00, 0x00, 0x00, // Shadow Function Instance [1]
+];
+let result: ShadowFunctions = data[4..].try_into().unwrap();
+assert_eq!(
+    ShadowFunctionsCapability {
+        number_of_shadow_functions: 2,
+        shadow_functions_instance_id: 1,
+    },
+    result.capability
+);
+assert!(result.control.shadow_functions_enable);
+assert_eq!(
+    vec![ShadowFunctionInstance { shadow_function_number: 2 }],
+    result.instances().take(1).collect::<Vec<_>>()
+);
+assert_eq!(vec![2, 5], result.shadowed_functions().collect::<Vec<_>>());
+```
+*/
+
+use core::slice;
+
+use heterob::{
+    bit_numbering::Lsb,
+    endianness::{Le, LeBytesTryInto},
+    Seq, P2, P3,
+};
+
+use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Shadow Functions
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ShadowFunctions<'a> {
+    pub capability: ShadowFunctionsCapability,
+    pub control: ShadowFunctionsControl,
+    instances_data: &'a [u8],
+}
+impl<'a> ShadowFunctions<'a> {
+    pub fn instances(&self) -> ShadowFunctionInstances<'a> {
+        ShadowFunctionInstances::new(self.instances_data)
+    }
+    /// Function Numbers occupied by shadow Functions
+    pub fn shadowed_functions(&self) -> impl Iterator<Item = u8> + 'a {
+        self.instances()
+            .map(|instance| instance.shadow_function_number)
+    }
+    /// Access attributes of registers including Shadow Function Instance entries
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.capability.number_of_shadow_functions as u16;
+        RegisterMap::from(REGISTERS).repeat(INSTANCE_REGISTERS, 4, count)
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for ShadowFunctions<'a> {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capability, control)),
+            tail,
+        } = P2(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "Shadow Functions",
+                size: 8,
+            })?;
+        let capability: ShadowFunctionsCapability = From::<u32>::from(capability);
+        let len = capability.number_of_shadow_functions as usize * ShadowFunctionInstance::SIZE;
+        let instances_data = tail.get(..len).ok_or(ExtendedCapabilityDataError {
+            name: "Shadow Function Instances",
+            size: 8 + len,
+        })?;
+        Ok(Self {
+            capability,
+            control: From::<u32>::from(control),
+            instances_data,
+        })
+    }
+}
+
+/// Owned [ShadowFunctions]
+///
+/// Converts back into [ShadowFunctions] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedShadowFunctions {
+    pub capability: ShadowFunctionsCapability,
+    pub control: ShadowFunctionsControl,
+    instances_data: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&ShadowFunctions<'a>> for OwnedShadowFunctions {
+    fn from(value: &ShadowFunctions<'a>) -> Self {
+        Self {
+            capability: value.capability.clone(),
+            control: value.control.clone(),
+            instances_data: value.instances_data.to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedShadowFunctions> for ShadowFunctions<'a> {
+    fn from(value: &'a OwnedShadowFunctions) -> Self {
+        Self {
+            capability: value.capability.clone(),
+            control: value.control.clone(),
+            instances_data: &value.instances_data,
+        }
+    }
+}
+
+/// Shadow Functions Capability registers without Shadow Function Instance entries
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4).hwinit(0x0000_ffff).rsvdp(0xffff_0000), // Shadow Functions Capability
+    Attr::new(0x08, 4).rw(0x0000_0001).rsvdp(0xffff_fffe), // Shadow Functions Control
+];
+
+/// First Shadow Function Instance entry, other entries follow every 4 bytes
+pub const INSTANCE_REGISTERS: &[Attr] = &[
+    Attr::new(0x0c, 4).hwinit(0x0000_00ff).rsvdp(0xffff_ff00), // Shadow Function Instance
+];
+
+/// Shadow Functions Capability
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShadowFunctionsCapability {
+    /// Number of Shadow Functions. Number of Shadow Function Instance entries.
+    pub number_of_shadow_functions: u8,
+    /// Shadow Functions Instance ID. Distinguishes multiple Shadow Functions capabilities of
+    /// the Function.
+    pub shadow_functions_instance_id: u8,
+}
+
+impl From<u32> for ShadowFunctionsCapability {
+    fn from(dword: u32) -> Self {
+        let Lsb((number_of_shadow_functions, shadow_functions_instance_id, ())) =
+            P3::<_, 8, 8, 16>(dword).into();
+        Self {
+            number_of_shadow_functions,
+            shadow_functions_instance_id,
+        }
+    }
+}
+
+/// Shadow Functions Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShadowFunctionsControl {
+    /// Shadow Functions Enable. Device responds on behalf of the shadow Functions.
+    pub shadow_functions_enable: bool,
+}
+
+impl From<u32> for ShadowFunctionsControl {
+    fn from(dword: u32) -> Self {
+        let Lsb((shadow_functions_enable, ())) = P2::<_, 1, 31>(dword).into();
+        Self {
+            shadow_functions_enable,
+        }
+    }
+}
+
+impl From<ShadowFunctionsControl> for u32 {
+    fn from(data: ShadowFunctionsControl) -> Self {
+        data.shadow_functions_enable as u32
+    }
+}
+
+impl Register for ShadowFunctionsControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_fffe;
+    const RW1C: u32 = 0;
+}
+
+/// An iterator through [ShadowFunctionInstance]
+#[derive(Debug, Clone)]
+pub struct ShadowFunctionInstances<'a>(pub slice::Chunks<'a, u8>);
+
+impl<'a> ShadowFunctionInstances<'a> {
+    pub fn new(slice: &'a [u8]) -> Self {
+        Self(slice.chunks(ShadowFunctionInstance::SIZE))
+    }
+}
+
+impl<'a> PartialEq for ShadowFunctionInstances<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.clone().eq(other.0.clone())
+    }
+}
+
+impl<'a> Eq for ShadowFunctionInstances<'a> {}
+
+impl<'a> Iterator for ShadowFunctionInstances<'a> {
+    type Item = ShadowFunctionInstance;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.0.next()?;
+        let Seq { head, .. } = slice.le_bytes_try_into().ok()?;
+        Some(From::<u32>::from(head))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ShadowFunctionInstances<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+/// Shadow Function Instance entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShadowFunctionInstance {
+    /// Shadow Function Number. Function Number (including ARI Function Number) occupied by
+    /// the shadow Function.
+    pub shadow_function_number: u8,
+}
+
+impl ShadowFunctionInstance {
+    pub const SIZE: usize = 4;
+}
+
+impl From<u32> for ShadowFunctionInstance {
+    fn from(dword: u32) -> Self {
+        let Lsb((shadow_function_number, ())) = P2::<_, 8, 24>(dword).into();
+        Self {
+            shadow_function_number,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn truncated_instances() {
+        let data = [
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(
+            Err(ExtendedCapabilityDataError {
+                name: "Shadow Function Instances",
+                size: 20,
+            }),
+            ShadowFunctions::try_from(data.as_slice())
+        );
+        let data = [
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        ];
+        let result: ShadowFunctions = data.as_slice().try_into().unwrap();
+        assert_eq!(4, result.registers().iter().count());
+    }
+}
@@ -10,11 +10,13 @@ use snafu::prelude::*;
 use heterob::{P16,P5,P6,P1, endianness::{FromLeBytes, LeBytesInto}, bit_numbering::Lsb};
 
 use crate::header::BaseAddresses;
+use crate::register::{Register, RegisterAttributes as Attr};
 use super::ExtendedCapabilityDataError;
 
 
 /// Single Root I/O Virtualization Error
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SingleRootIoVirtualizationError {
     #[snafu(display("can't read Element Self Description (4 bytes) from slice"))]
     ElementSelfDescription,
@@ -25,6 +27,7 @@ pub enum SingleRootIoVirtualizationError {
 
 /// Single Root I/O Virtualization (SR-IOV)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SingleRootIoVirtualization {
     /// SR-IOV Capabilities
     pub sriov_capabilities: SriovCapabilities,
@@ -98,8 +101,35 @@ impl<'a> TryFrom<&'a [u8]> for SingleRootIoVirtualization {
     }
 }
 
+/// SR-IOV Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                            // Extended Capability Header
+    Attr::new(0x04, 4),                            // SR-IOV Capabilities
+    Attr::new(0x08, 2).rw(0x003f).rsvdp(0xffc0),   // SR-IOV Control
+    Attr::new(0x0a, 2).rw1c(0x0001).rsvdz(0xfffe), // SR-IOV Status
+    Attr::new(0x0c, 2),                            // InitialVFs
+    Attr::new(0x0e, 2),                            // TotalVFs
+    Attr::new(0x10, 2).rw(0xffff),                 // NumVFs
+    Attr::new(0x12, 1),                            // Function Dependency Link
+    Attr::new(0x13, 1).rsvdp(0xff),
+    Attr::new(0x14, 2), // First VF Offset
+    Attr::new(0x16, 2), // VF Stride
+    Attr::new(0x18, 2).rsvdp(0xffff),
+    Attr::new(0x1a, 2),                 // VF Device ID
+    Attr::new(0x1c, 4),                 // Supported Page Sizes
+    Attr::new(0x20, 4).rw(0xffff_ffff), // System Page Size
+    Attr::new(0x24, 4).rw(0xffff_fff0), // VF BAR0
+    Attr::new(0x28, 4).rw(0xffff_fff0), // VF BAR1
+    Attr::new(0x2c, 4).rw(0xffff_fff0), // VF BAR2
+    Attr::new(0x30, 4).rw(0xffff_fff0), // VF BAR3
+    Attr::new(0x34, 4).rw(0xffff_fff0), // VF BAR4
+    Attr::new(0x38, 4).rw(0xffff_fff0), // VF BAR5
+    Attr::new(0x3c, 4),                 // VF Migration State Array Offset
+];
+
 /// SR-IOV Capabilities
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SriovCapabilities {
     /// VF Migration Capable
     pub vf_migration_capable: bool,
@@ -133,6 +163,7 @@ impl FromLeBytes<4> for SriovCapabilities {
 
 /// SR-IOV Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SriovControl {
     /// VF Enable
     pub vf_enable: bool,
@@ -167,12 +198,30 @@ impl From<u16> for SriovControl {
         }
     }
 }
+
+impl From<SriovControl> for u16 {
+    fn from(data: SriovControl) -> Self {
+        (data.vf_enable as u16)
+            | (data.vf_migration_enable as u16) << 1
+            | (data.vf_migration_interrupt_enable as u16) << 2
+            | (data.vf_mse as u16) << 3
+            | (data.ari_capable_hierarchy as u16) << 4
+            | (data.vf_10bit_tag_requester_enable as u16) << 5
+    }
+}
+
+impl Register for SriovControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xffc0;
+    const RW1C: u16 = 0;
+}
 impl FromLeBytes<2> for SriovControl {
     fn from_le_bytes(bytes: [u8;2]) -> Self { u16::from_le_bytes(bytes).into() }
 }
 
 /// SR-IOV Status
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SriovStatus {
     /// VF Migration Status
     pub vf_migration_status: bool,
@@ -187,6 +236,18 @@ impl From<u16> for SriovStatus {
         }
     }
 }
+
+impl From<SriovStatus> for u16 {
+    fn from(data: SriovStatus) -> Self {
+        data.vf_migration_status as u16
+    }
+}
+
+impl Register for SriovStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0001;
+}
 impl FromLeBytes<2> for SriovStatus {
     fn from_le_bytes(bytes: [u8;2]) -> Self { u16::from_le_bytes(bytes).into() }
 }
@@ -194,6 +255,7 @@ impl FromLeBytes<2> for SriovStatus {
 
 /// Defines the page size the system will use to map the VFs’ memory addresses
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PageSizes {
     /// Supported Page Sizes (RO)
     pub supported: u32,
@@ -0,0 +1,361 @@
+/*!
+# System Firmware Intermediary (SFI)
+
+The System Firmware Intermediary Extended Capability is implemented by Downstream Ports whose
+hot-plug and Downstream Port Containment events may be intermediated by system firmware before
+they reach the operating system. Firmware may also use the SFI Configuration Access Mechanism
+(CAM) to access configuration space below the Port.
+
+## Struct diagram
+[SystemFirmwareIntermediary]
+- [SfiCapability]
+- [SfiControl]
+- [SfiStatus]
+- [SfiCamAddress]
+
+[HotPlugIntermediation]
+
+## Examples
+
+```rust
+# use pcics::extended_capabilities::system_firmware_intermediary::*;
+let data = [
+    /* 00h */ 0x2c, 0x00, 0x01, 0x00, // Extended Capability Header
+    /* 04h */ 0x01, 0x00,             // SFI Capability
+    /* 06h */ 0x06, 0x00,             // SFI Control
+    /* 08h */ 0x03, 0x00,             // SFI Status
+    /* 0Ah */ 0x00, 0x00,             // Reserved
+    /* 0Ch */ 0x08, 0x01, 0x3a, 0x00, // SFI CAM Address
+    /* 10h */ 0x86, 0x80, 0x30, 0x20, // SFI CAM Data
+];
+let result: SystemFirmwareIntermediary = data[4..].try_into().unwrap();
+let sample = SystemFirmwareIntermediary {
+    capability: SfiCapability {
+        sfi_oob_pd_supported: true,
+    },
+    control: SfiControl {
+        sfi_err_cor_event_signaling_suppression: false,
+        sfi_oob_pd_enable: true,
+        sfi_hot_plug_interrupt_disable: true,
+        sfi_drs_signaling_enable: false,
+    },
+    status: SfiStatus {
+        sfi_oob_pd_changed: true,
+        sfi_oob_pd_state: true,
+    },
+    sfi_cam_address: SfiCamAddress {
+        bus: 0x3a,
+        device: 0,
+        function: 1,
+        offset: 0x008,
+    },
+    sfi_cam_data: 0x2030_8086,
+};
+assert_eq!(sample, result);
+
+let intermediation = result.hot_plug_intermediation();
+assert_eq!(
+    HotPlugIntermediation::Intermediated {
+        out_of_band_presence_detect: true
+    },
+    intermediation
+);
+assert!(intermediation.is_intermediated());
+assert_eq!(
+    "hot-plug is intermediated by system firmware, presence is detected out-of-band",
+    intermediation.to_string()
+);
+```
+*/
+
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P3, P5, P6, P7};
+
+use super::ExtendedCapabilityDataError;
+use crate::access::Address;
+use crate::register::{Register, RegisterAttributes as Attr};
+
+/// System Firmware Intermediary
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemFirmwareIntermediary {
+    pub capability: SfiCapability,
+    pub control: SfiControl,
+    pub status: SfiStatus,
+    pub sfi_cam_address: SfiCamAddress,
+    /// SFI CAM Data. Configuration space DWORD addressed by [SFI CAM
+    /// Address](SystemFirmwareIntermediary::sfi_cam_address).
+    pub sfi_cam_data: u32,
+}
+impl SystemFirmwareIntermediary {
+    /// Whether system firmware intermediates hot-plug events of the Port
+    pub fn hot_plug_intermediation(&self) -> HotPlugIntermediation {
+        if self.control.sfi_hot_plug_interrupt_disable {
+            HotPlugIntermediation::Intermediated {
+                out_of_band_presence_detect: self.capability.sfi_oob_pd_supported
+                    && self.control.sfi_oob_pd_enable,
+            }
+        } else {
+            HotPlugIntermediation::NotIntermediated
+        }
+    }
+}
+impl TryFrom<&[u8]> for SystemFirmwareIntermediary {
+    type Error = ExtendedCapabilityDataError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let Seq {
+            head: Le((capability, control, status, rsvdp, sfi_cam_address, sfi_cam_data)),
+            ..
+        } = P6(slice)
+            .try_into()
+            .map_err(|_| ExtendedCapabilityDataError {
+                name: "System Firmware Intermediary",
+                size: 16,
+            })?;
+        let _: u16 = rsvdp;
+        Ok(Self {
+            capability: From::<u16>::from(capability),
+            control: From::<u16>::from(control),
+            status: From::<u16>::from(status),
+            sfi_cam_address: From::<u32>::from(sfi_cam_address),
+            sfi_cam_data,
+        })
+    }
+}
+
+/// System Firmware Intermediary Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                              // Extended Capability Header
+    Attr::new(0x04, 2).hwinit(0x0001).rsvdp(0xfffe), // SFI Capability
+    Attr::new(0x06, 2).rw(0x000f).rsvdp(0xfff0),     // SFI Control
+    Attr::new(0x08, 2).rw1c(0x0001).rsvdz(0xfffc),   // SFI Status
+    Attr::new(0x0a, 2).rsvdp(0xffff),                // Reserved
+    Attr::new(0x0c, 4).rw(0x0fff_fffc).rsvdp(0xf000_0003), // SFI CAM Address
+    Attr::new(0x10, 4).rw(0xffff_ffff),              // SFI CAM Data
+];
+
+/// SFI Capability
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SfiCapability {
+    /// SFI OOB PD Supported. Port supports out-of-band presence detect.
+    pub sfi_oob_pd_supported: bool,
+}
+
+impl From<u16> for SfiCapability {
+    fn from(word: u16) -> Self {
+        let Lsb((sfi_oob_pd_supported, ())) = P2::<_, 1, 15>(word).into();
+        Self {
+            sfi_oob_pd_supported,
+        }
+    }
+}
+
+/// SFI Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SfiControl {
+    /// SFI ERR_COR Event Signaling Suppression. Suppresses ERR_COR Messages of the SFI ERR_COR
+    /// subclass.
+    pub sfi_err_cor_event_signaling_suppression: bool,
+    /// SFI OOB PD Enable. Presence Detect State reflects out-of-band presence detect.
+    pub sfi_oob_pd_enable: bool,
+    /// SFI Hot-Plug Interrupt Disable. Hot-Plug interrupts are not signaled to software.
+    pub sfi_hot_plug_interrupt_disable: bool,
+    /// SFI DRS Signaling Enable. Device Readiness Status Messages are signaled.
+    pub sfi_drs_signaling_enable: bool,
+}
+
+impl From<u16> for SfiControl {
+    fn from(word: u16) -> Self {
+        let Lsb((
+            sfi_err_cor_event_signaling_suppression,
+            sfi_oob_pd_enable,
+            sfi_hot_plug_interrupt_disable,
+            sfi_drs_signaling_enable,
+            (),
+        )) = P5::<_, 1, 1, 1, 1, 12>(word).into();
+        Self {
+            sfi_err_cor_event_signaling_suppression,
+            sfi_oob_pd_enable,
+            sfi_hot_plug_interrupt_disable,
+            sfi_drs_signaling_enable,
+        }
+    }
+}
+
+impl From<SfiControl> for u16 {
+    fn from(data: SfiControl) -> Self {
+        (data.sfi_err_cor_event_signaling_suppression as u16)
+            | (data.sfi_oob_pd_enable as u16) << 1
+            | (data.sfi_hot_plug_interrupt_disable as u16) << 2
+            | (data.sfi_drs_signaling_enable as u16) << 3
+    }
+}
+
+impl Register for SfiControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xfff0;
+    const RW1C: u16 = 0;
+}
+
+/// SFI Status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SfiStatus {
+    /// SFI OOB PD Changed
+    pub sfi_oob_pd_changed: bool,
+    /// SFI OOB PD State. Adapter is present according to out-of-band presence detect.
+    pub sfi_oob_pd_state: bool,
+}
+
+impl From<u16> for SfiStatus {
+    fn from(word: u16) -> Self {
+        let Lsb((sfi_oob_pd_changed, sfi_oob_pd_state, ())) = P3::<_, 1, 1, 14>(word).into();
+        Self {
+            sfi_oob_pd_changed,
+            sfi_oob_pd_state,
+        }
+    }
+}
+
+impl From<SfiStatus> for u16 {
+    fn from(data: SfiStatus) -> Self {
+        (data.sfi_oob_pd_changed as u16) | (data.sfi_oob_pd_state as u16) << 1
+    }
+}
+
+impl Register for SfiStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0x0001;
+}
+
+/// SFI CAM Address. Configuration space DWORD accessed through SFI CAM Data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SfiCamAddress {
+    pub bus: u8,
+    pub device: u8,
+    pub function: u8,
+    /// DWORD aligned configuration space offset (Extended Register Number and Register Number)
+    pub offset: u16,
+}
+
+impl SfiCamAddress {
+    /// Address of the function in `segment`
+    pub fn address(&self, segment: u16) -> Address {
+        Address {
+            segment,
+            bus: self.bus,
+            device: self.device,
+            function: self.function,
+        }
+    }
+}
+
+impl From<u32> for SfiCamAddress {
+    fn from(dword: u32) -> Self {
+        let Lsb(((), register_number, function, device, bus, extended_register_number, ())) =
+            P7::<_, 2, 6, 3, 5, 8, 4, 4>(dword).into();
+        let _: (u16, u16) = (register_number, extended_register_number);
+        Self {
+            bus,
+            device,
+            function,
+            offset: extended_register_number << 8 | register_number << 2,
+        }
+    }
+}
+
+impl From<SfiCamAddress> for u32 {
+    fn from(data: SfiCamAddress) -> Self {
+        (data.offset as u32 & 0xfc)
+            | (data.function as u32 & 0x7) << 8
+            | (data.device as u32 & 0x1f) << 11
+            | (data.bus as u32) << 16
+            | (data.offset as u32 & 0xf00) << 16
+    }
+}
+
+impl Register for SfiCamAddress {
+    type Value = u32;
+    const RSVDP: u32 = 0xf000_0003;
+    const RW1C: u32 = 0;
+}
+
+/// Hot-plug handling of the Port as configured by [SFI Control](SfiControl)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HotPlugIntermediation {
+    /// Hot-Plug interrupts are signaled to the operating system
+    NotIntermediated,
+    /// Hot-Plug interrupts are disabled, system firmware handles hot-plug events and notifies
+    /// the operating system
+    Intermediated {
+        /// Presence is detected by the out-of-band presence detect mechanism instead of the
+        /// in-band one
+        out_of_band_presence_detect: bool,
+    },
+}
+
+impl HotPlugIntermediation {
+    pub fn is_intermediated(&self) -> bool {
+        matches!(self, Self::Intermediated { .. })
+    }
+}
+
+impl core::fmt::Display for HotPlugIntermediation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotIntermediated => write!(f, "hot-plug is handled by the operating system"),
+            Self::Intermediated {
+                out_of_band_presence_detect,
+            } => write!(
+                f,
+                "hot-plug is intermediated by system firmware, presence is detected {}",
+                if *out_of_band_presence_detect {
+                    "out-of-band"
+                } else {
+                    "in-band"
+                }
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[test]
+    fn sfi_cam_address() {
+        let sample = SfiCamAddress {
+            bus: 0xff,
+            device: 0x1f,
+            function: 7,
+            offset: 0xffc,
+        };
+        assert_eq!(0x0fff_fffc, u32::from(sample.clone()));
+        assert_eq!(sample, SfiCamAddress::from(0xffff_ffff));
+        assert_eq!("0001:ff:1f.7", sample.address(1).to_string().as_str());
+    }
+
+    #[test]
+    fn hot_plug_intermediation() {
+        // OOB PD is enabled, but not supported
+        let data = [0x00, 0x00, 0x06, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let sfi: SystemFirmwareIntermediary = data.as_slice().try_into().unwrap();
+        assert_eq!(
+            HotPlugIntermediation::Intermediated {
+                out_of_band_presence_detect: false
+            },
+            sfi.hot_plug_intermediation()
+        );
+        let data = [0x01, 0x00, 0x02, 0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let sfi: SystemFirmwareIntermediary = data.as_slice().try_into().unwrap();
+        assert!(!sfi.hot_plug_intermediation().is_intermediated());
+    }
+}
@@ -44,8 +44,13 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P4, P9};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TphRequester<'a> {
     /// TPH Requester Capability
     pub tph_requester_capability: TphRequesterCapability<'a>,
@@ -114,8 +119,63 @@ impl<'a> TryFrom<&'a [u8]> for TphRequester<'a> {
     }
 }
 
+/// Owned [TphRequester]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedTphRequester {
+    /// TPH Requester Capability
+    pub tph_requester_capability: OwnedTphRequesterCapability,
+    /// TPH Requester Control
+    pub tph_requester_control: TphRequesterControl,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&TphRequester<'a>> for OwnedTphRequester {
+    fn from(tph: &TphRequester<'a>) -> Self {
+        Self {
+            tph_requester_capability: (&tph.tph_requester_capability).into(),
+            tph_requester_control: tph.tph_requester_control.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedTphRequester> for TphRequester<'a> {
+    fn from(tph: &'a OwnedTphRequester) -> Self {
+        Self {
+            tph_requester_capability: (&tph.tph_requester_capability).into(),
+            tph_requester_control: tph.tph_requester_control.clone(),
+        }
+    }
+}
+
+impl<'a> TphRequester<'a> {
+    /// Access attributes of registers including ST Table located in the capability
+    pub fn registers(&self) -> RegisterMap {
+        let count = match self.tph_requester_capability.st_table {
+            StTable::Valid { size, .. } => size + 1,
+            _ => 0,
+        };
+        RegisterMap::from(REGISTERS).repeat(ST_TABLE_REGISTERS, 2, count)
+    }
+}
+
+/// TPH Requester Capability registers without ST Table
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // TPH Requester Capability
+    Attr::new(0x08, 4).rw(0x0000_0307).rsvdp(0xffff_fcf8), // TPH Requester Control
+];
+
+/// First ST Table entry, other entries follow every 2 bytes
+pub const ST_TABLE_REGISTERS: &[Attr] = &[
+    Attr::new(0x0c, 2).rw(0xffff), // ST Table Entry
+];
+
 /// TPH Requester Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TphRequesterCapability<'a> {
     /// No ST Mode Supported
     pub no_st_mode_supported: bool,
@@ -131,6 +191,7 @@ pub struct TphRequesterCapability<'a> {
 
 /// Indicates if and where the ST Table is located
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StTable<'a> {
     /// ST Table is not present
     NotPresent,
@@ -146,8 +207,101 @@ pub enum StTable<'a> {
     Reserved,
 }
 
+/// Owned [TphRequesterCapability]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedTphRequesterCapability {
+    /// No ST Mode Supported
+    pub no_st_mode_supported: bool,
+    /// Interrupt Vector Mode Supported
+    pub interrupt_vector_mode_supported: bool,
+    /// Device Specific Mode Supported
+    pub device_specific_mode_supported: bool,
+    /// Extended TPH Requester Supported
+    pub extended_tph_requester_supported: bool,
+    /// ST Table
+    pub st_table: OwnedStTable,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&TphRequesterCapability<'a>> for OwnedTphRequesterCapability {
+    fn from(cap: &TphRequesterCapability<'a>) -> Self {
+        Self {
+            no_st_mode_supported: cap.no_st_mode_supported,
+            interrupt_vector_mode_supported: cap.interrupt_vector_mode_supported,
+            device_specific_mode_supported: cap.device_specific_mode_supported,
+            extended_tph_requester_supported: cap.extended_tph_requester_supported,
+            st_table: (&cap.st_table).into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedTphRequesterCapability> for TphRequesterCapability<'a> {
+    fn from(cap: &'a OwnedTphRequesterCapability) -> Self {
+        Self {
+            no_st_mode_supported: cap.no_st_mode_supported,
+            interrupt_vector_mode_supported: cap.interrupt_vector_mode_supported,
+            device_specific_mode_supported: cap.device_specific_mode_supported,
+            extended_tph_requester_supported: cap.extended_tph_requester_supported,
+            st_table: (&cap.st_table).into(),
+        }
+    }
+}
+
+/// Owned [StTable]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedStTable {
+    /// ST Table is not present
+    NotPresent,
+    /// ST Table is located in the TPH Requester Capability structure and all bytes are readable
+    Valid { size: u16, data: Vec<u8> },
+    /// ST Table is located in the TPH Requester Capability structure, but has invalid size.
+    Invalid { size: u16, data: Vec<u8> },
+    /// ST Table is located in the MSI-X Table
+    MsiXTable { size: u16 },
+    /// Reserved
+    Reserved,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&StTable<'a>> for OwnedStTable {
+    fn from(st_table: &StTable<'a>) -> Self {
+        match *st_table {
+            StTable::NotPresent => Self::NotPresent,
+            StTable::Valid { size, data } => Self::Valid {
+                size,
+                data: data.to_vec(),
+            },
+            StTable::Invalid { size, data } => Self::Invalid {
+                size,
+                data: data.to_vec(),
+            },
+            StTable::MsiXTable { size } => Self::MsiXTable { size },
+            StTable::Reserved => Self::Reserved,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedStTable> for StTable<'a> {
+    fn from(st_table: &'a OwnedStTable) -> Self {
+        match st_table {
+            OwnedStTable::NotPresent => Self::NotPresent,
+            OwnedStTable::Valid { size, data } => Self::Valid { size: *size, data },
+            OwnedStTable::Invalid { size, data } => Self::Invalid { size: *size, data },
+            OwnedStTable::MsiXTable { size } => Self::MsiXTable { size: *size },
+            OwnedStTable::Reserved => Self::Reserved,
+        }
+    }
+}
+
 /// Each implemented ST Entry is 16 bits
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TphStTableEntry {
     pub st_lower: u8,
     pub st_upper: u8,
@@ -155,6 +309,7 @@ pub struct TphStTableEntry {
 
 /// TPH Requester Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TphRequesterControl {
     /// ST Mode Select
     pub st_mode_select: StModeSelect,
@@ -173,8 +328,22 @@ impl From<u32> for TphRequesterControl {
     }
 }
 
+impl From<TphRequesterControl> for u32 {
+    fn from(data: TphRequesterControl) -> Self {
+        (u8::from(data.st_mode_select) as u32 & 0b111)
+            | (u8::from(data.tph_requester_enable) as u32 & 0b11) << 8
+    }
+}
+
+impl Register for TphRequesterControl {
+    type Value = u32;
+    const RSVDP: u32 = 0xffff_fcf8;
+    const RW1C: u32 = 0;
+}
+
 /// Selects the ST Mode of operation
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StModeSelect {
     /// No ST Mode
     NoStMode,
@@ -196,8 +365,20 @@ impl From<u8> for StModeSelect {
     }
 }
 
+impl From<StModeSelect> for u8 {
+    fn from(data: StModeSelect) -> Self {
+        match data {
+            StModeSelect::NoStMode => 0b00,
+            StModeSelect::InterruptVectorMode => 0b01,
+            StModeSelect::DeviceSpecificMode => 0b10,
+            StModeSelect::Reserved(v) => v,
+        }
+    }
+}
+
 /// Controls the ability to issue Request TLPs using either TPH or Extended TPH
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TphRequesterEnable {
     /// Function operating as a Requester is not permitted to issue Requests with TPH or Extended
     /// TPH
@@ -221,3 +402,14 @@ impl From<u8> for TphRequesterEnable {
         }
     }
 }
+
+impl From<TphRequesterEnable> for u8 {
+    fn from(data: TphRequesterEnable) -> Self {
+        match data {
+            TphRequesterEnable::NotPermitted => 0b00,
+            TphRequesterEnable::TphPermitted => 0b01,
+            TphRequesterEnable::Reserved => 0b10,
+            TphRequesterEnable::TphAndExtendedTphPermitted => 0b11,
+        }
+    }
+}
@@ -36,9 +36,14 @@ assert_eq!(sample, result);
 use heterob::{bit_numbering::Lsb, endianness::LeBytesTryInto, Seq, P3};
 
 use super::ExtendedCapabilityDataError;
+use crate::register::RegisterAttributes as Attr;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Vendor-Specific Extended Capability
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VendorSpecificExtendedCapability<'a> {
     pub header: VsecHeader,
     pub registers: VsecRegisters<'a>,
@@ -73,8 +78,44 @@ impl<'a> TryFrom<&'a [u8]> for VendorSpecificExtendedCapability<'a> {
     }
 }
 
+/// Owned [VendorSpecificExtendedCapability]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedVendorSpecificExtendedCapability {
+    pub header: VsecHeader,
+    pub registers: OwnedVsecRegisters,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&VendorSpecificExtendedCapability<'a>> for OwnedVendorSpecificExtendedCapability {
+    fn from(vsec: &VendorSpecificExtendedCapability<'a>) -> Self {
+        Self {
+            header: vsec.header.clone(),
+            registers: (&vsec.registers).into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedVendorSpecificExtendedCapability> for VendorSpecificExtendedCapability<'a> {
+    fn from(vsec: &'a OwnedVendorSpecificExtendedCapability) -> Self {
+        Self {
+            header: vsec.header.clone(),
+            registers: (&vsec.registers).into(),
+        }
+    }
+}
+
+/// Vendor-Specific Extended Capability registers, vendor-specific registers are not described
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+    Attr::new(0x04, 4), // Vendor-Specific Header
+];
+
 /// Vendor-Specific Header
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VsecHeader {
     /// Vendor-defined ID number that indicates the nature and format of the VSEC structure.
     pub vsec_id: u16,
@@ -87,6 +128,7 @@ pub struct VsecHeader {
 
 /// Vendor-Specific Registers
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum VsecRegisters<'a> {
     Valid(&'a [u8]),
     /// VSEC Length too short (should be >= 8)
@@ -94,3 +136,37 @@ pub enum VsecRegisters<'a> {
     /// Available data shorter than length in header
     Incomplete(&'a [u8]),
 }
+
+/// Owned [VsecRegisters]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedVsecRegisters {
+    Valid(Vec<u8>),
+    /// VSEC Length too short (should be >= 8)
+    InvalidLength(u16),
+    /// Available data shorter than length in header
+    Incomplete(Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&VsecRegisters<'a>> for OwnedVsecRegisters {
+    fn from(registers: &VsecRegisters<'a>) -> Self {
+        match registers {
+            VsecRegisters::Valid(data) => Self::Valid(data.to_vec()),
+            VsecRegisters::InvalidLength(length) => Self::InvalidLength(*length),
+            VsecRegisters::Incomplete(data) => Self::Incomplete(data.to_vec()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedVsecRegisters> for VsecRegisters<'a> {
+    fn from(registers: &'a OwnedVsecRegisters) -> Self {
+        match registers {
+            OwnedVsecRegisters::Valid(data) => Self::Valid(data),
+            OwnedVsecRegisters::InvalidLength(length) => Self::InvalidLength(*length),
+            OwnedVsecRegisters::Incomplete(data) => Self::Incomplete(data),
+        }
+    }
+}
@@ -0,0 +1,331 @@
+/*!
+# VF Resizable BAR
+
+The VF Resizable BAR capability permits hardware to communicate the resource sizes that are
+acceptable for operation via the VF Resizable BAR Capability and Control registers and system
+software to communicate the optimal size back to the hardware via the VF BAR Size field of the VF
+Resizable BAR Control register.
+
+## Struct diagram
+<pre>
+<a href="struct.VfResizableBar.html">VfResizableBar</a>
+└─ <a href="struct.VfResizableBarEntry.html">VfResizableBarEntry (1..=6)</a>
+   ├─ <a href="struct.VfResizableBarCapability.html">VfResizableBarCapability</a>
+   └─ <a href="struct.VfResizableBarControl.html">VfResizableBarControl</a>
+</pre>
+
+## Examples
+```rust
+# use pcics::extended_capabilities::vf_resizable_bar::*;
+# use pcics::extended_capabilities::SingleRootIoVirtualization;
+let data = [
+    /* 00h */ 0x24, 0x00, 0x01, 0x00, // Capability header
+    /* 04h */ 0x00, 0x70, 0x00, 0x00, // VF Resizable BAR Capability (0)
+    /* 08h */ 0x20, 0x01, 0x00, 0x00, // VF Resizable BAR Control (0)
+];
+
+let mut vf_rebar: VfResizableBar = data.as_slice().try_into().unwrap();
+let entry = vf_rebar.next().unwrap();
+
+let sample = VfResizableBarEntry {
+    capability: VfResizableBarCapability {
+        support_map_from_1mb_to_128tb: 0x7000,
+    },
+    control: VfResizableBarControl {
+        vf_bar_index: 0,
+        vf_number_of_resizable_bars: 1,
+        vf_bar_size: 1,
+        support_map_from_256tb_to_8eb: 0,
+    },
+};
+assert_eq!(sample, entry);
+assert_eq!(Some(2 << 20), entry.vf_bar_size());
+assert_eq!(
+    vec![256 << 20, 512 << 20, 1 << 30],
+    entry.supported_sizes().collect::<Vec<_>>()
+);
+
+// SR-IOV with 16 TotalVFs
+let mut sriov = [0u8; SingleRootIoVirtualization::BYTES];
+sriov[10] = 16;
+let sriov = SingleRootIoVirtualization::from(sriov);
+let footprints = entry.footprints(&sriov).collect::<Vec<_>>();
+assert_eq!(
+    VfBarFootprint { vf_bar_size: 1 << 30, total_size: 16 << 30 },
+    footprints[2]
+);
+```
+*/
+
+use core::slice;
+use heterob::{bit_numbering::Lsb, endianness::Le, Seq, P2, P6};
+
+use super::{resizable_bar::ResizableBar, SingleRootIoVirtualization};
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// VF Resizable BAR Error
+pub type VfResizableBarError = super::resizable_bar::ResizableBarError;
+
+/// An iterator through [VF Resizable BAR Entries](VfResizableBarEntry)
+#[derive(Debug, Clone)]
+pub struct VfResizableBar<'a>(pub slice::Chunks<'a, u8>);
+
+impl<'a> VfResizableBar<'a> {
+    /// Entry size = VF Resizable Bar Capability size + VF Resizable Bar Control size
+    pub const ENTRY_SIZE: usize = 4 + 4;
+}
+
+impl<'a> PartialEq for VfResizableBar<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.clone().eq(other.0.clone())
+    }
+}
+
+impl<'a> Eq for VfResizableBar<'a> {}
+
+impl<'a> TryFrom<&'a [u8]> for VfResizableBar<'a> {
+    type Error = VfResizableBarError;
+
+    /// Entries are validated the same way as [Resizable BAR](ResizableBar) entries
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        ResizableBar::try_from(slice).map(|ResizableBar(chunks)| Self(chunks))
+    }
+}
+
+/// Owned [VfResizableBar] with decoded entries
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedVfResizableBar(pub Vec<VfResizableBarEntry>);
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&VfResizableBar<'a>> for OwnedVfResizableBar {
+    fn from(vf_rebar: &VfResizableBar<'a>) -> Self {
+        Self(vf_rebar.clone().collect())
+    }
+}
+
+impl<'a> VfResizableBar<'a> {
+    /// Access attributes of registers of all entries
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.0.len() as u16;
+        RegisterMap::from(REGISTERS).repeat(ENTRY_REGISTERS, Self::ENTRY_SIZE as u16, count)
+    }
+}
+
+/// VF Resizable BAR Capability registers without entries
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4), // Extended Capability Header
+];
+
+/// Registers of the first VF Resizable BAR entry, other entries follow every 8 bytes
+pub const ENTRY_REGISTERS: &[Attr] = &[
+    Attr::new(0x04, 4), // VF Resizable BAR Capability
+    Attr::new(0x08, 4).rw(0x0000_3f00).rsvdp(0x0000_c018), // VF Resizable BAR Control
+];
+
+impl<'a> Iterator for VfResizableBar<'a> {
+    type Item = VfResizableBarEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.0.next()?;
+        let Seq {
+            head: Le((cap, ctrl)),
+            ..
+        } = P2(chunk).try_into().ok()?;
+        Some(VfResizableBarEntry {
+            capability: From::<u32>::from(cap),
+            control: From::<u32>::from(ctrl),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for VfResizableBar<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+/// VF Resizable BAR Entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VfResizableBarEntry {
+    pub capability: VfResizableBarCapability,
+    pub control: VfResizableBarControl,
+}
+
+impl VfResizableBarEntry {
+    /// Check if VFs support operating with the VF BAR sized to 2ᵖᵒʷᵉʳ
+    pub fn is_function_supports_power_of_two(&self, power: usize) -> bool {
+        match power {
+            20..=47 => self.capability.support_map_from_1mb_to_128tb & (1 << (power - 16)) != 0,
+            48..=63 => self.control.support_map_from_256tb_to_8eb & (1 << (power - 48)) != 0,
+            _ => false,
+        }
+    }
+    /// Current size of the BAR of every VF in bytes, `None` for sizes above 8 EB
+    pub fn vf_bar_size(&self) -> Option<u64> {
+        1u64.checked_shl(20 + self.control.vf_bar_size as u32)
+    }
+    /// Supported sizes of the BAR of every VF in bytes, in ascending order
+    pub fn supported_sizes(&self) -> impl Iterator<Item = u64> + '_ {
+        (20..=63)
+            .filter(|&power| self.is_function_supports_power_of_two(power))
+            .map(|power| 1 << power)
+    }
+    /// Memory space consumed by this BAR of all [TotalVFs](SingleRootIoVirtualization::total_vfs)
+    /// for each supported VF BAR size
+    pub fn footprints<'a>(
+        &'a self,
+        sriov: &SingleRootIoVirtualization,
+    ) -> impl Iterator<Item = VfBarFootprint> + 'a {
+        let total_vfs = sriov.total_vfs as u128;
+        self.supported_sizes()
+            .map(move |vf_bar_size| VfBarFootprint {
+                vf_bar_size,
+                total_size: vf_bar_size as u128 * total_vfs,
+            })
+    }
+}
+
+/// Memory space consumed by a VF BAR of all VFs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VfBarFootprint {
+    /// Size of the BAR of every VF in bytes
+    pub vf_bar_size: u64,
+    /// Size of the BAR of every VF multiplied by number of VFs
+    pub total_size: u128,
+}
+
+/// VF Resizable BAR Capability
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VfResizableBarCapability {
+    pub support_map_from_1mb_to_128tb: u32,
+}
+
+impl From<u32> for VfResizableBarCapability {
+    fn from(dword: u32) -> Self {
+        Self {
+            support_map_from_1mb_to_128tb: dword,
+        }
+    }
+}
+
+/// VF Resizable BAR Control
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VfResizableBarControl {
+    /// VF BAR Index. Points to the VF BAR in the SR-IOV capability (0 = VF BAR0)
+    pub vf_bar_index: u8,
+    /// VF Number of Resizable BARs. Valid in the first entry only.
+    pub vf_number_of_resizable_bars: u8,
+    /// VF BAR Size. Encoded as log₂ of the size in MB.
+    pub vf_bar_size: u8,
+    pub support_map_from_256tb_to_8eb: u16,
+}
+
+impl From<u32> for VfResizableBarControl {
+    fn from(dword: u32) -> Self {
+        let Lsb((
+            vf_bar_index,
+            (),
+            vf_number_of_resizable_bars,
+            vf_bar_size,
+            (),
+            support_map_from_256tb_to_8eb,
+        )) = P6::<_, 3, 2, 3, 6, 2, 16>(dword).into();
+        Self {
+            vf_bar_index,
+            vf_number_of_resizable_bars,
+            vf_bar_size,
+            support_map_from_256tb_to_8eb,
+        }
+    }
+}
+
+impl From<VfResizableBarControl> for u32 {
+    fn from(data: VfResizableBarControl) -> Self {
+        (data.vf_bar_index as u32 & 0b111)
+            | (data.vf_number_of_resizable_bars as u32 & 0b111) << 5
+            | (data.vf_bar_size as u32 & 0x3f) << 8
+            | (data.support_map_from_256tb_to_8eb as u32) << 16
+    }
+}
+
+impl Register for VfResizableBarControl {
+    type Value = u32;
+    const RSVDP: u32 = 0x0000_c018;
+    const RW1C: u32 = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[test]
+    fn short_data() {
+        // VF Number of Resizable BARs = 2, but only one entry follows the header
+        let data = [
+            0x24, 0x00, 0x01, 0x00, // Capability header
+            0x00, 0x00, 0x00, 0x00, // VF Resizable BAR Capability (0)
+            0x40, 0x00, 0x00, 0x00, // VF Resizable BAR Control (0)
+        ];
+        assert_eq!(
+            Err(VfResizableBarError::ShortData),
+            VfResizableBar::try_from(data.as_slice())
+        );
+        assert_eq!(
+            Err(VfResizableBarError::FirstEntry),
+            VfResizableBar::try_from(&data[..8])
+        );
+    }
+
+    #[test]
+    fn footprints() {
+        let entry = VfResizableBarEntry {
+            // 1 MB, 4 MB and 128 TB
+            capability: VfResizableBarCapability {
+                support_map_from_1mb_to_128tb: 0x8000_0050,
+            },
+            // 8 EB
+            control: VfResizableBarControl {
+                vf_bar_index: 2,
+                vf_number_of_resizable_bars: 1,
+                vf_bar_size: 0,
+                support_map_from_256tb_to_8eb: 0x8000,
+            },
+        };
+        // SR-IOV with 1000 TotalVFs
+        let mut sriov = [0u8; SingleRootIoVirtualization::BYTES];
+        sriov[10..12].copy_from_slice(&1000u16.to_le_bytes());
+        let sriov = SingleRootIoVirtualization::from(sriov);
+        let sample = vec![
+            VfBarFootprint {
+                vf_bar_size: 1 << 20,
+                total_size: 1000 << 20,
+            },
+            VfBarFootprint {
+                vf_bar_size: 4 << 20,
+                total_size: 4000 << 20,
+            },
+            VfBarFootprint {
+                vf_bar_size: 1 << 47,
+                total_size: 1000 << 47,
+            },
+            // Does not fit into u64
+            VfBarFootprint {
+                vf_bar_size: 1 << 63,
+                total_size: 1000 << 63,
+            },
+        ];
+        assert_eq!(sample, entry.footprints(&sriov).collect::<Vec<_>>());
+    }
+}
@@ -89,11 +89,16 @@ use super::ExtendedCapabilityDataError;
 use core::slice;
 
 use super::ECH_BYTES;
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Numeral unit for VC Arbitration Table Offset and Port Arbitration Table Offset
 const DQWORD: usize = 16;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VirtualChannel<'a> {
     data: &'a [u8],
     /// Port VC Capability Register 1
@@ -166,9 +171,81 @@ impl<'a> TryFrom<&'a [u8]> for VirtualChannel<'a> {
     }
 }
 
+/// Owned [VirtualChannel]
+///
+/// Converts back into [VirtualChannel] to decode variable-size registers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedVirtualChannel {
+    data: Vec<u8>,
+    /// Port VC Capability Register 1
+    pub port_vc_capability_1: PortVcCapability1,
+    /// Port VC Capability Register 2
+    pub port_vc_capability_2: PortVcCapability2,
+    /// Por VC Control Register
+    pub port_vc_control: PortVcControl,
+    /// Port VC Status Register
+    pub port_vc_status: PortVcStatus,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&VirtualChannel<'a>> for OwnedVirtualChannel {
+    fn from(value: &VirtualChannel<'a>) -> Self {
+        Self {
+            data: value.data.to_vec(),
+            port_vc_capability_1: value.port_vc_capability_1.clone(),
+            port_vc_capability_2: value.port_vc_capability_2.clone(),
+            port_vc_control: value.port_vc_control.clone(),
+            port_vc_status: value.port_vc_status.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a OwnedVirtualChannel> for VirtualChannel<'a> {
+    fn from(value: &'a OwnedVirtualChannel) -> Self {
+        Self {
+            data: &value.data,
+            port_vc_capability_1: value.port_vc_capability_1.clone(),
+            port_vc_capability_2: value.port_vc_capability_2.clone(),
+            port_vc_control: value.port_vc_control.clone(),
+            port_vc_status: value.port_vc_status.clone(),
+        }
+    }
+}
+
+impl<'a> VirtualChannel<'a> {
+    /// Access attributes of Port VC and VC Resource registers
+    ///
+    /// Arbitration tables are not described.
+    pub fn registers(&self) -> RegisterMap {
+        let count = self.port_vc_capability_1.extended_vc_count as u16 + 1;
+        RegisterMap::from(REGISTERS).repeat(VC_RESOURCE_REGISTERS, 0x0c, count)
+    }
+}
+
+/// Virtual Channel Capability registers
+pub const REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 4),                          // Extended Capability Header
+    Attr::new(0x04, 4),                          // Port VC Capability 1
+    Attr::new(0x08, 4),                          // Port VC Capability 2
+    Attr::new(0x0c, 2).rw(0x000f).rsvdp(0xfff0), // Port VC Control
+    Attr::new(0x0e, 2).rsvdz(0xfffe),            // Port VC Status
+];
+
+/// VC Resource registers of VC0, resources of other VCs follow every 0Ch bytes
+pub const VC_RESOURCE_REGISTERS: &[Attr] = &[
+    Attr::new(0x10, 4),                                    // VC Resource Capability
+    Attr::new(0x14, 4).rw(0x870f_00ff).rsvdp(0x78f0_ff00), // VC Resource Control
+    Attr::new(0x18, 2).rsvdp(0xffff),
+    Attr::new(0x1a, 2).rsvdz(0xfffc), // VC Resource Status
+];
+
 /// The Port VC Capability register 1 describes the configuration of the Virtual Channels
 /// associated with a PCI Express Port.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortVcCapability1 {
     /// Indicates the number of (extended) Virtual Channels in addition to the default VC supported
     /// by the device.
@@ -206,6 +283,7 @@ impl From<u32> for PortVcCapability1 {
 /// Indicates the reference clock for Virtual Channels that support time-based WRR Port
 /// Arbitration.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReferenceClock {
     /// 100 ns reference clock
     Rc100ns,
@@ -222,6 +300,7 @@ impl From<u8> for ReferenceClock {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortArbitrationTableEntrySize(u8);
 impl PortArbitrationTableEntrySize {
     pub fn bits(&self) -> usize {
@@ -278,8 +357,16 @@ impl<'a> Iterator for ExtendedVirtualChannels<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ExtendedVirtualChannels<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
 /// Extended Virtual Channel Error
 #[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ExtendedVirtualChannelError {
     number: u8,
     offset: usize,
@@ -287,6 +374,7 @@ pub struct ExtendedVirtualChannelError {
 
 /// Virtual Channel resources
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtendedVirtualChannel {
     /// VC Resource Capability Register
     pub vc_resource_capability: VcResourceCapability,
@@ -315,6 +403,7 @@ impl From<[u8; Self::SIZE]> for ExtendedVirtualChannel {
 /// The VC Resource Capability register describes the capabilities and configuration of a
 /// particular Virtual Channel resource
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcResourceCapability {
     /// Port Arbitration Capability
     pub port_arbitration_capability: PortArbitrationCapability,
@@ -367,6 +456,7 @@ impl From<u32> for VcResourceCapability {
 
 /// Indicates types of Port Arbitration supported by the VC resource
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortArbitrationCapability {
     /// Non-configurable hardware-fixed arbitration scheme, e.g., Round Robin (RR)
     pub hardware_fixed_arbitration: bool,
@@ -419,6 +509,13 @@ impl<'a> Iterator for PortArbitrationTable<'a> {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for PortArbitrationTable<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
 impl<'a> PartialEq for PortArbitrationTable<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.data.clone().eq(other.data.clone())
@@ -429,10 +526,12 @@ impl<'a> PartialEq for PortArbitrationTable<'a> {
 impl<'a> Eq for PortArbitrationTable<'a> {}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortArbitrationTableEntry(u8);
 
 /// VC Resource Control
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcResourceControl {
     /// TC/VC Map
     pub tc_or_vc_map: u8,
@@ -468,9 +567,26 @@ impl From<u32> for VcResourceControl {
     }
 }
 
+impl From<VcResourceControl> for u32 {
+    fn from(data: VcResourceControl) -> Self {
+        (data.tc_or_vc_map as u32 & 0xff)
+            | (data.load_port_arbitration_table as u32) << 16
+            | (u8::from(data.port_arbitration_select) as u32 & 0b111) << 17
+            | (data.vc_id as u32 & 0b111) << 24
+            | (data.vc_enable as u32) << 31
+    }
+}
+
+impl Register for VcResourceControl {
+    type Value = u32;
+    const RSVDP: u32 = 0x78f0_ff00;
+    const RW1C: u32 = 0;
+}
+
 /// Corresponding to one of the filed in the (Port Arbitration
 /// Capability)[PortArbitrationCapability]
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PortArbitrationSelect {
     /// Non-configurable hardware-fixed arbitration scheme, e.g., Round Robin (RR)
     HardwareFixedArbitration,
@@ -529,6 +645,7 @@ impl From<PortArbitrationSelect> for u8 {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcResourceStatus {
     /// Port Arbitration Table Status
     pub port_arbitration_table_status: bool,
@@ -547,9 +664,22 @@ impl From<u16> for VcResourceStatus {
     }
 }
 
+impl From<VcResourceStatus> for u16 {
+    fn from(data: VcResourceStatus) -> Self {
+        (data.port_arbitration_table_status as u16) | (data.vc_negotiation_pending as u16) << 1
+    }
+}
+
+impl Register for VcResourceStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
+
 /// The Port VC Capability register 2 provides further information about the configuration of the
 /// Virtual Channels associated with a PCI Express Port.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortVcCapability2 {
     /// VC Arbitration Capability
     pub vc_arbitration_capability: VcArbitrationCapability,
@@ -584,6 +714,7 @@ impl From<u32> for PortVcCapability2 {
 
 /// Indicates the types of VC Arbitration supported by the Function for the LPVC group.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcArbitrationCapability {
     /// Hardware fixed arbitration scheme, e.g., Round Robin
     pub hardware_fixed_arbitration: bool,
@@ -625,6 +756,13 @@ impl<'a> Iterator for VcArbitrationTable<'a> {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for VcArbitrationTable<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
 impl<'a> PartialEq for VcArbitrationTable<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.data.clone().eq(other.data.clone()) && self.vate == other.vate
@@ -635,6 +773,7 @@ impl<'a> Eq for VcArbitrationTable<'a> {}
 /// The VC Arbitration Table is a register array with fixed-size entries of 4 bits.
 /// Each 4-bit table entry corresponds to a phase within a WRR arbitration period.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VcArbitrationTableEntry {
     /// Indicating that the corresponding phase within the WRR arbitration period is assigned to
     /// the Virtual Channel indicated by the VC ID
@@ -659,6 +798,7 @@ impl From<u8> for VatEntryPair {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortVcControl {
     /// Load VC Arbitration Table
     pub load_vc_arbitration_table: bool,
@@ -677,9 +817,23 @@ impl From<u16> for PortVcControl {
     }
 }
 
+impl From<PortVcControl> for u16 {
+    fn from(data: PortVcControl) -> Self {
+        (data.load_vc_arbitration_table as u16)
+            | (u8::from(data.vc_arbitration_select) as u16 & 0b111) << 1
+    }
+}
+
+impl Register for PortVcControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xfff0;
+    const RW1C: u16 = 0;
+}
+
 /// The values of this field are corresponding to one of the field in the
 /// [VcArbitrationCapability].
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VcArbitrationSelect {
     /// Hardware fixed arbitration scheme, e.g., Round Robin
     HardwareFixedArbitration,
@@ -715,10 +869,22 @@ impl From<u8> for VcArbitrationSelect {
         }
     }
 }
+impl From<VcArbitrationSelect> for u8 {
+    fn from(data: VcArbitrationSelect) -> Self {
+        match data {
+            VcArbitrationSelect::HardwareFixedArbitration => 0,
+            VcArbitrationSelect::Wrr32phases => 1,
+            VcArbitrationSelect::Wrr64phases => 2,
+            VcArbitrationSelect::Wrr128phases => 3,
+            VcArbitrationSelect::Reserved(n) => n,
+        }
+    }
+}
 
 /// The Port VC Status register provides status of the configuration of Virtual Channels associated
 /// with a Port.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PortVcStatus {
     /// VC Arbitration Table Status
     pub vc_arbitration_table_status: bool,
@@ -733,6 +899,18 @@ impl From<u16> for PortVcStatus {
     }
 }
 
+impl From<PortVcStatus> for u16 {
+    fn from(data: PortVcStatus) -> Self {
+        data.vc_arbitration_table_status as u16
+    }
+}
+
+impl Register for PortVcStatus {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -753,6 +931,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unusual_byte_groupings)]
     fn port_vc_capability_2() {
         let data = 0b1111_0000000000000000_1010_1010;
         let result = PortVcCapability2::from(data);
@@ -791,6 +970,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unusual_byte_groupings)]
     fn vc_resource_capability() {
         let data = 0b10101010_0_1010101_1_1_000000_10101010;
         let result = VcResourceCapability::from(data);
@@ -813,6 +993,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unusual_byte_groupings)]
     fn vc_resource_control() {
         let data = 0b1_0000_101_0000_101_1_00000000_10101010;
         let result = VcResourceControl::from(data);
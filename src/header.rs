@@ -117,6 +117,9 @@ assert_eq!(sample, result);
 
 use core::array::TryFromSliceError;
 
+use crate::access::{self, ConfigAccess};
+use crate::register::{Register, RegisterAttributes as Attr, RegisterMap};
+
 mod command;
 pub use command::Command;
 
@@ -143,6 +146,7 @@ pub use cardbus_bridge_control::CardbusBridgeControl;
 
 /// Main structure
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     /// Identifies the manufacturer of the device. Where valid IDs are allocated by PCI-SIG to
     /// ensure uniqueness and 0xFFFF is an invalid value that will be returned on read accesses to
@@ -373,6 +377,138 @@ impl TryFrom<&[u8]> for Header {
     }
 }
 
+impl Header {
+    /// Read predefined header region through [ConfigAccess]
+    pub fn read_from<A: ConfigAccess + ?Sized>(access: &A) -> Result<Self, A::Error> {
+        let mut bytes = [0; Header::TOTAL_SIZE];
+        access::read_range(access, &mut bytes, 0, 0..Header::TOTAL_SIZE)?;
+        Ok(bytes.into())
+    }
+
+    /// Access attributes of header registers
+    ///
+    /// Only the first 16 bytes are described for the reserved header type.
+    pub fn registers(&self) -> RegisterMap {
+        match self.header_type {
+            HeaderType::Normal(_) => NORMAL_REGISTERS.into(),
+            HeaderType::Bridge(_) => BRIDGE_REGISTERS.into(),
+            HeaderType::Cardbus(_) => CARDBUS_REGISTERS.into(),
+            HeaderType::Reserved(_) => {
+                RegisterMap::from(NORMAL_REGISTERS).truncate(Header::COMMON_SIZE as u16)
+            }
+        }
+    }
+}
+
+/// Type 00h header registers
+pub const NORMAL_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 2),                              // Vendor ID
+    Attr::new(0x02, 2),                              // Device ID
+    Attr::new(0x04, 2).rw(0x077f).rsvdp(0xf800),     // Command
+    Attr::new(0x06, 2).rsvdz(0x0047).rw1c(0xf900),   // Status
+    Attr::new(0x08, 1),                              // Revision ID
+    Attr::new(0x09, 1),                              // Class Code: Programming Interface
+    Attr::new(0x0a, 1),                              // Class Code: Sub-Class
+    Attr::new(0x0b, 1),                              // Class Code: Base Class
+    Attr::new(0x0c, 1).rw(0xff),                     // Cache Line Size
+    Attr::new(0x0d, 1).rw(0xff),                     // Latency Timer
+    Attr::new(0x0e, 1),                              // Header Type
+    Attr::new(0x0f, 1).rw(0x40).rsvdp(0x30),         // BIST
+    Attr::new(0x10, 4).rw(0xffff_fff0),              // Base Address 0
+    Attr::new(0x14, 4).rw(0xffff_fff0),              // Base Address 1
+    Attr::new(0x18, 4).rw(0xffff_fff0),              // Base Address 2
+    Attr::new(0x1c, 4).rw(0xffff_fff0),              // Base Address 3
+    Attr::new(0x20, 4).rw(0xffff_fff0),              // Base Address 4
+    Attr::new(0x24, 4).rw(0xffff_fff0),              // Base Address 5
+    Attr::new(0x28, 4),                              // Cardbus CIS Pointer
+    Attr::new(0x2c, 2),                              // Subsystem Vendor ID
+    Attr::new(0x2e, 2),                              // Subsystem ID
+    Attr::new(0x30, 4).rw(0xffff_f801).rsvdp(0x7fe), // Expansion ROM Base Address
+    Attr::new(0x34, 1),                              // Capabilities Pointer
+    Attr::new(0x35, 1).rsvdp(0xff),
+    Attr::new(0x36, 2).rsvdp(0xffff),
+    Attr::new(0x38, 4).rsvdp(0xffff_ffff),
+    Attr::new(0x3c, 1).rw(0xff), // Interrupt Line
+    Attr::new(0x3d, 1),          // Interrupt Pin
+    Attr::new(0x3e, 1),          // Min_Gnt
+    Attr::new(0x3f, 1),          // Max_Lat
+];
+
+/// Type 01h header registers
+pub const BRIDGE_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 2),                            // Vendor ID
+    Attr::new(0x02, 2),                            // Device ID
+    Attr::new(0x04, 2).rw(0x077f).rsvdp(0xf800),   // Command
+    Attr::new(0x06, 2).rsvdz(0x0047).rw1c(0xf900), // Status
+    Attr::new(0x08, 1),                            // Revision ID
+    Attr::new(0x09, 1),                            // Class Code: Programming Interface
+    Attr::new(0x0a, 1),                            // Class Code: Sub-Class
+    Attr::new(0x0b, 1),                            // Class Code: Base Class
+    Attr::new(0x0c, 1).rw(0xff),                   // Cache Line Size
+    Attr::new(0x0d, 1).rw(0xff),                   // Latency Timer
+    Attr::new(0x0e, 1),                            // Header Type
+    Attr::new(0x0f, 1).rw(0x40).rsvdp(0x30),       // BIST
+    Attr::new(0x10, 4).rw(0xffff_fff0),            // Base Address 0
+    Attr::new(0x14, 4).rw(0xffff_fff0),            // Base Address 1
+    Attr::new(0x18, 1).rw(0xff),                   // Primary Bus Number
+    Attr::new(0x19, 1).rw(0xff),                   // Secondary Bus Number
+    Attr::new(0x1a, 1).rw(0xff),                   // Subordinate Bus Number
+    Attr::new(0x1b, 1).rw(0xff),                   // Secondary Latency Timer
+    Attr::new(0x1c, 1).rw(0xf0),                   // I/O Base
+    Attr::new(0x1d, 1).rw(0xf0),                   // I/O Limit
+    Attr::new(0x1e, 2).rsvdz(0x005f).rw1c(0xf900), // Secondary Status
+    Attr::new(0x20, 2).rw(0xfff0),                 // Memory Base
+    Attr::new(0x22, 2).rw(0xfff0),                 // Memory Limit
+    Attr::new(0x24, 2).rw(0xfff0),                 // Prefetchable Memory Base
+    Attr::new(0x26, 2).rw(0xfff0),                 // Prefetchable Memory Limit
+    Attr::new(0x28, 4).rw(0xffff_ffff),            // Prefetchable Base Upper 32 Bits
+    Attr::new(0x2c, 4).rw(0xffff_ffff),            // Prefetchable Limit Upper 32 Bits
+    Attr::new(0x30, 2).rw(0xffff),                 // I/O Base Upper 16 Bits
+    Attr::new(0x32, 2).rw(0xffff),                 // I/O Limit Upper 16 Bits
+    Attr::new(0x34, 1),                            // Capabilities Pointer
+    Attr::new(0x35, 1).rsvdp(0xff),
+    Attr::new(0x36, 2).rsvdp(0xffff),
+    Attr::new(0x38, 4).rw(0xffff_f801).rsvdp(0x7fe), // Expansion ROM Base Address
+    Attr::new(0x3c, 1).rw(0xff),                     // Interrupt Line
+    Attr::new(0x3d, 1),                              // Interrupt Pin
+    Attr::new(0x3e, 2).rw(0x0bff).rw1c(0x0400).rsvdp(0xf000), // Bridge Control
+];
+
+/// Type 02h header registers
+pub const CARDBUS_REGISTERS: &[Attr] = &[
+    Attr::new(0x00, 2),                            // Vendor ID
+    Attr::new(0x02, 2),                            // Device ID
+    Attr::new(0x04, 2).rw(0x077f).rsvdp(0xf800),   // Command
+    Attr::new(0x06, 2).rsvdz(0x0047).rw1c(0xf900), // Status
+    Attr::new(0x08, 1),                            // Revision ID
+    Attr::new(0x09, 1),                            // Class Code: Programming Interface
+    Attr::new(0x0a, 1),                            // Class Code: Sub-Class
+    Attr::new(0x0b, 1),                            // Class Code: Base Class
+    Attr::new(0x0c, 1).rw(0xff),                   // Cache Line Size
+    Attr::new(0x0d, 1).rw(0xff),                   // Latency Timer
+    Attr::new(0x0e, 1),                            // Header Type
+    Attr::new(0x0f, 1).rw(0x40).rsvdp(0x30),       // BIST
+    Attr::new(0x10, 4).rw(0xffff_f000),            // CardBus Socket/ExCa Base Address
+    Attr::new(0x14, 1),                            // Capabilities Pointer
+    Attr::new(0x15, 1).rsvdp(0xff),
+    Attr::new(0x16, 2).rsvdz(0x005f).rw1c(0xf900), // Secondary Status
+    Attr::new(0x18, 1).rw(0xff),                   // PCI Bus Number
+    Attr::new(0x19, 1).rw(0xff),                   // CardBus Bus Number
+    Attr::new(0x1a, 1).rw(0xff),                   // Subordinate Bus Number
+    Attr::new(0x1b, 1).rw(0xff),                   // CardBus Latency Timer
+    Attr::new(0x1c, 4).rw(0xffff_f000),            // Memory Base Address 0
+    Attr::new(0x20, 4).rw(0xffff_f000),            // Memory Limit 0
+    Attr::new(0x24, 4).rw(0xffff_f000),            // Memory Base Address 1
+    Attr::new(0x28, 4).rw(0xffff_f000),            // Memory Limit 1
+    Attr::new(0x2c, 4).rw(0xffff_fffc),            // I/O Base Address 0
+    Attr::new(0x30, 4).rw(0xffff_fffc),            // I/O Limit 0
+    Attr::new(0x34, 4).rw(0xffff_fffc),            // I/O Base Address 1
+    Attr::new(0x38, 4).rw(0xffff_fffc),            // I/O Limit 1
+    Attr::new(0x3c, 1).rw(0xff),                   // Interrupt Line
+    Attr::new(0x3d, 1),                            // Interrupt Pin
+    Attr::new(0x3e, 2).rw(0x07ef).rsvdp(0xf810),   // Bridge Control
+];
+
 
 
 
@@ -381,6 +517,7 @@ impl TryFrom<&[u8]> for Header {
 /// Identifies the layout of the second part of the predefined header (beginning at byte 10h in
 /// Configuration Space) and also whether or not the device contains multiple functions.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HeaderType {
     Normal(Normal),
     Bridge(Bridge),
@@ -416,6 +553,7 @@ impl<'a> From<&'a HeaderType> for u8 {
 
 /// General device (Type 00h)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Normal {
     pub base_addresses: BaseAddresses<6>,
     /// Points to the Card Information Structure and is used by devices that share silicon between CardBus and PCI.
@@ -436,6 +574,7 @@ pub struct Normal {
 
 /// PCI-to-PCI bridge (Type 01h)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bridge {
     /// Base Address Registers
     pub base_addresses: BaseAddresses<2>,
@@ -463,6 +602,7 @@ pub struct Bridge {
 /// The I/O Base and I/O Limit registers define an address range that is used by the bridge to
 /// determine when to forward I/O transactions from one interface to the other.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BridgeIoAddressRange {
     /// Bridge does not implement an I/O address range
     NotImplemented,
@@ -519,6 +659,7 @@ impl BridgeIoAddressRange {
 /// memory address range which is used by the bridge to determine when to forward memory
 /// transactions from one interface to the other
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BridgePrefetchableMemory {
     /// Bridge does not implement a prefetchable memory address range
     NotImplemented,
@@ -574,6 +715,7 @@ impl BridgePrefetchableMemory {
 
 /// PCI-to-CardBus bridge (Type 02h)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cardbus {
     pub base_addresses: BaseAddresses<1>,
     /// Secondary status
@@ -604,8 +746,63 @@ pub struct Cardbus {
     /// PC Card 16 Bit IF Legacy Mode Base Address
     pub legacy_mode_base_address: Option<u32>,
     /// Reserved
+    #[cfg_attr(feature = "serde", serde(with = "serde_reserved"))]
     pub reserved: Option<[u8; Self::RESERVED_SIZE]>,
 }
+
+/// Serde supports arrays up to 32 elements only, so CardBus reserved registers are serialized
+/// as a byte sequence
+#[cfg(feature = "serde")]
+mod serde_reserved {
+    use serde::{de, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        reserved: &Option<[u8; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        reserved
+            .as_ref()
+            .map(|bytes| &bytes[..])
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<Option<[u8; N]>, D::Error> {
+        struct Bytes<const N: usize>;
+        impl<'de, const N: usize> de::Visitor<'de> for Bytes<N> {
+            type Value = Option<[u8; N]>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "an optional sequence of {} bytes", N)
+            }
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+            fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+                d.deserialize_seq(self)
+            }
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                v.try_into()
+                    .map(Some)
+                    .map_err(|_| E::invalid_length(v.len(), &self))
+            }
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = [0; N];
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                if seq.next_element::<u8>()?.is_some() {
+                    return Err(de::Error::invalid_length(N + 1, &self));
+                }
+                Ok(Some(bytes))
+            }
+        }
+        deserializer.deserialize_option(Bytes)
+    }
+}
 impl Cardbus {
     const RESERVED_SIZE: usize = 0x80 - 0x48;
     pub fn try_set_optional_registers(&mut self, slice: &[u8]) -> Result<(), TryFromSliceError> {
@@ -627,6 +824,7 @@ impl Cardbus {
 
 /// Represents that status and allows control of a devices BIST (built-in self test).
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BuiltInSelfTest {
     /// Device supports BIST
     pub is_capable: bool,
@@ -657,10 +855,16 @@ impl From<BuiltInSelfTest> for u8 {
         result
     }
 }
+impl Register for BuiltInSelfTest {
+    type Value = u8;
+    const RSVDP: u8 = 0b0011_0000;
+    const RW1C: u8 = 0;
+}
 
 
 /// Specifies which interrupt pin the device uses.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InterruptPin {
     Unused,
     IntA,
@@ -669,6 +873,7 @@ pub enum InterruptPin {
     IntD,
     Reserved(u8),
 }
+#[allow(clippy::derivable_impls)]
 impl Default for InterruptPin {
     fn default() -> Self { Self::Unused }
 }
@@ -702,6 +907,7 @@ impl From<InterruptPin> for u8 {
 /// The IO Base Register and I/O Limit Register defines the address range that is used by the
 /// bridge to determine when to forward an I/O transaction to the CardBus.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IoAccessAddressRange {
     Addr16Bit {
         base: u16,
@@ -778,6 +984,7 @@ impl From<IoAccessAddressRange > for [[u16;2]; 2] {
 
 /// Handle the base address and size information for expansion ROM
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExpansionRom {
     pub address: u32,
     pub reserved: u16,
@@ -853,6 +1060,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_fallible_conversions)]
     fn header_type_normal() {
         // SATA controller [0106]: Intel Corporation Q170/Q150/B150/H170/H110/Z170/CM236 Chipset SATA Controller [AHCI Mode] [8086:a102] (rev 31) (prog-if 01 [AHCI 1.0])
         // Subsystem: Dell Device [1028:06a5]
@@ -924,6 +1132,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_fallible_conversions)]
     fn header_type_bridge() {
         // PCI bridge [0604]: Renesas Technology Corp. SH7758 PCIe Switch [PS] [1912:001d] (prog-if 00 [Normal decode])
         // Control: I/O+ Mem+ BusMaster+ SpecCycle- MemWINV- VGASnoop- ParErr- Stepping- SERR- FastB2B- DisINTx-
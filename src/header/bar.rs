@@ -84,7 +84,49 @@ impl<const N: usize> Iterator for BaseAddresses<N> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for BaseAddresses<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.clone())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for BaseAddresses<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, Unexpected};
+
+        struct Visitor<const N: usize>;
+        impl<'de, const N: usize> de::Visitor<'de> for Visitor<N> {
+            type Value = BaseAddresses<N>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a sequence of base addresses in {} regions", N)
+            }
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bars: [Option<BaseAddress>; N] = core::array::from_fn(|_| None);
+                while let Some(ba) = seq.next_element::<BaseAddress>()? {
+                    // 64-bit base address occupies the next region too
+                    let end = match ba.base_address_type {
+                        BaseAddressType::MemorySpace64 { .. } => ba.region + 2,
+                        _ => ba.region + 1,
+                    };
+                    if end > N {
+                        let region = Unexpected::Unsigned(ba.region as u64);
+                        return Err(de::Error::invalid_value(region, &self));
+                    }
+                    let region = ba.region;
+                    bars[region] = Some(ba);
+                }
+                Ok(BaseAddresses::new(bars.into_iter().flatten().collect()))
+            }
+        }
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
 impl<const N: usize> FromIterator<BaseAddress> for [u32; N] {
+    #[allow(clippy::unnecessary_cast)]
     fn from_iter<I: IntoIterator<Item = BaseAddress>>(iter: I) -> Self {
         let mut dwords = [0; N];
         for ba in iter.into_iter() {
@@ -129,6 +171,7 @@ impl<const N: usize> Eq for BaseAddresses<N> {}
 /// Base Address Registers (or BARs) can be used to hold memory addresses used
 /// by the device, or offsets for port addresses
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BaseAddress {
     pub region: usize,
     pub base_address_type: BaseAddressType,
@@ -136,6 +179,7 @@ pub struct BaseAddress {
 
 /// Base address possible types
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BaseAddressType {
     /// 32-bit Memory Space mapping
     MemorySpace32 {
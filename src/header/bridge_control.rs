@@ -7,8 +7,11 @@
 
 use heterob::{bit_numbering::Lsb, P13};
 
+use crate::register::Register;
+
 /// Bridge Control Register
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BridgeControl {
     /// Controls the bridge’s response to address and data parity errors on the secondary interface
     pub parity_error_response_enable: bool,
@@ -77,6 +80,29 @@ impl From<u16> for BridgeControl {
     }
 }
 
+impl From<BridgeControl> for u16 {
+    fn from(data: BridgeControl) -> Self {
+        (data.parity_error_response_enable as u16)
+            | (data.serr_enable as u16) << 1
+            | (data.isa_enable as u16) << 2
+            | (data.vga_enable as u16) << 3
+            | (data.vga_16_enable as u16) << 4
+            | (data.master_abort_mode as u16) << 5
+            | (data.secondary_bus_reset as u16) << 6
+            | (data.fast_back_to_back_enable as u16) << 7
+            | (data.primary_discard_timer as u16) << 8
+            | (data.secondary_discard_timer as u16) << 9
+            | (data.discard_timer_status as u16) << 10
+            | (data.discard_timer_serr_enable as u16) << 11
+    }
+}
+
+impl Register for BridgeControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xf000;
+    const RW1C: u16 = 0x0400;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
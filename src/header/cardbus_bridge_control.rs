@@ -3,8 +3,11 @@
 
 use heterob::{bit_numbering::Lsb, P12};
 
+use crate::register::Register;
+
 /// Bridge Control Register (Offset = 3EH)
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CardbusBridgeControl {
     /// Controls the response to parity errors on the CardBus
     pub parity_error_response_enable: bool,
@@ -63,6 +66,27 @@ impl From<u16> for CardbusBridgeControl {
     }
 }
 
+impl From<CardbusBridgeControl> for u16 {
+    fn from(data: CardbusBridgeControl) -> Self {
+        (data.parity_error_response_enable as u16)
+            | (data.serr_enable as u16) << 1
+            | (data.isa_enable as u16) << 2
+            | (data.vga_enable as u16) << 3
+            | (data.master_abort_mode as u16) << 5
+            | (data.cardbus_reset as u16) << 6
+            | (data.ireq_int_enable as u16) << 7
+            | (data.memory_0_prefetch_enable as u16) << 8
+            | (data.memory_1_prefetch_enable as u16) << 9
+            | (data.write_posting_enable as u16) << 10
+    }
+}
+
+impl Register for CardbusBridgeControl {
+    type Value = u16;
+    const RSVDP: u16 = 0xf810;
+    const RW1C: u16 = 0;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,6 +2,7 @@ use heterob::{endianness::Le, P3};
 
 /// Used to identify the generic operation of the Function and, in some cases, a specific register level programming interface
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClassCode {
     /// Specific register-level programming interface (if any) so that device independent software
     /// can interact with the device
@@ -240,6 +241,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::unnecessary_fallible_conversions)]
     fn meaning() {
         let data = [0x00, 0x00, 0x05];
         let result: ClassCode = data.try_into().unwrap();
@@ -1,11 +1,14 @@
 use heterob::{bit_numbering::Lsb, P12};
 
+use crate::register::Register;
+
 /// Provides control over a device's ability to generate and respond to PCI cycles.
 ///
 /// Where the only functionality guaranteed to be supported by all devices is, when a 0 is written
 /// to this register, the device is disconnected from the PCI bus for all accesses except
 /// Configuration Space access.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Command {
     pub io_space: bool,
     pub memory_space: bool,
@@ -54,6 +57,29 @@ impl From<u16> for Command {
     }
 }
 
+impl From<Command> for u16 {
+    fn from(data: Command) -> Self {
+        (data.io_space as u16)
+            | (data.memory_space as u16) << 1
+            | (data.bus_master as u16) << 2
+            | (data.special_cycles as u16) << 3
+            | (data.memory_write_and_invalidate_enable as u16) << 4
+            | (data.vga_palette_snoop as u16) << 5
+            | (data.parity_error_response as u16) << 6
+            | (data.stepping as u16) << 7
+            | (data.serr_enable as u16) << 8
+            | (data.fast_back_to_back_enable as u16) << 9
+            | (data.interrupt_disable as u16) << 10
+            | (data.reserved as u16 & 0b11111) << 11
+    }
+}
+
+impl Register for Command {
+    type Value = u16;
+    const RSVDP: u16 = 0xf800;
+    const RW1C: u16 = 0;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +104,11 @@ mod tests {
         };
         assert_eq!(sample, result);
     }
+
+    #[test]
+    fn into_word() {
+        let command: Command = 0xAAAA.into();
+        assert_eq!(0xAAAA, u16::from(command.clone()));
+        assert_eq!(0x02AA, command.write_value(0x0000));
+    }
 }
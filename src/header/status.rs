@@ -1,5 +1,7 @@
 use heterob::{bit_numbering::Lsb, P13};
 
+use crate::register::Register;
+
 /// The Status register is used to record status information for PCI bus related events.
 ///
 /// Devices may not need to implement all bits, depending on device functionality. Reserved bits
@@ -11,6 +13,7 @@ use heterob::{bit_numbering::Lsb, P13};
 ///
 /// Status type selected by generic constant [char] 'P', 'B' or 'C'
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Status<const T: char> {
     pub reserved: u8,
     pub interrupt_status: bool,
@@ -65,8 +68,33 @@ impl<const T: char> From<u16> for Status<T> {
     }
 }
 
+impl<const T: char> From<Status<T>> for u16 {
+    fn from(data: Status<T>) -> Self {
+        (data.reserved as u16 & 0b111)
+            | (data.interrupt_status as u16) << 3
+            | (data.capabilities_list as u16) << 4
+            | (data.is_66mhz_capable as u16) << 5
+            | (data.user_definable_features as u16) << 6
+            | (data.fast_back_to_back_capable as u16) << 7
+            | (data.master_data_parity_error as u16) << 8
+            | (u8::from(data.devsel_timing) as u16) << 9
+            | (data.signaled_target_abort as u16) << 11
+            | (data.received_target_abort as u16) << 12
+            | (data.received_master_abort as u16) << 13
+            | (data.system_error as u16) << 14
+            | (data.detected_parity_error as u16) << 15
+    }
+}
+
+impl<const T: char> Register for Status<T> {
+    type Value = u16;
+    const RSVDP: u16 = 0;
+    const RW1C: u16 = 0xf900;
+}
+
 /// Specifies allowable timings for assertion of DEVSEL#
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DevselTiming {
     /// fast
     Fast,
@@ -88,6 +116,16 @@ impl From<u8> for DevselTiming {
         }
     }
 }
+impl From<DevselTiming> for u8 {
+    fn from(data: DevselTiming) -> Self {
+        match data {
+            DevselTiming::Fast => 0b00,
+            DevselTiming::Medium => 0b01,
+            DevselTiming::Slow => 0b10,
+            DevselTiming::Undefined => 0b11,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -114,4 +152,13 @@ mod tests {
         };
         assert_eq!(sample, result);
     }
+
+    #[test]
+    fn into_word() {
+        let status: Status<'P'> = 0xAAAA.into();
+        assert_eq!(0xAAAA, u16::from(status.clone()));
+        assert_eq!(0x02AA, status.write_value(0xAAAA));
+        let status: Status<'P'> = 0x8000.into();
+        assert_eq!(0x82AA, status.clear_value(0xAAAA));
+    }
 }
@@ -2,10 +2,19 @@
 
 #![no_std]
 
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 #[macro_use]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+
+pub mod access;
+pub use access::{ConfigAccess, ConfigWrite};
+
+pub mod register;
+pub use register::Register;
 
 pub mod header;
 pub use header::Header;
@@ -16,6 +25,19 @@ pub use capabilities::Capabilities;
 pub mod extended_capabilities;
 pub use extended_capabilities::ExtendedCapabilities;
 
+pub mod ecam;
+
+pub mod lspci;
+pub use lspci::Lspci;
+
+#[cfg(feature = "alloc")]
+pub mod configuration_space;
+#[cfg(feature = "alloc")]
+pub use configuration_space::ConfigurationSpace;
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub mod sysfs;
+
 
 /// Device dependent region starts at 0x40 offset
 pub const DDR_OFFSET: usize = 0x40;
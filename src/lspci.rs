@@ -0,0 +1,2566 @@
+/*!
+## lspci compatible output
+
+[Lspci] renders decoded configuration space the way `lspci -vvv` prints a function: header
+registers (Control, Status, Latency, Interrupt, Regions, bridge windows) followed by
+`Capabilities: [xx] ...` lines for standard and extended capabilities. Flags use the same `+`/`-`
+notation, so the output could be compared with real lspci captures line by line.
+
+Differences from lspci:
+- The first line with bus address and device names is not printed, names are not resolved
+- Region sizes are not known from configuration space, so `[size=...]` is omitted
+- Interrupt line is reported for functions with interrupt pin in use, IRQ is the Interrupt Line
+  register value instead of kernel IRQ number
+- Capabilities without detailed decoding are printed with the title line only
+
+With the `alloc` feature `dump` module parses `lspci -xxx`/`lspci -xxxx` hex dumps back into
+configuration space data.
+
+## Example
+```rust
+# use pcics::lspci::Lspci;
+let data = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/device/8086_9dc8/config"
+));
+let lspci = Lspci::try_from(&data[..]).unwrap();
+let text = lspci.to_string();
+assert!(text.contains("\tCapabilities: [60] MSI: Enable+ Count=1/1 Maskable- 64bit+\n"));
+assert!(text.contains("\t\tAddress: 00000000fee00578  Data: 0000\n"));
+```
+*/
+
+#[cfg(feature = "alloc")]
+pub mod dump;
+
+use core::array::TryFromSliceError;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{
+    capabilities::{
+        message_signaled_interrups::MessageAddress,
+        msi_x::Bir,
+        pci_express::{
+            Device, Device2, DeviceType, EndpointL0sAcceptableLatency, EndpointL1AcceptableLatency,
+            L0sExitLatency, L1ExitLatency, Link, Link2, PhantomFunctionsSupported, Root, Slot,
+            SlotPowerLimit,
+        },
+        power_management_interface::{AuxCurrent, PowerState},
+        sata::BarLocation,
+        vendor_specific::Virtio,
+        Capability, CapabilityError, CapabilityKind, PciExpress, VendorSpecific,
+    },
+    extended_capabilities::{
+        advanced_error_reporting::{CorrectableError, UncorrectableError},
+        l1_pm_substates::PortTPowerOn,
+        latency_tolerance_reporting::MaxLatency,
+        resizable_bar::ResizableBarEntry,
+        secondary_pci_express::LaneErrorStatus,
+        vf_resizable_bar::VfResizableBar,
+        AccessControlServices, AdvancedErrorReporting, DataObjectExchange,
+        DownstreamPortContainment, ExtendedCapability, ExtendedCapabilityError,
+        ExtendedCapabilityKind, L1PmSubstates, LaneMarginingAtTheReceiver,
+        LatencyToleranceReporting, PhysicalLayer16GTps, PhysicalLayer32GTps, PhysicalLayer64GTps,
+        PrecisionTimeMeasurement, ResizableBar, SecondaryPciExpress, SingleRootIoVirtualization,
+    },
+    header::{
+        BaseAddress, BaseAddressType, BridgeIoAddressRange, BridgePrefetchableMemory, DevselTiming,
+        ExpansionRom, HeaderType, InterruptPin, IoAccessAddressRange, Status,
+    },
+    Capabilities, ExtendedCapabilities, Header, DDR_OFFSET, ECS_OFFSET,
+};
+
+/// `lspci -vvv` style view of a function configuration space
+///
+/// Capabilities are printed if `data` covers device dependent region, extended capabilities are
+/// printed if `data` covers extended configuration space and the function has PCI Express
+/// capability.
+#[derive(Debug, Clone)]
+pub struct Lspci<'a> {
+    header: Header,
+    data: &'a [u8],
+}
+
+impl<'a> Lspci<'a> {
+    /// View of configuration space `data` starting at offset 0 with already decoded `header`
+    pub fn new(header: Header, data: &'a [u8]) -> Self {
+        Self { header, data }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Lspci<'a> {
+    type Error = TryFromSliceError;
+
+    /// Fails only if `slice` is shorter than [Header::TOTAL_SIZE].
+    fn try_from(slice: &'a [u8]) -> Result<Self, Self::Error> {
+        let header = Header::try_from(slice)?;
+        Ok(Self::new(header, slice))
+    }
+}
+
+impl<'a> Display for Lspci<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let header = &self.header;
+        header_lines(f, header)?;
+        let ddr = match self.data.get(DDR_OFFSET..ECS_OFFSET) {
+            Some(ddr) if header.status.capabilities_list => ddr,
+            _ => return Ok(()),
+        };
+        let mut is_pcie = false;
+        let mut caps = Capabilities::new(ddr, header);
+        loop {
+            let pointer = caps.pointer();
+            match caps.next() {
+                Some(Ok(cap)) => {
+                    is_pcie |= matches!(cap.kind, CapabilityKind::PciExpress(_));
+                    capability(f, &cap)?;
+                }
+                Some(Err(CapabilityError::Header { .. })) => {
+                    writeln!(f, "\tCapabilities: <access denied>")?
+                }
+                Some(Err(CapabilityError::Loop { .. })) => {
+                    writeln!(f, "\tCapabilities: [{:02x}] <chain looped>", pointer)?
+                }
+                Some(Err(_)) => writeln!(f, "\tCapabilities: [{:02x}] <chain broken>", pointer)?,
+                None => break,
+            }
+        }
+        let ecs = match self.data.get(ECS_OFFSET..) {
+            Some(ecs) if is_pcie && !ecs.is_empty() => ecs,
+            _ => return Ok(()),
+        };
+        let mut ecaps = ExtendedCapabilities::new(ecs);
+        loop {
+            let offset = ecaps.next_capability_offset();
+            match ecaps.next() {
+                Some(Ok(ecap)) => extended_capability(f, &ecap)?,
+                Some(Err(ExtendedCapabilityError::Loop { .. })) => {
+                    writeln!(f, "\tCapabilities: [{:03x}] <chain looped>", offset)?
+                }
+                Some(Err(_)) => writeln!(f, "\tCapabilities: [{:03x}] <chain broken>", offset)?,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// lspci `+`/`-` flag notation
+struct Flag(bool);
+
+impl Display for Flag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0 { "+" } else { "-" })
+    }
+}
+
+fn header_lines(f: &mut Formatter<'_>, header: &Header) -> fmt::Result {
+    let subsystem = match &header.header_type {
+        HeaderType::Normal(normal) => Some((normal.sub_vendor_id, normal.sub_device_id)),
+        HeaderType::Cardbus(cardbus) => {
+            cardbus.subsystem_vendor_id.zip(cardbus.subsystem_device_id)
+        }
+        _ => None,
+    };
+    if let Some((vendor_id, device_id)) = subsystem {
+        if vendor_id != 0 && vendor_id != 0xffff {
+            writeln!(
+                f,
+                "\tSubsystem: Device [{:04x}:{:04x}]",
+                vendor_id, device_id
+            )?;
+        }
+    }
+    let cmd = &header.command;
+    writeln!(
+        f,
+        "\tControl: I/O{} Mem{} BusMaster{} SpecCycle{} MemWINV{} VGASnoop{} ParErr{} \
+         Stepping{} SERR{} FastB2B{} DisINTx{}",
+        Flag(cmd.io_space),
+        Flag(cmd.memory_space),
+        Flag(cmd.bus_master),
+        Flag(cmd.special_cycles),
+        Flag(cmd.memory_write_and_invalidate_enable),
+        Flag(cmd.vga_palette_snoop),
+        Flag(cmd.parity_error_response),
+        Flag(cmd.stepping),
+        Flag(cmd.serr_enable),
+        Flag(cmd.fast_back_to_back_enable),
+        Flag(cmd.interrupt_disable),
+    )?;
+    let st = &header.status;
+    writeln!(
+        f,
+        "\tStatus: Cap{} 66MHz{} UDF{} FastB2B{} ParErr{} DEVSEL={} >TAbort{} <TAbort{} \
+         <MAbort{} >SERR{} <PERR{} INTx{}",
+        Flag(st.capabilities_list),
+        Flag(st.is_66mhz_capable),
+        Flag(st.user_definable_features),
+        Flag(st.fast_back_to_back_capable),
+        Flag(st.master_data_parity_error),
+        devsel(&st.devsel_timing),
+        Flag(st.signaled_target_abort),
+        Flag(st.received_target_abort),
+        Flag(st.received_master_abort),
+        Flag(st.system_error),
+        Flag(st.detected_parity_error),
+        Flag(st.interrupt_status),
+    )?;
+    if cmd.bus_master {
+        write!(f, "\tLatency: {}", header.latency_timer)?;
+        if let HeaderType::Normal(normal) = &header.header_type {
+            let (min_gnt, max_lat) = (normal.min_grant as u32, normal.max_latency as u32);
+            match (min_gnt, max_lat) {
+                (0, 0) => (),
+                (min, 0) => write!(f, " ({}ns min)", min * 250)?,
+                (0, max) => write!(f, " ({}ns max)", max * 250)?,
+                (min, max) => write!(f, " ({}ns min, {}ns max)", min * 250, max * 250)?,
+            }
+        }
+        if header.cache_line_size != 0 {
+            write!(
+                f,
+                ", Cache Line Size: {} bytes",
+                header.cache_line_size as u32 * 4
+            )?;
+        }
+        writeln!(f)?;
+    }
+    let pin = match header.interrupt_pin {
+        InterruptPin::Unused => None,
+        InterruptPin::IntA => Some(1),
+        InterruptPin::IntB => Some(2),
+        InterruptPin::IntC => Some(3),
+        InterruptPin::IntD => Some(4),
+        InterruptPin::Reserved(v) => Some(v),
+    };
+    if let Some(pin) = pin {
+        writeln!(
+            f,
+            "\tInterrupt: pin {} routed to IRQ {}",
+            (b'A' - 1).wrapping_add(pin) as char,
+            header.interrupt_line
+        )?;
+    }
+    if header.bist.is_capable {
+        if header.bist.is_running {
+            writeln!(f, "\tBIST is running")?;
+        } else {
+            writeln!(f, "\tBIST result: {:02x}", header.bist.completion_code)?;
+        }
+    }
+    match &header.header_type {
+        HeaderType::Normal(normal) => {
+            base_addresses(f, header, normal.base_addresses.clone())?;
+            expansion_rom(f, &normal.expansion_rom)
+        }
+        HeaderType::Bridge(bridge) => {
+            base_addresses(f, header, bridge.base_addresses.clone())?;
+            writeln!(
+                f,
+                "\tBus: primary={:02x}, secondary={:02x}, subordinate={:02x}, sec-latency={}",
+                bridge.primary_bus_number,
+                bridge.secondary_bus_number,
+                bridge.subordinate_bus_number,
+                bridge.secondary_latency_timer,
+            )?;
+            match bridge.io_address_range {
+                BridgeIoAddressRange::NotImplemented => {
+                    writeln!(f, "\tI/O behind bridge: {:08x}-{:08x}", 0, 0xfff)?
+                }
+                BridgeIoAddressRange::IoAddr16 { base, limit } => writeln!(
+                    f,
+                    "\tI/O behind bridge: {:08x}-{:08x}",
+                    base,
+                    limit as u32 + 0xfff
+                )?,
+                BridgeIoAddressRange::IoAddr32 { base, limit } => writeln!(
+                    f,
+                    "\tI/O behind bridge: {:08x}-{:08x}",
+                    base,
+                    limit.wrapping_add(0xfff)
+                )?,
+                BridgeIoAddressRange::Malformed { base, limit }
+                | BridgeIoAddressRange::Reserved { base, limit } => {
+                    writeln!(f, "\t!!! Unknown I/O range types {:x}/{:x}", base, limit)?
+                }
+            }
+            if bridge.memory_base & 0xf != 0 || bridge.memory_limit & 0xf != 0 {
+                writeln!(
+                    f,
+                    "\t!!! Unknown memory range types {:x}/{:x}",
+                    bridge.memory_base, bridge.memory_limit
+                )?;
+            } else {
+                writeln!(
+                    f,
+                    "\tMemory behind bridge: {:08x}-{:08x}",
+                    (bridge.memory_base as u32) << 16,
+                    ((bridge.memory_limit as u32) << 16).wrapping_add(0xfffff)
+                )?;
+            }
+            match bridge.prefetchable_memory {
+                BridgePrefetchableMemory::NotImplemented => writeln!(
+                    f,
+                    "\tPrefetchable memory behind bridge: {:08x}-{:08x}",
+                    0, 0xfffff
+                )?,
+                BridgePrefetchableMemory::MemAddr32 { base, limit } => writeln!(
+                    f,
+                    "\tPrefetchable memory behind bridge: {:08x}-{:08x}",
+                    base,
+                    limit.wrapping_add(0xfffff)
+                )?,
+                BridgePrefetchableMemory::MemAddr64 { base, limit } => writeln!(
+                    f,
+                    "\tPrefetchable memory behind bridge: {:016x}-{:016x}",
+                    base,
+                    limit.wrapping_add(0xfffff)
+                )?,
+                BridgePrefetchableMemory::Malformed { base, limit }
+                | BridgePrefetchableMemory::Reserved { base, limit } => writeln!(
+                    f,
+                    "\t!!! Unknown prefetchable memory range types {:x}/{:x}",
+                    base, limit
+                )?,
+            }
+            secondary_status(f, &bridge.secondary_status)?;
+            expansion_rom(f, &bridge.expansion_rom)?;
+            let bc = &bridge.bridge_control;
+            writeln!(
+                f,
+                "\tBridgeCtl: Parity{} SERR{} NoISA{} VGA{} MAbort{} >Reset{} FastB2B{}",
+                Flag(bc.parity_error_response_enable),
+                Flag(bc.serr_enable),
+                Flag(bc.isa_enable),
+                Flag(bc.vga_enable),
+                Flag(bc.master_abort_mode),
+                Flag(bc.secondary_bus_reset),
+                Flag(bc.fast_back_to_back_enable),
+            )?;
+            writeln!(
+                f,
+                "\t\tPriDiscTmr{} SecDiscTmr{} DiscTmrStat{} DiscTmrSERREn{}",
+                Flag(bc.primary_discard_timer),
+                Flag(bc.secondary_discard_timer),
+                Flag(bc.discard_timer_status),
+                Flag(bc.discard_timer_serr_enable),
+            )
+        }
+        HeaderType::Cardbus(cardbus) => {
+            base_addresses(f, header, cardbus.base_addresses.clone())?;
+            writeln!(
+                f,
+                "\tBus: primary={:02x}, secondary={:02x}, subordinate={:02x}, sec-latency={}",
+                cardbus.pci_bus_number,
+                cardbus.cardbus_bus_number,
+                cardbus.subordinate_bus_number,
+                cardbus.cardbus_latency_timer,
+            )?;
+            let bc = &cardbus.bridge_control;
+            let windows = [
+                (
+                    cardbus.memory_base_address_0,
+                    cardbus.memory_limit_address_0,
+                    bc.memory_0_prefetch_enable,
+                ),
+                (
+                    cardbus.memory_base_address_1,
+                    cardbus.memory_limit_address_1,
+                    bc.memory_1_prefetch_enable,
+                ),
+            ];
+            for (i, (base, limit, prefetchable)) in windows.into_iter().enumerate() {
+                writeln!(
+                    f,
+                    "\tMemory window {}: {:08x}-{:08x}{}{}",
+                    i,
+                    base,
+                    limit.wrapping_add(0xfff),
+                    if cmd.memory_space { "" } else { " [disabled]" },
+                    if prefetchable { " (prefetchable)" } else { "" },
+                )?;
+            }
+            let windows = [
+                &cardbus.io_access_address_range_0,
+                &cardbus.io_access_address_range_1,
+            ];
+            for (i, range) in windows.into_iter().enumerate() {
+                let (base, limit) = match *range {
+                    IoAccessAddressRange::Addr16Bit { base, limit } => (base as u32, limit as u32),
+                    IoAccessAddressRange::Addr32Bit { base, limit } => (base, limit),
+                    IoAccessAddressRange::Unknown {
+                        io_address_capability,
+                        base_lower,
+                        base_upper,
+                        limit_lower,
+                        limit_upper,
+                    } => {
+                        if io_address_capability & 1 == 0 {
+                            (base_lower as u32, limit_lower as u32)
+                        } else {
+                            (
+                                (base_upper as u32) << 16 | base_lower as u32,
+                                (limit_upper as u32) << 16 | limit_lower as u32,
+                            )
+                        }
+                    }
+                };
+                writeln!(
+                    f,
+                    "\tI/O window {}: {:08x}-{:08x}{}",
+                    i,
+                    base,
+                    limit.wrapping_add(3),
+                    if cmd.io_space { "" } else { " [disabled]" },
+                )?;
+            }
+            if cardbus.secondary_status.system_error {
+                writeln!(f, "\tSecondary status: SERR")?;
+            }
+            writeln!(
+                f,
+                "\tBridgeCtl: Parity{} SERR{} ISA{} VGA{} MAbort{} >Reset{} 16bInt{} PostWrite{}",
+                Flag(bc.parity_error_response_enable),
+                Flag(bc.serr_enable),
+                Flag(bc.isa_enable),
+                Flag(bc.vga_enable),
+                Flag(bc.master_abort_mode),
+                Flag(bc.cardbus_reset),
+                Flag(bc.ireq_int_enable),
+                Flag(bc.write_posting_enable),
+            )?;
+            match cardbus.legacy_mode_base_address {
+                None => writeln!(f, "\t<access denied to the rest>"),
+                Some(0) => Ok(()),
+                Some(exca) => writeln!(f, "\t16-bit legacy interface ports at {:04x}", exca),
+            }
+        }
+        HeaderType::Reserved(_) => Ok(()),
+    }
+}
+
+fn devsel(timing: &DevselTiming) -> &'static str {
+    match timing {
+        DevselTiming::Fast => "fast",
+        DevselTiming::Medium => "medium",
+        DevselTiming::Slow => "slow",
+        DevselTiming::Undefined => "??",
+    }
+}
+
+fn secondary_status(f: &mut Formatter<'_>, st: &Status<'B'>) -> fmt::Result {
+    writeln!(
+        f,
+        "\tSecondary status: 66MHz{} FastB2B{} ParErr{} DEVSEL={} >TAbort{} <TAbort{} \
+         <MAbort{} <SERR{} <PERR{}",
+        Flag(st.is_66mhz_capable),
+        Flag(st.fast_back_to_back_capable),
+        Flag(st.master_data_parity_error),
+        devsel(&st.devsel_timing),
+        Flag(st.signaled_target_abort),
+        Flag(st.received_target_abort),
+        Flag(st.received_master_abort),
+        Flag(st.system_error),
+        Flag(st.detected_parity_error),
+    )
+}
+
+fn base_addresses(
+    f: &mut Formatter<'_>,
+    header: &Header,
+    bars: impl Iterator<Item = BaseAddress>,
+) -> fmt::Result {
+    let cmd = &header.command;
+    for BaseAddress {
+        region,
+        base_address_type,
+    } in bars
+    {
+        write!(f, "\tRegion {}: ", region)?;
+        let (base_address, kind, prefetchable) = match base_address_type {
+            BaseAddressType::IoSpace { base_address } => {
+                write!(f, "I/O ports at ")?;
+                if base_address != 0 || cmd.io_space {
+                    write!(f, "{:04x}", base_address)?;
+                } else {
+                    write!(f, "<unassigned>")?;
+                }
+                if !cmd.io_space {
+                    write!(f, " [disabled]")?;
+                }
+                writeln!(f)?;
+                continue;
+            }
+            BaseAddressType::MemorySpace32 {
+                prefetchable,
+                base_address,
+            } => (Some(base_address as u64), "32-bit", prefetchable),
+            BaseAddressType::MemorySpaceBelow1M {
+                prefetchable,
+                base_address,
+            } => (Some(base_address as u64), "low-1M", prefetchable),
+            BaseAddressType::MemorySpace64 {
+                prefetchable,
+                base_address,
+            } => (Some(base_address), "64-bit", prefetchable),
+            BaseAddressType::MemorySpaceReserved {
+                prefetchable,
+                base_address,
+            } => (Some(base_address as u64), "type 3", prefetchable),
+            BaseAddressType::MemorySpace64Broken { prefetchable } => (None, "64-bit", prefetchable),
+        };
+        write!(f, "Memory at ")?;
+        match base_address {
+            None => write!(f, "<invalid-64bit-slot>")?,
+            Some(0) => write!(f, "<unassigned>")?,
+            Some(base_address) => write!(f, "{:08x}", base_address)?,
+        }
+        write!(
+            f,
+            " ({}, {}prefetchable)",
+            kind,
+            if prefetchable { "" } else { "non-" }
+        )?;
+        if !cmd.memory_space {
+            write!(f, " [disabled]")?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+fn expansion_rom(f: &mut Formatter<'_>, rom: &ExpansionRom) -> fmt::Result {
+    if rom.address == 0 && rom.reserved == 0 && !rom.is_enabled {
+        return Ok(());
+    }
+    write!(f, "\tExpansion ROM at ")?;
+    if rom.address != 0 {
+        write!(f, "{:08x}", rom.address)?;
+    } else {
+        write!(f, "<unassigned>")?;
+    }
+    if !rom.is_enabled {
+        write!(f, " [disabled]")?;
+    }
+    writeln!(f)
+}
+
+fn capability(f: &mut Formatter<'_>, cap: &Capability<'_>) -> fmt::Result {
+    write!(f, "\tCapabilities: [{:02x}] ", cap.pointer)?;
+    match &cap.kind {
+        CapabilityKind::NullCapability => writeln!(f, "Null"),
+        CapabilityKind::PowerManagementInterface(pm) => {
+            let caps = &pm.capabilities;
+            writeln!(f, "Power Management version {}", caps.version)?;
+            let aux_current = match caps.aux_current {
+                AuxCurrent::SelfPowered => 0,
+                AuxCurrent::MaxCurrent55mA => 55,
+                AuxCurrent::MaxCurrent100mA => 100,
+                AuxCurrent::MaxCurrent160mA => 160,
+                AuxCurrent::MaxCurrent220mA => 220,
+                AuxCurrent::MaxCurrent270mA => 270,
+                AuxCurrent::MaxCurrent320mA => 320,
+                AuxCurrent::MaxCurrent375mA => 375,
+            };
+            let pme = &caps.pme_support;
+            writeln!(
+                f,
+                "\t\tFlags: PMEClk{} DSI{} D1{} D2{} AuxCurrent={}mA \
+                 PME(D0{},D1{},D2{},D3hot{},D3cold{})",
+                Flag(caps.pme_clock),
+                Flag(caps.device_specific_initialization),
+                Flag(caps.d1_support),
+                Flag(caps.d2_support),
+                aux_current,
+                Flag(pme.d0),
+                Flag(pme.d1),
+                Flag(pme.d2),
+                Flag(pme.d3_hot),
+                Flag(pme.d3_cold),
+            )?;
+            let ctrl = &pm.control;
+            let power_state = match ctrl.power_state {
+                PowerState::D0 => 0,
+                PowerState::D1 => 1,
+                PowerState::D2 => 2,
+                PowerState::D3Hot => 3,
+            };
+            writeln!(
+                f,
+                "\t\tStatus: D{} NoSoftRst{} PME-Enable{} DSel={} DScale={} PME{}",
+                power_state,
+                Flag(ctrl.no_soft_reset),
+                Flag(ctrl.pme_enabled),
+                u8::from(ctrl.data_select),
+                u8::from(ctrl.data_scale),
+                Flag(ctrl.pme_status),
+            )?;
+            let bridge = &pm.bridge;
+            if bridge.reserved != 0 || bridge.b2_b3 || bridge.bpcc_enabled {
+                writeln!(
+                    f,
+                    "\t\tBridge: PM{} B3{}",
+                    Flag(bridge.bpcc_enabled),
+                    Flag(!bridge.b2_b3)
+                )?;
+            }
+            Ok(())
+        }
+        CapabilityKind::AcceleratedGraphicsPort(agp) => writeln!(
+            f,
+            "AGP version {:x}.{:x}",
+            agp.identifier.major, agp.identifier.minor
+        ),
+        CapabilityKind::VitalProductData(_) => writeln!(f, "Vital Product Data"),
+        CapabilityKind::SlotIdentification(slot_id) => writeln!(
+            f,
+            "Slot ID: {} slots, First{}, chassis {:02x}",
+            slot_id.expansion_slot.expansion_slots_provided,
+            Flag(slot_id.expansion_slot.first_in_chassis),
+            slot_id.chassis_number
+        ),
+        CapabilityKind::MessageSignaledInterrups(msi) => {
+            let mc = &msi.message_control;
+            writeln!(
+                f,
+                "MSI: Enable{} Count={}/{} Maskable{} 64bit{}",
+                Flag(mc.msi_enable),
+                mc.multiple_message_enable.number_of_vectors(),
+                mc.multiple_message_capable.number_of_vectors(),
+                Flag(mc.per_vector_masking_capable),
+                Flag(mc.a_64_bit_address_capable),
+            )?;
+            match msi.message_address {
+                MessageAddress::Dword(address) => write!(f, "\t\tAddress: {:08x}", address)?,
+                MessageAddress::Qword(address) => write!(f, "\t\tAddress: {:016x}", address)?,
+            }
+            writeln!(f, "  Data: {:04x}", msi.message_data)?;
+            if mc.per_vector_masking_capable {
+                writeln!(
+                    f,
+                    "\t\tMasking: {:08x}  Pending: {:08x}",
+                    msi.mask_bits.unwrap_or(0),
+                    msi.pending_bits.unwrap_or(0)
+                )?;
+            }
+            Ok(())
+        }
+        CapabilityKind::CompactPciHotSwap(_) => writeln!(f, "CompactPCI hot-swap <?>"),
+        CapabilityKind::PciX(_) => writeln!(f, "PCI-X non-bridge device"),
+        CapabilityKind::PciXBridge(_) => writeln!(f, "PCI-X bridge device"),
+        CapabilityKind::Hypertransport(_) => writeln!(f, "HyperTransport"),
+        CapabilityKind::VendorSpecific(VendorSpecific::Unspecified(slice)) => writeln!(
+            f,
+            "Vendor Specific Information: Len={:02x} <?>",
+            slice.len() + 3
+        ),
+        CapabilityKind::VendorSpecific(VendorSpecific::Virtio(virtio)) => {
+            let (name, bar, offset, size, multiplier) = match *virtio {
+                Virtio::CommonCfg { bar, offset, size } => ("CommonCfg", bar, offset, size, None),
+                Virtio::Notify {
+                    bar,
+                    offset,
+                    size,
+                    multiplier,
+                } => ("Notify", bar, offset, size, multiplier),
+                Virtio::Isr { bar, offset, size } => ("ISR", bar, offset, size, None),
+                Virtio::DeviceCfg { bar, offset, size } => ("DeviceCfg", bar, offset, size, None),
+                Virtio::Unknown { bar, offset, size } => ("<unknown>", bar, offset, size, None),
+            };
+            writeln!(f, "Vendor Specific Information: VirtIO: {}", name)?;
+            write!(f, "\t\tBAR={} offset={:08x} size={:08x}", bar, offset, size)?;
+            if let Some(multiplier) = multiplier {
+                write!(f, " multiplier={:08x}", multiplier)?;
+            }
+            writeln!(f)
+        }
+        CapabilityKind::DebugPort(debug_port) => writeln!(
+            f,
+            "Debug port: BAR={} offset={:04x}",
+            debug_port.bar_number, debug_port.offset
+        ),
+        CapabilityKind::CompactPciResourceControl(_) => {
+            writeln!(f, "CompactPCI central resource control <?>")
+        }
+        CapabilityKind::PciHotPlug(_) => writeln!(f, "Hot-plug capable"),
+        CapabilityKind::BridgeSubsystemVendorId(ssvid) => writeln!(
+            f,
+            "Subsystem: Device [{:04x}:{:04x}]",
+            ssvid.subsystem_vendor_id, ssvid.subsystem_id
+        ),
+        CapabilityKind::Agp8x(_) => writeln!(f, "AGP3 <?>"),
+        CapabilityKind::SecureDevice(_) => writeln!(f, "Secure device <?>"),
+        CapabilityKind::PciExpress(pcie) => pci_express(f, pcie),
+        CapabilityKind::MsiX(msi_x) => {
+            let mc = &msi_x.message_control;
+            writeln!(
+                f,
+                "MSI-X: Enable{} Count={} Masked{}",
+                Flag(mc.msi_x_enable),
+                mc.table_size + 1,
+                Flag(mc.function_mask)
+            )?;
+            writeln!(
+                f,
+                "\t\tVector table: BAR={} offset={:08x}",
+                bir(&msi_x.table.bir),
+                msi_x.table.offset
+            )?;
+            writeln!(
+                f,
+                "\t\tPBA: BAR={} offset={:08x}",
+                bir(&msi_x.pending_bit_array.bir),
+                msi_x.pending_bit_array.offset
+            )
+        }
+        CapabilityKind::Sata(sata) => {
+            write!(
+                f,
+                "SATA HBA v{}.{}",
+                sata.revision.major, sata.revision.minor
+            )?;
+            let bar = match sata.bar_location {
+                BarLocation::Bar0 => 0,
+                BarLocation::Bar1 => 1,
+                BarLocation::Bar2 => 2,
+                BarLocation::Bar3 => 3,
+                BarLocation::Bar4 => 4,
+                BarLocation::Bar5 => 5,
+                BarLocation::SataCapability1 => return writeln!(f, " InCfgSpace"),
+                BarLocation::Reserved(v) => return writeln!(f, " BAR??{}", v),
+            };
+            writeln!(f, " BAR{} Offset={:08x}", bar, sata.bar_offset.0)
+        }
+        CapabilityKind::AdvancedFeatures(af) => {
+            writeln!(f, "PCI Advanced Features")?;
+            writeln!(
+                f,
+                "\t\tAFCap: TP{} FLR{}",
+                Flag(af.capabilities.transactions_pending),
+                Flag(af.capabilities.function_level_reset)
+            )?;
+            writeln!(f, "\t\tAFCtrl: FLR{}", Flag(af.control.initiate_flr))?;
+            writeln!(
+                f,
+                "\t\tAFStatus: TP{}",
+                Flag(af.status.transactions_pending)
+            )
+        }
+        CapabilityKind::EnhancedAllocation(ea) => {
+            writeln!(f, "Enhanced Allocation (EA): NumEntries={}", ea.num_entries)
+        }
+        CapabilityKind::FlatteningPortalBridge(_) => writeln!(f, "Flattening Portal Bridge"),
+        CapabilityKind::Reserved(id) => writeln!(f, "Capability ID {:#02x}", id),
+    }
+}
+
+fn bir(bir: &Bir) -> u8 {
+    match *bir {
+        Bir::Bar10h => 0,
+        Bir::Bar14h => 1,
+        Bir::Bar18h => 2,
+        Bir::Bar1Ch => 3,
+        Bir::Bar20h => 4,
+        Bir::Bar24h => 5,
+        Bir::Reserved(v) => v,
+    }
+}
+
+fn pci_express(f: &mut Formatter<'_>, pcie: &PciExpress) -> fmt::Result {
+    write!(f, "Express (v{}) ", pcie.version)?;
+    let dt = &pcie.device_type;
+    let (link, link_2, slot, root) = match dt {
+        DeviceType::Endpoint { link, link_2 } => {
+            write!(f, "Endpoint")?;
+            (Some(link), link_2.as_ref(), None, None)
+        }
+        DeviceType::LegacyEndpoint { link, link_2 } => {
+            write!(f, "Legacy Endpoint")?;
+            (Some(link), link_2.as_ref(), None, None)
+        }
+        DeviceType::RootComplexIntegratedEndpoint => {
+            write!(f, "Root Complex Integrated Endpoint")?;
+            (None, None, None, None)
+        }
+        DeviceType::RootComplexEventCollector { root } => {
+            write!(f, "Root Complex Event Collector")?;
+            (None, None, None, Some(root))
+        }
+        DeviceType::RootPort {
+            link,
+            link_2,
+            slot,
+            root,
+            ..
+        } => {
+            write!(f, "Root Port (Slot{})", Flag(pcie.slot_implemented))?;
+            let slot = Some(slot).filter(|_| pcie.slot_implemented);
+            (Some(link), link_2.as_ref(), slot, Some(root))
+        }
+        DeviceType::UpstreamPort { link, link_2 } => {
+            write!(f, "Upstream Port")?;
+            (Some(link), link_2.as_ref(), None, None)
+        }
+        DeviceType::DownstreamPort {
+            link, link_2, slot, ..
+        } => {
+            write!(f, "Downstream Port (Slot{})", Flag(pcie.slot_implemented))?;
+            let slot = Some(slot).filter(|_| pcie.slot_implemented);
+            (Some(link), link_2.as_ref(), slot, None)
+        }
+        DeviceType::PcieToPciBridge { link, link_2 } => {
+            write!(f, "PCI-Express to PCI/PCI-X Bridge")?;
+            (Some(link), link_2.as_ref(), None, None)
+        }
+        DeviceType::PciToPcieBridge { link, link_2, .. } => {
+            write!(f, "PCI/PCI-X to PCI-Express Bridge")?;
+            (Some(link), link_2.as_ref(), None, None)
+        }
+        DeviceType::Reserved { id, link, .. } => {
+            write!(f, "Unknown type {}", id)?;
+            (Some(link), None, None, None)
+        }
+    };
+    writeln!(f, ", MSI {:02x}", pcie.interrupt_message_number)?;
+    express_device(f, dt, &pcie.device)?;
+    if let Some(link) = link {
+        express_link(f, dt, link)?;
+    }
+    if let Some(slot) = slot {
+        express_slot(f, slot)?;
+    }
+    if let Some(root) = root {
+        express_root(f, root)?;
+    }
+    if let Some(device_2) = &pcie.device_2 {
+        express_device_2(f, dt, device_2)?;
+    }
+    if let Some(link_2) = link_2 {
+        express_link_2(f, dt, link_2)?;
+    }
+    Ok(())
+}
+
+fn power_limit(limit: &SlotPowerLimit) -> f32 {
+    if limit.scale == 1.0 {
+        match limit.value {
+            0xf0 => return 250.0,
+            0xf1 => return 275.0,
+            0xf2 => return 300.0,
+            _ => (),
+        }
+    }
+    limit.into()
+}
+
+fn express_device(f: &mut Formatter<'_>, dt: &DeviceType, device: &Device) -> fmt::Result {
+    let is_endpoint = matches!(
+        dt,
+        DeviceType::Endpoint { .. } | DeviceType::LegacyEndpoint { .. }
+    );
+    let cap = &device.capabilities;
+    let phantom_functions = match cap.phantom_functions_supported {
+        PhantomFunctionsSupported::NoBits => 0,
+        PhantomFunctionsSupported::MostSignificantBit => 1,
+        PhantomFunctionsSupported::TwoMostSignificantBits => 3,
+        PhantomFunctionsSupported::AllBits => 7,
+    };
+    write!(
+        f,
+        "\t\tDevCap:\tMaxPayload {} bytes, PhantFunc {}",
+        128u32 << u8::from(cap.max_payload_size_supported),
+        phantom_functions
+    )?;
+    if is_endpoint {
+        let l0s = match cap.endpoint_l0s_acceptable_latency {
+            EndpointL0sAcceptableLatency::Max64ns => "<64ns",
+            EndpointL0sAcceptableLatency::Max128ns => "<128ns",
+            EndpointL0sAcceptableLatency::Max256ns => "<256ns",
+            EndpointL0sAcceptableLatency::Max512ns => "<512ns",
+            EndpointL0sAcceptableLatency::Max1us => "<1us",
+            EndpointL0sAcceptableLatency::Max2us => "<2us",
+            EndpointL0sAcceptableLatency::Max4us => "<4us",
+            EndpointL0sAcceptableLatency::NoLimit => "unlimited",
+        };
+        let l1 = match cap.endpoint_l1_acceptable_latency {
+            EndpointL1AcceptableLatency::Max1us => "<1us",
+            EndpointL1AcceptableLatency::Max2us => "<2us",
+            EndpointL1AcceptableLatency::Max4us => "<4us",
+            EndpointL1AcceptableLatency::Max8us => "<8us",
+            EndpointL1AcceptableLatency::Max16us => "<16us",
+            EndpointL1AcceptableLatency::Max32us => "<32us",
+            EndpointL1AcceptableLatency::Max64us => "<64us",
+            EndpointL1AcceptableLatency::NoLimit => "unlimited",
+        };
+        write!(f, ", Latency L0s {}, L1 {}", l0s, l1)?;
+    }
+    writeln!(f)?;
+    write!(
+        f,
+        "\t\t\tExtTag{}",
+        Flag(matches!(
+            cap.extended_tag_field_supported,
+            crate::capabilities::pci_express::ExtendedTagFieldSupported::Eight
+        ))
+    )?;
+    if is_endpoint
+        || matches!(
+            dt,
+            DeviceType::UpstreamPort { .. } | DeviceType::PcieToPciBridge { .. }
+        )
+    {
+        write!(
+            f,
+            " AttnBtn{} AttnInd{} PwrInd{}",
+            Flag(cap.attention_button_present),
+            Flag(cap.attention_indicator_present),
+            Flag(cap.power_indicator_present)
+        )?;
+    }
+    write!(f, " RBE{}", Flag(cap.role_based_error_reporting))?;
+    if dt.is_endpoint() {
+        write!(f, " FLReset{}", Flag(cap.function_level_reset_capability))?;
+    }
+    if matches!(
+        dt,
+        DeviceType::Endpoint { .. }
+            | DeviceType::UpstreamPort { .. }
+            | DeviceType::PcieToPciBridge { .. }
+    ) {
+        write!(
+            f,
+            " SlotPowerLimit {:.3}W",
+            power_limit(&cap.captured_slot_power_limit)
+        )?;
+    }
+    writeln!(f)?;
+
+    let ctl = &device.control;
+    writeln!(
+        f,
+        "\t\tDevCtl:\tCorrErr{} NonFatalErr{} FatalErr{} UnsupReq{}",
+        Flag(ctl.correctable_error_reporting_enable),
+        Flag(ctl.non_fatal_error_reporting_enable),
+        Flag(ctl.fatal_error_reporting_enable),
+        Flag(ctl.unsupported_request_reporting_enable),
+    )?;
+    write!(
+        f,
+        "\t\t\tRlxdOrd{} ExtTag{} PhantFunc{} AuxPwr{} NoSnoop{}",
+        Flag(ctl.enable_relaxed_ordering),
+        Flag(ctl.extended_tag_field_enable),
+        Flag(ctl.phantom_functions_enable),
+        Flag(ctl.aux_power_pm_enable),
+        Flag(ctl.enable_no_snoop),
+    )?;
+    if matches!(dt, DeviceType::PcieToPciBridge { .. }) {
+        write!(f, " BrConfRtry{}", Flag(ctl.bcre_or_flreset))?;
+    }
+    if dt.is_endpoint() && cap.function_level_reset_capability {
+        write!(f, " FLReset{}", Flag(ctl.bcre_or_flreset))?;
+    }
+    writeln!(f)?;
+    writeln!(
+        f,
+        "\t\t\tMaxPayload {} bytes, MaxReadReq {} bytes",
+        128u32 << u8::from(ctl.max_payload_size),
+        128u32 << u8::from(ctl.max_read_request_size),
+    )?;
+
+    let sta = &device.status;
+    writeln!(
+        f,
+        "\t\tDevSta:\tCorrErr{} NonFatalErr{} FatalErr{} UnsupReq{} AuxPwr{} TransPend{}",
+        Flag(sta.correctable_error_detected),
+        Flag(sta.non_fatal_error_detected),
+        Flag(sta.fatal_error_detected),
+        Flag(sta.unsupported_request_detected),
+        Flag(sta.aux_power_detected),
+        Flag(sta.transactions_pending),
+    )
+}
+
+fn link_speed(speed: u8) -> &'static str {
+    match speed {
+        1 => "2.5GT/s",
+        2 => "5GT/s",
+        3 => "8GT/s",
+        4 => "16GT/s",
+        5 => "32GT/s",
+        6 => "64GT/s",
+        _ => "unknown",
+    }
+}
+
+fn link_compare(dt: &DeviceType, sta: u8, cap: u8) -> &'static str {
+    if dt.is_downstream_port() {
+        ""
+    } else if sta < cap {
+        " (downgraded)"
+    } else if sta > cap {
+        " (strange)"
+    } else {
+        " (ok)"
+    }
+}
+
+fn express_link(f: &mut Formatter<'_>, dt: &DeviceType, link: &Link) -> fmt::Result {
+    let cap = &link.capabilities;
+    let aspm = u8::from(cap.active_state_power_management_support.clone());
+    let cap_speed = u8::from(cap.max_link_speed);
+    let cap_width = u8::from(cap.maximum_link_width.clone());
+    write!(
+        f,
+        "\t\tLnkCap:\tPort #{}, Speed {}, Width x{}, ASPM {}",
+        cap.port_number,
+        link_speed(cap_speed),
+        cap_width,
+        ["not supported", "L0s", "L1", "L0s L1"][aspm as usize & 0b11],
+    )?;
+    if aspm != 0 {
+        write!(f, ", Exit Latency ")?;
+        if aspm & 0b01 != 0 {
+            let l0s = match cap.l0s_exit_latency {
+                L0sExitLatency::Lt64ns => "<64ns",
+                L0sExitLatency::Ge64nsAndLt128ns => "<128ns",
+                L0sExitLatency::Ge128nsAndLt256ns => "<256ns",
+                L0sExitLatency::Ge256nsAndLt512ns => "<512ns",
+                L0sExitLatency::Ge512nsAndLt1us => "<1us",
+                L0sExitLatency::Ge1usAndLt2us => "<2us",
+                L0sExitLatency::Ge2usAndLt4us => "<4us",
+                L0sExitLatency::Gt4ns => "unlimited",
+            };
+            write!(f, "L0s {}", l0s)?;
+        }
+        if aspm & 0b10 != 0 {
+            let l1 = match cap.l1_exit_latency {
+                L1ExitLatency::Lt1us => "<1us",
+                L1ExitLatency::Ge1usAndLt2us => "<2us",
+                L1ExitLatency::Ge2usAndLt4us => "<4us",
+                L1ExitLatency::Ge4usAndLt8us => "<8us",
+                L1ExitLatency::Ge8usAndLt16us => "<16us",
+                L1ExitLatency::Ge16usAndLt32us => "<32us",
+                L1ExitLatency::Ge32usAndLt64us => "<64us",
+                L1ExitLatency::Gt64ns => "unlimited",
+            };
+            let sep = if aspm & 0b01 != 0 { ", " } else { "" };
+            write!(f, "{}L1 {}", sep, l1)?;
+        }
+    }
+    writeln!(f)?;
+    writeln!(
+        f,
+        "\t\t\tClockPM{} Surprise{} LLActRep{} BwNot{} ASPMOptComp{}",
+        Flag(cap.clock_power_management),
+        Flag(cap.surprise_down_error_reporting_capable),
+        Flag(cap.data_link_layer_link_active_reporting_capable),
+        Flag(cap.link_bandwidth_notification_capability),
+        Flag(cap.aspm_optionality_compliance),
+    )?;
+
+    let ctl = &link.control;
+    let aspm = u8::from(ctl.active_state_power_management_control.clone());
+    write!(
+        f,
+        "\t\tLnkCtl:\tASPM {};",
+        ["Disabled", "L0s Enabled", "L1 Enabled", "L0s L1 Enabled"][aspm as usize & 0b11]
+    )?;
+    if matches!(
+        dt,
+        DeviceType::RootPort { .. }
+            | DeviceType::Endpoint { .. }
+            | DeviceType::LegacyEndpoint { .. }
+            | DeviceType::PcieToPciBridge { .. }
+    ) {
+        let rcb = if bool::from(ctl.read_completion_boundary) {
+            128
+        } else {
+            64
+        };
+        write!(f, " RCB {} bytes", rcb)?;
+    }
+    writeln!(
+        f,
+        " Disabled{} CommClk{}",
+        Flag(ctl.link_disable),
+        Flag(ctl.common_clock_configuration)
+    )?;
+    writeln!(
+        f,
+        "\t\t\tExtSynch{} ClockPM{} AutWidDis{} BWInt{} AutBWInt{}",
+        Flag(ctl.extended_synch),
+        Flag(ctl.enable_clock_power_management),
+        Flag(ctl.hardware_autonomous_width_disable),
+        Flag(ctl.link_bandwidth_management_interrupt_enable),
+        Flag(ctl.link_autonomous_bandwidth_interrupt_enable),
+    )?;
+
+    let sta = &link.status;
+    let sta_speed = u8::from(sta.current_link_speed);
+    let sta_width = u8::from(sta.negotiated_link_width.clone());
+    writeln!(
+        f,
+        "\t\tLnkSta:\tSpeed {}{}, Width x{}{}",
+        link_speed(sta_speed),
+        link_compare(dt, sta_speed, cap_speed),
+        sta_width,
+        link_compare(dt, sta_width, cap_width),
+    )?;
+    writeln!(
+        f,
+        "\t\t\tTrErr{} Train{} SlotClk{} DLActive{} BWMgmt{} ABWMgmt{}",
+        Flag(sta.link_training_error),
+        Flag(sta.link_training),
+        Flag(sta.slot_clock_configuration),
+        Flag(sta.data_link_layer_link_active),
+        Flag(sta.link_bandwidth_management_status),
+        Flag(sta.link_autonomous_bandwidth_status),
+    )
+}
+
+fn indicator(control: u8) -> &'static str {
+    ["Unknown", "On", "Blink", "Off"][control as usize & 0b11]
+}
+
+fn express_slot(f: &mut Formatter<'_>, slot: &Slot) -> fmt::Result {
+    let cap = &slot.capabilities;
+    writeln!(
+        f,
+        "\t\tSltCap:\tAttnBtn{} PwrCtrl{} MRL{} AttnInd{} PwrInd{} HotPlug{} Surprise{}",
+        Flag(cap.attention_button_present),
+        Flag(cap.power_controller_present),
+        Flag(cap.mrl_sensor_present),
+        Flag(cap.attention_indicator_present),
+        Flag(cap.power_indicator_present),
+        Flag(cap.hot_plug_capable),
+        Flag(cap.hot_plug_surprise),
+    )?;
+    writeln!(
+        f,
+        "\t\t\tSlot #{}, PowerLimit {:.3}W; Interlock{} NoCompl{}",
+        cap.physical_slot_number,
+        power_limit(&cap.slot_power_limit),
+        Flag(cap.electromechanical_interlock_present),
+        Flag(cap.no_command_completed_support),
+    )?;
+    let ctl = &slot.control;
+    writeln!(
+        f,
+        "\t\tSltCtl:\tEnable: AttnBtn{} PwrFlt{} MRL{} PresDet{} CmdCplt{} HPIrq{} LinkChg{}",
+        Flag(ctl.attention_button_pressed_enable),
+        Flag(ctl.power_fault_detected_enable),
+        Flag(ctl.mrl_sensor_changed_enable),
+        Flag(ctl.presence_detect_changed_enable),
+        Flag(ctl.command_completed_interrupt_enable),
+        Flag(ctl.hot_plug_interrupt_enable),
+        Flag(ctl.data_link_layer_state_changed_enable),
+    )?;
+    writeln!(
+        f,
+        "\t\t\tControl: AttnInd {}, PwrInd {}, Power{} Interlock{}",
+        indicator(u8::from(ctl.attention_indicator_control.clone())),
+        indicator(u8::from(ctl.power_indicator_control.clone())),
+        Flag(ctl.power_controller_control),
+        Flag(ctl.electromechanical_interlock_control),
+    )?;
+    let sta = &slot.status;
+    writeln!(
+        f,
+        "\t\tSltSta:\tStatus: AttnBtn{} PowerFlt{} MRL{} CmdCplt{} PresDet{} Interlock{}",
+        Flag(sta.attention_button_pressed),
+        Flag(sta.power_fault_detected),
+        Flag(sta.mrl_sensor_state),
+        Flag(sta.command_completed),
+        Flag(sta.presence_detect_state),
+        Flag(sta.electromechanical_interlock_status),
+    )?;
+    writeln!(
+        f,
+        "\t\t\tChanged: MRL{} PresDet{} LinkState{}",
+        Flag(sta.mrl_sensor_changed),
+        Flag(sta.presence_detect_changed),
+        Flag(sta.data_link_layer_state_changed),
+    )
+}
+
+fn express_root(f: &mut Formatter<'_>, root: &Root) -> fmt::Result {
+    let ctl = &root.control;
+    writeln!(
+        f,
+        "\t\tRootCtl: ErrCorrectable{} ErrNon-Fatal{} ErrFatal{} PMEIntEna{} CRSVisible{}",
+        Flag(ctl.system_error_on_correctable_error_enable),
+        Flag(ctl.system_error_on_non_fatal_error_enable),
+        Flag(ctl.system_error_on_fatal_error_enable),
+        Flag(ctl.pme_interrupt_enable),
+        Flag(ctl.crs_software_visibility_enable),
+    )?;
+    writeln!(
+        f,
+        "\t\tRootCap: CRSVisible{}",
+        Flag(root.capabilities.crs_software_visibility)
+    )?;
+    writeln!(
+        f,
+        "\t\tRootSta: PME ReqID {:04x}, PMEStatus{} PMEPending{}",
+        root.status.pme_requester_id,
+        Flag(root.status.pme_status),
+        Flag(root.status.pme_pending),
+    )
+}
+
+fn express_device_2(f: &mut Formatter<'_>, dt: &DeviceType, device_2: &Device2) -> fmt::Result {
+    let is_port = matches!(
+        dt,
+        DeviceType::RootPort { .. } | DeviceType::DownstreamPort { .. }
+    );
+    let cap = &device_2.capabilities;
+    let ranges = match u8::from(cap.completion_timeout_ranges_supported.clone()) {
+        0 => "Not Supported",
+        1 => "Range A",
+        2 => "Range B",
+        3 => "Range AB",
+        6 => "Range BC",
+        7 => "Range ABC",
+        14 => "Range BCD",
+        15 => "Range ABCD",
+        _ => "Unknown",
+    };
+    writeln!(
+        f,
+        "\t\tDevCap2: Completion Timeout: {}, TimeoutDis{}, NROPrPrP{}, LTR{}",
+        ranges,
+        Flag(cap.completion_timeout_disable_supported),
+        Flag(cap.no_ro_enabled_pr_pr_passing),
+        Flag(cap.ltr_mechanism_supported),
+    )?;
+    let obff = [
+        "Not Supported",
+        "Via message",
+        "Via WAKE#",
+        "Via message/WAKE#",
+    ];
+    write!(
+        f,
+        "\t\t\t 10BitTagComp{}, 10BitTagReq{}, OBFF {}, ExtFmt{}, EETLPPrefix{}",
+        Flag(cap.support_10bit_tag_completer),
+        Flag(cap.support_10bit_tag_requester),
+        obff[u8::from(cap.obff_supported.clone()) as usize & 0b11],
+        Flag(cap.extended_fmt_field_supported),
+        Flag(cap.end_end_tlp_prefix_supported),
+    )?;
+    if cap.end_end_tlp_prefix_supported {
+        let max = match u8::from(cap.max_end_end_tlp_prefixes) {
+            0 => 4,
+            v => v,
+        };
+        write!(f, ", MaxEETLPPrefixes {}", max)?;
+    }
+    writeln!(f)?;
+    let epr = [
+        "Not Supported",
+        "Dev Specific",
+        "Form Factor Dev Specific",
+        "Reserved",
+    ];
+    writeln!(
+        f,
+        "\t\t\t EmergencyPowerReduction {}, EmergencyPowerReductionInit{}",
+        epr[u8::from(cap.emergency_power_reduction_supported.clone()) as usize & 0b11],
+        Flag(cap.emergency_power_reduction_initialization_required),
+    )?;
+    let tph = u8::from(cap.tph_completer_supported.clone());
+    write!(
+        f,
+        "\t\t\t FRS{}, TPHComp{}, ExtTPHComp{}",
+        Flag(cap.frs_supported),
+        Flag(tph & 0b01 != 0),
+        Flag(tph & 0b10 != 0),
+    )?;
+    if is_port {
+        write!(f, ", ARIFwd{}", Flag(cap.ari_forwarding_supported))?;
+    }
+    writeln!(f)?;
+    write!(f, "\t\t\t AtomicOpsCap:")?;
+    if is_port {
+        write!(f, " Routing{}", Flag(cap.atomic_op_routing_supported))?;
+    }
+    if matches!(dt, DeviceType::RootPort { .. }) || dt.is_endpoint() {
+        write!(
+            f,
+            " 32bit{} 64bit{} 128bitCAS{}",
+            Flag(cap.u32_atomicop_completer_supported),
+            Flag(cap.u64_atomicop_completer_supported),
+            Flag(cap.u128_cas_completer_supported),
+        )?;
+    }
+    writeln!(f)?;
+
+    let ctl = &device_2.control;
+    let timeout = match u8::from(ctl.completion_timeout_value.clone()) {
+        0 => "50us to 50ms",
+        1 => "50us to 100us",
+        2 => "1ms to 10ms",
+        5 => "16ms to 55ms",
+        6 => "65ms to 210ms",
+        9 => "260ms to 900ms",
+        10 => "1s to 3.5s",
+        13 => "4s to 13s",
+        14 => "17s to 64s",
+        _ => "Unknown",
+    };
+    let obff = ["Disabled", "Via message A", "Via message B", "Via WAKE#"];
+    write!(
+        f,
+        "\t\tDevCtl2: Completion Timeout: {}, TimeoutDis{}, LTR{}, OBFF {}",
+        timeout,
+        Flag(ctl.completion_timeout_disable),
+        Flag(ctl.ltr_mechanism_enable),
+        obff[u8::from(ctl.obff_enable.clone()) as usize & 0b11],
+    )?;
+    if is_port {
+        write!(f, ", ARIFwd{}", Flag(ctl.ari_forwarding_enable))?;
+    }
+    writeln!(f)?;
+    let is_requester = matches!(dt, DeviceType::RootPort { .. }) || dt.is_endpoint();
+    let is_egress = matches!(
+        dt,
+        DeviceType::RootPort { .. } | DeviceType::UpstreamPort { .. }
+    ) || matches!(dt, DeviceType::DownstreamPort { .. });
+    if is_requester || is_egress {
+        write!(f, "\t\t\t AtomicOpsCtl:")?;
+        if is_requester {
+            write!(f, " ReqEn{}", Flag(ctl.atomic_op_requester_enable))?;
+        }
+        if is_egress {
+            write!(f, " EgressBlck{}", Flag(ctl.atomic_op_egress_blocking))?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+fn express_link_2(f: &mut Formatter<'_>, dt: &DeviceType, link_2: &Link2) -> fmt::Result {
+    let de_emphasis = |minus_3_5db: bool| if minus_3_5db { "-3.5dB" } else { "-6dB" };
+    let ctl = &link_2.control;
+    let target_speed = match u8::from(ctl.target_link_speed) {
+        0 => "Unknown",
+        v => link_speed(v),
+    };
+    write!(
+        f,
+        "\t\tLnkCtl2: Target Link Speed: {}, EnterCompliance{} SpeedDis{}",
+        target_speed,
+        Flag(ctl.enter_compliance),
+        Flag(ctl.hardware_autonomous_speed_disable),
+    )?;
+    if matches!(dt, DeviceType::DownstreamPort { .. }) {
+        write!(
+            f,
+            ", Selectable De-emphasis: {}",
+            de_emphasis(bool::from(ctl.selectable_de_emphasis.clone()))
+        )?;
+    }
+    writeln!(f)?;
+    let margin = match ctl.transmit_margin.0 {
+        0 => "Normal Operating Range",
+        1 => "800-1200mV(full-swing)/400-700mV(half-swing)",
+        2..=6 => "200-400mV(full-swing)/100-200mV(half-swing)",
+        _ => "Unknown",
+    };
+    writeln!(
+        f,
+        "\t\t\t Transmit Margin: {}, EnterModifiedCompliance{} ComplianceSOS{}",
+        margin,
+        Flag(ctl.enter_modified_compliance),
+        Flag(ctl.compliance_sos),
+    )?;
+    writeln!(
+        f,
+        "\t\t\t Compliance De-emphasis: {}",
+        de_emphasis(ctl.compliance_preset_or_de_emphasis.0 & 1 != 0)
+    )?;
+    let sta = &link_2.status;
+    writeln!(
+        f,
+        "\t\tLnkSta2: Current De-emphasis Level: {}, EqualizationComplete{}, \
+         EqualizationPhase1{}",
+        de_emphasis(bool::from(sta.current_de_emphasis_level.clone())),
+        Flag(sta.equalization_complete),
+        Flag(sta.equalization_phase_1_successful),
+    )?;
+    writeln!(
+        f,
+        "\t\t\t EqualizationPhase2{}, EqualizationPhase3{}, LinkEqualizationRequest{}",
+        Flag(sta.equalization_phase_2_successful),
+        Flag(sta.equalization_phase_3_successful),
+        Flag(sta.link_equalization_request),
+    )
+}
+
+fn extended_capability(f: &mut Formatter<'_>, ecap: &ExtendedCapability<'_>) -> fmt::Result {
+    write!(
+        f,
+        "\tCapabilities: [{:03x} v{}] ",
+        ecap.offset, ecap.version
+    )?;
+    match &ecap.kind {
+        ExtendedCapabilityKind::Null => writeln!(f, "Null"),
+        ExtendedCapabilityKind::AdvancedErrorReporting(aer) => advanced_error_reporting(f, aer),
+        ExtendedCapabilityKind::VirtualChannel(_)
+        | ExtendedCapabilityKind::VirtualChannelMfvcPresent(_) => writeln!(f, "Virtual Channel"),
+        ExtendedCapabilityKind::DeviceSerialNumber(dsn) => {
+            let [b0, b1, b2, b3] = dsn.lower_dword.to_le_bytes();
+            let [b4, b5, b6, b7] = dsn.upper_dword.to_le_bytes();
+            writeln!(
+                f,
+                "Device Serial Number {:02x}-{:02x}-{:02x}-{:02x}-{:02x}-{:02x}-{:02x}-{:02x}",
+                b7, b6, b5, b4, b3, b2, b1, b0
+            )
+        }
+        ExtendedCapabilityKind::PowerBudgeting(_) => writeln!(f, "Power Budgeting <?>"),
+        ExtendedCapabilityKind::RootComplexLinkDeclaration(_) => {
+            writeln!(f, "Root Complex Link")
+        }
+        ExtendedCapabilityKind::RootComplexInternalLinkControl(_) => {
+            writeln!(f, "Root Complex Internal Link <?>")
+        }
+        ExtendedCapabilityKind::RootComplexEventCollectorEndpointAssociation(_) => {
+            writeln!(f, "Root Complex Event Collector <?>")
+        }
+        ExtendedCapabilityKind::MultifunctionVirtualChannel(_) => {
+            writeln!(f, "Multi-Function Virtual Channel <?>")
+        }
+        ExtendedCapabilityKind::RootComplexRegisterBlockHeader(_) => {
+            writeln!(f, "Root Complex Register Block <?>")
+        }
+        ExtendedCapabilityKind::VendorSpecificExtendedCapability(vsec) => writeln!(
+            f,
+            "Vendor Specific Information: ID={:04x} Rev={} Len={:03x} <?>",
+            vsec.header.vsec_id, vsec.header.vsec_rev, vsec.header.vsec_length
+        ),
+        ExtendedCapabilityKind::ConfigurationAccessCorrelation(_) => {
+            writeln!(f, "Configuration Access Correlation <?>")
+        }
+        ExtendedCapabilityKind::AccessControlServices(acs) => access_control_services(f, acs),
+        ExtendedCapabilityKind::AlternativeRoutingIdInterpretation(_) => {
+            writeln!(f, "Alternative Routing-ID Interpretation (ARI)")
+        }
+        ExtendedCapabilityKind::AddressTranslationServices(_) => {
+            writeln!(f, "Address Translation Service (ATS)")
+        }
+        ExtendedCapabilityKind::SingleRootIoVirtualization(sriov) => {
+            single_root_io_virtualization(f, sriov)
+        }
+        ExtendedCapabilityKind::MultiRootIoVirtualization(_) => {
+            writeln!(f, "Multi-Root I/O Virtualization <?>")
+        }
+        ExtendedCapabilityKind::Multicast(_) => writeln!(f, "Multicast"),
+        ExtendedCapabilityKind::PageRequestInterface(_) => {
+            writeln!(f, "Page Request Interface (PRI)")
+        }
+        ExtendedCapabilityKind::ReservedForAmd(_) => writeln!(f, "Reserved for AMD <?>"),
+        ExtendedCapabilityKind::ResizableBar(rebar) => resizable_bar(f, rebar),
+        ExtendedCapabilityKind::DynamicPowerAllocation(_) => {
+            writeln!(f, "Dynamic Power Allocation <?>")
+        }
+        ExtendedCapabilityKind::TphRequester(_) => writeln!(f, "Transaction Processing Hints"),
+        ExtendedCapabilityKind::LatencyToleranceReporting(ltr) => {
+            latency_tolerance_reporting(f, ltr)
+        }
+        ExtendedCapabilityKind::SecondaryPciExpress(sec) => secondary_pci_express(f, sec),
+        ExtendedCapabilityKind::ProtocolMultiplexing(_) => {
+            writeln!(f, "Protocol Multiplexing <?>")
+        }
+        ExtendedCapabilityKind::ProcessAddressSpaceId(_) => {
+            writeln!(f, "Process Address Space ID (PASID)")
+        }
+        ExtendedCapabilityKind::LnRequester(_) => writeln!(f, "LN Requester <?>"),
+        ExtendedCapabilityKind::DownstreamPortContainment(dpc) => {
+            downstream_port_containment(f, dpc)
+        }
+        ExtendedCapabilityKind::L1PmSubstates(l1pm) => l1_pm_substates(f, l1pm),
+        ExtendedCapabilityKind::PrecisionTimeMeasurement(ptm) => precision_time_measurement(f, ptm),
+        ExtendedCapabilityKind::PciExpressOverMphy(_) => writeln!(f, "PCI Express over M_PHY <?>"),
+        ExtendedCapabilityKind::FrsQueuing(_) => writeln!(f, "FRS Queueing <?>"),
+        ExtendedCapabilityKind::ReadinessTimeReporting(_) => {
+            writeln!(f, "Readiness Time Reporting <?>")
+        }
+        ExtendedCapabilityKind::DesignatedVendorSpecificExtendedCapability(dvsec) => writeln!(
+            f,
+            "Designated Vendor-Specific: Vendor={:04x} ID={:04x} Rev={} Len={}",
+            dvsec.dvsec_vendor_id, dvsec.dvsec_id, dvsec.dvsec_revision, dvsec.dvsec_length
+        ),
+        ExtendedCapabilityKind::VfResizableBar(rebar) => vf_resizable_bar(f, rebar),
+        ExtendedCapabilityKind::DataLinkFeature(_) => writeln!(f, "Data Link Feature <?>"),
+        ExtendedCapabilityKind::PhysicalLayer16GTps(phy) => physical_layer_16_gtps(f, phy),
+        ExtendedCapabilityKind::LaneMarginingAtTheReceiver(lmr) => {
+            lane_margining_at_the_receiver(f, lmr)
+        }
+        ExtendedCapabilityKind::HierarchyId(_) => writeln!(f, "Hierarchy ID <?>"),
+        ExtendedCapabilityKind::NativePcieEnclosureManagement(_) => {
+            writeln!(f, "Native PCIe Enclosure Management <?>")
+        }
+        ExtendedCapabilityKind::PhysicalLayer32GTps(phy) => physical_layer_32_gtps(f, phy),
+        ExtendedCapabilityKind::AlternateProtocol(_) => writeln!(f, "Alternate Protocol <?>"),
+        ExtendedCapabilityKind::SystemFirmwareIntermediary(_) => {
+            writeln!(f, "System Firmware Intermediary <?>")
+        }
+        ExtendedCapabilityKind::ShadowFunctions(_) => writeln!(f, "Shadow Functions <?>"),
+        ExtendedCapabilityKind::DataObjectExchange(doe) => data_object_exchange(f, doe),
+        ExtendedCapabilityKind::Device3(_) => writeln!(f, "Device 3 <?>"),
+        ExtendedCapabilityKind::IntegrityAndDataEncryption(_) => {
+            writeln!(f, "Integrity & Data Encryption <?>")
+        }
+        ExtendedCapabilityKind::PhysicalLayer64GTps(phy) => physical_layer_64_gtps(f, phy),
+        ExtendedCapabilityKind::FlitLogging(_) => writeln!(f, "Flit Logging <?>"),
+        ExtendedCapabilityKind::FlitPerformanceMeasurement(_) => {
+            writeln!(f, "Flit Performance Measurement <?>")
+        }
+        ExtendedCapabilityKind::FlitErrorInjection(_) => writeln!(f, "Flit Error Injection <?>"),
+        ExtendedCapabilityKind::Reserved(_) => {
+            writeln!(f, "Extended Capability ID {:#x}", ecap.id())
+        }
+    }
+}
+
+fn uncorrectable_errors(f: &mut Formatter<'_>, name: &str, ue: &UncorrectableError) -> fmt::Result {
+    writeln!(
+        f,
+        "\t\t{}DLP{} SDES{} TLP{} FCP{} CmpltTO{} CmpltAbrt{} UnxCmplt{} RxOF{} MalfTLP{} \
+         ECRC{} UnsupReq{} ACSViol{}",
+        name,
+        Flag(ue.data_link_protocol_error_status),
+        Flag(ue.surprise_down_error_status),
+        Flag(ue.poisoned_tlp_received_status),
+        Flag(ue.flow_control_protocol_error_status),
+        Flag(ue.completion_timeout_status),
+        Flag(ue.completer_abort_status),
+        Flag(ue.unexpected_completion_status),
+        Flag(ue.receiver_overflow_status),
+        Flag(ue.malformed_tlp_status),
+        Flag(ue.ecrc_error_status),
+        Flag(ue.unsupported_request_error_status),
+        Flag(ue.acs_violation_status),
+    )
+}
+
+fn correctable_errors(f: &mut Formatter<'_>, name: &str, ce: &CorrectableError) -> fmt::Result {
+    writeln!(
+        f,
+        "\t\t{}RxErr{} BadTLP{} BadDLLP{} Rollover{} Timeout{} NonFatalErr{}",
+        name,
+        Flag(ce.receiver_error_status),
+        Flag(ce.bad_tlp_status),
+        Flag(ce.bad_dllp_status),
+        Flag(ce.replay_num_rollover_status),
+        Flag(ce.replay_timer_timeout_status),
+        Flag(ce.advisory_non_fatal_error_status),
+    )
+}
+
+fn advanced_error_reporting(f: &mut Formatter<'_>, aer: &AdvancedErrorReporting) -> fmt::Result {
+    writeln!(f, "Advanced Error Reporting")?;
+    uncorrectable_errors(f, "UESta:\t", &aer.uncorrectable_error_status.0)?;
+    uncorrectable_errors(f, "UEMsk:\t", &aer.uncorrectable_error_mask)?;
+    uncorrectable_errors(f, "UESvrt:\t", &aer.uncorrectable_error_severity)?;
+    correctable_errors(f, "CESta:\t", &aer.correctable_error_status.0)?;
+    correctable_errors(f, "CEMsk:\t", &aer.correctable_error_mask)?;
+    let cap = &aer.advanced_error_capabilities_and_control;
+    writeln!(
+        f,
+        "\t\tAERCap:\tFirst Error Pointer: {:02x}, GenCap{} CGenEn{} ChkCap{} ChkEn{}",
+        cap.first_error_pointer,
+        Flag(cap.ecrc_generation_capable),
+        Flag(cap.ecrc_generation_enable),
+        Flag(cap.ecrc_check_capable),
+        Flag(cap.ecrc_check_enable),
+    )
+}
+
+fn access_control_services(f: &mut Formatter<'_>, acs: &AccessControlServices) -> fmt::Result {
+    writeln!(f, "Access Control Services")?;
+    let cap = &acs.acs_capability;
+    writeln!(
+        f,
+        "\t\tACSCap:\tSrcValid{} TransBlk{} ReqRedir{} CmpltRedir{} UpstreamFwd{} EgressCtrl{} \
+         DirectTrans{}",
+        Flag(cap.acs_source_validation),
+        Flag(cap.acs_translation_blocking),
+        Flag(cap.acs_p2p_request_redirect),
+        Flag(cap.acs_p2p_completion_redirect),
+        Flag(cap.acs_upstream_forwarding),
+        Flag(cap.acs_p2p_egress_control),
+        Flag(cap.acs_direct_translated_p2p),
+    )?;
+    let ctl = &acs.acs_control;
+    writeln!(
+        f,
+        "\t\tACSCtl:\tSrcValid{} TransBlk{} ReqRedir{} CmpltRedir{} UpstreamFwd{} EgressCtrl{} \
+         DirectTrans{}",
+        Flag(ctl.acs_source_validation_enable),
+        Flag(ctl.acs_translation_blocking_enable),
+        Flag(ctl.acs_p2p_request_redirect_enable),
+        Flag(ctl.acs_p2p_completion_redirect_enable),
+        Flag(ctl.acs_upstream_forwarding_enable),
+        Flag(ctl.acs_p2p_egress_control_enable),
+        Flag(ctl.acs_direct_translated_p2p_enable),
+    )
+}
+
+fn single_root_io_virtualization(
+    f: &mut Formatter<'_>,
+    sriov: &SingleRootIoVirtualization,
+) -> fmt::Result {
+    writeln!(f, "Single Root I/O Virtualization (SR-IOV)")?;
+    let cap = &sriov.sriov_capabilities;
+    writeln!(
+        f,
+        "\t\tIOVCap:\tMigration{}, Interrupt Message Number: {:03x}",
+        Flag(cap.vf_migration_capable),
+        cap.vf_migration_interrupt_message_number,
+    )?;
+    let ctl = &sriov.sriov_control;
+    writeln!(
+        f,
+        "\t\tIOVCtl:\tEnable{} Migration{} Interrupt{} MSE{} ARIHierarchy{}",
+        Flag(ctl.vf_enable),
+        Flag(ctl.vf_migration_enable),
+        Flag(ctl.vf_migration_interrupt_enable),
+        Flag(ctl.vf_mse),
+        Flag(ctl.ari_capable_hierarchy),
+    )?;
+    writeln!(
+        f,
+        "\t\tIOVSta:\tMigration{}",
+        Flag(sriov.sriov_status.vf_migration_status)
+    )?;
+    writeln!(
+        f,
+        "\t\tInitial VFs: {}, Total VFs: {}, Number of VFs: {}, Function Dependency Link: {:02x}",
+        sriov.initial_vfs, sriov.total_vfs, sriov.num_vfs, sriov.function_dependency_link,
+    )?;
+    writeln!(
+        f,
+        "\t\tVF offset: {}, stride: {}, Device ID: {:04x}",
+        sriov.first_vf_offset, sriov.vf_stride, sriov.vf_device_id,
+    )?;
+    writeln!(
+        f,
+        "\t\tSupported Page Size: {:08x}, System Page Size: {:08x}",
+        sriov.page_sizes.supported, sriov.page_sizes.system,
+    )?;
+    // VF BARs are memory only, lspci masks type bits of any other encoding the same way
+    for BaseAddress {
+        region,
+        base_address_type,
+    } in sriov.base_addresses.clone()
+    {
+        write!(f, "\t\tRegion {}: Memory at ", region)?;
+        let (base_address, kind, prefetchable) = match base_address_type {
+            BaseAddressType::MemorySpace64 {
+                prefetchable,
+                base_address,
+            } => (Some(base_address), 64, prefetchable),
+            BaseAddressType::MemorySpace32 {
+                prefetchable,
+                base_address,
+            } => (Some(base_address as u64), 32, prefetchable),
+            BaseAddressType::MemorySpaceBelow1M {
+                prefetchable,
+                base_address,
+            }
+            | BaseAddressType::MemorySpaceReserved {
+                prefetchable,
+                base_address,
+            } => (Some(base_address as u64), 64, prefetchable),
+            BaseAddressType::IoSpace { base_address } => {
+                (Some(base_address as u64 & !0xf), 32, false)
+            }
+            BaseAddressType::MemorySpace64Broken { prefetchable } => (None, 64, prefetchable),
+        };
+        match base_address {
+            Some(base_address) => write!(f, "{:016x}", base_address)?,
+            None => write!(f, "<invalid-64bit-slot>")?,
+        }
+        writeln!(
+            f,
+            " ({}-bit, {}prefetchable)",
+            kind,
+            if prefetchable { "" } else { "non-" }
+        )?;
+    }
+    let msao = sriov.vf_migration_state_array_offset;
+    writeln!(
+        f,
+        "\t\tVF Migration: offset: {:08x}, BIR: {:x}",
+        msao & !0b111,
+        msao & 0b111
+    )
+}
+
+/// BAR sizes as `BAR n: current size: .., supported: ..` line
+fn resizable_bar_entry(
+    f: &mut Formatter<'_>,
+    bar_index: u8,
+    bar_size: u8,
+    is_supported: impl Fn(usize) -> bool,
+) -> fmt::Result {
+    write!(f, "\t\tBAR {}: current size: ", bar_index)?;
+    match ResizableBarEntry::BAR_SIZES.get(bar_size as usize) {
+        Some(size) => write!(f, "{}", size)?,
+        None => write!(f, "<unknown>")?,
+    }
+    write!(f, ", supported:")?;
+    for (power, size) in (20..).zip(ResizableBarEntry::BAR_SIZES) {
+        if is_supported(power) {
+            write!(f, " {}", size)?;
+        }
+    }
+    writeln!(f)
+}
+
+fn resizable_bar(f: &mut Formatter<'_>, rebar: &ResizableBar) -> fmt::Result {
+    writeln!(f, "Physical Resizable BAR")?;
+    for entry in rebar.clone() {
+        let ctl = &entry.control;
+        resizable_bar_entry(f, ctl.bar_index, ctl.bar_size, |power| {
+            entry.is_function_supports_power_of_two(power)
+        })?;
+    }
+    Ok(())
+}
+
+fn vf_resizable_bar(f: &mut Formatter<'_>, rebar: &VfResizableBar) -> fmt::Result {
+    writeln!(f, "Virtual Resizable BAR")?;
+    for entry in rebar.clone() {
+        let ctl = &entry.control;
+        resizable_bar_entry(f, ctl.vf_bar_index, ctl.vf_bar_size, |power| {
+            entry.is_function_supports_power_of_two(power)
+        })?;
+    }
+    Ok(())
+}
+
+/// Latency in ns, scales above 2²⁵ are not limited unlike [MaxLatency::value]
+fn max_latency(latency: &MaxLatency) -> u64 {
+    (latency.value as u64) << (5 * latency.scale as u64)
+}
+
+fn latency_tolerance_reporting(
+    f: &mut Formatter<'_>,
+    ltr: &LatencyToleranceReporting,
+) -> fmt::Result {
+    writeln!(f, "Latency Tolerance Reporting")?;
+    writeln!(
+        f,
+        "\t\tMax snoop latency: {}ns",
+        max_latency(&ltr.max_snoop_latency)
+    )?;
+    writeln!(
+        f,
+        "\t\tMax no snoop latency: {}ns",
+        max_latency(&ltr.max_no_snoop_latency)
+    )
+}
+
+fn secondary_pci_express(f: &mut Formatter<'_>, sec: &SecondaryPciExpress) -> fmt::Result {
+    writeln!(f, "Secondary PCI Express")?;
+    let ctl = &sec.link_control_3;
+    writeln!(
+        f,
+        "\t\tLnkCtl3: LnkEquIntrruptEn{} PerformEqu{}",
+        Flag(ctl.link_equalization_request_interrupt_enable),
+        Flag(ctl.perform_equalization),
+    )?;
+    write!(f, "\t\tLaneErrStat: ")?;
+    let LaneErrorStatus(status) = sec.lane_error_status;
+    if status == 0 {
+        write!(f, "0")?;
+    } else {
+        write!(f, "LaneErr at lane:")?;
+        for lane in (0..32).filter(|lane| status & (1 << lane) != 0) {
+            write!(f, " {}", lane)?;
+        }
+    }
+    writeln!(f)
+}
+
+fn downstream_port_containment(
+    f: &mut Formatter<'_>,
+    dpc: &DownstreamPortContainment,
+) -> fmt::Result {
+    writeln!(f, "Downstream Port Containment")?;
+    let cap = &dpc.dpc_capability;
+    writeln!(
+        f,
+        "\t\tDpcCap:\tINT Msg #{}, RPExt{} PoisonedTLP{} SwTrigger{} RP PIO Log {}, \
+         DL_ActiveErr{}",
+        cap.dpc_interrupt_message_number,
+        Flag(cap.rp_extensions_for_dpc),
+        Flag(cap.poisoned_tlp_egress_blocking_supported),
+        Flag(cap.dpc_software_triggering_supported),
+        cap.rp_pio_log_size,
+        Flag(cap.dl_active_err_cor_signaling_supported),
+    )?;
+    let ctl = &dpc.dpc_control;
+    writeln!(
+        f,
+        "\t\tDpcCtl:\tTrigger:{:x} Cmpl{} INT{} ErrCor{} PoisonedTLP{} SwTrigger{} \
+         DL_ActiveErr{}",
+        u8::from(ctl.dpc_trigger_enable),
+        Flag(ctl.dpc_completion_control),
+        Flag(ctl.dpc_interrupt_enable),
+        Flag(ctl.dpc_err_cor_enable),
+        Flag(ctl.poisoned_tlp_egress_blocking_enable),
+        Flag(ctl.dpc_software_trigger),
+        Flag(ctl.dl_active_err_cor_enable),
+    )?;
+    let sta = &dpc.dpc_status;
+    writeln!(
+        f,
+        "\t\tDpcSta:\tTrigger{} Reason:{:02x} INT{} RPBusy{} TriggerExt:{:02x} RP PIO ErrPtr:{:02x}",
+        Flag(sta.dpc_trigger_status),
+        sta.dpc_trigger_reason.value(),
+        Flag(sta.dpc_interrupt_status),
+        Flag(sta.dpc_rp_busy),
+        sta.dpc_trigger_reason.extension_value(),
+        sta.rp_pio_first_error_pointer,
+    )?;
+    writeln!(f, "\t\tSource:\t{:04x}", dpc.dpc_error_source_id)
+}
+
+/// T_POWER_ON in µs or `<error>` for reserved scale
+struct TPowerOn<'a>(&'a PortTPowerOn);
+
+impl<'a> Display for TPowerOn<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0.value() {
+            Some(value) => write!(f, "{}us", value),
+            None => write!(f, "<error>"),
+        }
+    }
+}
+
+fn l1_pm_substates(f: &mut Formatter<'_>, l1pm: &L1PmSubstates) -> fmt::Result {
+    writeln!(f, "L1 PM Substates")?;
+    let cap = &l1pm.l1_pm_substates_capabilities;
+    writeln!(
+        f,
+        "\t\tL1SubCap: PCI-PM_L1.2{} PCI-PM_L1.1{} ASPM_L1.2{} ASPM_L1.1{} L1_PM_Substates{}",
+        Flag(cap.pci_pm_l1_2_supported),
+        Flag(cap.pci_pm_l1_1_supported),
+        Flag(cap.aspm_l1_2_supported),
+        Flag(cap.aspm_l1_1_supported),
+        Flag(cap.l1_pm_substates_supported),
+    )?;
+    let l1_2_supported = cap.pci_pm_l1_2_supported || cap.aspm_l1_2_supported;
+    if l1_2_supported {
+        writeln!(
+            f,
+            "\t\t\t  PortCommonModeRestoreTime={}us PortTPowerOnTime={}",
+            cap.port_common_mode_restore_time,
+            TPowerOn(&cap.port_t_power_on),
+        )?;
+    }
+    let ctl1 = &l1pm.l1_pm_substates_control_1;
+    writeln!(
+        f,
+        "\t\tL1SubCtl1: PCI-PM_L1.2{} PCI-PM_L1.1{} ASPM_L1.2{} ASPM_L1.1{}",
+        Flag(ctl1.pci_pm_l1_2_enable),
+        Flag(ctl1.pci_pm_l1_1_enable),
+        Flag(ctl1.aspm_l1_2_enable),
+        Flag(ctl1.aspm_l1_1_enable),
+    )?;
+    if l1_2_supported {
+        write!(
+            f,
+            "\t\t\t   T_CommonMode={}us",
+            ctl1.common_mode_restore_time
+        )?;
+        let threshold = &ctl1.ltr_l1_2_threshold;
+        if threshold.scale > 5 {
+            writeln!(f, " LTR1.2_Threshold=<error>")?;
+        } else {
+            writeln!(f, " LTR1.2_Threshold={}ns", max_latency(threshold))?;
+        }
+    }
+    write!(f, "\t\tL1SubCtl2:")?;
+    if l1_2_supported {
+        write!(
+            f,
+            " T_PwrOn={}",
+            TPowerOn(&l1pm.l1_pm_substates_control_2.t_power_on)
+        )?;
+    }
+    writeln!(f)
+}
+
+fn precision_time_measurement(
+    f: &mut Formatter<'_>,
+    ptm: &PrecisionTimeMeasurement,
+) -> fmt::Result {
+    writeln!(f, "Precision Time Measurement")?;
+    let cap = &ptm.ptm_capability;
+    writeln!(
+        f,
+        "\t\tPTMCap: Requester:{} Responder:{} Root:{}",
+        Flag(cap.ptm_requester_capable),
+        Flag(cap.ptm_responder_capable),
+        Flag(cap.ptm_root_capable),
+    )?;
+    write!(f, "\t\tPTMClockGranularity: ")?;
+    match cap.local_clock_granularity {
+        0x00 => writeln!(f, "Unimplemented")?,
+        0xff => writeln!(f, "Greater than 254ns")?,
+        ns => writeln!(f, "{}ns", ns)?,
+    }
+    let ctl = &ptm.ptm_control;
+    writeln!(
+        f,
+        "\t\tPTMControl: Enabled:{} RootSelected:{}",
+        Flag(ctl.ptm_enable),
+        Flag(ctl.root_select),
+    )?;
+    write!(f, "\t\tPTMEffectiveGranularity: ")?;
+    match ctl.effective_granularity {
+        0x00 => writeln!(f, "Unknown"),
+        0xff => writeln!(f, "Greater than 254ns"),
+        ns => writeln!(f, "{}ns", ns),
+    }
+}
+
+fn physical_layer_16_gtps(f: &mut Formatter<'_>, phy: &PhysicalLayer16GTps) -> fmt::Result {
+    writeln!(f, "Physical Layer 16.0 GT/s")?;
+    let sta = &phy.status;
+    writeln!(
+        f,
+        "\t\tPhy16Sta: EquComplete{} EquPhase1{} EquPhase2{} EquPhase3{} LinkEquRequest{}",
+        Flag(sta.equalization_complete),
+        Flag(sta.equalization_phase_1_successful),
+        Flag(sta.equalization_phase_2_successful),
+        Flag(sta.equalization_phase_3_successful),
+        Flag(sta.link_equalization_request),
+    )
+}
+
+fn lane_margining_at_the_receiver(
+    f: &mut Formatter<'_>,
+    lmr: &LaneMarginingAtTheReceiver,
+) -> fmt::Result {
+    writeln!(f, "Lane Margining at the Receiver")?;
+    writeln!(
+        f,
+        "\t\tPortCap: Uses Driver{}",
+        Flag(lmr.port_capabilities.margining_uses_driver_software)
+    )?;
+    writeln!(
+        f,
+        "\t\tPortSta: MargReady{} MargSoftReady{}",
+        Flag(lmr.port_status.margining_ready),
+        Flag(lmr.port_status.margining_software_ready),
+    )
+}
+
+fn physical_layer_32_gtps(f: &mut Formatter<'_>, phy: &PhysicalLayer32GTps) -> fmt::Result {
+    writeln!(f, "Physical Layer 32.0 GT/s")?;
+    let cap = &phy.capabilities;
+    writeln!(
+        f,
+        "\t\tPhy32Cap: EqualizationBypass{} NoEqualizationNeeded{}",
+        Flag(cap.equalization_bypass_to_highest_rate_supported),
+        Flag(cap.no_equalization_needed_supported),
+    )?;
+    writeln!(
+        f,
+        "\t\t\t  ModTsMode0{} ModTsMode1{} ModTsMode2{}",
+        Flag(cap.modified_ts_usage_mode_0_supported),
+        Flag(cap.modified_ts_usage_mode_1_supported),
+        Flag(cap.modified_ts_usage_mode_2_supported),
+    )?;
+    let ctl = &phy.control;
+    writeln!(
+        f,
+        "\t\tPhy32Ctl: EqualizationBypassDis{} NoEqualizationNeededDis{}",
+        Flag(ctl.equalization_bypass_to_highest_rate_disable),
+        Flag(ctl.no_equalization_needed_disable),
+    )?;
+    writeln!(
+        f,
+        "\t\t\t  ModTsUsageModeSel: {}",
+        ctl.modified_ts_usage_mode_selected
+    )?;
+    let sta = &phy.status;
+    writeln!(
+        f,
+        "\t\tPhy32Sta: EquComplete{} EquPhase1{} EquPhase2{} EquPhase3{} LinkEquRequest{}",
+        Flag(sta.equalization_complete),
+        Flag(sta.equalization_phase_1_successful),
+        Flag(sta.equalization_phase_2_successful),
+        Flag(sta.equalization_phase_3_successful),
+        Flag(sta.link_equalization_request),
+    )?;
+    writeln!(
+        f,
+        "\t\t\t  ModTsRecv{} RxEnhancedLinkBehaviorCtrl: {}",
+        Flag(sta.modified_ts_received),
+        sta.received_enhanced_link_behavior_control,
+    )?;
+    writeln!(
+        f,
+        "\t\t\t  TxPrecodeOn{} TxPrecodeReq{} NoEqualizationNeededRecv{}",
+        Flag(sta.transmitter_precoding_on),
+        Flag(sta.transmitter_precode_request),
+        Flag(sta.no_equalization_needed_received),
+    )
+}
+
+fn data_object_exchange(f: &mut Formatter<'_>, doe: &DataObjectExchange) -> fmt::Result {
+    writeln!(f, "Data Object Exchange")?;
+    let cap = &doe.capabilities;
+    writeln!(f, "\t\tDOECap: IntSup{}", Flag(cap.doe_interrupt_support))?;
+    if cap.doe_interrupt_support {
+        writeln!(f, "\t\t\tIntMsgNum {}", cap.doe_interrupt_message_number)?;
+    }
+    writeln!(
+        f,
+        "\t\tDOECtl: IntEn{}",
+        Flag(doe.control.doe_interrupt_enable)
+    )?;
+    let sta = &doe.status;
+    writeln!(
+        f,
+        "\t\tDOESta: Busy{} IntSta{} Error{} ObjectReady{}",
+        Flag(sta.doe_busy),
+        Flag(sta.doe_interrupt_status),
+        Flag(sta.doe_error),
+        Flag(sta.data_object_ready),
+    )
+}
+
+fn physical_layer_64_gtps(f: &mut Formatter<'_>, phy: &PhysicalLayer64GTps) -> fmt::Result {
+    writeln!(f, "Physical Layer 64.0 GT/s")?;
+    let sta = &phy.status;
+    writeln!(
+        f,
+        "\t\tPhy64Sta: EquComplete{} EquPhase1{} EquPhase2{} EquPhase3{} LinkEquRequest{}",
+        Flag(sta.equalization_complete),
+        Flag(sta.equalization_phase_1_successful),
+        Flag(sta.equalization_phase_2_successful),
+        Flag(sta.equalization_phase_3_successful),
+        Flag(sta.link_equalization_request),
+    )?;
+    writeln!(
+        f,
+        "\t\t\t  TxPrecodeOn{} TxPrecodeReq{} NoEqualizationNeededRecv{}",
+        Flag(sta.transmitter_precoding_on),
+        Flag(sta.transmitter_precode_request),
+        Flag(sta.no_equalization_needed_received),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    /// Make lspci capture comparable: tabs expanded by terminal are not distinguishable from
+    /// spaces, region sizes are not known from configuration space
+    fn normalize(s: &str) -> Vec<String> {
+        s.lines()
+            .map(|line| {
+                let line = match line.find(" [size=") {
+                    Some(start) => {
+                        let end = line[start..]
+                            .find(']')
+                            .map_or(line.len(), |e| start + e + 1);
+                        [&line[..start], &line[end..]].concat()
+                    }
+                    None => line.to_string(),
+                };
+                line.split_whitespace().collect::<Vec<_>>().join(" ")
+            })
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn header_type_normal() {
+        let capture = "
+            Subsystem: Device [1028:06a5]
+            Control: I/O+ Mem+ BusMaster+ SpecCycle- MemWINV- VGASnoop- ParErr+ Stepping- SERR+ FastB2B- DisINTx+
+            Status: Cap+ 66MHz+ UDF- FastB2B+ ParErr- DEVSEL=medium >TAbort- <TAbort- <MAbort- >SERR- <PERR- INTx-
+            Latency: 0
+            Interrupt: pin A routed to IRQ 30
+            Region 0: Memory at 93014000 (32-bit, non-prefetchable) [size=8K]
+            Region 1: Memory at 93017000 (32-bit, non-prefetchable) [size=256]
+            Region 2: I/O ports at 3040 [size=8]
+            Region 3: I/O ports at 3048 [size=4]
+            Region 4: I/O ports at 3020 [size=32]
+            Region 5: Memory at 93016000 (32-bit, non-prefetchable) [size=2K]
+        ";
+        let data = [
+            0x86, 0x80, 0x02, 0xa1, 0x47, 0x05, 0xb0, 0x02, 0x31, 0x01, 0x06, 0x01, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x40, 0x01, 0x93, 0x00, 0x70, 0x01, 0x93, 0x41, 0x30, 0x00, 0x00,
+            0x49, 0x30, 0x00, 0x00, 0x21, 0x30, 0x00, 0x00, 0x00, 0x60, 0x01, 0x93, 0x00, 0x00,
+            0x00, 0x00, 0x28, 0x10, 0xa5, 0x06, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            // Interrupt Line replaced with IRQ number reported by kernel
+            30, 0x01, 0x00, 0x00,
+        ];
+        let result = Lspci::try_from(&data[..]).unwrap().to_string();
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn header_type_bridge() {
+        let capture = "
+            Control: I/O+ Mem+ BusMaster+ SpecCycle- MemWINV- VGASnoop- ParErr- Stepping- SERR- FastB2B- DisINTx-
+            Status: Cap+ 66MHz- UDF- FastB2B- ParErr- DEVSEL=fast >TAbort- <TAbort- <MAbort- >SERR- <PERR- INTx-
+            Latency: 0
+            BIST result: 00
+            Bus: primary=04, secondary=05, subordinate=08, sec-latency=0
+            I/O behind bridge: 0000f000-00000fff
+            Memory behind bridge: 92000000-929fffff
+            Prefetchable memory behind bridge: 0000000091000000-0000000091ffffff
+            Secondary status: 66MHz- FastB2B- ParErr- DEVSEL=fast >TAbort- <TAbort- <MAbort- <SERR- <PERR-
+            BridgeCtl: Parity+ SERR+ NoISA- VGA+ MAbort- >Reset- FastB2B-
+                PriDiscTmr- SecDiscTmr- DiscTmrStat- DiscTmrSERREn-
+        ";
+        let data = [
+            0x12, 0x19, 0x1d, 0x00, 0x07, 0x00, 0x10, 0x00, 0x00, 0x00, 0x04, 0x06, 0x00, 0x00,
+            0x01, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x05, 0x08, 0x00,
+            0xf1, 0x01, 0x00, 0x00, 0x00, 0x92, 0x90, 0x92, 0x01, 0x91, 0xf1, 0x91, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0xff, 0x00, 0x1b, 0x00,
+        ];
+        let result = Lspci::try_from(&data[..]).unwrap().to_string();
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn header_type_cardbus() {
+        let capture = "
+            Subsystem: Device [3322:5544]
+            Control: I/O- Mem- BusMaster+ SpecCycle- MemWINV+ VGASnoop+ ParErr- Stepping+ SERR- FastB2B- DisINTx-
+            Status: Cap+ 66MHz+ UDF+ FastB2B- ParErr+ DEVSEL=medium >TAbort+ <TAbort- <MAbort- >SERR+ <PERR- INTx+
+            Latency: 41, Cache Line Size: 968 bytes
+            Interrupt: pin Z routed to IRQ 6
+            Region 0: Memory at 35f88000 (32-bit, non-prefetchable) [disabled]
+            Bus: primary=6d, secondary=ba, subordinate=fe, sec-latency=252
+            Memory window 0: 11f54000-22475fff [disabled] (prefetchable)
+            Memory window 1: 33853000-44d0cfff [disabled]
+            I/O window 0: 00000060-00000073 [disabled]
+            I/O window 1: 00060060-00070073 [disabled]
+            BridgeCtl: Parity+ SERR- ISA+ VGA- MAbort- >Reset+ 16bInt- PostWrite+
+            16-bit legacy interface ports at 3322
+        ";
+        let mut data = [0u8; 0x80];
+        data[..0x48].copy_from_slice(&[
+            0x8e, 0xdf, 0xee, 0x05, 0xb4, 0x00, 0x78, 0x4b, 0x37, 0x00, 0x07, 0x06, 0xf2, 0x29,
+            0x82, 0x00, 0x00, 0x80, 0xf8, 0x35, 0x80, 0x00, 0x00, 0x00, 0x6d, 0xba, 0xfe, 0xfc,
+            0x00, 0x40, 0xf5, 0x11, 0x00, 0x50, 0x47, 0x22, 0x00, 0x30, 0x85, 0x33, 0x00, 0xc0,
+            0xd0, 0x44, 0x60, 0x00, 0x00, 0x00, 0x70, 0x00, 0x00, 0x00, 0x61, 0x00, 0x06, 0x00,
+            0x70, 0x00, 0x07, 0x00, 0x06, 0x1a, 0x45, 0x05, 0x22, 0x33, 0x44, 0x55, 0x22, 0x33,
+            0x00, 0x00,
+        ]);
+        let mut header = Header::try_from(&data[..]).unwrap();
+        if let HeaderType::Cardbus(ref mut cardbus) = header.header_type {
+            cardbus
+                .try_set_optional_registers(&data[DDR_OFFSET..])
+                .unwrap();
+        }
+        let result = Lspci::new(header, &data).to_string();
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn capabilities() {
+        let capture = "
+            Capabilities: [50] Power Management version 3
+                    Flags: PMEClk- DSI- D1- D2- AuxCurrent=55mA PME(D0-,D1-,D2-,D3hot+,D3cold+)
+                    Status: D0 NoSoftRst+ PME-Enable- DSel=0 DScale=0 PME-
+            Capabilities: [80] Vendor Specific Information: Len=14 <?>
+            Capabilities: [60] MSI: Enable+ Count=1/1 Maskable- 64bit+
+                    Address: 00000000fee00578  Data: 0000
+        ";
+        let data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_9dc8/config"
+        ));
+        let result = Lspci::try_from(&data[..]).unwrap().to_string();
+        let result = result
+            .lines()
+            .skip_while(|line| !line.starts_with("\tCapabilities"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn pci_express_endpoint() {
+        let capture = "
+            Capabilities: [a0] Express (v2) Endpoint, MSI 00
+                    DevCap: MaxPayload 512 bytes, PhantFunc 0, Latency L0s <512ns, L1 <64us
+                            ExtTag- AttnBtn- AttnInd- PwrInd- RBE+ FLReset+ SlotPowerLimit 0.000W
+                    DevCtl: CorrErr- NonFatalErr+ FatalErr+ UnsupReq+
+                            RlxdOrd+ ExtTag- PhantFunc- AuxPwr- NoSnoop- FLReset-
+                            MaxPayload 256 bytes, MaxReadReq 512 bytes
+                    DevSta: CorrErr+ NonFatalErr- FatalErr- UnsupReq+ AuxPwr- TransPend-
+                    LnkCap: Port #0, Speed 8GT/s, Width x4, ASPM L0s L1, Exit Latency L0s <2us, L1 <16us
+                            ClockPM- Surprise- LLActRep- BwNot- ASPMOptComp+
+                    LnkCtl: ASPM Disabled; RCB 64 bytes Disabled- CommClk+
+                            ExtSynch- ClockPM- AutWidDis- BWInt- AutBWInt-
+                    LnkSta: Speed 8GT/s (ok), Width x4 (ok)
+                            TrErr- Train- SlotClk+ DLActive- BWMgmt- ABWMgmt-
+                    DevCap2: Completion Timeout: Range ABCD, TimeoutDis+, NROPrPrP-, LTR+
+                             10BitTagComp-, 10BitTagReq-, OBFF Not Supported, ExtFmt-, EETLPPrefix-
+                             EmergencyPowerReduction Not Supported, EmergencyPowerReductionInit-
+                             FRS-, TPHComp-, ExtTPHComp-
+                             AtomicOpsCap: 32bit- 64bit- 128bitCAS-
+                    DevCtl2: Completion Timeout: 65ms to 210ms, TimeoutDis-, LTR-, OBFF Disabled
+                             AtomicOpsCtl: ReqEn-
+                    LnkCtl2: Target Link Speed: 2.5GT/s, EnterCompliance- SpeedDis-
+                             Transmit Margin: Normal Operating Range, EnterModifiedCompliance- ComplianceSOS-
+                             Compliance De-emphasis: -6dB
+                    LnkSta2: Current De-emphasis Level: -3.5dB, EqualizationComplete+, EqualizationPhase1+
+                             EqualizationPhase2+, EqualizationPhase3+, LinkEqualizationRequest-
+        ";
+        let pcie = [
+            0x10, 0x00, 0x02, 0x00, 0xc2, 0x8c, 0x00, 0x10, 0x3e, 0x20, 0x09, 0x00, 0x43, 0x5c,
+            0x42, 0x00, 0x40, 0x00, 0x43, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x08, 0x00, 0x00, 0x06, 0x00,
+            0x00, 0x00, 0x0e, 0x00, 0x00, 0x00, 0x01, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        let mut data = [0u8; ECS_OFFSET];
+        // Capabilities List status bit and pointer
+        data[0x06] = 0x10;
+        data[0x34] = 0xa0;
+        data[0xa0..0xa0 + pcie.len()].copy_from_slice(&pcie);
+        let result = Lspci::try_from(&data[..]).unwrap().to_string();
+        let result = result
+            .lines()
+            .skip_while(|line| !line.starts_with("\tCapabilities"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn advanced_error_reporting() {
+        let capture = "
+            Capabilities: [100 v1] Advanced Error Reporting
+                    UESta:  DLP- SDES- TLP- FCP- CmpltTO- CmpltAbrt- UnxCmplt- RxOF- MalfTLP- ECRC- UnsupReq- ACSViol-
+                    UEMsk:  DLP- SDES- TLP- FCP- CmpltTO- CmpltAbrt+ UnxCmplt+ RxOF- MalfTLP- ECRC- UnsupReq- ACSViol-
+                    UESvrt: DLP+ SDES+ TLP+ FCP+ CmpltTO- CmpltAbrt- UnxCmplt- RxOF+ MalfTLP+ ECRC+ UnsupReq- ACSViol-
+                    CESta:  RxErr- BadTLP- BadDLLP- Rollover- Timeout- NonFatalErr+
+                    CEMsk:  RxErr+ BadTLP+ BadDLLP+ Rollover+ Timeout+ NonFatalErr+
+                    AERCap: First Error Pointer: 00, GenCap+ CGenEn- ChkCap+ ChkEn-
+        ";
+        // AER registers assembled from the flags of the capture
+        let registers: [u32; 7] = [
+            0x0001_0001,
+            0x0000_0000,
+            0x0001_8000,
+            0x000e_3030,
+            0x0000_2000,
+            0x0000_31c1,
+            0x0000_00a0,
+        ];
+        let ecs = registers
+            .iter()
+            .flat_map(|r| r.to_le_bytes())
+            .chain([0; 0x30])
+            .collect::<Vec<_>>();
+        let ecap = ExtendedCapabilities::new(&ecs).next().unwrap().unwrap();
+        let result = std::format!(
+            "{}",
+            DisplayFn(|f: &mut Formatter<'_>| extended_capability(f, &ecap))
+        );
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn single_root_io_virtualization() {
+        let capture = "
+            Capabilities: [100 v1] Single Root I/O Virtualization (SR-IOV)
+                    IOVCap: Migration-, Interrupt Message Number: 000
+                    IOVCtl: Enable- Migration- Interrupt- MSE- ARIHierarchy-
+                    IOVSta: Migration-
+                    Initial VFs: 8, Total VFs: 8, Number of VFs: 0, Function Dependency Link: 00
+                    VF offset: 384, stride: 4, Device ID: 1520
+                    Supported Page Size: 00000553, System Page Size: 00000001
+                    Region 0: Memory at 00000000a0180000 (64-bit, prefetchable)
+                    Region 3: Memory at 00000000a01a0000 (64-bit, prefetchable)
+                    VF Migration: offset: 00000000, BIR: 0
+        ";
+        let registers: [u32; 16] = [
+            0x0001_0010,
+            0x0000_0002,
+            0x0000_0000,
+            0x0008_0008,
+            0x0000_0000,
+            0x0004_0180,
+            0x1520_0000,
+            0x0000_0553,
+            0x0000_0001,
+            0xa018_000c,
+            0x0000_0000,
+            0x0000_0000,
+            0xa01a_000c,
+            0x0000_0000,
+            0x0000_0000,
+            0x0000_0000,
+        ];
+        let ecs = registers
+            .iter()
+            .flat_map(|r| r.to_le_bytes())
+            .collect::<Vec<_>>();
+        let ecap = ExtendedCapabilities::new(&ecs).next().unwrap().unwrap();
+        let result = std::format!(
+            "{}",
+            DisplayFn(|f: &mut Formatter<'_>| extended_capability(f, &ecap))
+        );
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn extended_capability_details() {
+        let capture = "
+            Capabilities: [100 v1] L1 PM Substates
+                    L1SubCap: PCI-PM_L1.2+ PCI-PM_L1.1+ ASPM_L1.2+ ASPM_L1.1+ L1_PM_Substates+
+                              PortCommonModeRestoreTime=40us PortTPowerOnTime=50us
+                    L1SubCtl1: PCI-PM_L1.2+ PCI-PM_L1.1+ ASPM_L1.2+ ASPM_L1.1+
+                               T_CommonMode=40us LTR1.2_Threshold=81920ns
+                    L1SubCtl2: T_PwrOn=50us
+            Capabilities: [100 v1] Latency Tolerance Reporting
+                    Max snoop latency: 71680ns
+                    Max no snoop latency: 71680ns
+            Capabilities: [100 v1] Precision Time Measurement
+                    PTMCap: Requester:+ Responder:- Root:-
+                    PTMClockGranularity: Unimplemented
+                    PTMControl: Enabled:+ RootSelected:-
+                    PTMEffectiveGranularity: Unknown
+            Capabilities: [100 v1] Downstream Port Containment
+                    DpcCap: INT Msg #0, RPExt- PoisonedTLP+ SwTrigger+ RP PIO Log 4, DL_ActiveErr+
+                    DpcCtl: Trigger:1 Cmpl- INT+ ErrCor- PoisonedTLP- SwTrigger- DL_ActiveErr-
+                    DpcSta: Trigger- Reason:00 INT- RPBusy- TriggerExt:00 RP PIO ErrPtr:1f
+                    Source: 0000
+        ";
+        // Every structure is placed at the start of extended configuration space
+        let structures: [&[u32]; 4] = [
+            &[0x0001_001e, 0x0029_281f, 0x4050_280f, 0x0000_0029],
+            &[0x0001_0018, 0x0846_0846],
+            &[0x0001_001f, 0x0000_0001, 0x0000_0001],
+            &[0x0001_001d, 0x0009_14c0, 0x0000_1f00],
+        ];
+        let result = structures
+            .iter()
+            .map(|registers| {
+                let ecs = registers
+                    .iter()
+                    .flat_map(|r| r.to_le_bytes())
+                    .collect::<Vec<_>>();
+                let ecap = ExtendedCapabilities::new(&ecs).next().unwrap().unwrap();
+                std::format!(
+                    "{}",
+                    DisplayFn(|f: &mut Formatter<'_>| extended_capability(f, &ecap))
+                )
+            })
+            .collect::<String>();
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn physical_layer() {
+        let capture = "
+            Capabilities: [100 v1] Physical Layer 16.0 GT/s
+                    Phy16Sta: EquComplete+ EquPhase1+ EquPhase2+ EquPhase3+ LinkEquRequest-
+            Capabilities: [100 v1] Physical Layer 32.0 GT/s
+                    Phy32Cap: EqualizationBypass+ NoEqualizationNeeded+
+                              ModTsMode0+ ModTsMode1+ ModTsMode2-
+                    Phy32Ctl: EqualizationBypassDis- NoEqualizationNeededDis-
+                              ModTsUsageModeSel: 1
+                    Phy32Sta: EquComplete+ EquPhase1+ EquPhase2+ EquPhase3+ LinkEquRequest-
+                              ModTsRecv+ RxEnhancedLinkBehaviorCtrl: 0
+                              TxPrecodeOn- TxPrecodeReq- NoEqualizationNeededRecv-
+            Capabilities: [100 v1] Physical Layer 64.0 GT/s
+                    Phy64Sta: EquComplete+ EquPhase1- EquPhase2- EquPhase3- LinkEquRequest-
+                              TxPrecodeOn+ TxPrecodeReq- NoEqualizationNeededRecv+
+        ";
+        // Every structure is placed at the start of extended configuration space
+        let structures: [&[u32]; 3] = [
+            &[0x0001_0026, 0, 0, 0x0000_000f, 0, 0, 0, 0],
+            &[
+                0x0001_002a,
+                0x0000_0303,
+                0x0000_0100,
+                0x0000_002f,
+                0x8086_0001,
+                0x0100_0000,
+                0x8086_0001,
+                0x0100_0000,
+            ],
+            &[0x0001_0031, 0, 0, 0x0000_00a1],
+        ];
+        let result = structures
+            .iter()
+            .map(|registers| {
+                let ecs = registers
+                    .iter()
+                    .flat_map(|r| r.to_le_bytes())
+                    .collect::<Vec<_>>();
+                let ecap = ExtendedCapabilities::new(&ecs).next().unwrap().unwrap();
+                std::format!(
+                    "{}",
+                    DisplayFn(|f: &mut Formatter<'_>| extended_capability(f, &ecap))
+                )
+            })
+            .collect::<String>();
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn lane_margining_at_the_receiver() {
+        let capture = "
+            Capabilities: [100 v1] Lane Margining at the Receiver
+                    PortCap: Uses Driver-
+                    PortSta: MargReady+ MargSoftReady-
+        ";
+        let registers: [u32; 2] = [0x0001_0027, 0x0001_0000];
+        let ecs = registers
+            .iter()
+            .flat_map(|r| r.to_le_bytes())
+            .collect::<Vec<_>>();
+        let ecap = ExtendedCapabilities::new(&ecs).next().unwrap().unwrap();
+        let result = std::format!(
+            "{}",
+            DisplayFn(|f: &mut Formatter<'_>| extended_capability(f, &ecap))
+        );
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn data_object_exchange() {
+        let capture = "
+            Capabilities: [100 v1] Data Object Exchange
+                    DOECap: IntSup+
+                            IntMsgNum 3
+                    DOECtl: IntEn+
+                    DOESta: Busy+ IntSta- Error- ObjectReady+
+        ";
+        let registers: [u32; 6] = [
+            0x0001_002e,
+            0x0000_0007,
+            0x0000_0002,
+            0x8000_0001,
+            0x0000_0000,
+            0x0000_0000,
+        ];
+        let ecs = registers
+            .iter()
+            .flat_map(|r| r.to_le_bytes())
+            .collect::<Vec<_>>();
+        let ecap = ExtendedCapabilities::new(&ecs).next().unwrap().unwrap();
+        let result = std::format!(
+            "{}",
+            DisplayFn(|f: &mut Formatter<'_>| extended_capability(f, &ecap))
+        );
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    #[test]
+    fn extended_capabilities() {
+        let capture = "
+            Control: I/O+ Mem+ BusMaster+ SpecCycle- MemWINV- VGASnoop- ParErr+ Stepping- SERR+ FastB2B- DisINTx+
+            Status: Cap+ 66MHz- UDF- FastB2B- ParErr- DEVSEL=fast >TAbort- <TAbort- <MAbort- >SERR- <PERR- INTx-
+            Latency: 0
+            Interrupt: pin A routed to IRQ 255
+            Bus: primary=ae, secondary=af, subordinate=af, sec-latency=0
+            I/O behind bridge: 0000f000-00000fff
+            Memory behind bridge: e1a00000-e1afffff
+            Prefetchable memory behind bridge: 00000000e1000000-00000000e18fffff
+            Secondary status: 66MHz- FastB2B- ParErr- DEVSEL=fast >TAbort- <TAbort- <MAbort+ <SERR- <PERR-
+            BridgeCtl: Parity+ SERR+ NoISA- VGA- MAbort- >Reset- FastB2B-
+                PriDiscTmr- SecDiscTmr- DiscTmrStat- DiscTmrSERREn-
+            Capabilities: [40] Subsystem: Device [8086:0000]
+            Capabilities: [60] MSI: Enable+ Count=1/2 Maskable+ 64bit-
+                Address: fee00038  Data: 0000
+                Masking: 00000002  Pending: 00000000
+            Capabilities: [90] Express (v2) Root Port (Slot+), MSI 00
+                DevCap: MaxPayload 256 bytes, PhantFunc 0
+                    ExtTag+ RBE+
+                DevCtl: CorrErr- NonFatalErr- FatalErr+ UnsupReq-
+                    RlxdOrd- ExtTag+ PhantFunc- AuxPwr- NoSnoop-
+                    MaxPayload 256 bytes, MaxReadReq 128 bytes
+                DevSta: CorrErr- NonFatalErr- FatalErr- UnsupReq- AuxPwr- TransPend-
+                LnkCap: Port #5, Speed 8GT/s, Width x16, ASPM L1, Exit Latency L1 <16us
+                    ClockPM- Surprise+ LLActRep+ BwNot+ ASPMOptComp+
+                LnkCtl: ASPM Disabled; RCB 64 bytes Disabled- CommClk+
+                    ExtSynch- ClockPM- AutWidDis- BWInt- AutBWInt-
+                LnkSta: Speed 8GT/s, Width x4
+                    TrErr- Train- SlotClk+ DLActive+ BWMgmt- ABWMgmt-
+                SltCap: AttnBtn- PwrCtrl- MRL- AttnInd- PwrInd- HotPlug- Surprise-
+                    Slot #4, PowerLimit 75.000W; Interlock- NoCompl-
+                SltCtl: Enable: AttnBtn- PwrFlt- MRL- PresDet- CmdCplt- HPIrq- LinkChg-
+                    Control: AttnInd Off, PwrInd Off, Power- Interlock-
+                SltSta: Status: AttnBtn- PowerFlt- MRL- CmdCplt- PresDet+ Interlock-
+                    Changed: MRL- PresDet+ LinkState+
+                RootCtl: ErrCorrectable- ErrNon-Fatal+ ErrFatal+ PMEIntEna+ CRSVisible+
+                RootCap: CRSVisible+
+                RootSta: PME ReqID 0000, PMEStatus- PMEPending-
+                DevCap2: Completion Timeout: Range BCD, TimeoutDis+, NROPrPrP-, LTR-
+                     10BitTagComp-, 10BitTagReq-, OBFF Not Supported, ExtFmt-, EETLPPrefix-
+                     EmergencyPowerReduction Not Supported, EmergencyPowerReductionInit-
+                     FRS-, TPHComp+, ExtTPHComp-, ARIFwd+
+                     AtomicOpsCap: Routing- 32bit+ 64bit+ 128bitCAS+
+                DevCtl2: Completion Timeout: 65ms to 210ms, TimeoutDis-, LTR-, OBFF Disabled, ARIFwd+
+                     AtomicOpsCtl: ReqEn- EgressBlck-
+                LnkCtl2: Target Link Speed: 8GT/s, EnterCompliance- SpeedDis-
+                     Transmit Margin: Normal Operating Range, EnterModifiedCompliance- ComplianceSOS-
+                     Compliance De-emphasis: -6dB
+                LnkSta2: Current De-emphasis Level: -3.5dB, EqualizationComplete+, EqualizationPhase1+
+                     EqualizationPhase2+, EqualizationPhase3+, LinkEqualizationRequest-
+            Capabilities: [e0] Power Management version 3
+                Flags: PMEClk- DSI- D1- D2- AuxCurrent=0mA PME(D0+,D1-,D2-,D3hot+,D3cold+)
+                Status: D0 NoSoftRst+ PME-Enable- DSel=0 DScale=0 PME-
+            Capabilities: [100 v1] Vendor Specific Information: ID=0002 Rev=0 Len=00c <?>
+            Capabilities: [110 v1] Access Control Services
+                ACSCap: SrcValid+ TransBlk+ ReqRedir+ CmpltRedir+ UpstreamFwd+ EgressCtrl- DirectTrans-
+                ACSCtl: SrcValid- TransBlk- ReqRedir- CmpltRedir- UpstreamFwd- EgressCtrl- DirectTrans-
+            Capabilities: [148 v1] Advanced Error Reporting
+                UESta: DLP- SDES- TLP- FCP- CmpltTO- CmpltAbrt- UnxCmplt- RxOF- MalfTLP- ECRC- UnsupReq- ACSViol-
+                UEMsk: DLP- SDES- TLP- FCP- CmpltTO- CmpltAbrt- UnxCmplt+ RxOF- MalfTLP- ECRC- UnsupReq+ ACSViol+
+                UESvrt: DLP+ SDES+ TLP+ FCP+ CmpltTO+ CmpltAbrt+ UnxCmplt- RxOF+ MalfTLP+ ECRC+ UnsupReq- ACSViol-
+                CESta: RxErr- BadTLP- BadDLLP- Rollover- Timeout- NonFatalErr-
+                CEMsk: RxErr+ BadTLP+ BadDLLP+ Rollover+ Timeout+ NonFatalErr+
+                AERCap: First Error Pointer: 00, GenCap+ CGenEn+ ChkCap+ ChkEn+
+            Capabilities: [1d0 v1] Vendor Specific Information: ID=0003 Rev=1 Len=00a <?>
+            Capabilities: [250 v1] Secondary PCI Express
+                LnkCtl3: LnkEquIntrruptEn- PerformEqu-
+                LaneErrStat: 0
+            Capabilities: [280 v1] Vendor Specific Information: ID=0005 Rev=3 Len=018 <?>
+            Capabilities: [298 v1] Vendor Specific Information: ID=0007 Rev=0 Len=024 <?>
+            Capabilities: [300 v1] Vendor Specific Information: ID=0008 Rev=0 Len=038 <?>
+        ";
+        let data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_2030/config"
+        ));
+        let result = Lspci::try_from(&data[..]).unwrap().to_string();
+        assert_eq!(normalize(capture), normalize(&result));
+    }
+
+    struct DisplayFn<F>(F);
+
+    impl<F> Display for DisplayFn<F>
+    where
+        F: Fn(&mut Formatter<'_>) -> fmt::Result,
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            (self.0)(f)
+        }
+    }
+}
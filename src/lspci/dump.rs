@@ -0,0 +1,325 @@
+/*!
+## lspci hex dumps
+
+Parser of `lspci -x`, `lspci -xxx` and `lspci -xxxx` output. Every `[ssss:]bb:dd.f` heading
+line starts a new [Dump], hex lines following it fill the function configuration space. Detail
+lines of verbose output (indented) and empty lines are skipped, so `lspci -vvvxxxx` output
+could be pasted as is.
+
+Dump length is rounded up to the nearest configuration space size (64, 256 or 4096 bytes).
+Bytes that were not captured read as zeros from [Dump::data], [Dump::is_captured] tells them
+apart. [ConfigAccess] reads of not captured registers fail.
+
+## Example
+```rust
+# use pcics::{lspci::dump, Capabilities, Header, DDR_OFFSET, ECS_OFFSET};
+let text = "\
+00:1f.3 Audio device: Intel Corporation Sunrise Point-LP HD Audio (rev 21)
+00: 86 80 c8 9d 06 04 10 00 21 00 03 04 10 20 00 00
+10: 04 40 22 ea 00 00 00 00 00 00 00 00 00 00 00 00
+20: 04 00 21 ea 00 00 00 00 00 00 00 00 28 10 d1 07
+30: 00 00 00 00 50 00 00 00 00 00 00 00 ff 01 00 00
+";
+let dumps = dump::parse(text).unwrap();
+assert_eq!(1, dumps.len());
+let dump = &dumps[0];
+assert_eq!(Some("0000:00:1f.3".parse().unwrap()), dump.address);
+assert_eq!(64, dump.data().len());
+
+let header = Header::try_from(dump.data()).unwrap();
+assert_eq!((0x8086, 0x9dc8), (header.vendor_id, header.device_id));
+assert!(header.status.capabilities_list);
+// Capabilities are not captured
+assert!(!dump.is_captured(DDR_OFFSET));
+assert!(dump.data().get(DDR_OFFSET..ECS_OFFSET).is_none());
+```
+*/
+
+use alloc::vec::Vec;
+
+use snafu::prelude::*;
+
+use crate::access::{Address, ConfigAccess, OutOfBoundsError};
+use crate::{DDR_OFFSET, ECS_OFFSET};
+
+/// Configuration space size including extended configuration space
+const MAX_LENGTH: usize = 4096;
+/// Number of bytes in one hex line
+const LINE_LENGTH: usize = 16;
+
+/// Hex dump parsing error with 1-based line number
+#[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+pub enum DumpError {
+    #[snafu(display("line {line}: neither heading nor hex line"))]
+    Unrecognized { line: usize },
+    #[snafu(display("line {line}: offset {offset:#x} is not aligned to 16 bytes"))]
+    Misaligned { line: usize, offset: usize },
+    #[snafu(display("line {line}: offset {offset:#x} is out of configuration space"))]
+    OutOfRange { line: usize, offset: usize },
+    #[snafu(display("line {line}: offset {offset:#x} is captured twice"))]
+    Duplicate { line: usize, offset: usize },
+    #[snafu(display("line {line}: {token:?} is not a hex byte"))]
+    InvalidByte {
+        line: usize,
+        token: alloc::string::String,
+    },
+    #[snafu(display("line {line}: more than 16 bytes"))]
+    TooLong { line: usize },
+}
+
+/// Configuration space of one function captured by lspci
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dump {
+    /// Address from the heading line, `None` if hex lines were not preceded by a heading
+    pub address: Option<Address>,
+    data: Vec<u8>,
+    captured: Vec<bool>,
+}
+
+impl Dump {
+    fn new(address: Option<Address>) -> Self {
+        Self {
+            address,
+            data: Vec::new(),
+            captured: Vec::new(),
+        }
+    }
+
+    /// Configuration space data starting at offset 0, not captured bytes are zeros
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Byte at `offset` was present in the dump
+    pub fn is_captured(&self, offset: usize) -> bool {
+        self.captured.get(offset).copied().unwrap_or(false)
+    }
+
+    /// Captured byte at `offset`
+    pub fn get(&self, offset: usize) -> Option<u8> {
+        if self.is_captured(offset) {
+            self.data.get(offset).copied()
+        } else {
+            None
+        }
+    }
+
+    fn insert(&mut self, offset: usize, bytes: &[u8]) {
+        let end = offset + bytes.len();
+        if end > self.data.len() {
+            let len = if end <= DDR_OFFSET {
+                DDR_OFFSET
+            } else if end <= ECS_OFFSET {
+                ECS_OFFSET
+            } else {
+                MAX_LENGTH
+            };
+            self.data.resize(len, 0);
+            self.captured.resize(len, false);
+        }
+        self.data[offset..end].copy_from_slice(bytes);
+        self.captured[offset..end].fill(true);
+    }
+}
+
+/// Captured registers only
+impl ConfigAccess for Dump {
+    type Error = OutOfBoundsError;
+
+    fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+        let start = offset as usize;
+        let bytes = self.data.get(start..start + 4);
+        let is_captured = (start..start + 4).all(|offset| self.is_captured(offset));
+        bytes
+            .filter(|_| is_captured)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(OutOfBoundsError { offset })
+    }
+}
+
+/// Parse lspci hex dump `text` into one [Dump] per function
+pub fn parse(text: &str) -> Result<Vec<Dump>, DumpError> {
+    let mut dumps: Vec<Dump> = Vec::new();
+    for (i, s) in text.lines().enumerate() {
+        let line = i + 1;
+        if s.trim().is_empty() || s.starts_with(|c: char| c.is_whitespace()) {
+            continue;
+        }
+        let (head, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let offset = match head.strip_suffix(':') {
+            Some(offset) => offset,
+            None => {
+                let address = head.parse().ok().context(UnrecognizedSnafu { line })?;
+                dumps.push(Dump::new(Some(address)));
+                continue;
+            }
+        };
+        let is_offset =
+            matches!(offset.len(), 2..=4) && offset.bytes().all(|b| b.is_ascii_hexdigit());
+        ensure!(is_offset, UnrecognizedSnafu { line });
+        let offset = usize::from_str_radix(offset, 16).unwrap();
+        ensure!(offset % LINE_LENGTH == 0, MisalignedSnafu { line, offset });
+        ensure!(offset < MAX_LENGTH, OutOfRangeSnafu { line, offset });
+
+        let mut bytes = [0u8; LINE_LENGTH];
+        let mut len = 0;
+        for token in rest.split_whitespace() {
+            ensure!(len < LINE_LENGTH, TooLongSnafu { line });
+            let is_byte = token.len() == 2 && token.bytes().all(|b| b.is_ascii_hexdigit());
+            ensure!(is_byte, InvalidByteSnafu { line, token });
+            bytes[len] = u8::from_str_radix(token, 16).unwrap();
+            len += 1;
+        }
+        let end = (offset + len).min(MAX_LENGTH);
+        let bytes = &bytes[..end - offset];
+
+        if dumps.is_empty() {
+            dumps.push(Dump::new(None));
+        }
+        let dump = dumps.last_mut().unwrap();
+        let is_duplicate = (offset..end).any(|offset| dump.is_captured(offset));
+        ensure!(!is_duplicate, DuplicateSnafu { line, offset });
+        dump.insert(offset, bytes);
+    }
+    Ok(dumps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Capabilities, ExtendedCapabilities, Header};
+    use core::fmt::Write;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    /// `lspci -xxxx` like text
+    fn hex_dump(heading: &str, data: &[u8]) -> String {
+        let mut s = String::new();
+        writeln!(s, "{}", heading).unwrap();
+        for (i, chunk) in data.chunks(LINE_LENGTH).enumerate() {
+            write!(s, "{:02x}:", i * LINE_LENGTH).unwrap();
+            for b in chunk {
+                write!(s, " {:02x}", b).unwrap();
+            }
+            writeln!(s).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn functions() {
+        let ecs_data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_2030/config"
+        ));
+        let ddr_data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_9dc8/config"
+        ));
+        let text = [
+            hex_dump("0000:00:00.0 PCI bridge: Intel Corporation Sky Lake-E PCI Express Root Port A (rev 04)", ecs_data),
+            hex_dump("00:1f.3 Audio device: Intel Corporation Sunrise Point-LP HD Audio (rev 21)", ddr_data),
+            // Verbose details and trailing empty line
+            "\tSubsystem: Dell Device 07d1\n\n".to_string(),
+        ]
+        .concat();
+        let dumps = parse(&text).unwrap();
+        assert_eq!(2, dumps.len());
+
+        let dump = &dumps[0];
+        assert_eq!(Some("0000:00:00.0".parse().unwrap()), dump.address);
+        assert_eq!(&ecs_data[..], dump.data());
+        let header = Header::try_from(dump.data()).unwrap();
+        let caps = Capabilities::new(&dump.data()[DDR_OFFSET..ECS_OFFSET], &header);
+        assert_eq!(4, caps.count());
+        let ecaps = ExtendedCapabilities::new(&dump.data()[ECS_OFFSET..]);
+        assert_eq!(8, ecaps.count());
+
+        let dump = &dumps[1];
+        assert_eq!(Some("0000:00:1f.3".parse().unwrap()), dump.address);
+        assert_eq!(&ddr_data[..], dump.data());
+        assert!(dump.is_captured(ECS_OFFSET - 1));
+        assert!(!dump.is_captured(ECS_OFFSET));
+    }
+
+    #[test]
+    fn partial() {
+        let data = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_9dc8/config"
+        ));
+        // Header and the line with MSI capability at 0x60
+        let text = hex_dump("00:1f.3", data)
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| matches!(i, 0..=4 | 7))
+            .map(|(_, line)| std::format!("{}\n", line))
+            .collect::<String>();
+        let dumps = parse(&text).unwrap();
+        let dump = &dumps[0];
+        assert_eq!(ECS_OFFSET, dump.data().len());
+        assert_eq!(Some(data[0x60]), dump.get(0x60));
+        assert_eq!(None, dump.get(0x50));
+        assert_eq!(0, dump.data()[0x50]);
+
+        // Power Management at 0x50 is not captured
+        let header = Header::read_from(dump).unwrap();
+        let mut ddr = [0; crate::DDR_LENGTH];
+        assert_eq!(
+            Err(OutOfBoundsError { offset: 0x50 }),
+            Capabilities::read_from(dump, &header, &mut ddr).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn without_heading() {
+        let dumps = parse("00: 86 80 c8 9d\n").unwrap();
+        assert_eq!(1, dumps.len());
+        assert_eq!(None, dumps[0].address);
+        assert_eq!(Some(0x9d), dumps[0].get(3));
+        assert_eq!(None, dumps[0].get(4));
+    }
+
+    #[test]
+    fn errors() {
+        let result = |text| parse(text).map(|_| ());
+        assert_eq!(
+            Err(DumpError::Unrecognized { line: 2 }),
+            result("00:1f.3 Audio\nAudio device: Intel")
+        );
+        assert_eq!(Err(DumpError::Unrecognized { line: 1 }), result("0g: 00"));
+        assert_eq!(
+            Err(DumpError::Misaligned {
+                line: 1,
+                offset: 0x18
+            }),
+            result("18: 00")
+        );
+        assert_eq!(
+            Err(DumpError::OutOfRange {
+                line: 1,
+                offset: 0x1000
+            }),
+            result("1000: 00")
+        );
+        assert_eq!(
+            Err(DumpError::Duplicate { line: 3, offset: 0 }),
+            result("00:1f.3\n00: 00\n00: 00")
+        );
+        assert_eq!(
+            Err(DumpError::InvalidByte {
+                line: 1,
+                token: "0x".into()
+            }),
+            result("00: 00 0x")
+        );
+        assert_eq!(
+            Err(DumpError::TooLong { line: 1 }),
+            result("00: 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f 10")
+        );
+        assert_eq!(
+            "line 1: offset 0x18 is not aligned to 16 bytes",
+            parse("18: 00").unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,848 @@
+/*!
+## Register write-back
+
+Every decoded control and status register could be encoded back into its raw value, e.g.
+`u16::from(command)`. Encoded value contains fields exactly as they were decoded, all bits that
+are not decoded are zero.
+
+Writing encoded value as is isn't safe: it could change reserved (RsvdP) bits or bits of fields
+that are not decoded by this crate, and any status bit read as 1 would be cleared by writing it
+back (RW1C). [Register::write_value] merges encoded value with the current register value, so
+only RW fields are changed, and [Register::clear_value] produces value that clears selected
+status bits only.
+
+## Example
+```rust
+# use pcics::{capabilities::pci_express::DeviceStatus, header::Command, register::Register};
+let current = 0x0547;
+let mut command = Command::from(current);
+command.bus_master = false;
+assert_eq!(0x0543, command.write_value(current));
+
+// Correctable and Fatal Error Detected are set
+let current = 0x0005;
+let mut status = DeviceStatus::from(current);
+// Leave everything as is
+assert_eq!(0x0000, status.clone().write_value(current));
+// Clear only Fatal Error Detected
+status.correctable_error_detected = false;
+assert_eq!(0x0004, status.clear_value(current));
+```
+
+## Access attributes
+
+[RegisterAttributes] describes access attribute of every bit of the register as defined by
+specification. Header, capabilities and extended capabilities provide [RegisterMap] of their
+registers, e.g. [Header::registers](crate::Header::registers), so configuration space emulator
+could apply writes the same way the hardware does.
+
+```rust
+# use pcics::{header::NORMAL_REGISTERS, register::RegisterMap};
+let map = RegisterMap::from(NORMAL_REGISTERS);
+// Status register
+let status = map.find(0x06).unwrap();
+assert_eq!((0x06, 2), (status.offset, status.width));
+// Guest writes 1 to Received Master Abort (RW1C) and Interrupt Status (RO)
+assert_eq!(0x0018, status.write(0x2018, 0x2008));
+```
+*/
+
+use core::ops::{BitAnd, BitOr, Not};
+
+/// Register which could be written back to configuration space
+pub trait Register: Sized + Into<Self::Value> {
+    /// Raw register value: [u8], [u16] or [u32]
+    type Value: Copy
+        + BitAnd<Output = Self::Value>
+        + BitOr<Output = Self::Value>
+        + Not<Output = Self::Value>;
+    /// Reserved and not decoded bits which current value should be written back (RsvdP)
+    const RSVDP: Self::Value;
+    /// Status bits cleared by writing 1 (RW1C)
+    const RW1C: Self::Value;
+
+    /// Value to write to change RW fields only
+    ///
+    /// RsvdP bits are taken from `current` register value, RW1C bits are zeroed.
+    fn write_value(self, current: Self::Value) -> Self::Value {
+        let value = self.into();
+        (value & !(Self::RSVDP | Self::RW1C)) | (current & Self::RSVDP)
+    }
+
+    /// Value to write to clear RW1C bits set in `self`
+    ///
+    /// All other bits are taken from `current` register value.
+    fn clear_value(self, current: Self::Value) -> Self::Value {
+        (self.into() & Self::RW1C) | (current & !Self::RW1C)
+    }
+}
+
+/// Access attributes of every bit of the register
+///
+/// Each bit has exactly one attribute. Sticky variants (ROS, RWS, RW1CS) are reported as their
+/// non-sticky counterparts. Register is constructed with all bits [RO](Self::ro), other
+/// attributes are assigned by const builder methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterAttributes {
+    /// Offset from the start of the structure
+    pub offset: u16,
+    /// Width in bytes: 1, 2 or 4
+    pub width: u8,
+    /// Read-only (RO, ROS)
+    pub ro: u32,
+    /// Hardware initialized, read-only after initialization (HwInit)
+    pub hwinit: u32,
+    /// Read-write (RW, RWS)
+    pub rw: u32,
+    /// Write 1 to clear status (RW1C, RW1CS)
+    pub rw1c: u32,
+    /// Reserved, software must preserve value read (RsvdP)
+    pub rsvdp: u32,
+    /// Reserved, software must write zero (RsvdZ)
+    pub rsvdz: u32,
+}
+
+impl RegisterAttributes {
+    /// Read-only register
+    pub const fn new(offset: u16, width: u8) -> Self {
+        let mask = match width {
+            1 => 0xff,
+            2 => 0xffff,
+            4 => 0xffff_ffff,
+            _ => panic!("register width should be 1, 2 or 4 bytes"),
+        };
+        Self {
+            offset,
+            width,
+            ro: mask,
+            hwinit: 0,
+            rw: 0,
+            rw1c: 0,
+            rsvdp: 0,
+            rsvdz: 0,
+        }
+    }
+    const fn take(mut self, mask: u32) -> Self {
+        if self.ro & mask != mask {
+            panic!("bits should be RO before attribute assignment");
+        }
+        self.ro &= !mask;
+        self
+    }
+    /// Set HwInit bits
+    pub const fn hwinit(self, mask: u32) -> Self {
+        let mut result = self.take(mask);
+        result.hwinit |= mask;
+        result
+    }
+    /// Set RW bits
+    pub const fn rw(self, mask: u32) -> Self {
+        let mut result = self.take(mask);
+        result.rw |= mask;
+        result
+    }
+    /// Set RW1C bits
+    pub const fn rw1c(self, mask: u32) -> Self {
+        let mut result = self.take(mask);
+        result.rw1c |= mask;
+        result
+    }
+    /// Set RsvdP bits
+    pub const fn rsvdp(self, mask: u32) -> Self {
+        let mut result = self.take(mask);
+        result.rsvdp |= mask;
+        result
+    }
+    /// Set RsvdZ bits
+    pub const fn rsvdz(self, mask: u32) -> Self {
+        let mut result = self.take(mask);
+        result.rsvdz |= mask;
+        result
+    }
+    /// Same register placed `shift` bytes further
+    pub const fn shift(mut self, shift: u16) -> Self {
+        self.offset += shift;
+        self
+    }
+    /// All bits of the register
+    pub const fn mask(&self) -> u32 {
+        self.ro | self.hwinit | self.rw | self.rw1c | self.rsvdp | self.rsvdz
+    }
+    /// Register occupies byte at `offset`
+    pub const fn contains(&self, offset: u16) -> bool {
+        self.offset <= offset && offset < self.offset + self.width as u16
+    }
+    /// Register value after `value` was written to the register containing `current` value
+    ///
+    /// RW bits are taken from `value`, RW1C bits set in `value` are cleared, all other bits
+    /// are left as is.
+    pub const fn write(&self, current: u32, value: u32) -> u32 {
+        let keep = self.ro | self.hwinit | self.rsvdp | self.rsvdz;
+        (current & keep) | (value & self.rw) | (current & self.rw1c & !value)
+    }
+}
+
+/// Access attributes of all registers of the structure
+///
+/// Structure could contain a block of registers repeated several times, e.g. Resizable BAR
+/// entries. Such block is described once with offsets of the first instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterMap {
+    /// Registers always present in the structure
+    pub registers: &'static [RegisterAttributes],
+    /// Registers of the first repeated block instance
+    pub repeated: &'static [RegisterAttributes],
+    /// Distance between repeated blocks in bytes
+    pub stride: u16,
+    /// Number of repeated blocks
+    pub count: u16,
+}
+
+impl RegisterMap {
+    pub const fn new(registers: &'static [RegisterAttributes]) -> Self {
+        Self {
+            registers,
+            repeated: &[],
+            stride: 0,
+            count: 0,
+        }
+    }
+    /// Add `count` instances of `repeated` block every `stride` bytes
+    pub const fn repeat(
+        self,
+        repeated: &'static [RegisterAttributes],
+        stride: u16,
+        count: u16,
+    ) -> Self {
+        Self {
+            repeated,
+            stride,
+            count,
+            ..self
+        }
+    }
+    /// Drop fixed registers starting at `end` offset and beyond
+    pub fn truncate(self, end: u16) -> Self {
+        let len = self
+            .registers
+            .iter()
+            .take_while(|reg| reg.offset < end)
+            .count();
+        Self {
+            registers: &self.registers[..len],
+            ..self
+        }
+    }
+    /// All registers ordered as described
+    pub fn iter(&self) -> impl Iterator<Item = RegisterAttributes> {
+        let Self {
+            registers,
+            repeated,
+            stride,
+            count,
+        } = *self;
+        let repeated = (0..count).flat_map(move |n| {
+            repeated
+                .iter()
+                .map(move |reg| reg.shift(n.wrapping_mul(stride)))
+        });
+        registers.iter().copied().chain(repeated)
+    }
+    /// Register which occupies byte at `offset`
+    pub fn find(&self, offset: u16) -> Option<RegisterAttributes> {
+        self.iter().find(|reg| reg.contains(offset))
+    }
+    /// Number of bytes from the structure start up to the end of the last register
+    pub fn size(&self) -> u16 {
+        self.iter()
+            .map(|reg| reg.offset + reg.width as u16)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl From<&'static [RegisterAttributes]> for RegisterMap {
+    fn from(registers: &'static [RegisterAttributes]) -> Self {
+        Self::new(registers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use super::*;
+    use crate::{
+        capabilities::{pci_express::DeviceType, CapabilityKind},
+        extended_capabilities::ExtendedCapabilityKind,
+        header::HeaderType,
+        Capabilities, ExtendedCapabilities, Header, DDR_OFFSET, ECS_OFFSET,
+    };
+    use pretty_assertions::assert_eq;
+
+    const DEVICES: [&[u8]; 2] = [
+        include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_2030/config"
+        )),
+        include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/device/8086_9dc8/config"
+        )),
+    ];
+
+    #[track_caller]
+    fn round_trip<T>(data: &T) -> usize
+    where
+        T: Register + From<T::Value> + Clone + Debug + PartialEq,
+    {
+        assert_eq!(*data, T::from(data.clone().into()));
+        1
+    }
+
+    #[test]
+    fn decode_encoded() {
+        let mut count = 0;
+        for config in DEVICES {
+            let header = Header::try_from(&config[..DDR_OFFSET]).unwrap();
+            count += round_trip(&header.command);
+            count += round_trip(&header.status);
+            count += round_trip(&header.bist);
+            if let HeaderType::Bridge(bridge) = &header.header_type {
+                count += round_trip(&bridge.secondary_status);
+                count += round_trip(&bridge.bridge_control);
+            }
+
+            let caps = Capabilities::new(&config[DDR_OFFSET..ECS_OFFSET], &header);
+            for cap in caps {
+                match cap.unwrap().kind {
+                    CapabilityKind::MessageSignaledInterrups(msi) => {
+                        count += round_trip(&msi.message_control);
+                    }
+                    CapabilityKind::PowerManagementInterface(pmi) => {
+                        count += round_trip(&pmi.control);
+                    }
+                    CapabilityKind::PciExpress(pcie) => {
+                        count += round_trip(&pcie.device.control);
+                        count += round_trip(&pcie.device.status);
+                        if let Some(device_2) = &pcie.device_2 {
+                            count += round_trip(&device_2.control);
+                            count += round_trip(&device_2.status);
+                        }
+                        if let DeviceType::RootPort {
+                            link,
+                            link_2,
+                            slot,
+                            slot_2,
+                            root,
+                        } = &pcie.device_type
+                        {
+                            count += round_trip(&link.control);
+                            count += round_trip(&link.status);
+                            count += round_trip(&slot.control);
+                            count += round_trip(&slot.status);
+                            count += round_trip(&root.control);
+                            count += round_trip(&root.status);
+                            if let Some(link_2) = link_2 {
+                                count += round_trip(&link_2.control);
+                                count += round_trip(&link_2.status);
+                            }
+                            if let Some(slot_2) = slot_2 {
+                                count += round_trip(&slot_2.control);
+                                count += round_trip(&slot_2.status);
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            let ecaps = config.get(ECS_OFFSET..).filter(|ecs| !ecs.is_empty());
+            for ecap in ecaps.into_iter().flat_map(ExtendedCapabilities::new) {
+                match ecap.unwrap().kind {
+                    ExtendedCapabilityKind::AccessControlServices(acs) => {
+                        count += round_trip(&acs.acs_control);
+                    }
+                    ExtendedCapabilityKind::AdvancedErrorReporting(aer) => {
+                        count += round_trip(&aer.uncorrectable_error_status);
+                        for data in [
+                            &aer.uncorrectable_error_mask,
+                            &aer.uncorrectable_error_severity,
+                        ] {
+                            assert_eq!(*data, u32::from(data.clone()).into());
+                            count += 1;
+                        }
+                        count += round_trip(&aer.correctable_error_status);
+                        assert_eq!(
+                            aer.correctable_error_mask,
+                            u32::from(aer.correctable_error_mask.clone()).into()
+                        );
+                        count += 1;
+                        count += round_trip(&aer.advanced_error_capabilities_and_control);
+                        count += aer.root_error_command.as_ref().map_or(0, round_trip);
+                        count += aer.root_error_status.as_ref().map_or(0, round_trip);
+                    }
+                    ExtendedCapabilityKind::SecondaryPciExpress(spe) => {
+                        count += round_trip(&spe.link_control_3);
+                    }
+                    _ => (),
+                }
+            }
+        }
+        assert_eq!(36, count, "checked registers");
+    }
+
+    #[test]
+    fn status_write_back() {
+        use crate::extended_capabilities::advanced_error_reporting::{
+            CorrectableErrorStatus as Ce, UncorrectableErrorStatus as Ue,
+        };
+        let mut count = 0;
+        for config in DEVICES {
+            let ecaps = config.get(ECS_OFFSET..).filter(|ecs| !ecs.is_empty());
+            for ecap in ecaps.into_iter().flat_map(ExtendedCapabilities::new) {
+                let ecap = ecap.unwrap();
+                if let ExtendedCapabilityKind::AdvancedErrorReporting(aer) = ecap.kind {
+                    let dword = |offset: usize| {
+                        let start = ecap.offset as usize + offset;
+                        u32::from_le_bytes(config[start..start + 4].try_into().unwrap())
+                    };
+                    assert_eq!(aer.uncorrectable_error_status, Ue::from(dword(0x04)));
+                    assert_eq!(aer.correctable_error_status, Ce::from(dword(0x10)));
+                    // Decoded value is written back as is, also while errors are pending
+                    for current in [dword(0x04), dword(0x04) | Ue::RW1C] {
+                        let status = Ue::from(current);
+                        assert_eq!(0, status.write_value(current) & Ue::RW1C, "clears nothing");
+                    }
+                    for current in [dword(0x10), dword(0x10) | Ce::RW1C] {
+                        let status = Ce::from(current);
+                        assert_eq!(0, status.write_value(current) & Ce::RW1C, "clears nothing");
+                    }
+                    count += 1;
+                }
+            }
+        }
+        assert_eq!(1, count, "checked AER structures");
+    }
+
+    #[track_caller]
+    fn check_layout(map: RegisterMap, size: usize) -> usize {
+        let mut end = 0;
+        for reg in map.iter() {
+            let full = u32::MAX >> (32 - 8 * reg.width as u32);
+            assert_eq!(
+                full,
+                reg.mask(),
+                "{:#x}: attributes cover all bits",
+                reg.offset
+            );
+            assert_eq!(
+                0,
+                reg.offset % reg.width as u16,
+                "{:#x}: aligned",
+                reg.offset
+            );
+            assert!(end <= reg.offset, "{:#x}: ordered", reg.offset);
+            end = reg.offset + reg.width as u16;
+        }
+        assert!(end as usize <= size, "{:#x}: fits structure", end);
+        map.iter().count()
+    }
+
+    #[test]
+    fn attributes_layout() {
+        let mut count = 0;
+        for config in DEVICES {
+            let header = Header::try_from(&config[..DDR_OFFSET]).unwrap();
+            count += check_layout(header.registers(), DDR_OFFSET);
+
+            let caps = Capabilities::new(&config[DDR_OFFSET..ECS_OFFSET], &header);
+            for cap in caps.map(Result::unwrap) {
+                let map = cap.kind.registers().expect("decoded capability");
+                count += check_layout(map, ECS_OFFSET - cap.pointer as usize);
+            }
+
+            let ecaps = config.get(ECS_OFFSET..).filter(|ecs| !ecs.is_empty());
+            for ecap in ecaps.into_iter().flat_map(ExtendedCapabilities::new) {
+                let ecap = ecap.unwrap();
+                let map = ecap.kind.registers().expect("decoded extended capability");
+                count += check_layout(map, config.len() - ecap.offset as usize);
+            }
+        }
+        assert_eq!(155, count, "described registers");
+    }
+
+    #[test]
+    fn tables_layout() {
+        let tables: [&[RegisterAttributes]; 93] = [
+            crate::capabilities::accelerated_graphics_port::REGISTERS,
+            crate::capabilities::advanced_features::REGISTERS,
+            crate::capabilities::bridge_subsystem_vendor_id::REGISTERS,
+            crate::capabilities::compact_pci_hot_swap::REGISTERS,
+            crate::capabilities::compact_pci_resource_control::REGISTERS,
+            crate::capabilities::debug_port::REGISTERS,
+            crate::capabilities::enhanced_allocation::REGISTERS,
+            crate::capabilities::flattening_portal_bridge::REGISTERS,
+            crate::capabilities::hypertransport::HOST_OR_SECONDARY_REGISTERS,
+            crate::capabilities::hypertransport::MSI_MAPPING_REGISTERS,
+            crate::capabilities::hypertransport::REGISTERS,
+            crate::capabilities::hypertransport::SLAVE_OR_PRIMARY_REGISTERS,
+            crate::capabilities::message_signaled_interrups::MSI_32BIT_PVM_REGISTERS,
+            crate::capabilities::message_signaled_interrups::MSI_32BIT_REGISTERS,
+            crate::capabilities::message_signaled_interrups::MSI_64BIT_PVM_REGISTERS,
+            crate::capabilities::message_signaled_interrups::MSI_64BIT_REGISTERS,
+            crate::capabilities::msi_x::REGISTERS,
+            crate::capabilities::pci_express::REGISTERS,
+            crate::capabilities::pci_hot_plug::REGISTERS,
+            crate::capabilities::pci_hot_plug::SHPC_REGISTERS,
+            crate::capabilities::pci_hot_plug::SLOT_REGISTERS,
+            crate::capabilities::pci_x::BRIDGE_REGISTERS,
+            crate::capabilities::pci_x::REGISTERS,
+            crate::capabilities::power_management_interface::REGISTERS,
+            crate::capabilities::sata::REGISTERS,
+            crate::capabilities::secure_device::REGISTERS,
+            crate::capabilities::slot_identification::REGISTERS,
+            crate::capabilities::vendor_specific::REGISTERS,
+            crate::capabilities::vital_product_data::REGISTERS,
+            crate::extended_capabilities::access_control_services::EGRESS_CONTROL_VECTOR_REGISTERS,
+            crate::extended_capabilities::access_control_services::REGISTERS,
+            crate::extended_capabilities::address_translation_services::REGISTERS,
+            crate::extended_capabilities::advanced_error_reporting::REGISTERS,
+            crate::extended_capabilities::alternate_protocol::REGISTERS,
+            crate::extended_capabilities::alternative_routing_id_interpolation::REGISTERS,
+            crate::extended_capabilities::configuration_access_correlation::REGISTERS,
+            crate::extended_capabilities::data_link_feature::REGISTERS,
+            crate::extended_capabilities::data_object_exchange::REGISTERS,
+            crate::extended_capabilities::designated_vendor_specific_extended_capability::REGISTERS,
+            crate::extended_capabilities::designated_vendor_specific_extended_capability::compute_express_link::pcie_dvsec_for_cxl_device::REGISTERS,
+            crate::extended_capabilities::device_serial_number::REGISTERS,
+            crate::extended_capabilities::downstream_port_containment::REGISTERS,
+            crate::extended_capabilities::dynamic_power_allocation::POWER_ALLOCATION_ARRAY_REGISTERS,
+            crate::extended_capabilities::dynamic_power_allocation::REGISTERS,
+            crate::extended_capabilities::frs_queuing::REGISTERS,
+            crate::extended_capabilities::hierarchy_id::REGISTERS,
+            crate::extended_capabilities::l1_pm_substates::REGISTERS,
+            crate::extended_capabilities::lane_margining_at_the_receiver::LANE_REGISTERS,
+            crate::extended_capabilities::lane_margining_at_the_receiver::REGISTERS,
+            crate::extended_capabilities::latency_tolerance_reporting::REGISTERS,
+            crate::extended_capabilities::ln_requester::REGISTERS,
+            crate::extended_capabilities::multi_root_io_virtualization::REGISTERS,
+            crate::extended_capabilities::multicast::REGISTERS,
+            crate::extended_capabilities::multifunction_virtual_channel::REGISTERS,
+            crate::extended_capabilities::multifunction_virtual_channel::VC_RESOURCE_REGISTERS,
+            crate::extended_capabilities::native_pcie_enclosure_management::REGISTERS,
+            crate::extended_capabilities::page_request_interface::REGISTERS,
+            crate::extended_capabilities::pci_express_over_m_phy::REGISTERS,
+            crate::extended_capabilities::physical_layer_16_gtps::LANE_EQUALIZATION_CONTROL_REGISTERS,
+            crate::extended_capabilities::physical_layer_16_gtps::REGISTERS,
+            crate::extended_capabilities::physical_layer_32_gtps::LANE_EQUALIZATION_CONTROL_REGISTERS,
+            crate::extended_capabilities::physical_layer_32_gtps::REGISTERS,
+            crate::extended_capabilities::physical_layer_64_gtps::LANE_EQUALIZATION_CONTROL_REGISTERS,
+            crate::extended_capabilities::physical_layer_64_gtps::REGISTERS,
+            crate::extended_capabilities::power_budgeting::REGISTERS,
+            crate::extended_capabilities::precision_time_measurement::REGISTERS,
+            crate::extended_capabilities::process_address_space_id::REGISTERS,
+            crate::extended_capabilities::protocol_multiplexing::PROTOCOL_ARRAY_REGISTERS,
+            crate::extended_capabilities::protocol_multiplexing::REGISTERS,
+            crate::extended_capabilities::readiness_time_reporting::REGISTERS,
+            crate::extended_capabilities::resizable_bar::ENTRY_REGISTERS,
+            crate::extended_capabilities::resizable_bar::REGISTERS,
+            crate::extended_capabilities::root_complex_event_collector_endpoint_association::REGISTERS,
+            crate::extended_capabilities::root_complex_internal_link_control::REGISTERS,
+            crate::extended_capabilities::root_complex_link_declaration::LINK_ENTRY_REGISTERS,
+            crate::extended_capabilities::root_complex_link_declaration::REGISTERS,
+            crate::extended_capabilities::root_complex_register_block_header::REGISTERS,
+            crate::extended_capabilities::secondary_pci_express::LANE_EQUALIZATION_CONTROL_REGISTERS,
+            crate::extended_capabilities::secondary_pci_express::REGISTERS,
+            crate::extended_capabilities::shadow_functions::INSTANCE_REGISTERS,
+            crate::extended_capabilities::shadow_functions::REGISTERS,
+            crate::extended_capabilities::single_root_io_virtualization::REGISTERS,
+            crate::extended_capabilities::system_firmware_intermediary::REGISTERS,
+            crate::extended_capabilities::tph_requester::REGISTERS,
+            crate::extended_capabilities::tph_requester::ST_TABLE_REGISTERS,
+            crate::extended_capabilities::vendor_specific_extended_capability::REGISTERS,
+            crate::extended_capabilities::vf_resizable_bar::ENTRY_REGISTERS,
+            crate::extended_capabilities::vf_resizable_bar::REGISTERS,
+            crate::extended_capabilities::virtual_channel::REGISTERS,
+            crate::extended_capabilities::virtual_channel::VC_RESOURCE_REGISTERS,
+            crate::header::BRIDGE_REGISTERS,
+            crate::header::CARDBUS_REGISTERS,
+            crate::header::NORMAL_REGISTERS,
+        ];
+        for registers in tables {
+            check_layout(registers.into(), 0x1000);
+        }
+    }
+
+    fn rw1c<T: Register>() -> u32
+    where
+        T::Value: Into<u32>,
+    {
+        T::RW1C.into()
+    }
+
+    #[test]
+    fn attributes_agree_with_registers() {
+        use crate::capabilities::{
+            compact_pci_hot_swap, compact_pci_resource_control, hypertransport, pci_express,
+            pci_hot_plug, pci_x, power_management_interface,
+        };
+        use crate::extended_capabilities::{
+            advanced_error_reporting, alternate_protocol, data_object_exchange,
+            designated_vendor_specific_extended_capability as dvsec, downstream_port_containment,
+            dynamic_power_allocation, frs_queuing, lane_margining_at_the_receiver,
+            multi_root_io_virtualization, native_pcie_enclosure_management, page_request_interface,
+            pci_express_over_m_phy, physical_layer_16_gtps, physical_layer_32_gtps,
+            physical_layer_64_gtps, shadow_functions, single_root_io_virtualization,
+            system_firmware_intermediary,
+        };
+        use crate::header;
+        use dvsec::compute_express_link::pcie_dvsec_for_cxl_device as cxl;
+
+        let sample: [(&[RegisterAttributes], u16, u32); 47] = [
+            (
+                header::NORMAL_REGISTERS,
+                0x06,
+                rw1c::<header::Status<'P'>>(),
+            ),
+            (
+                header::BRIDGE_REGISTERS,
+                0x1e,
+                rw1c::<header::Status<'S'>>(),
+            ),
+            (
+                header::BRIDGE_REGISTERS,
+                0x3e,
+                rw1c::<header::BridgeControl>(),
+            ),
+            (
+                power_management_interface::REGISTERS,
+                0x04,
+                rw1c::<power_management_interface::Control>(),
+            ),
+            (
+                pci_express::REGISTERS,
+                0x0a,
+                rw1c::<pci_express::DeviceStatus>(),
+            ),
+            (
+                pci_express::REGISTERS,
+                0x12,
+                rw1c::<pci_express::LinkStatus>(),
+            ),
+            (
+                pci_express::REGISTERS,
+                0x1a,
+                rw1c::<pci_express::SlotStatus>(),
+            ),
+            (
+                pci_express::REGISTERS,
+                0x20,
+                rw1c::<pci_express::RootStatus>(),
+            ),
+            (
+                pci_express::REGISTERS,
+                0x32,
+                rw1c::<pci_express::LinkStatus2>(),
+            ),
+            (
+                compact_pci_hot_swap::REGISTERS,
+                0x02,
+                rw1c::<compact_pci_hot_swap::HotSwapControlStatus>(),
+            ),
+            (
+                compact_pci_resource_control::REGISTERS,
+                0x04,
+                rw1c::<compact_pci_resource_control::CentralResourceStatus>(),
+            ),
+            (
+                pci_hot_plug::SHPC_REGISTERS,
+                0x20,
+                rw1c::<pci_hot_plug::SerrIntEnable>(),
+            ),
+            (
+                pci_hot_plug::SLOT_REGISTERS,
+                0x24,
+                rw1c::<pci_hot_plug::SlotRegister>(),
+            ),
+            (pci_x::REGISTERS, 0x04, rw1c::<pci_x::Status>()),
+            (pci_x::REGISTERS, 0x08, rw1c::<pci_x::EccControlAndStatus>()),
+            (
+                pci_x::BRIDGE_REGISTERS,
+                0x02,
+                rw1c::<pci_x::SecondaryStatus>(),
+            ),
+            (pci_x::BRIDGE_REGISTERS, 0x04, rw1c::<pci_x::BridgeStatus>()),
+            (
+                hypertransport::SLAVE_OR_PRIMARY_REGISTERS,
+                0x04,
+                rw1c::<hypertransport::LinkControl>(),
+            ),
+            (
+                hypertransport::SLAVE_OR_PRIMARY_REGISTERS,
+                0x16,
+                rw1c::<hypertransport::ErrorHandling>(),
+            ),
+            (
+                hypertransport::HOST_OR_SECONDARY_REGISTERS,
+                0x02,
+                rw1c::<hypertransport::HostOrSecondaryCommand>(),
+            ),
+            (
+                advanced_error_reporting::REGISTERS,
+                0x30,
+                rw1c::<advanced_error_reporting::RootErrorStatus>(),
+            ),
+            (
+                advanced_error_reporting::REGISTERS,
+                0x04,
+                rw1c::<advanced_error_reporting::UncorrectableErrorStatus>(),
+            ),
+            (
+                advanced_error_reporting::REGISTERS,
+                0x10,
+                rw1c::<advanced_error_reporting::CorrectableErrorStatus>(),
+            ),
+            (
+                downstream_port_containment::REGISTERS,
+                0x08,
+                rw1c::<downstream_port_containment::DpcStatus>(),
+            ),
+            (
+                dynamic_power_allocation::REGISTERS,
+                0x0c,
+                rw1c::<dynamic_power_allocation::DpaStatus>(),
+            ),
+            (
+                frs_queuing::REGISTERS,
+                0x08,
+                rw1c::<frs_queuing::FrsQueuingStatus>(),
+            ),
+            (
+                page_request_interface::REGISTERS,
+                0x06,
+                rw1c::<page_request_interface::PageRequestStatus>(),
+            ),
+            (
+                single_root_io_virtualization::REGISTERS,
+                0x0a,
+                rw1c::<single_root_io_virtualization::SriovStatus>(),
+            ),
+            (
+                multi_root_io_virtualization::REGISTERS,
+                0x0a,
+                rw1c::<multi_root_io_virtualization::MriovStatus>(),
+            ),
+            (
+                pci_express_over_m_phy::REGISTERS,
+                0x18,
+                rw1c::<pci_express_over_m_phy::MpciePhyControlData>(),
+            ),
+            (
+                physical_layer_16_gtps::REGISTERS,
+                0x0c,
+                rw1c::<physical_layer_16_gtps::Status16GTps>(),
+            ),
+            (
+                physical_layer_16_gtps::REGISTERS,
+                0x10,
+                rw1c::<physical_layer_16_gtps::DataParityMismatchStatus>(),
+            ),
+            (
+                physical_layer_16_gtps::REGISTERS,
+                0x18,
+                rw1c::<physical_layer_16_gtps::DataParityMismatchStatus>(),
+            ),
+            (
+                physical_layer_32_gtps::REGISTERS,
+                0x08,
+                rw1c::<physical_layer_32_gtps::Control32GTps>(),
+            ),
+            (
+                physical_layer_32_gtps::REGISTERS,
+                0x0c,
+                rw1c::<physical_layer_32_gtps::Status32GTps>(),
+            ),
+            (
+                physical_layer_64_gtps::REGISTERS,
+                0x0c,
+                rw1c::<physical_layer_64_gtps::Status64GTps>(),
+            ),
+            (
+                lane_margining_at_the_receiver::LANE_REGISTERS,
+                0x08,
+                rw1c::<lane_margining_at_the_receiver::MarginingLaneControl>(),
+            ),
+            (
+                native_pcie_enclosure_management::REGISTERS,
+                0x08,
+                rw1c::<native_pcie_enclosure_management::NpemControl>(),
+            ),
+            (
+                native_pcie_enclosure_management::REGISTERS,
+                0x0c,
+                rw1c::<native_pcie_enclosure_management::NpemStatus>(),
+            ),
+            (
+                alternate_protocol::REGISTERS,
+                0x08,
+                rw1c::<alternate_protocol::AlternateProtocolControl>(),
+            ),
+            (
+                system_firmware_intermediary::REGISTERS,
+                0x06,
+                rw1c::<system_firmware_intermediary::SfiControl>(),
+            ),
+            (
+                system_firmware_intermediary::REGISTERS,
+                0x08,
+                rw1c::<system_firmware_intermediary::SfiStatus>(),
+            ),
+            (
+                system_firmware_intermediary::REGISTERS,
+                0x0c,
+                rw1c::<system_firmware_intermediary::SfiCamAddress>(),
+            ),
+            (
+                shadow_functions::REGISTERS,
+                0x08,
+                rw1c::<shadow_functions::ShadowFunctionsControl>(),
+            ),
+            (
+                data_object_exchange::REGISTERS,
+                0x08,
+                rw1c::<data_object_exchange::DoeControl>(),
+            ),
+            (
+                data_object_exchange::REGISTERS,
+                0x0c,
+                rw1c::<data_object_exchange::DoeStatus>(),
+            ),
+            (cxl::REGISTERS, 0x0e, rw1c::<cxl::CxlStatus>()),
+        ];
+        for (registers, offset, rw1c) in sample {
+            let reg = RegisterMap::from(registers).find(offset).unwrap();
+            assert_eq!((offset, rw1c), (reg.offset, reg.rw1c));
+        }
+    }
+
+    #[test]
+    fn apply_write() {
+        // Bridge Secondary Status: Received Master Abort (RW1C) is set
+        let config = DEVICES[0];
+        let header = Header::try_from(&config[..DDR_OFFSET]).unwrap();
+        let reg = header.registers().find(0x1e).unwrap();
+        let current = u16::from_le_bytes([config[0x1e], config[0x1f]]) as u32;
+        assert_eq!(0x2000, current);
+        // Writing back current value clears status
+        assert_eq!(0x0000, reg.write(current, current));
+        // Writing zero changes nothing
+        assert_eq!(current, reg.write(current, 0));
+
+        // Header Command: RsvdP bits are preserved, RW bits are replaced
+        let command = RegisterMap::from(crate::header::NORMAL_REGISTERS)
+            .find(0x04)
+            .unwrap();
+        assert_eq!(0xf806, command.write(0xf800, 0xffff_0006));
+    }
+}
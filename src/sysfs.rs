@@ -0,0 +1,314 @@
+/*!
+## Linux sysfs
+
+Linux exposes every PCI function as a `/sys/bus/pci/devices/ssss:bb:dd.f` directory with a
+binary `config` file. Unprivileged users could read only the first 64 bytes of the file, so
+capabilities are decoded only when the corresponding region is available.
+
+## Example
+```rust,no_run
+# use pcics::sysfs::Sysfs;
+let sysfs = Sysfs::default();
+for address in sysfs.addresses().unwrap() {
+    let device = sysfs.device(address).unwrap();
+    println!(
+        "{} [{:04x}:{:04x}]",
+        device.address, device.header.vendor_id, device.header.device_id
+    );
+    for cap in device.capabilities().into_iter().flatten() {
+        println!("  {:?}", cap);
+    }
+}
+```
+*/
+
+use std::{
+    fs::{self, File},
+    io,
+    os::unix::fs::FileExt,
+    path::{Path, PathBuf},
+    string::ToString,
+    vec::Vec,
+};
+
+use snafu::prelude::*;
+
+use crate::{
+    access::{Address, ConfigAccess},
+    Capabilities, ExtendedCapabilities, Header, DDR_OFFSET, ECS_OFFSET,
+};
+
+/// Default sysfs PCI devices directory
+pub const SYSFS_PCI_DEVICES: &str = "/sys/bus/pci/devices";
+
+/// sysfs reading error
+#[derive(Snafu, Debug)]
+pub enum SysfsError {
+    #[snafu(display("{} read error: {source}", path.display()))]
+    Io { path: PathBuf, source: io::Error },
+    #[snafu(display("[{address}] configuration space is shorter than header ({len} bytes)"))]
+    Header { address: Address, len: usize },
+}
+
+/// PCI devices directory of sysfs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sysfs {
+    root: PathBuf,
+}
+
+impl Default for Sysfs {
+    fn default() -> Self {
+        Self::new(SYSFS_PCI_DEVICES)
+    }
+}
+
+impl Sysfs {
+    /// Use `root` instead of [SYSFS_PCI_DEVICES], e.g. fake sysfs tree
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// PCI devices directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Sorted addresses of all PCI functions
+    ///
+    /// Directory entries not named as *ssss:bb:dd.f* are skipped.
+    pub fn addresses(&self) -> Result<Vec<Address>, SysfsError> {
+        let entries = fs::read_dir(&self.root).context(IoSnafu { path: &self.root })?;
+        let mut result = Vec::new();
+        for entry in entries {
+            let entry = entry.context(IoSnafu { path: &self.root })?;
+            if let Some(address) = entry.file_name().to_str().and_then(|s| s.parse().ok()) {
+                result.push(address);
+            }
+        }
+        result.sort_unstable();
+        Ok(result)
+    }
+
+    /// Path to the function `config` file
+    pub fn config_path(&self, address: Address) -> PathBuf {
+        self.root.join(address.to_string()).join("config")
+    }
+
+    /// Open the function `config` file for reading registers through [ConfigAccess]
+    pub fn config(&self, address: Address) -> Result<ConfigFile, SysfsError> {
+        let path = self.config_path(address);
+        let file = File::open(&path).context(IoSnafu { path: &path })?;
+        Ok(ConfigFile { path, file })
+    }
+
+    /// Read and decode configuration space of the function
+    pub fn device(&self, address: Address) -> Result<Device, SysfsError> {
+        let path = self.config_path(address);
+        let config = fs::read(&path).context(IoSnafu { path })?;
+        let header = config
+            .get(..DDR_OFFSET)
+            .and_then(|slice| Header::try_from(slice).ok())
+            .context(HeaderSnafu {
+                address,
+                len: config.len(),
+            })?;
+        Ok(Device {
+            address,
+            config,
+            header,
+        })
+    }
+
+    /// Read and decode configuration space of all functions
+    pub fn devices(&self) -> Result<Vec<Device>, SysfsError> {
+        self.addresses()?
+            .into_iter()
+            .map(|address| self.device(address))
+            .collect()
+    }
+}
+
+/// Decoded PCI function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    pub address: Address,
+    /// Raw configuration space (64, 256 or 4096 bytes)
+    pub config: Vec<u8>,
+    pub header: Header,
+}
+
+impl Device {
+    /// Capabilities list, if device dependent region was read
+    pub fn capabilities(&self) -> Option<Capabilities<'_>> {
+        self.config
+            .get(DDR_OFFSET..ECS_OFFSET)
+            .map(|ddr| Capabilities::new(ddr, &self.header))
+    }
+
+    /// Extended capabilities list, if extended configuration space was read
+    pub fn extended_capabilities(&self) -> Option<ExtendedCapabilities<'_>> {
+        self.config
+            .get(ECS_OFFSET..)
+            .filter(|ecs| !ecs.is_empty())
+            .map(ExtendedCapabilities::new)
+    }
+}
+
+/// Opened sysfs `config` file
+///
+/// Every register is read with a separate positioned read.
+#[derive(Debug)]
+pub struct ConfigFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl ConfigFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ConfigAccess for ConfigFile {
+    type Error = SysfsError;
+
+    fn read_u32(&self, offset: u16) -> Result<u32, Self::Error> {
+        let mut buf = [0; 4];
+        self.file
+            .read_exact_at(&mut buf, offset.into())
+            .context(IoSnafu { path: &self.path })?;
+        Ok(u32::from_le_bytes(buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extended_capabilities::ExtendedCapabilityKind;
+    use pretty_assertions::assert_eq;
+    use std::{format, prelude::v1::*};
+
+    const DATA: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/device/8086_2030/config"
+    ));
+
+    /// Fake sysfs tree removed on drop
+    struct FakeSysfs(PathBuf);
+    impl FakeSysfs {
+        fn new(name: &str, devices: &[(&str, &[u8])]) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("pcics-sysfs-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            for (address, config) in devices {
+                let dir = root.join(address);
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(dir.join("config"), config).unwrap();
+            }
+            Self(root)
+        }
+    }
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn addresses() {
+        let fake = FakeSysfs::new(
+            "addresses",
+            &[
+                ("0000:3a:00.1", DATA),
+                ("0001:00:1f.0", DATA),
+                ("0000:00:00.0", DATA),
+            ],
+        );
+        fs::create_dir_all(fake.0.join("not-a-device")).unwrap();
+        let result = Sysfs::new(&fake.0).addresses().unwrap();
+        let sample = vec![
+            Address {
+                segment: 0,
+                bus: 0,
+                device: 0,
+                function: 0,
+            },
+            Address {
+                segment: 0,
+                bus: 0x3a,
+                device: 0,
+                function: 1,
+            },
+            Address {
+                segment: 1,
+                bus: 0,
+                device: 0x1f,
+                function: 0,
+            },
+        ];
+        assert_eq!(sample, result);
+    }
+
+    #[test]
+    fn devices() {
+        let fake = FakeSysfs::new(
+            "devices",
+            &[
+                ("0000:00:00.0", DATA),
+                ("0000:00:01.0", &DATA[..ECS_OFFSET]),
+                ("0000:00:02.0", &DATA[..DDR_OFFSET]),
+            ],
+        );
+        let devices = Sysfs::new(&fake.0).devices().unwrap();
+        let result = devices
+            .iter()
+            .map(|d| {
+                (
+                    d.address.device,
+                    d.header.device_id,
+                    d.capabilities().map(|caps| caps.count()),
+                    d.extended_capabilities().map(|ecaps| ecaps.count()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let sample = vec![
+            (0, 0x2030, Some(4), Some(8)),
+            (1, 0x2030, Some(4), None),
+            (2, 0x2030, None, None),
+        ];
+        assert_eq!(sample, result);
+    }
+
+    #[test]
+    fn short_config() {
+        let fake = FakeSysfs::new("short_config", &[("0000:00:00.0", &DATA[..0x20])]);
+        let address = "0000:00:00.0".parse().unwrap();
+        let result = Sysfs::new(&fake.0).device(address);
+        assert!(matches!(result, Err(SysfsError::Header { len: 0x20, .. })));
+    }
+
+    #[test]
+    fn config_file_access() {
+        let fake = FakeSysfs::new("config_file_access", &[("0000:00:00.0", DATA)]);
+        let sysfs = Sysfs::new(&fake.0);
+        let address = "0000:00:00.0".parse().unwrap();
+        let config = sysfs.config(address).unwrap();
+        let header = Header::read_from(&config).unwrap();
+        assert_eq!(sysfs.device(address).unwrap().header, header);
+        let mut ecs = [0; crate::ECS_LENGTH];
+        let result = ExtendedCapabilities::read_from(&config, &mut ecs)
+            .unwrap()
+            .filter(|ecap| {
+                matches!(
+                    ecap,
+                    Ok(ecap) if matches!(ecap.kind, ExtendedCapabilityKind::AdvancedErrorReporting(_))
+                )
+            })
+            .count();
+        assert_eq!(1, result);
+        assert!(matches!(
+            config.read_u32(0x1000),
+            Err(SysfsError::Io { .. })
+        ));
+    }
+}
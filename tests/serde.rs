@@ -0,0 +1,100 @@
+//! Serialization of decoded types
+//!
+//! Kept out of the library unit tests: `serde_json` implements `PartialEq<Value>` for integers,
+//! which breaks type inference of `assert_eq!(0u32, x.into())` there.
+#![cfg(feature = "serde")]
+
+use pcics::{
+    header::{BaseAddress, BaseAddressType, HeaderType},
+    Capabilities, ExtendedCapabilities, Header, DDR_OFFSET, ECS_OFFSET,
+};
+use pretty_assertions::assert_eq;
+
+const DATA: &[u8] = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/data/device/8086_9dc8/config"
+));
+
+#[test]
+fn header_round_trip() {
+    let header = Header::try_from(DATA).unwrap();
+    let json = serde_json::to_value(&header).unwrap();
+    let bars = match &header.header_type {
+        HeaderType::Normal(normal) => normal.base_addresses.clone().collect::<Vec<_>>(),
+        _ => unreachable!(),
+    };
+    let bars = serde_json::to_value(bars).unwrap();
+    assert_eq!(bars, json["header_type"]["Normal"]["base_addresses"]);
+    assert_eq!(header, serde_json::from_value(json).unwrap());
+}
+
+#[test]
+fn base_addresses_out_of_regions() {
+    // Normal header has only 6 regions
+    let mut json = serde_json::to_value(Header::try_from(DATA).unwrap()).unwrap();
+    let bar = serde_json::to_value(BaseAddress {
+        region: 6,
+        base_address_type: BaseAddressType::IoSpace { base_address: 0x10 },
+    })
+    .unwrap();
+    json["header_type"]["Normal"]["base_addresses"] = serde_json::Value::Array(vec![bar]);
+    assert!(serde_json::from_value::<Header>(json).is_err());
+}
+
+#[test]
+fn cardbus_reserved_round_trip() {
+    // CardBus reserved registers are longer than arrays supported by serde
+    let mut data = [0u8; 0x80];
+    data[0x0e] = 0x02;
+    data[0x7f] = 0xaa;
+    let mut header = Header::try_from(&data[..]).unwrap();
+    if let HeaderType::Cardbus(ref mut cardbus) = header.header_type {
+        cardbus
+            .try_set_optional_registers(&data[DDR_OFFSET..])
+            .unwrap();
+    }
+    let json = serde_json::to_string(&header).unwrap();
+    assert_eq!(header, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn iterators_as_sequences() {
+    let header = Header::try_from(DATA).unwrap();
+    let caps = Capabilities::new(&DATA[DDR_OFFSET..ECS_OFFSET], &header);
+    assert_eq!(
+        serde_json::to_value(caps.collect::<Vec<_>>()).unwrap(),
+        serde_json::to_value(caps).unwrap()
+    );
+
+    let data = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/device/8086_2030/config"
+    ));
+    let ecaps = ExtendedCapabilities::new(&data[ECS_OFFSET..]);
+    let json = serde_json::to_value(ecaps).unwrap();
+    assert_eq!(8, json.as_array().unwrap().len());
+    assert_eq!(
+        serde_json::to_value(ecaps.collect::<Vec<_>>()).unwrap(),
+        json
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_capabilities_round_trip() {
+    let data = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/device/8086_2030/config"
+    ));
+    let cs = pcics::ConfigurationSpace::try_from(&data[..]).unwrap();
+    let json = serde_json::to_string(&cs.capabilities).unwrap();
+    assert_eq!(
+        cs.capabilities,
+        serde_json::from_str::<Vec<_>>(&json).unwrap()
+    );
+    let json = serde_json::to_string(&cs.extended_capabilities).unwrap();
+    assert_eq!(
+        cs.extended_capabilities,
+        serde_json::from_str::<Vec<_>>(&json).unwrap()
+    );
+}